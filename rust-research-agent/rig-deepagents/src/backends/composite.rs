@@ -4,10 +4,14 @@
 //! Python Reference: deepagents/backends/composite.py
 
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
-use super::protocol::{Backend, FileInfo, GrepMatch};
+use super::protocol::{Backend, BackendTransaction, GrepOptions, FileInfo, GrepMatch, LS_RECURSIVE_MAX_ENTRIES};
+use super::path_utils::is_under_path;
 use crate::error::{BackendError, WriteResult, EditResult};
+use crate::state::FileData;
 
 /// 라우트 설정
 pub struct Route {
@@ -19,6 +23,7 @@ pub struct Route {
 /// Python: CompositeBackend
 ///
 /// 경로 접두사를 기반으로 요청을 다른 백엔드로 라우팅합니다.
+/// `ls`/`glob`/`grep`은 조회 경로 하위의 모든 마운트로 팬아웃되어 결과를 합칩니다.
 pub struct CompositeBackend {
     default: Arc<dyn Backend>,
     routes: Vec<Route>,
@@ -32,85 +37,186 @@ impl CompositeBackend {
         }
     }
 
-    /// 라우트 추가 (빌더 패턴)
-    pub fn with_route(mut self, prefix: &str, backend: Arc<dyn Backend>) -> Self {
+    /// 접두사 하위 경로를 지정한 백엔드에 마운트 (빌더 패턴)
+    ///
+    /// 경로는 가장 긴 일치 접두사의 백엔드로 라우팅되며, 일치하는 마운트가 없으면
+    /// 기본 백엔드가 처리합니다. 마운트된 백엔드에는 접두사가 제거된 경로가 전달됩니다.
+    /// 같은 접두사로 다시 마운트하면 기존 마운트를 교체합니다.
+    ///
+    /// ```rust,ignore
+    /// let backend = CompositeBackend::new(Arc::new(FilesystemBackend::new("./workspace")))
+    ///     .mount("/memory", Arc::new(MemoryBackend::new()));
+    /// ```
+    pub fn mount(mut self, prefix: &str, backend: Arc<dyn Backend>) -> Self {
+        let prefix = format!("/{}", prefix.trim_matches('/'));
+        self.routes.retain(|r| r.prefix != prefix);
+        self.routes.push(Route { prefix, backend });
         // 길이 순으로 정렬 (가장 긴 것 먼저)
-        let route = Route {
-            prefix: prefix.to_string(),
-            backend,
-        };
-        self.routes.push(route);
-        self.routes.sort_by(|a, b| b.prefix.len().cmp(&a.prefix.len()));
+        self.routes.sort_by_key(|r| std::cmp::Reverse(r.prefix.len()));
         self
     }
 
-    /// 경로에 맞는 백엔드와 변환된 경로 반환
-    fn get_backend_and_path(&self, path: &str) -> (Arc<dyn Backend>, String) {
+    /// 라우트 추가 (빌더 패턴) - `mount`와 동일
+    pub fn with_route(self, prefix: &str, backend: Arc<dyn Backend>) -> Self {
+        self.mount(prefix, backend)
+    }
+
+    /// 경로를 소유하는 마운트 (가장 긴 일치 접두사). `None`이면 기본 백엔드
+    fn route_for(&self, path: &str) -> Option<&Route> {
         // 경로 정규화 (후행 슬래시 제거)
         let normalized_path = path.trim_end_matches('/');
 
-        for route in &self.routes {
+        self.routes.iter().find(|route| {
             let route_prefix = route.prefix.trim_end_matches('/');
-
             // 정확히 일치하거나 route_prefix/ 로 시작하는 경우
-            if normalized_path == route_prefix ||
-               normalized_path.starts_with(&format!("{}/", route_prefix)) {
-                let suffix = if normalized_path == route_prefix {
-                    ""
-                } else {
-                    &normalized_path[route_prefix.len()..]
-                };
+            normalized_path == route_prefix ||
+                normalized_path.starts_with(&format!("{}/", route_prefix))
+        })
+    }
 
-                let stripped = if suffix.is_empty() {
+    /// 경로 하위에 위치한 마운트 목록 (경로 자체를 소유한 마운트 제외)
+    fn mounts_under(&self, path: &str) -> Vec<&Route> {
+        let normalized_path = path.trim_end_matches('/');
+        self.routes
+            .iter()
+            .filter(|r| {
+                r.prefix.trim_end_matches('/') != normalized_path && is_under_path(&r.prefix, path)
+            })
+            .collect()
+    }
+
+    /// 경로에 맞는 백엔드와 변환된 경로 반환
+    fn get_backend_and_path(&self, path: &str) -> (Arc<dyn Backend>, String) {
+        let route = self.route_for(path);
+        let backend = route.map_or(&self.default, |r| &r.backend).clone();
+        (backend, Self::stripped_path(route, path))
+    }
+
+    /// 복합 백엔드 경로를 마운트 내부 경로로 변환 (접두사 제거)
+    fn stripped_path(route: Option<&Route>, path: &str) -> String {
+        match route {
+            Some(route) => {
+                let normalized_path = path.trim_end_matches('/');
+                let suffix = &normalized_path[route.prefix.trim_end_matches('/').len()..];
+
+                if suffix.is_empty() {
                     "/".to_string()
                 } else {
                     suffix.to_string()
-                };
-                return (route.backend.clone(), stripped);
+                }
             }
+            None => path.to_string(),
         }
-        (self.default.clone(), path.to_string())
+    }
+
+    /// 마운트 내부 경로를 복합 백엔드 경로로 변환
+    fn mounted_path(route: Option<&Route>, path: &str) -> String {
+        match route {
+            Some(route) => format!("{}{}", route.prefix.trim_end_matches('/'), path),
+            None => path.to_string(),
+        }
+    }
+
+    /// 경로가 해당 마운트 소유인지 확인 (더 긴 마운트에 가려진 항목 필터링용)
+    fn is_owned_by(&self, route: Option<&Route>, path: &str) -> bool {
+        self.route_for(path).map(|r| &r.prefix) == route.map(|r| &r.prefix)
     }
 
     /// 결과 경로에 접두사 복원
     fn restore_prefix(&self, path: &str, original_path: &str) -> String {
-        let normalized_original = original_path.trim_end_matches('/');
-        for route in &self.routes {
-            let normalized_prefix = route.prefix.trim_end_matches('/');
-            // Check both exact match and prefix match with boundary
-            if normalized_original == normalized_prefix ||
-               normalized_original.starts_with(&format!("{}/", normalized_prefix)) {
-                return format!("{}{}", normalized_prefix, path);
-            }
-        }
-        path.to_string()
+        Self::mounted_path(self.route_for(original_path), path)
     }
 }
 
 #[async_trait]
 impl Backend for CompositeBackend {
     async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
-        // 루트 경로면 모든 백엔드에서 수집
-        if path == "/" {
-            let mut results = self.default.ls("/").await?;
+        let owner = self.route_for(path);
+        let (backend, stripped) = self.get_backend_and_path(path);
+        let children = self.mounts_under(path);
 
-            // 라우트된 디렉토리 추가
-            for route in &self.routes {
-                results.push(FileInfo::dir(&route.prefix));
-            }
+        let listed = match backend.ls(&stripped).await {
+            Ok(listed) => listed,
+            // 디렉토리가 마운트 지점으로만 존재하는 경우
+            Err(BackendError::FileNotFound(_)) if !children.is_empty() => Vec::new(),
+            Err(e) => return Err(e),
+        };
 
-            results.sort_by(|a, b| a.path.cmp(&b.path));
-            return Ok(results);
+        let mut results: Vec<FileInfo> = listed
+            .into_iter()
+            .filter_map(|mut info| {
+                info.path = Self::mounted_path(owner, &info.path);
+                self.is_owned_by(owner, &info.path).then_some(info)
+            })
+            .collect();
+
+        // 하위 마운트 지점을 디렉토리 항목으로 합침
+        // (예: `/a/b` 마운트는 ls("/")에서 `/a/`로 보임)
+        let base = path.trim_end_matches('/');
+        for route in children {
+            let relative = route.prefix[base.len()..].trim_start_matches('/');
+            let first = relative.split('/').next().unwrap_or(relative);
+            let dir_path = format!("{}/{}/", base, first);
+
+            if !results.iter().any(|f| f.path.trim_end_matches('/') == dir_path.trim_end_matches('/')) {
+                results.push(FileInfo::dir(&dir_path));
+            }
         }
 
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(results)
+    }
+
+
+    async fn ls_recursive(&self, path: &str, depth: usize) -> Result<Vec<FileInfo>, BackendError> {
+        let owner = self.route_for(path);
         let (backend, stripped) = self.get_backend_and_path(path);
-        let mut results = backend.ls(&stripped).await?;
+        let children = self.mounts_under(path);
+        if children.is_empty() {
+            return backend.ls_recursive(&stripped, depth).await;
+        }
 
-        // 경로 복원
-        for info in &mut results {
-            info.path = self.restore_prefix(&info.path, path);
+        // 결과는 `path` 기준 상대 경로이므로 마운트 소유 여부는 절대 경로로 확인
+        let base = path.trim_end_matches('/');
+        let absolute = |relative: &str| format!("{}/{}", base, relative.trim_end_matches('/'));
+
+        let mut results: Vec<FileInfo> = match backend.ls_recursive(&stripped, depth).await {
+            Ok(listed) => listed,
+            Err(BackendError::FileNotFound(_)) => Vec::new(),
+            Err(e) => return Err(e),
         }
+        .into_iter()
+        .filter(|info| self.is_owned_by(owner, &absolute(&info.path)))
+        .collect();
+
+        for route in children {
+            let relative = route.prefix[base.len()..].trim_matches('/').to_string();
+            let levels = relative.split('/').count();
+
+            // 마운트 지점까지의 디렉토리 항목 (예: `/a/b` 마운트는 `a`, `a/b`)
+            let mut dir = String::new();
+            for component in relative.split('/').take(depth) {
+                if !dir.is_empty() {
+                    dir.push('/');
+                }
+                dir.push_str(component);
+                if !results.iter().any(|f| f.path.trim_end_matches('/') == dir) {
+                    results.push(FileInfo::dir(&dir));
+                }
+            }
 
+            if levels < depth {
+                let nested = route.backend.ls_recursive("/", depth - levels).await?;
+                results.extend(nested.into_iter().filter_map(|info| {
+                    let path = format!("{}/{}", relative, info.path);
+                    self.is_owned_by(Some(route), &absolute(&path))
+                        .then_some(FileInfo { path, ..info })
+                }));
+            }
+        }
+
+        results.sort_by(|a, b| a.path.split('/').cmp(b.path.split('/')));
+        results.truncate(LS_RECURSIVE_MAX_ENTRIES);
         Ok(results)
     }
 
@@ -119,6 +225,11 @@ impl Backend for CompositeBackend {
         backend.read(&stripped, offset, limit).await
     }
 
+    async fn read_file_range(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        let (backend, stripped) = self.get_backend_and_path(path);
+        backend.read_file_range(&stripped, offset, limit).await
+    }
+
     async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError> {
         let (backend, stripped) = self.get_backend_and_path(path);
         let mut result = backend.write(&stripped, content).await?;
@@ -140,6 +251,32 @@ impl Backend for CompositeBackend {
         Ok(result)
     }
 
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        let (backend, stripped) = self.get_backend_and_path(path);
+        backend.read_bytes(&stripped).await
+    }
+
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        let (backend, stripped) = self.get_backend_and_path(path);
+        let mut result = backend.write_bytes(&stripped, content).await?;
+
+        // 경로 복원
+        if result.path.is_some() {
+            result.path = Some(path.to_string());
+        }
+
+        // files_update 키도 복원
+        if let Some(ref mut files_update) = result.files_update {
+            let restored: std::collections::HashMap<String, crate::state::FileData> = files_update
+                .drain()
+                .map(|(k, v)| (self.restore_prefix(&k, path), v))
+                .collect();
+            *files_update = restored;
+        }
+
+        Ok(result)
+    }
+
     async fn edit(
         &self,
         path: &str,
@@ -168,31 +305,24 @@ impl Backend for CompositeBackend {
     }
 
     async fn glob(&self, pattern: &str, base_path: &str) -> Result<Vec<FileInfo>, BackendError> {
-        // 특정 라우트 경로인 경우 해당 백엔드만 검색
-        for route in &self.routes {
-            let route_prefix = route.prefix.trim_end_matches('/');
-            if base_path.starts_with(route_prefix) &&
-               (base_path.len() == route_prefix.len() || base_path[route_prefix.len()..].starts_with('/')) {
-                let (backend, stripped) = self.get_backend_and_path(base_path);
-                let mut results = backend.glob(pattern, &stripped).await?;
-
-                for info in &mut results {
-                    info.path = self.restore_prefix(&info.path, base_path);
-                }
-                return Ok(results);
-            }
-        }
-
-        // 루트 또는 라우트되지 않은 경로 - 모든 백엔드에서 집계
-        let mut all_results = self.default.glob(pattern, base_path).await?;
-
-        for route in &self.routes {
-            let mut route_results = route.backend.glob(pattern, "/").await?;
-            for info in &mut route_results {
-                let prefix = route.prefix.trim_end_matches('/');
-                info.path = format!("{}{}", prefix, info.path);
-            }
-            all_results.extend(route_results);
+        let owner = self.route_for(base_path);
+        let (backend, stripped) = self.get_backend_and_path(base_path);
+
+        let mut all_results: Vec<FileInfo> = backend.glob(pattern, &stripped).await?
+            .into_iter()
+            .filter_map(|mut info| {
+                info.path = Self::mounted_path(owner, &info.path);
+                self.is_owned_by(owner, &info.path).then_some(info)
+            })
+            .collect();
+
+        // base_path 하위 마운트에서 집계
+        for route in self.mounts_under(base_path) {
+            let route_results = route.backend.glob(pattern, "/").await?;
+            all_results.extend(route_results.into_iter().filter_map(|mut info| {
+                info.path = Self::mounted_path(Some(route), &info.path);
+                self.is_owned_by(Some(route), &info.path).then_some(info)
+            }));
         }
 
         all_results.sort_by(|a, b| a.path.cmp(&b.path));
@@ -204,39 +334,28 @@ impl Backend for CompositeBackend {
         pattern: &str,
         path: Option<&str>,
         glob_filter: Option<&str>,
+        options: GrepOptions,
     ) -> Result<Vec<GrepMatch>, BackendError> {
         let search_path = path.unwrap_or("/");
-
-        // Use get_backend_and_path for consistent routing logic
+        let owner = self.route_for(search_path);
         let (backend, stripped) = self.get_backend_and_path(search_path);
 
-        // Check if this is a routed path (not default backend)
-        let is_routed = !std::ptr::eq(
-            Arc::as_ptr(&backend) as *const (),
-            Arc::as_ptr(&self.default) as *const ()
-        );
-
-        if is_routed {
-            // 특정 경로가 라우트에 매칭되면 해당 백엔드만 검색
-            let mut results = backend.grep(pattern, Some(&stripped), glob_filter).await?;
-
-            for m in &mut results {
-                m.path = self.restore_prefix(&m.path, search_path);
-            }
-
-            return Ok(results);
-        }
-
-        // 전체 검색 (루트 또는 default backend 경로)
-        let mut all_results = self.default.grep(pattern, path, glob_filter).await?;
-
-        for route in &self.routes {
-            let mut route_results = route.backend.grep(pattern, Some("/"), glob_filter).await?;
-            for m in &mut route_results {
-                let prefix = route.prefix.trim_end_matches('/');
-                m.path = format!("{}{}", prefix, m.path);
-            }
-            all_results.extend(route_results);
+        // 경로를 소유한 백엔드 검색 (더 긴 마운트에 가려진 결과는 제외)
+        let mut all_results: Vec<GrepMatch> = backend.grep(pattern, Some(&stripped), glob_filter, options).await?
+            .into_iter()
+            .filter_map(|mut m| {
+                m.path = Self::mounted_path(owner, &m.path);
+                self.is_owned_by(owner, &m.path).then_some(m)
+            })
+            .collect();
+
+        // 검색 경로 하위 마운트로 팬아웃
+        for route in self.mounts_under(search_path) {
+            let route_results = route.backend.grep(pattern, Some("/"), glob_filter, options).await?;
+            all_results.extend(route_results.into_iter().filter_map(|mut m| {
+                m.path = Self::mounted_path(Some(route), &m.path);
+                self.is_owned_by(Some(route), &m.path).then_some(m)
+            }));
         }
 
         Ok(all_results)
@@ -251,6 +370,78 @@ impl Backend for CompositeBackend {
         let (backend, stripped) = self.get_backend_and_path(path);
         backend.delete(&stripped).await
     }
+
+    /// 기본 백엔드와 모든 마운트가 트랜잭션을 지원할 때만 `Some`
+    fn transaction(&self) -> Option<Box<dyn BackendTransaction + '_>> {
+        let default = self.default.transaction()?;
+        let routes = self.routes
+            .iter()
+            .map(|route| route.backend.transaction())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Box::new(CompositeTransaction { backend: self, default, routes }))
+    }
+
+    /// 루트(`/`)를 소유한 백엔드의 디렉토리
+    fn root_dir(&self) -> Option<&Path> {
+        match self.route_for("/") {
+            Some(route) => route.backend.root_dir(),
+            None => self.default.root_dir(),
+        }
+    }
+}
+
+/// 복합 트랜잭션
+///
+/// 경로를 소유한 마운트의 트랜잭션으로 읽기/쓰기를 라우팅합니다.
+///
+/// **Note:** 커밋은 마운트별로 순차 진행되므로 마운트 사이에서는 원자적이지 않습니다.
+struct CompositeTransaction<'a> {
+    backend: &'a CompositeBackend,
+    default: Box<dyn BackendTransaction + 'a>,
+    /// `backend.routes`와 같은 순서
+    routes: Vec<Box<dyn BackendTransaction + 'a>>,
+}
+
+impl CompositeTransaction<'_> {
+    fn route_index(&self, path: &str) -> Option<usize> {
+        let route = self.backend.route_for(path)?;
+        self.backend.routes.iter().position(|r| r.prefix == route.prefix)
+    }
+}
+
+#[async_trait]
+impl BackendTransaction for CompositeTransaction<'_> {
+    async fn read_plain(&self, path: &str) -> Result<String, BackendError> {
+        let stripped = CompositeBackend::stripped_path(self.backend.route_for(path), path);
+        match self.route_index(path) {
+            Some(index) => self.routes[index].read_plain(&stripped).await,
+            None => self.default.read_plain(&stripped).await,
+        }
+    }
+
+    async fn write(&mut self, path: &str, content: &str) -> Result<(), BackendError> {
+        let stripped = CompositeBackend::stripped_path(self.backend.route_for(path), path);
+        match self.route_index(path) {
+            Some(index) => self.routes[index].write(&stripped, content).await,
+            None => self.default.write(&stripped, content).await,
+        }
+    }
+
+    async fn commit(self: Box<Self>) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+        let CompositeTransaction { backend, default, routes } = *self;
+        let mut updates = default.commit().await?;
+
+        for (route, tx) in backend.routes.iter().zip(routes) {
+            if let Some(files) = tx.commit().await? {
+                updates.get_or_insert_with(HashMap::new).extend(
+                    files.into_iter()
+                        .map(|(path, data)| (CompositeBackend::mounted_path(Some(route), &path), data)),
+                );
+            }
+        }
+
+        Ok(updates)
+    }
 }
 
 #[cfg(test)]
@@ -294,7 +485,7 @@ mod tests {
         composite.write("/other.txt", "hello there").await.unwrap();
 
         // 전체 검색
-        let matches = composite.grep("hello", None, None).await.unwrap();
+        let matches = composite.grep("hello", None, None, GrepOptions::default()).await.unwrap();
         assert_eq!(matches.len(), 2);
     }
 
@@ -382,4 +573,158 @@ mod tests {
         let files = composite.ls("/memories").await.unwrap();
         assert!(!files.is_empty(), "Should find files under /memories route");
     }
+
+    #[tokio::test]
+    async fn test_composite_backend_mount_overlapping_prefixes() {
+        let default = Arc::new(MemoryBackend::new());
+        let memory = Arc::new(MemoryBackend::new());
+        let archive = Arc::new(MemoryBackend::new());
+
+        let composite = CompositeBackend::new(default.clone())
+            .mount("/memory", memory.clone())
+            .mount("/memory/archive/", archive.clone());
+
+        composite.write("/memory/a.txt", "a").await.unwrap();
+        composite.write("/memory/archive/b.txt", "b").await.unwrap();
+        composite.write("/c.txt", "c").await.unwrap();
+
+        // 가장 긴 접두사로 라우팅
+        assert!(memory.exists("/a.txt").await.unwrap());
+        assert!(archive.exists("/b.txt").await.unwrap());
+        assert!(!memory.exists("/archive/b.txt").await.unwrap());
+        assert!(default.exists("/c.txt").await.unwrap());
+
+        let root: Vec<_> = composite.ls("/").await.unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(root, vec!["/c.txt", "/memory/"]);
+
+        let nested: Vec<_> = composite.ls("/memory").await.unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(nested, vec!["/memory/a.txt", "/memory/archive/"]);
+
+        let archived: Vec<_> = composite.ls("/memory/archive").await.unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(archived, vec!["/memory/archive/b.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_ls_shows_nested_mount_point() {
+        let composite = CompositeBackend::new(Arc::new(MemoryBackend::new()))
+            .mount("/data/cache", Arc::new(MemoryBackend::new()));
+
+        let root = composite.ls("/").await.unwrap();
+        assert_eq!(root.len(), 1);
+        assert_eq!(root[0].path, "/data/");
+        assert!(root[0].is_dir);
+
+        let data = composite.ls("/data").await.unwrap();
+        assert_eq!(data[0].path, "/data/cache/");
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_grep_fans_out_across_mounts() {
+        let default = Arc::new(MemoryBackend::new());
+        let memory = Arc::new(MemoryBackend::new());
+        let archive = Arc::new(MemoryBackend::new());
+
+        let composite = CompositeBackend::new(default.clone())
+            .mount("/memory", memory.clone())
+            .mount("/memory/archive", archive.clone());
+
+        composite.write("/src/main.txt", "needle in src").await.unwrap();
+        composite.write("/memory/notes.txt", "needle in memory").await.unwrap();
+        composite.write("/memory/archive/old.txt", "needle in archive").await.unwrap();
+
+        let mut all: Vec<_> = composite.grep("needle", None, None, GrepOptions::default()).await.unwrap()
+            .into_iter().map(|m| m.path).collect();
+        all.sort();
+        assert_eq!(all, vec!["/memory/archive/old.txt", "/memory/notes.txt", "/src/main.txt"]);
+
+        // /memory 검색은 하위 마운트까지 포함하고 기본 백엔드는 제외
+        let mut scoped: Vec<_> = composite.grep("needle", Some("/memory"), None, GrepOptions::default()).await.unwrap()
+            .into_iter().map(|m| m.path).collect();
+        scoped.sort();
+        assert_eq!(scoped, vec!["/memory/archive/old.txt", "/memory/notes.txt"]);
+
+        // 마운트와 무관한 경로는 팬아웃하지 않음
+        let src = composite.grep("needle", Some("/src"), None, GrepOptions::default()).await.unwrap();
+        assert_eq!(src.len(), 1);
+        assert_eq!(src[0].path, "/src/main.txt");
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_ls_recursive_includes_mounts() {
+        let composite = CompositeBackend::new(Arc::new(MemoryBackend::new()))
+            .mount("/memory", Arc::new(MemoryBackend::new()))
+            .mount("/memory/archive", Arc::new(MemoryBackend::new()));
+
+        composite.write("/src/main.txt", "main").await.unwrap();
+        composite.write("/memory/notes.txt", "notes").await.unwrap();
+        composite.write("/memory/archive/old.txt", "old").await.unwrap();
+
+        let paths: Vec<_> = composite.ls_recursive("/", 3).await.unwrap()
+            .into_iter().map(|f| f.path).collect();
+        assert_eq!(paths, vec![
+            "memory", "memory/archive", "memory/archive/old.txt", "memory/notes.txt",
+            "src", "src/main.txt",
+        ]);
+
+        // 마운트 내부 경로는 해당 백엔드로 그대로 전달
+        let paths: Vec<_> = composite.ls_recursive("/memory/archive", 2).await.unwrap()
+            .into_iter().map(|f| f.path).collect();
+        assert_eq!(paths, vec!["old.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_read_file_range_routes_to_mount() {
+        let composite = CompositeBackend::new(Arc::new(MemoryBackend::new()))
+            .mount("/docs", Arc::new(MemoryBackend::new()));
+        composite.write("/docs/guide.md", "one\ntwo\nthree").await.unwrap();
+
+        let range = composite.read_file_range("/docs/guide.md", 1, 1).await.unwrap();
+        assert!(range.contains("two"));
+        assert!(!range.contains("three"));
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_transaction_routes_by_prefix() {
+        let default = Arc::new(MemoryBackend::new());
+        let memories = Arc::new(MemoryBackend::new());
+        let composite = CompositeBackend::new(default.clone())
+            .mount("/memories", memories.clone());
+
+        let mut tx = composite.transaction().unwrap();
+        tx.write("/memories/notes.txt", "remember").await.unwrap();
+        tx.write("/todo.txt", "write tests").await.unwrap();
+        assert_eq!(tx.read_plain("/memories/notes.txt").await.unwrap(), "remember");
+        assert!(!memories.exists("/notes.txt").await.unwrap());
+
+        let updates = tx.commit().await.unwrap().unwrap();
+        assert!(updates.contains_key("/memories/notes.txt"));
+        assert!(updates.contains_key("/todo.txt"));
+        assert_eq!(memories.read_plain("/notes.txt").await.unwrap(), "remember");
+        assert_eq!(default.read_plain("/todo.txt").await.unwrap(), "write tests");
+
+        // 마운트의 바이너리 파일은 텍스트로 읽을 수 없음
+        memories.write_bytes("/photo.png", &[0x89, 0x50, 0x00, 0xff]).await.unwrap();
+        let tx = composite.transaction().unwrap();
+        assert!(matches!(
+            tx.read_plain("/memories/photo.png").await,
+            Err(BackendError::Unsupported(_))
+        ));
+        drop(tx);
+
+        // 트랜잭션을 지원하지 않는 마운트가 있으면 None
+        let readonly = Arc::new(crate::backends::ReadOnlyBackend::new(Arc::new(MemoryBackend::new())));
+        let composite = composite.mount("/readonly", readonly);
+        assert!(composite.transaction().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_composite_backend_root_dir_from_root_owner() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let composite = CompositeBackend::new(Arc::new(crate::backends::FilesystemBackend::new(temp.path())))
+            .mount("/memories", Arc::new(MemoryBackend::new()));
+        assert!(composite.root_dir().is_some());
+
+        let composite = CompositeBackend::new(Arc::new(MemoryBackend::new()));
+        assert!(composite.root_dir().is_none());
+    }
 }
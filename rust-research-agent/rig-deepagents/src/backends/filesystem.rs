@@ -4,13 +4,16 @@
 //! Python Reference: deepagents/backends/filesystem.py
 
 use async_trait::async_trait;
-use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 use tokio::fs;
-use glob::Pattern;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use chrono::{DateTime, Utc};
 
-use super::protocol::{Backend, FileInfo, GrepMatch};
+use super::path_utils::GlobMatcher;
+use super::protocol::{Backend, GrepOptions, BackendTransaction, FileInfo, GrepMatch};
 use crate::error::{BackendError, WriteResult, EditResult};
+use crate::state::FileData;
 
 /// 파일시스템 백엔드
 /// Python: FilesystemBackend
@@ -21,23 +24,65 @@ pub struct FilesystemBackend {
     root: PathBuf,
     /// 가상 모드 - 모든 경로를 루트 내부로 제한
     virtual_mode: bool,
+    /// 샌드박스 모드 - 모든 경로를 canonicalize하여 루트 탈출 차단
+    sandboxed: bool,
+    /// 변경 감시자 (첫 `subscribe()` 호출 시 시작)
+    #[cfg(feature = "watch")]
+    watcher: std::sync::Mutex<Option<super::watch::FileWatcher>>,
 }
 
 impl FilesystemBackend {
     pub fn new(root: impl AsRef<Path>) -> Self {
-        Self {
-            root: root.as_ref().to_path_buf(),
-            virtual_mode: true,
-        }
+        Self::with_virtual_mode(root, true)
     }
 
     pub fn with_virtual_mode(root: impl AsRef<Path>, virtual_mode: bool) -> Self {
         Self {
             root: root.as_ref().to_path_buf(),
             virtual_mode,
+            sandboxed: false,
+            #[cfg(feature = "watch")]
+            watcher: std::sync::Mutex::new(None),
         }
     }
 
+    /// 샌드박스 백엔드 생성
+    ///
+    /// 신뢰할 수 없는 모델 출력이 파일 작업을 결정할 때 사용합니다.
+    /// 모든 경로는 루트 기준 가상 경로로 해석되며 (`/etc/passwd`는 `{root}/etc/passwd`),
+    /// `..`로 루트 위로 올라가거나 심볼릭 링크가 루트 외부를 가리키면
+    /// `BackendError::PathEscape`를 반환합니다. 루트 디렉토리는 존재해야 합니다.
+    pub fn new_sandboxed(root: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let root = root.as_ref().canonicalize()
+            .map_err(|e| BackendError::Io(format!("{}: {}", root.as_ref().display(), e)))?;
+
+        Ok(Self {
+            sandboxed: true,
+            ..Self::with_virtual_mode(root, true)
+        })
+    }
+
+    /// 파일 변경 이벤트 구독 (`watch` feature)
+    ///
+    /// 루트 디렉토리 하위의 생성/수정/삭제 이벤트를 스트리밍합니다.
+    /// 감시자는 첫 호출 시 시작되어 백엔드가 drop될 때까지 유지되며,
+    /// 이후 호출은 같은 감시자에 구독자를 추가합니다.
+    ///
+    /// 같은 경로/종류의 이벤트는 50ms 간격으로 debounce됩니다
+    /// (자세한 내용은 [`super::watch`] 참고).
+    #[cfg(feature = "watch")]
+    pub fn subscribe(&self) -> Result<tokio::sync::broadcast::Receiver<super::watch::FileEvent>, BackendError> {
+        let mut guard = self.watcher.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(watcher) = guard.as_ref() {
+            return Ok(watcher.subscribe());
+        }
+
+        let watcher = super::watch::FileWatcher::start(&self.root, self.virtual_mode)?;
+        let receiver = watcher.subscribe();
+        *guard = Some(watcher);
+        Ok(receiver)
+    }
+
     /// 경로 검증 및 해결
     ///
     /// # Security: 심볼릭 링크를 통한 루트 탈출 방지
@@ -45,6 +90,10 @@ impl FilesystemBackend {
     /// 부모 디렉토리가 심볼릭 링크인 경우 이를 canonicalize하여
     /// 루트 외부로의 탈출을 차단합니다.
     fn resolve_path(&self, path: &str) -> Result<PathBuf, BackendError> {
+        if self.sandboxed {
+            return self.resolve_sandboxed(path);
+        }
+
         if self.virtual_mode {
             // 경로 탐색 방지
             if path.contains("..") || path.starts_with("~") {
@@ -96,6 +145,46 @@ impl FilesystemBackend {
         }
     }
 
+    /// 샌드박스 경로 해결
+    ///
+    /// `..`는 루트 아래에서만 허용되도록 어휘적으로 처리한 뒤, 존재하는 가장 깊은
+    /// 상위 경로를 canonicalize하여 심볼릭 링크가 루트 외부로 향하는지 확인합니다.
+    /// 대상을 찾을 수 없는 심볼릭 링크도 탈출로 간주합니다.
+    fn resolve_sandboxed(&self, path: &str) -> Result<PathBuf, BackendError> {
+        let escape = || BackendError::PathEscape(path.to_string());
+
+        let mut target = self.root.clone();
+        let mut depth = 0usize;
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(segment) => {
+                    target.push(segment);
+                    depth += 1;
+                }
+                Component::ParentDir => {
+                    if depth == 0 {
+                        return Err(escape());
+                    }
+                    target.pop();
+                    depth -= 1;
+                }
+                Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+            }
+        }
+
+        // 존재하는(심볼릭 링크 포함) 가장 깊은 경로 - 루트는 항상 존재
+        let existing = target
+            .ancestors()
+            .find(|p| p.symlink_metadata().is_ok())
+            .unwrap_or(&self.root);
+        let canonical = existing.canonicalize().map_err(|_| escape())?;
+        if !canonical.starts_with(&self.root) {
+            return Err(escape());
+        }
+
+        Ok(target)
+    }
+
     /// 가상 경로로 변환
     fn to_virtual_path(&self, path: &Path) -> String {
         if self.virtual_mode {
@@ -117,6 +206,105 @@ impl FilesystemBackend {
     }
 }
 
+/// 스테이징된 파일 (임시 파일 + 내용)
+struct StagedFile {
+    temp: PathBuf,
+    content: String,
+}
+
+/// 파일시스템 트랜잭션
+///
+/// 쓰기는 대상과 같은 디렉토리의 임시 파일(`.{name}.{uuid}.tmp`)에 기록되고,
+/// 커밋 시 `rename`으로 교체됩니다. 커밋 없이 drop되면 임시 파일과
+/// 스테이징 중 새로 만든 디렉토리를 삭제합니다.
+///
+/// **Note:** 각 `rename`은 원자적이지만 여러 파일의 교체는 순차적입니다.
+/// 모든 내용이 임시 파일에 기록된 뒤에만 교체를 시작하므로
+/// 스테이징 도중의 실패는 대상 파일에 영향을 주지 않습니다.
+struct FilesystemTransaction<'a> {
+    backend: &'a FilesystemBackend,
+    staged: HashMap<PathBuf, StagedFile>,
+    /// 스테이징 중 새로 만든 디렉토리 (롤백 시 제거)
+    created_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl BackendTransaction for FilesystemTransaction<'_> {
+    async fn read_plain(&self, path: &str) -> Result<String, BackendError> {
+        let resolved = self.backend.resolve_path(path)?;
+        if let Some(staged) = self.staged.get(&resolved) {
+            return Ok(staged.content.clone());
+        }
+
+        if !resolved.is_file() {
+            return Err(BackendError::FileNotFound(path.to_string()));
+        }
+        fs::read_to_string(&resolved).await
+            .map_err(|e| BackendError::Io(e.to_string()))
+    }
+
+    async fn write(&mut self, path: &str, content: &str) -> Result<(), BackendError> {
+        let resolved = self.backend.resolve_path(path)?;
+
+        let temp = match self.staged.get(&resolved) {
+            Some(existing) => existing.temp.clone(),
+            None => {
+                let parent = resolved.parent()
+                    .ok_or_else(|| BackendError::InvalidPath(path.to_string()))?;
+                let missing: Vec<PathBuf> = parent.ancestors()
+                    .take_while(|dir| !dir.exists())
+                    .map(Path::to_path_buf)
+                    .collect();
+                fs::create_dir_all(parent).await
+                    .map_err(|e| BackendError::Io(e.to_string()))?;
+                self.created_dirs.extend(missing);
+
+                let file_name = resolved.file_name()
+                    .ok_or_else(|| BackendError::InvalidPath(path.to_string()))?
+                    .to_string_lossy();
+                parent.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()))
+            }
+        };
+
+        fs::write(&temp, content).await
+            .map_err(|e| BackendError::Io(e.to_string()))?;
+
+        self.staged.insert(resolved, StagedFile { temp, content: content.to_string() });
+        Ok(())
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+        let staged: Vec<_> = self.staged.drain().collect();
+
+        for (index, (target, file)) in staged.iter().enumerate() {
+            if let Err(e) = fs::rename(&file.temp, target).await {
+                // 남은 임시 파일 정리
+                for (_, rest) in &staged[index..] {
+                    let _ = std::fs::remove_file(&rest.temp);
+                }
+                return Err(BackendError::Io(e.to_string()));
+            }
+        }
+
+        self.created_dirs.clear();
+        // 외부 백엔드이므로 files_update = None
+        Ok(None)
+    }
+}
+
+impl Drop for FilesystemTransaction<'_> {
+    fn drop(&mut self) {
+        for staged in self.staged.values() {
+            let _ = std::fs::remove_file(&staged.temp);
+        }
+        // 깊은 디렉토리부터 제거 (다른 내용이 생긴 디렉토리는 `remove_dir`가 남겨 둠)
+        self.created_dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in &self.created_dirs {
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
 #[async_trait]
 impl Backend for FilesystemBackend {
     async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
@@ -160,21 +348,35 @@ impl Backend for FilesystemBackend {
     }
 
     async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        self.read_file_range(path, offset, limit).await
+    }
+
+    /// 필요한 라인까지만 스트리밍으로 읽음 (파일 전체를 메모리에 올리지 않음)
+    async fn read_file_range(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
         let resolved = self.resolve_path(path)?;
 
         if !resolved.exists() || !resolved.is_file() {
             return Err(BackendError::FileNotFound(path.to_string()));
         }
 
-        let content = fs::read_to_string(&resolved).await
+        let file = fs::File::open(&resolved).await
             .map_err(|e| BackendError::Io(e.to_string()))?;
+        let mut lines = BufReader::new(file).lines();
+
+        let mut selected = Vec::new();
+        let mut index = 0;
+        while selected.len() < limit {
+            let Some(line) = lines.next_line().await
+                .map_err(|e| BackendError::Io(e.to_string()))? else {
+                break;
+            };
+            if index >= offset {
+                selected.push(line);
+            }
+            index += 1;
+        }
 
-        let lines: Vec<&str> = content.lines().collect();
-        let start = offset.min(lines.len());
-        let end = (offset + limit).min(lines.len());
-
-        let selected = lines[start..end].join("\n");
-        Ok(Self::format_with_line_numbers(&selected, offset))
+        Ok(Self::format_with_line_numbers(&selected.join("\n"), offset))
     }
 
     async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError> {
@@ -200,6 +402,41 @@ impl Backend for FilesystemBackend {
         Ok(WriteResult::success_external(path))
     }
 
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        let resolved = self.resolve_path(path)?;
+
+        if !resolved.exists() {
+            return Err(BackendError::FileNotFound(path.to_string()));
+        }
+        if resolved.is_dir() {
+            return Err(BackendError::IsDirectory(path.to_string()));
+        }
+
+        fs::read(&resolved).await
+            .map_err(|e| BackendError::Io(e.to_string()))
+    }
+
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        let resolved = self.resolve_path(path)?;
+
+        if resolved.exists() {
+            return Ok(WriteResult::error(&format!(
+                "Cannot write to {} because it already exists. Read and then make an edit.",
+                path
+            )));
+        }
+
+        if let Some(parent) = resolved.parent() {
+            fs::create_dir_all(parent).await
+                .map_err(|e| BackendError::Io(e.to_string()))?;
+        }
+
+        fs::write(&resolved, content).await
+            .map_err(|e| BackendError::Io(e.to_string()))?;
+
+        Ok(WriteResult::success_external(path))
+    }
+
     async fn edit(
         &self,
         path: &str,
@@ -249,8 +486,7 @@ impl Backend for FilesystemBackend {
             return Ok(vec![]);
         }
 
-        let glob_pattern = Pattern::new(pattern)
-            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+        let glob_pattern = GlobMatcher::new(pattern)?;
 
         let mut results = Vec::new();
 
@@ -290,6 +526,7 @@ impl Backend for FilesystemBackend {
         pattern: &str,
         path: Option<&str>,
         glob_filter: Option<&str>,
+        options: GrepOptions,
     ) -> Result<Vec<GrepMatch>, BackendError> {
         let search_path = path.unwrap_or("/");
         let resolved = self.resolve_path(search_path)?;
@@ -305,9 +542,9 @@ impl Backend for FilesystemBackend {
             } else {
                 format!("**/{}", g)
             };
-            Pattern::new(&normalized)
-        }).transpose()
-            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+            GlobMatcher::new(&normalized)
+        }).transpose()?;
+        let matcher = options.matcher(pattern)?;
 
         let mut results = Vec::new();
         let walker = walkdir::WalkDir::new(&resolved);
@@ -343,11 +580,7 @@ impl Backend for FilesystemBackend {
             let virt_path = self.to_virtual_path(entry.path());
 
             // 리터럴 검색
-            for (line_num, line) in content.lines().enumerate() {
-                if line.contains(pattern) {
-                    results.push(GrepMatch::new(&virt_path, line_num + 1, line));
-                }
-            }
+            results.extend(matcher.search(&virt_path, &content));
         }
 
         Ok(results)
@@ -370,6 +603,18 @@ impl Backend for FilesystemBackend {
 
         Ok(())
     }
+
+    fn transaction(&self) -> Option<Box<dyn BackendTransaction + '_>> {
+        Some(Box::new(FilesystemTransaction {
+            backend: self,
+            staged: HashMap::new(),
+            created_dirs: Vec::new(),
+        }))
+    }
+
+    fn root_dir(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
 }
 
 #[cfg(test)]
@@ -409,6 +654,63 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_sandboxed_backend_rejects_traversal() {
+        let temp = TempDir::new().unwrap();
+        let backend = FilesystemBackend::new_sandboxed(temp.path()).unwrap();
+        backend.write("/docs/notes.txt", "inside").await.unwrap();
+
+        for path in ["../../etc/passwd", "/docs/../../etc/passwd", "docs/../../../tmp"] {
+            let result = backend.read(path, 0, 100).await;
+            assert!(matches!(result, Err(BackendError::PathEscape(_))), "{}", path);
+        }
+        let result = backend.write("/../outside.txt", "pwned").await;
+        assert!(matches!(result, Err(BackendError::PathEscape(_))));
+
+        // 루트 내부에 머무는 `..`는 허용
+        let content = backend.read("/docs/../docs/./notes.txt", 0, 100).await.unwrap();
+        assert!(content.contains("inside"));
+
+        // 절대 경로는 루트 기준으로 해석되어 호스트 파일에 닿지 않음
+        let result = backend.read("/etc/passwd", 0, 100).await;
+        assert!(matches!(result, Err(BackendError::FileNotFound(_))));
+        backend.write("/etc/passwd", "sandboxed").await.unwrap();
+        assert!(temp.path().join("etc/passwd").is_file());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_sandboxed_backend_rejects_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret data").unwrap();
+
+        symlink(outside.path(), root.path().join("escape_dir")).unwrap();
+        symlink(outside.path().join("secret.txt"), root.path().join("escape_file")).unwrap();
+        symlink(outside.path().join("missing.txt"), root.path().join("dangling")).unwrap();
+        std::fs::write(root.path().join("inner.txt"), "inner").unwrap();
+        symlink(root.path().join("inner.txt"), root.path().join("inner_link")).unwrap();
+
+        let backend = FilesystemBackend::new_sandboxed(root.path()).unwrap();
+
+        for path in ["/escape_dir/secret.txt", "/escape_file"] {
+            let result = backend.read(path, 0, 100).await;
+            assert!(matches!(result, Err(BackendError::PathEscape(_))), "{}", path);
+        }
+        for path in ["/escape_dir/new.txt", "/dangling"] {
+            let result = backend.write(path, "pwned").await;
+            assert!(matches!(result, Err(BackendError::PathEscape(_))), "{}", path);
+        }
+        assert!(!outside.path().join("new.txt").exists());
+        assert!(!outside.path().join("missing.txt").exists());
+
+        // 루트 내부를 가리키는 링크는 허용
+        let content = backend.read("/inner_link", 0, 100).await.unwrap();
+        assert!(content.contains("inner"));
+    }
+
     #[tokio::test]
     async fn test_filesystem_backend_write_and_read() {
         let temp = TempDir::new().unwrap();
@@ -431,6 +733,68 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_filesystem_backend_binary_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let backend = FilesystemBackend::new(temp.path());
+        let bytes: Vec<u8> = (0..=255).collect();
+
+        let result = backend.write_bytes("/images/logo.png", &bytes).await.unwrap();
+        assert!(result.is_ok());
+
+        // 디스크에는 원본 바이트가 그대로 저장됨
+        let on_disk = std::fs::read(temp.path().join("images/logo.png")).unwrap();
+        assert_eq!(on_disk, bytes);
+        assert_eq!(backend.read_bytes("/images/logo.png").await.unwrap(), bytes);
+
+        let again = backend.write_bytes("/images/logo.png", &bytes).await.unwrap();
+        assert!(!again.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_backend_transaction_commit() {
+        let temp = TempDir::new().unwrap();
+        let backend = FilesystemBackend::new(temp.path());
+        backend.write("/a.txt", "old a").await.unwrap();
+
+        let mut tx = backend.transaction().unwrap();
+        tx.write("/a.txt", "new a").await.unwrap();
+        tx.write("/nested/b.txt", "new b").await.unwrap();
+
+        // 커밋 전에는 원본 유지
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("old a"));
+        assert!(!backend.exists("/nested/b.txt").await.unwrap());
+
+        assert!(tx.commit().await.unwrap().is_none());
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("new a"));
+        assert!(backend.read("/nested/b.txt", 0, 10).await.unwrap().contains("new b"));
+
+        // 임시 파일이 남지 않아야 함
+        let leftovers = backend.glob("**/*.tmp", "/").await.unwrap();
+        assert!(leftovers.is_empty(), "temp files left behind: {:?}", leftovers);
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_backend_transaction_rollback_on_drop() {
+        let temp = TempDir::new().unwrap();
+        let backend = FilesystemBackend::new(temp.path());
+        backend.write("/a.txt", "original").await.unwrap();
+
+        {
+            let mut tx = backend.transaction().unwrap();
+            tx.write("/a.txt", "changed").await.unwrap();
+            tx.write("/b.txt", "new").await.unwrap();
+            tx.write("/nested/deep/c.txt", "new").await.unwrap();
+            assert_eq!(tx.read_plain("/a.txt").await.unwrap(), "changed");
+        }
+
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("original"));
+        assert!(!backend.exists("/b.txt").await.unwrap());
+
+        let entries = std::fs::read_dir(temp.path()).unwrap().count();
+        assert_eq!(entries, 1, "only the original file should remain");
+    }
+
     #[tokio::test]
     async fn test_filesystem_backend_grep_path_glob() {
         let temp = TempDir::new().unwrap();
@@ -445,7 +809,7 @@ mod tests {
         let backend = FilesystemBackend::new(temp.path());
 
         // **/*.rs 패턴으로 검색 - .rs 파일만 매칭
-        let results = backend.grep("fn", None, Some("**/*.rs")).await.unwrap();
+        let results = backend.grep("fn", None, Some("**/*.rs"), GrepOptions::default()).await.unwrap();
 
         assert!(!results.is_empty(), "Should find matches in .rs files");
         assert!(
@@ -454,7 +818,7 @@ mod tests {
         );
 
         // *.rs 패턴도 작동해야 함 (자동으로 **/ 접두사 추가)
-        let results2 = backend.grep("fn", None, Some("*.rs")).await.unwrap();
+        let results2 = backend.grep("fn", None, Some("*.rs"), GrepOptions::default()).await.unwrap();
         assert!(!results2.is_empty(), "*.rs pattern should also work");
     }
 }
@@ -8,15 +8,24 @@
 //! - `grep`는 리터럴 검색 (정규식 아님)
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
-use glob::Pattern;
 
-use super::protocol::{Backend, FileInfo, GrepMatch};
-use super::path_utils::{normalize_path, is_under_path};
+use super::protocol::{Backend, GrepOptions, BackendTransaction, FileInfo, GrepMatch};
+use super::path_utils::{normalize_path, is_under_path, GlobMatcher};
 use crate::error::{BackendError, WriteResult, EditResult};
 use crate::state::FileData;
 
+/// 인메모리 파일시스템 스냅샷
+///
+/// `MemoryBackend::snapshot()`으로 캡처하고 `restore()`로 복원합니다.
+/// JSON 등으로 직렬화하여 실행 간에 보존하거나 서브에이전트의 초기 파일 상태로 사용할 수 있습니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub files: HashMap<String, FileData>,
+}
+
 /// 인메모리 백엔드
 /// Python: StateBackend - 상태에 파일 저장
 ///
@@ -39,6 +48,23 @@ impl MemoryBackend {
         }
     }
 
+    /// 현재 파일 상태 캡처
+    pub async fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot {
+            files: self.files.read().await.clone(),
+        }
+    }
+
+    /// 스냅샷으로 복원 (현재 파일은 모두 교체됨)
+    pub async fn restore(&self, snapshot: &MemorySnapshot) {
+        *self.files.write().await = snapshot.files.clone();
+    }
+
+    /// 모든 파일 삭제
+    pub async fn clear(&self) {
+        self.files.write().await.clear();
+    }
+
     /// 라인 번호 포맷팅
     fn format_with_line_numbers(content: &str, offset: usize) -> String {
         content
@@ -50,6 +76,63 @@ impl MemoryBackend {
     }
 }
 
+/// 인메모리 트랜잭션
+///
+/// 스테이징 `HashMap`에 쓰기를 모았다가 커밋 시 단일 쓰기 잠금 안에서 반영합니다.
+struct MemoryTransaction<'a> {
+    backend: &'a MemoryBackend,
+    staged: HashMap<String, String>,
+}
+
+#[async_trait]
+impl BackendTransaction for MemoryTransaction<'_> {
+    async fn read_plain(&self, path: &str) -> Result<String, BackendError> {
+        let path = normalize_path(path)?;
+        if let Some(content) = self.staged.get(&path) {
+            return Ok(content.clone());
+        }
+
+        let files = self.backend.files.read().await;
+        match files.get(&path) {
+            // base64 내용을 텍스트로 편집하면 커밋 시 is_binary가 해제되어 파일이 손상됨
+            Some(f) if f.is_binary => Err(BackendError::Unsupported(format!(
+                "Cannot read binary file {} as text", path
+            ))),
+            Some(f) => Ok(f.as_string()),
+            None => Err(BackendError::FileNotFound(path)),
+        }
+    }
+
+    async fn write(&mut self, path: &str, content: &str) -> Result<(), BackendError> {
+        let path = normalize_path(path)?;
+        self.staged.insert(path, content.to_string());
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+        let mut files = self.backend.files.write().await;
+        let mut updates = HashMap::new();
+
+        for (path, content) in self.staged {
+            let file_data = match files.get_mut(&path) {
+                Some(existing) => {
+                    existing.update(&content);
+                    existing.clone()
+                }
+                None => {
+                    let created = FileData::new(&content);
+                    files.insert(path.clone(), created.clone());
+                    created
+                }
+            };
+            updates.insert(path, file_data);
+        }
+
+        // 체크포인트 백엔드이므로 files_update 포함
+        Ok(Some(updates))
+    }
+}
+
 impl Default for MemoryBackend {
     fn default() -> Self {
         Self::new()
@@ -91,7 +174,7 @@ impl Backend for MemoryBackend {
                 }
             } else if !relative.is_empty() {
                 // 파일
-                let size = data.content.iter().map(|s| s.len()).sum::<usize>() as u64;
+                let size = data.size() as u64;
                 results.push(FileInfo::file_with_time(
                     file_path,
                     size,
@@ -139,6 +222,33 @@ impl Backend for MemoryBackend {
         Ok(WriteResult::success_with_update(&path, file_data))
     }
 
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        let path = normalize_path(path)?;
+        let files = self.files.read().await;
+
+        let file = files.get(&path).ok_or_else(|| BackendError::FileNotFound(path.clone()))?;
+        file.to_bytes()
+            .map_err(|e| BackendError::Io(format!("Corrupted binary data in {}: {}", path, e)))
+    }
+
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        let path = normalize_path(path)?;
+        let mut files = self.files.write().await;
+
+        if files.contains_key(&path) {
+            return Ok(WriteResult::error(&format!(
+                "Cannot write to {} because it already exists. Read and then make an edit, or write to a new path.",
+                path
+            )));
+        }
+
+        let file_data = FileData::from_bytes(content);
+        files.insert(path.clone(), file_data.clone());
+
+        // 체크포인트 백엔드이므로 files_update 포함
+        Ok(WriteResult::success_with_update(&path, file_data))
+    }
+
     async fn edit(
         &self,
         path: &str,
@@ -151,6 +261,10 @@ impl Backend for MemoryBackend {
 
         let file = files.get_mut(&path).ok_or_else(|| BackendError::FileNotFound(path.clone()))?;
 
+        if file.is_binary {
+            return Ok(EditResult::error(&format!("Cannot edit binary file {}", path)));
+        }
+
         let content = file.as_string();
         let occurrences = content.matches(old_string).count();
 
@@ -183,8 +297,7 @@ impl Backend for MemoryBackend {
         let base = normalize_path(base_path)?;
         let files = self.files.read().await;
 
-        let glob_pattern = Pattern::new(pattern)
-            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+        let glob_pattern = GlobMatcher::new(pattern)?;
 
         let mut results = Vec::new();
         for (file_path, data) in files.iter() {
@@ -195,7 +308,7 @@ impl Backend for MemoryBackend {
 
             let match_path = file_path.trim_start_matches('/');
             if glob_pattern.matches(match_path) {
-                let size = data.content.iter().map(|s| s.len()).sum::<usize>() as u64;
+                let size = data.size() as u64;
                 results.push(FileInfo::file_with_time(
                     file_path,
                     size,
@@ -217,11 +330,12 @@ impl Backend for MemoryBackend {
         pattern: &str,
         path: Option<&str>,
         glob_filter: Option<&str>,
+        options: GrepOptions,
     ) -> Result<Vec<GrepMatch>, BackendError> {
         let files = self.files.read().await;
 
-        let glob_pattern = glob_filter.map(Pattern::new).transpose()
-            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+        let glob_pattern = glob_filter.map(GlobMatcher::new).transpose()?;
+        let matcher = options.matcher(pattern)?;
 
         let mut results = Vec::new();
 
@@ -241,10 +355,20 @@ impl Backend for MemoryBackend {
                 }
             }
 
-            // 리터럴 검색 (정규식 아님)
-            for (line_num, line) in data.content.iter().enumerate() {
-                if line.contains(pattern) {
-                    results.push(GrepMatch::new(file_path, line_num + 1, line));
+            // 바이너리 파일은 검색 대상에서 제외
+            if data.is_binary {
+                continue;
+            }
+
+            // 리터럴 검색 (GrepOptions::regex가 켜져 있으면 정규식)
+            if matcher.is_multiline() {
+                results.extend(matcher.search(file_path, &data.as_string()));
+            } else {
+                for (line_num, line) in data.content.iter().enumerate() {
+                    if matcher.is_match(line) {
+                        let m = GrepMatch::new(file_path, line_num + 1, line);
+                        results.push(matcher.add_context(m, &data.content));
+                    }
                 }
             }
         }
@@ -268,6 +392,13 @@ impl Backend for MemoryBackend {
 
         Ok(())
     }
+
+    fn transaction(&self) -> Option<Box<dyn BackendTransaction + '_>> {
+        Some(Box::new(MemoryTransaction {
+            backend: self,
+            staged: HashMap::new(),
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -338,16 +469,137 @@ mod tests {
         assert_eq!(files.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_memory_backend_glob_brace_expansion() {
+        let backend = MemoryBackend::new();
+        backend.write("/src/main.rs", "fn main()").await.unwrap();
+        backend.write("/src/nested/deep/Cargo.toml", "[package]").await.unwrap();
+        backend.write("/src/readme.md", "# Readme").await.unwrap();
+        backend.write("/docs/lib.rs", "pub mod").await.unwrap();
+
+        let files = backend.glob("src/**/*.{rs,toml}", "/").await.unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["/src/main.rs", "/src/nested/deep/Cargo.toml"]);
+
+        let files = backend.glob("{docs,src}/*.{md,rs}", "/").await.unwrap();
+        assert_eq!(files.len(), 3);
+
+        let matches = backend
+            .grep("pub", None, Some("**/*.{rs,toml}"), GrepOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_memory_backend_grep_literal() {
         let backend = MemoryBackend::new();
         backend.write("/test.rs", "fn main() {\n    println!(\"hello\");\n}").await.unwrap();
 
         // 리터럴 검색 - 정규식 메타문자가 리터럴로 처리됨
-        let matches = backend.grep("()", None, None).await.unwrap();
+        let matches = backend.grep("()", None, None, GrepOptions::default()).await.unwrap();
         assert!(!matches.is_empty()); // "()" 를 리터럴로 찾음
     }
 
+    #[tokio::test]
+    async fn test_memory_backend_grep_glob_with_regex() {
+        let backend = MemoryBackend::new();
+        backend.write("/src/lib.rs", "// TODO(alice): split module
+fn lib() {}").await.unwrap();
+        backend.write("/src/util/io.rs", "fn read() {}
+// TODO(bob): buffer").await.unwrap();
+        backend.write("/src/util/io.rs.bak", "// TODO(carol): stale").await.unwrap();
+        backend.write("/docs/notes.md", "TODO(dave): write docs").await.unwrap();
+
+        let options = GrepOptions::default().with_regex(true);
+        let matches = backend.grep_glob(r"TODO\((\w+)\)", "**/*.rs", options).await.unwrap();
+
+        let found: Vec<_> = matches.iter().map(|m| (m.path.as_str(), m.line)).collect();
+        assert_eq!(found, vec![("/src/lib.rs", 1), ("/src/util/io.rs", 2)]);
+
+        // 잘못된 정규식은 패턴 에러
+        let err = backend.grep_glob("TODO(", "**/*.rs", options).await.unwrap_err();
+        assert!(matches!(err, BackendError::Pattern(_)));
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_grep_glob_caps_matches() {
+        use crate::backends::GREP_GLOB_MAX_MATCHES;
+
+        let backend = MemoryBackend::new();
+        let content = vec!["hit"; GREP_GLOB_MAX_MATCHES].join("\n");
+        backend.write("/a/one.txt", &content).await.unwrap();
+        backend.write("/b/two.txt", &content).await.unwrap();
+
+        let matches = backend.grep_glob("hit", "**/*.txt", GrepOptions::default()).await.unwrap();
+        assert_eq!(matches.len(), GREP_GLOB_MAX_MATCHES);
+        assert!(matches.iter().all(|m| m.path == "/a/one.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_grep_context_at_file_boundaries() {
+        let backend = MemoryBackend::new();
+        backend.write("/notes.md", "first hit\nmiddle\nlast hit").await.unwrap();
+
+        // 컨텍스트가 파일 범위를 넘어도 경계에서 잘림
+        let options = GrepOptions::default().with_context(5, 5);
+        let matches = backend.grep("hit", None, None, options).await.unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].context_before.is_empty());
+        assert_eq!(matches[0].context_after, vec!["middle", "last hit"]);
+        assert_eq!(matches[1].context_before, vec!["first hit", "middle"]);
+        assert!(matches[1].context_after.is_empty());
+
+        // 여러 줄 매칭은 매칭이 끝난 라인 이후부터 after 컨텍스트
+        let options = GrepOptions::default().with_multiline(true).with_context(1, 1);
+        let matches = backend.grep("hit\nmiddle", None, None, options).await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].context_before.is_empty());
+        assert_eq!(matches[0].context_after, vec!["last hit"]);
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_binary_roundtrip() {
+        let backend = MemoryBackend::new();
+        let bytes: Vec<u8> = vec![0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0x0a, 0x80];
+
+        let result = backend.write_bytes("/doc.pdf", &bytes).await.unwrap();
+        assert!(result.is_ok());
+        assert!(result.files_update.unwrap()["/doc.pdf"].is_binary);
+
+        assert_eq!(backend.read_bytes("/doc.pdf").await.unwrap(), bytes);
+
+        // 바이너리 파일은 grep/edit 대상이 아님
+        assert!(backend.grep("P", None, None, GrepOptions::default()).await.unwrap().is_empty());
+        assert!(!backend.edit("/doc.pdf", "J", "K", false).await.unwrap().is_ok());
+
+        let files = backend.ls("/").await.unwrap();
+        assert_eq!(files[0].size, Some(bytes.len() as u64));
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_snapshot_restore_roundtrip() {
+        let backend = MemoryBackend::new();
+        backend.write("/notes.md", "# Notes\nline two").await.unwrap();
+        backend.write("/data/blob.bin", "placeholder").await.unwrap();
+
+        let snapshot = backend.snapshot().await;
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        backend.clear().await;
+        assert!(backend.ls("/").await.unwrap().is_empty());
+
+        let restored: MemorySnapshot = serde_json::from_str(&json).unwrap();
+        backend.restore(&restored).await;
+
+        assert_eq!(backend.read_plain("/notes.md").await.unwrap(), "# Notes\nline two");
+        assert!(backend.exists("/data/blob.bin").await.unwrap());
+
+        // 스냅샷으로 새 백엔드 시드
+        let seeded = MemoryBackend::with_files(restored.files);
+        assert_eq!(seeded.read_plain("/notes.md").await.unwrap(), "# Notes\nline two");
+    }
+
     #[tokio::test]
     async fn test_memory_backend_delete() {
         let backend = MemoryBackend::new();
@@ -375,6 +627,41 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_memory_backend_transaction_commit() {
+        let backend = MemoryBackend::new();
+        backend.write("/a.txt", "old a").await.unwrap();
+
+        let mut tx = backend.transaction().unwrap();
+        let content = tx.read_plain("/a.txt").await.unwrap();
+        tx.write("/a.txt", &content.replace("old", "new")).await.unwrap();
+        tx.write("/b.txt", "new b").await.unwrap();
+
+        // 커밋 전에는 반영되지 않음
+        assert!(!backend.exists("/b.txt").await.unwrap());
+        assert_eq!(tx.read_plain("/b.txt").await.unwrap(), "new b");
+
+        let updates = tx.commit().await.unwrap().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("new a"));
+        assert!(backend.read("/b.txt", 0, 10).await.unwrap().contains("new b"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_transaction_rollback_on_drop() {
+        let backend = MemoryBackend::new();
+        backend.write("/a.txt", "original").await.unwrap();
+
+        {
+            let mut tx = backend.transaction().unwrap();
+            tx.write("/a.txt", "changed").await.unwrap();
+            tx.write("/b.txt", "new").await.unwrap();
+        }
+
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("original"));
+        assert!(!backend.exists("/b.txt").await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_memory_backend_glob_respects_base_path() {
         let backend = MemoryBackend::new();
@@ -7,10 +7,27 @@ pub mod protocol;
 pub mod memory;
 pub mod filesystem;
 pub mod composite;
+pub mod readonly;
+pub mod quota;
+pub mod overlay;
 pub mod path_utils;
+#[cfg(feature = "backend-s3")]
+pub mod s3;
+#[cfg(feature = "watch")]
+pub mod watch;
 
-pub use protocol::{Backend, FileInfo, GrepMatch};
-pub use memory::MemoryBackend;
+pub use protocol::{
+    Backend, BackendTransaction, FileInfo, GrepMatch, GrepMatcher, GrepOptions,
+    GREP_GLOB_MAX_MATCHES, LS_RECURSIVE_MAX_ENTRIES,
+};
+pub use memory::{MemoryBackend, MemorySnapshot};
 pub use filesystem::FilesystemBackend;
 pub use composite::CompositeBackend;
-pub use path_utils::{normalize_path, is_under_path};
+pub use readonly::ReadOnlyBackend;
+pub use quota::QuotaBackend;
+pub use overlay::OverlayBackend;
+pub use path_utils::{normalize_path, is_under_path, GlobMatcher};
+#[cfg(feature = "backend-s3")]
+pub use s3::S3Backend;
+#[cfg(feature = "watch")]
+pub use watch::{FileEvent, FileEventKind};
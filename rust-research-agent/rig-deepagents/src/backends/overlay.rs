@@ -0,0 +1,513 @@
+// src/backends/overlay.rs
+//! Copy-on-write 오버레이 백엔드
+//!
+//! 읽기는 기반(base) 백엔드까지 내려가고, 쓰기는 인메모리 상위(upper) 레이어에만 기록됩니다.
+//! 실제 파일시스템을 건드리지 않고 변경을 탐색해야 하는 SubAgent 샌드박스에 사용하며,
+//! 결과가 마음에 들면 `commit()`으로 기반 백엔드에 반영합니다.
+
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::memory::MemoryBackend;
+use super::path_utils::normalize_path;
+use super::protocol::{Backend, FileInfo, GrepMatch, GrepOptions};
+use crate::error::{BackendError, EditResult, WriteResult};
+
+/// 오버레이 백엔드
+///
+/// - 읽기: 상위 레이어에 있으면 상위, 없으면 기반 백엔드
+/// - 쓰기/편집: 상위 레이어에만 기록 (기반 파일 편집 시 먼저 상위로 복사)
+/// - 삭제: 상위 파일은 제거하고, 기반 파일은 가림 표시(whiteout)만 남김
+/// - `ls`/`glob`/`grep`: 두 레이어를 합친 뷰 (상위가 우선)
+///
+/// `root_dir()`은 `None`이므로 `ShellTool`처럼 디스크에 직접 접근하는 도구는 오버레이를 우회할 수 없습니다.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let workspace: Arc<dyn Backend> = Arc::new(FilesystemBackend::new("./workspace"));
+/// let sandbox = Arc::new(OverlayBackend::new(workspace));
+///
+/// // 서브에이전트는 sandbox에서 자유롭게 수정
+/// sandbox.edit("/src/lib.rs", "old", "new", false).await?;
+///
+/// // 검토 후 반영하거나 폐기
+/// sandbox.commit().await?;
+/// ```
+pub struct OverlayBackend {
+    base: Arc<dyn Backend>,
+    /// 상위 레이어의 조회용 뷰 (`ls`/`glob`/`grep`/`read`)
+    upper: MemoryBackend,
+    /// 상위 레이어 파일의 원본 내용 (편집과 커밋은 이 내용을 기준으로 함)
+    contents: RwLock<HashMap<String, UpperContent>>,
+    /// 상위 레이어에서 삭제되어 기반 파일을 가리는 경로
+    whiteouts: RwLock<HashSet<String>>,
+}
+
+/// 상위 레이어 파일 내용
+///
+/// `MemoryBackend`는 줄 단위로 저장해 CRLF와 마지막 개행을 잃으므로,
+/// 기반에 반영할 내용은 바이트 그대로 따로 보관합니다.
+#[derive(Debug, Clone)]
+enum UpperContent {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl UpperContent {
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => UpperContent::Text(text),
+            Err(e) => UpperContent::Binary(e.into_bytes()),
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            UpperContent::Text(text) => text.as_bytes(),
+            UpperContent::Binary(bytes) => bytes,
+        }
+    }
+}
+
+impl OverlayBackend {
+    pub fn new(base: Arc<dyn Backend>) -> Self {
+        Self {
+            base,
+            upper: MemoryBackend::new(),
+            contents: RwLock::new(HashMap::new()),
+            whiteouts: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// 기반 백엔드 참조
+    pub fn base(&self) -> &Arc<dyn Backend> {
+        &self.base
+    }
+
+    /// 상위(쓰기) 레이어 참조
+    pub fn upper(&self) -> &MemoryBackend {
+        &self.upper
+    }
+
+    /// 커밋되지 않은 변경 경로 (쓰기/편집과 삭제, 정렬됨)
+    pub async fn changed_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.contents.read().await.keys().cloned().collect();
+        paths.extend(self.whiteouts.read().await.iter().cloned());
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// 상위 레이어의 변경을 기반 백엔드에 반영하고 상위 레이어를 비움
+    ///
+    /// 상위 파일을 먼저 기반에 덮어쓰고, 쓰기가 모두 성공한 뒤에만 삭제를 적용합니다.
+    /// 기반 백엔드가 트랜잭션을 지원하면 텍스트 파일은 한 번에 반영됩니다.
+    ///
+    /// Returns: 반영된 경로 수
+    pub async fn commit(&self) -> Result<usize, BackendError> {
+        let mut whiteouts = self.whiteouts.write().await;
+        let mut contents = self.contents.write().await;
+
+        let (text, binary): (Vec<_>, Vec<_>) = contents.iter()
+            .partition(|(_, content)| matches!(content, UpperContent::Text(_)));
+        match self.base.transaction() {
+            Some(mut tx) => {
+                for (path, content) in &text {
+                    if let UpperContent::Text(text) = content {
+                        tx.write(path, text).await?;
+                    }
+                }
+                tx.commit().await?;
+            }
+            None => {
+                for (path, content) in &text {
+                    self.replace_in_base(path, content).await?;
+                }
+            }
+        }
+        for (path, content) in &binary {
+            self.replace_in_base(path, content).await?;
+        }
+
+        for path in whiteouts.iter() {
+            if !contents.contains_key(path) && self.base.exists(path).await? {
+                self.base.delete(path).await?;
+            }
+        }
+
+        let committed = contents.keys().chain(whiteouts.iter()).collect::<HashSet<_>>().len();
+        whiteouts.clear();
+        contents.clear();
+        self.upper.clear().await;
+        Ok(committed)
+    }
+
+    /// 커밋하지 않고 모든 변경을 폐기
+    pub async fn discard(&self) {
+        self.whiteouts.write().await.clear();
+        self.contents.write().await.clear();
+        self.upper.clear().await;
+    }
+
+    /// 기반 백엔드의 파일을 상위 레이어 내용으로 교체
+    ///
+    /// 트랜잭션이 없는 기반은 삭제 후 쓰기로 교체하므로, 쓰기가 실패하면 원래 내용을 되돌려 씁니다.
+    async fn replace_in_base(&self, path: &str, content: &UpperContent) -> Result<(), BackendError> {
+        let original = if self.base.exists(path).await? {
+            let original = self.base.read_bytes(path).await?;
+            self.base.delete(path).await?;
+            Some(original)
+        } else {
+            None
+        };
+
+        let written = match self.write_to_base(path, content).await {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+        if let Some(original) = original {
+            self.write_to_base(path, &UpperContent::from_bytes(original)).await?;
+        }
+        Err(written)
+    }
+
+    async fn write_to_base(&self, path: &str, content: &UpperContent) -> Result<(), BackendError> {
+        let result = match content {
+            UpperContent::Text(text) => self.base.write(path, text).await?,
+            UpperContent::Binary(bytes) => self.base.write_bytes(path, bytes).await?,
+        };
+        match result.error {
+            Some(error) => Err(BackendError::Io(error)),
+            None => Ok(()),
+        }
+    }
+
+    /// 편집할 원본 텍스트 (상위에 없으면 기반에서 그대로 읽음)
+    async fn raw_text(&self, path: &str) -> Result<Option<String>, BackendError> {
+        let upper = self.contents.read().await.get(path).cloned();
+        let content = match upper {
+            Some(content) => content,
+            None if self.is_whiteout(path).await => {
+                return Err(BackendError::FileNotFound(path.to_string()));
+            }
+            None => UpperContent::from_bytes(self.base.read_bytes(path).await?),
+        };
+        match content {
+            UpperContent::Text(text) => Ok(Some(text)),
+            UpperContent::Binary(_) => Ok(None),
+        }
+    }
+
+    async fn is_whiteout(&self, path: &str) -> bool {
+        self.whiteouts.read().await.contains(path)
+    }
+
+    /// 경로를 보여주는 레이어 (삭제된 경로면 `FileNotFound`)
+    async fn layer_for(&self, path: &str) -> Result<(&dyn Backend, String), BackendError> {
+        let path = normalize_path(path)?;
+        if self.upper.exists(&path).await? {
+            let upper: &dyn Backend = &self.upper;
+            return Ok((upper, path));
+        }
+        if self.is_whiteout(&path).await {
+            return Err(BackendError::FileNotFound(path));
+        }
+        Ok((self.base.as_ref(), path))
+    }
+
+    /// 기반 레이어 항목이 합친 뷰에서 보이는지 (상위에 가려지거나 삭제되지 않음)
+    async fn base_visible(&self, path: &str) -> Result<bool, BackendError> {
+        let path = path.trim_end_matches('/');
+        Ok(!self.is_whiteout(path).await && !self.upper.exists(path).await?)
+    }
+
+    /// 두 레이어의 항목을 경로 기준으로 합침 (상위 우선, 정렬됨)
+    async fn merge_entries(
+        &self,
+        base: Vec<FileInfo>,
+        upper: Vec<FileInfo>,
+    ) -> Result<Vec<FileInfo>, BackendError> {
+        let mut merged = BTreeMap::new();
+        for entry in base {
+            if entry.is_dir || self.base_visible(&entry.path).await? {
+                merged.insert(entry.path.clone(), entry);
+            }
+        }
+        for entry in upper {
+            merged.insert(entry.path.clone(), entry);
+        }
+        Ok(merged.into_values().collect())
+    }
+}
+
+#[async_trait]
+impl Backend for OverlayBackend {
+    async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        let base = self.base.ls(path).await?;
+        let upper = self.upper.ls(path).await?;
+        self.merge_entries(base, upper).await
+    }
+
+    async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        let (layer, path) = self.layer_for(path).await?;
+        layer.read(&path, offset, limit).await
+    }
+
+    async fn read_file_range(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        let (layer, path) = self.layer_for(path).await?;
+        layer.read_file_range(&path, offset, limit).await
+    }
+
+    async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError> {
+        let path = normalize_path(path)?;
+        if self.exists(&path).await? {
+            return Ok(WriteResult::error(&format!(
+                "Cannot write to {} because it already exists. Read and then make an edit, or write to a new path.",
+                path
+            )));
+        }
+
+        // 가림 표시는 유지: 커밋 시 기반 파일을 새 내용으로 교체해야 함
+        let result = self.upper.write(&path, content).await?;
+        if result.is_ok() {
+            self.contents.write().await.insert(path, UpperContent::Text(content.to_string()));
+        }
+        Ok(result)
+    }
+
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        let (layer, path) = self.layer_for(path).await?;
+        if let Some(content) = self.contents.read().await.get(&path) {
+            return Ok(content.as_bytes().to_vec());
+        }
+        layer.read_bytes(&path).await
+    }
+
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        let path = normalize_path(path)?;
+        if self.exists(&path).await? {
+            return Ok(WriteResult::error(&format!(
+                "Cannot write to {} because it already exists. Read and then make an edit, or write to a new path.",
+                path
+            )));
+        }
+
+        let result = self.upper.write_bytes(&path, content).await?;
+        if result.is_ok() {
+            self.contents.write().await.insert(path, UpperContent::Binary(content.to_vec()));
+        }
+        Ok(result)
+    }
+
+    async fn edit(
+        &self,
+        path: &str,
+        old_string: &str,
+        new_string: &str,
+        replace_all: bool
+    ) -> Result<EditResult, BackendError> {
+        let path = normalize_path(path)?;
+
+        // 기반 파일은 원본 바이트 그대로 상위 레이어로 복사한 뒤 편집
+        let content = match self.raw_text(&path).await? {
+            Some(content) => content,
+            None => return Ok(EditResult::error(&format!("Cannot edit binary file {}", path))),
+        };
+        let occurrences = content.matches(old_string).count();
+
+        if occurrences == 0 {
+            return Ok(EditResult::error(&format!("String '{}' not found in file", old_string)));
+        }
+
+        if !replace_all && occurrences > 1 {
+            return Ok(EditResult::error(&format!(
+                "String '{}' found {} times. Use replace_all=true or provide more context.",
+                old_string, occurrences
+            )));
+        }
+
+        let new_content = if replace_all {
+            content.replace(old_string, new_string)
+        } else {
+            content.replacen(old_string, new_string, 1)
+        };
+
+        let mut contents = self.contents.write().await;
+        let mut tx = self.upper.transaction()
+            .ok_or_else(|| BackendError::Unsupported("overlay upper layer requires transactions".to_string()))?;
+        tx.write(&path, &new_content).await?;
+        let files_update = tx.commit().await?;
+        contents.insert(path.clone(), UpperContent::Text(new_content));
+
+        let actual_occurrences = if replace_all { occurrences } else { 1 };
+        let mut result = EditResult::success_external(&path, actual_occurrences);
+        result.files_update = files_update;
+        Ok(result)
+    }
+
+    async fn glob(&self, pattern: &str, base_path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        let base = self.base.glob(pattern, base_path).await?;
+        let upper = self.upper.glob(pattern, base_path).await?;
+        self.merge_entries(base, upper).await
+    }
+
+    async fn grep(
+        &self,
+        pattern: &str,
+        path: Option<&str>,
+        glob_filter: Option<&str>,
+        options: GrepOptions,
+    ) -> Result<Vec<GrepMatch>, BackendError> {
+        let mut results = Vec::new();
+        for m in self.base.grep(pattern, path, glob_filter, options).await? {
+            if self.base_visible(&m.path).await? {
+                results.push(m);
+            }
+        }
+        results.extend(self.upper.grep(pattern, path, glob_filter, options).await?);
+        results.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        Ok(results)
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, BackendError> {
+        let path = normalize_path(path)?;
+        if self.upper.exists(&path).await? {
+            return Ok(true);
+        }
+        Ok(!self.is_whiteout(&path).await && self.base.exists(&path).await?)
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), BackendError> {
+        let path = normalize_path(path)?;
+        let in_upper = self.upper.exists(&path).await?;
+        let in_base = !self.is_whiteout(&path).await && self.base.exists(&path).await?;
+        if !in_upper && !in_base {
+            return Err(BackendError::FileNotFound(path));
+        }
+
+        if in_upper {
+            self.upper.delete(&path).await?;
+            self.contents.write().await.remove(&path);
+        }
+        if self.base.exists(&path).await? {
+            self.whiteouts.write().await.insert(path);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::FilesystemBackend;
+    use tempfile::TempDir;
+
+    async fn overlay_on_disk() -> (TempDir, Arc<dyn Backend>, OverlayBackend) {
+        let temp = TempDir::new().unwrap();
+        let base: Arc<dyn Backend> = Arc::new(FilesystemBackend::new(temp.path()));
+        base.write("/src/lib.rs", "fn old() {}\n// TODO: rename").await.unwrap();
+        base.write("/README.md", "Project readme").await.unwrap();
+        let overlay = OverlayBackend::new(base.clone());
+        (temp, base, overlay)
+    }
+
+    #[tokio::test]
+    async fn test_overlay_reads_through_to_base() {
+        let (_temp, _base, overlay) = overlay_on_disk().await;
+
+        let content = overlay.read("/src/lib.rs", 0, 100).await.unwrap();
+        assert!(content.contains("fn old() {}"));
+        assert!(overlay.exists("/README.md").await.unwrap());
+
+        let paths: Vec<_> = overlay.ls("/").await.unwrap().into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["/README.md", "/src/"]);
+
+        let matches = overlay.grep("TODO", None, None, GrepOptions::default()).await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, "/src/lib.rs");
+    }
+
+    #[tokio::test]
+    async fn test_overlay_isolates_writes_from_base() {
+        let (temp, base, overlay) = overlay_on_disk().await;
+
+        overlay.write("/notes.md", "scratch").await.unwrap();
+        let result = overlay.edit("/src/lib.rs", "old", "new", false).await.unwrap();
+        assert!(result.is_ok());
+        overlay.delete("/README.md").await.unwrap();
+
+        // 합친 뷰에는 변경이 보임
+        assert!(overlay.read("/src/lib.rs", 0, 100).await.unwrap().contains("fn new() {}"));
+        assert!(!overlay.exists("/README.md").await.unwrap());
+        assert!(matches!(
+            overlay.read("/README.md", 0, 100).await,
+            Err(BackendError::FileNotFound(_))
+        ));
+        let paths: Vec<_> = overlay.ls("/").await.unwrap().into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["/notes.md", "/src/"]);
+        let matches = overlay.grep("fn ", None, None, GrepOptions::default()).await.unwrap();
+        assert_eq!(matches[0].text, "fn new() {}");
+
+        // 기반(디스크)은 그대로
+        assert!(!temp.path().join("notes.md").exists());
+        assert!(base.read("/src/lib.rs", 0, 100).await.unwrap().contains("fn old() {}"));
+        assert!(base.exists("/README.md").await.unwrap());
+
+        // 기존 파일 쓰기는 기반 파일에 대해서도 거부
+        let result = overlay.write("/src/lib.rs", "clobber").await.unwrap();
+        assert!(!result.is_ok());
+
+        overlay.discard().await;
+        assert!(overlay.changed_paths().await.is_empty());
+        assert!(overlay.read("/src/lib.rs", 0, 100).await.unwrap().contains("fn old() {}"));
+        assert!(overlay.exists("/README.md").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_overlay_commit_flushes_to_base() {
+        let (temp, base, overlay) = overlay_on_disk().await;
+
+        overlay.write("/notes.md", "scratch").await.unwrap();
+        overlay.edit("/src/lib.rs", "old", "new", false).await.unwrap();
+        overlay.delete("/README.md").await.unwrap();
+        assert_eq!(
+            overlay.changed_paths().await,
+            vec!["/README.md", "/notes.md", "/src/lib.rs"]
+        );
+
+        assert_eq!(overlay.commit().await.unwrap(), 3);
+
+        assert_eq!(std::fs::read_to_string(temp.path().join("notes.md")).unwrap(), "scratch");
+        assert!(base.read("/src/lib.rs", 0, 100).await.unwrap().contains("fn new() {}"));
+        assert!(!temp.path().join("README.md").exists());
+
+        // 커밋 후 상위 레이어는 비어 있고 뷰는 기반과 같음
+        assert!(overlay.changed_paths().await.is_empty());
+        assert!(overlay.read("/src/lib.rs", 0, 100).await.unwrap().contains("fn new() {}"));
+        assert!(!overlay.exists("/README.md").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_overlay_commit_preserves_exact_bytes() {
+        let (temp, base, overlay) = overlay_on_disk().await;
+        std::fs::write(temp.path().join("crlf.txt"), "alpha\r\nbeta\r\n").unwrap();
+        let large: String = (0..60_000).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(temp.path().join("large.txt"), &large).unwrap();
+
+        overlay.write("/notes.md", "scratch\n").await.unwrap();
+        overlay.edit("/crlf.txt", "beta", "gamma", false).await.unwrap();
+        overlay.edit("/large.txt", "line 59999\n", "last line\n", false).await.unwrap();
+        assert_eq!(overlay.read_bytes("/crlf.txt").await.unwrap(), b"alpha\r\ngamma\r\n");
+
+        overlay.commit().await.unwrap();
+
+        assert_eq!(std::fs::read(temp.path().join("notes.md")).unwrap(), b"scratch\n");
+        assert_eq!(std::fs::read(temp.path().join("crlf.txt")).unwrap(), b"alpha\r\ngamma\r\n");
+        let expected = large.replace("line 59999\n", "last line\n");
+        assert_eq!(std::fs::read_to_string(temp.path().join("large.txt")).unwrap(), expected);
+        assert!(base.exists("/src/lib.rs").await.unwrap());
+    }
+}
@@ -20,10 +20,16 @@
 //!
 //! ## Usage
 //! All backend methods should use `normalize_path()` for path validation and
-//! `is_under_path()` for directory containment checks.
+//! `is_under_path()` for directory containment checks. Glob patterns should be
+//! compiled with `GlobMatcher` so every backend supports the same syntax.
+
+use glob::Pattern;
 
 use crate::error::BackendError;
 
+/// 브레이스 확장으로 생성할 수 있는 최대 패턴 수
+const MAX_BRACE_EXPANSIONS: usize = 256;
+
 /// 경로 정규화
 /// - 앞에 `/` 추가
 /// - 연속된 슬래시 제거 (`//` -> `/`)
@@ -70,6 +76,129 @@ pub fn is_under_path(path: &str, base_path: &str) -> bool {
     path.starts_with(&format!("{}/", normalized_base))
 }
 
+/// 백엔드 공통 glob 매처
+///
+/// `glob` 크레이트의 `*`, `**`, `?`, `[...]` 문법에 `{a,b}` 브레이스 확장을 더합니다.
+/// `globset`을 새로 의존하는 대신 패턴을 확장해 여러 `glob::Pattern` 중 하나라도
+/// 맞으면 매칭으로 봅니다 (예: `src/**/*.{rs,toml}`). 중첩 브레이스는 지원하며,
+/// `+(...)`, `!(...)` 같은 extglob은 지원하지 않습니다.
+#[derive(Debug, Clone)]
+pub struct GlobMatcher {
+    patterns: Vec<Pattern>,
+}
+
+impl GlobMatcher {
+    /// 패턴 컴파일
+    pub fn new(pattern: &str) -> Result<Self, BackendError> {
+        let expanded = expand_braces(pattern);
+        if expanded.len() > MAX_BRACE_EXPANSIONS {
+            return Err(BackendError::Pattern(format!(
+                "Brace expansion of '{}' produces more than {} patterns",
+                pattern, MAX_BRACE_EXPANSIONS
+            )));
+        }
+
+        let patterns = expanded
+            .iter()
+            .map(|p| Pattern::new(p))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+        Ok(Self { patterns })
+    }
+
+    /// 경로가 패턴 중 하나와 일치하는지 확인
+    pub fn matches(&self, path: &str) -> bool {
+        self.patterns.iter().any(|p| p.matches(path))
+    }
+}
+
+/// `{a,b}` 브레이스 확장
+///
+/// 짝이 맞지 않는 브레이스와 `[...]` 안의 브레이스는 그대로 둡니다.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some((open, close)) = find_brace_group(pattern) else {
+        return vec![pattern.to_string()];
+    };
+
+    let prefix = &pattern[..open];
+    let inner = &pattern[open + 1..close];
+    let suffix = &pattern[close + 1..];
+    let alternatives = split_alternatives(inner);
+
+    let mut results = Vec::new();
+    if alternatives.len() == 1 {
+        // 쉼표가 없는 브레이스는 리터럴 - 나머지만 확장
+        for rest in expand_braces(suffix) {
+            for body in expand_braces(inner) {
+                results.push(format!("{}{{{}}}{}", prefix, body, rest));
+            }
+        }
+    } else {
+        for alternative in alternatives {
+            results.extend(expand_braces(&format!("{}{}{}", prefix, alternative, suffix)));
+            if results.len() > MAX_BRACE_EXPANSIONS {
+                break;
+            }
+        }
+    }
+    results
+}
+
+/// 첫 번째 최상위 브레이스 그룹의 (여는 위치, 닫는 위치)
+fn find_brace_group(pattern: &str) -> Option<(usize, usize)> {
+    let bytes = pattern.as_bytes();
+    let mut open = None;
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            // 문자 클래스는 건너뜀
+            b'[' => {
+                if let Some(end) = pattern[i + 1..].find(']') {
+                    i += end + 1;
+                }
+            }
+            b'{' => {
+                if depth == 0 {
+                    open = Some(i);
+                }
+                depth += 1;
+            }
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return open.map(|start| (start, i));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// 최상위 쉼표로 대안 분리
+fn split_alternatives(inner: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&inner[start..]);
+    parts
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +248,37 @@ mod tests {
         assert!(!is_under_path("/dir2/file.txt", "/dir"));
         assert!(!is_under_path("/directory/file.txt", "/dir"));
     }
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(expand_braces("*.rs"), vec!["*.rs"]);
+        assert_eq!(expand_braces("src/**/*.{rs,toml}"), vec!["src/**/*.rs", "src/**/*.toml"]);
+        assert_eq!(
+            expand_braces("{docs,src/{a,b}}/*.md"),
+            vec!["docs/*.md", "src/a/*.md", "src/b/*.md"]
+        );
+        // 쉼표 없는 브레이스, 짝 없는 브레이스, 문자 클래스 안의 브레이스는 리터럴
+        assert_eq!(expand_braces("{x}.{rs,md}"), vec!["{x}.rs", "{x}.md"]);
+        assert_eq!(expand_braces("a{b,c"), vec!["a{b,c"]);
+        assert_eq!(expand_braces("[{]*.{a,b}"), vec!["[{]*.a", "[{]*.b"]);
+    }
+
+    #[test]
+    fn test_glob_matcher_braces_and_globstar() {
+        let matcher = GlobMatcher::new("src/**/*.{rs,toml}").unwrap();
+        assert!(matcher.matches("src/main.rs"));
+        assert!(matcher.matches("src/backends/nested/deep/Cargo.toml"));
+        assert!(!matcher.matches("src/readme.md"));
+        assert!(!matcher.matches("docs/lib.rs"));
+
+        // 기존 패턴은 그대로 동작
+        let plain = GlobMatcher::new("**/*.rs").unwrap();
+        assert!(plain.matches("lib.rs"));
+        assert!(plain.matches("a/b/c/lib.rs"));
+        assert!(!plain.matches("a/b/c/lib.toml"));
+        assert!(GlobMatcher::new("*.txt").unwrap().matches("notes.txt"));
+
+        assert!(GlobMatcher::new("[").is_err());
+        assert!(GlobMatcher::new("{a,b}{c,d}{e,f}{g,h}{i,j}{k,l}{m,n}{o,p}{q,r}").is_err());
+    }
 }
@@ -5,7 +5,18 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use regex::{Regex, RegexBuilder};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use crate::error::{BackendError, WriteResult, EditResult};
+use crate::state::FileData;
+
+/// `ls_recursive`가 반환하는 최대 항목 수 (컨텍스트 폭증 방지)
+pub const LS_RECURSIVE_MAX_ENTRIES: usize = 500;
+
+/// `grep_glob`이 반환하는 최대 매칭 수 (컨텍스트 폭증 방지)
+pub const GREP_GLOB_MAX_MATCHES: usize = 200;
 
 /// 파일 정보
 /// Python: FileInfo(TypedDict)
@@ -45,11 +56,185 @@ pub struct GrepMatch {
     pub path: String,
     pub line: usize,
     pub text: String,
+    /// 매칭 앞의 라인들 (`GrepOptions::context_before`, 파일 시작에서 잘림)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    /// 매칭 뒤의 라인들 (`GrepOptions::context_after`, 파일 끝에서 잘림)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
 }
 
 impl GrepMatch {
     pub fn new(path: &str, line: usize, text: &str) -> Self {
-        Self { path: path.to_string(), line, text: text.to_string() }
+        Self {
+            path: path.to_string(),
+            line,
+            text: text.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+        }
+    }
+}
+
+/// Grep 검색 옵션
+///
+/// 패턴은 기본적으로 리터럴로 취급되며(`regex`로 정규식 사용 가능), 옵션은 매칭 방식만 조정합니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrepOptions {
+    /// 대소문자 무시
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// 여러 줄에 걸친 매칭 허용 (패턴에 `\n` 포함 가능)
+    #[serde(default)]
+    pub multiline: bool,
+    /// 매칭 앞에 함께 반환할 라인 수 (`grep -B`)
+    #[serde(default)]
+    pub context_before: usize,
+    /// 매칭 뒤에 함께 반환할 라인 수 (`grep -A`)
+    #[serde(default)]
+    pub context_after: usize,
+    /// 패턴을 정규식으로 해석 (`regex` crate 문법, 선형 시간 매칭이라 ReDoS 위험 없음)
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl GrepOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    pub fn with_multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    pub fn with_regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// 매칭 앞뒤 컨텍스트 라인 수 설정 (`grep -B before -A after`)
+    pub fn with_context(mut self, before: usize, after: usize) -> Self {
+        self.context_before = before;
+        self.context_after = after;
+        self
+    }
+
+    /// 패턴에 대한 matcher 생성
+    ///
+    /// `regex`가 꺼져 있으면 패턴은 `regex::escape`로 이스케이프되므로 정규식 메타문자는
+    /// 리터럴로 처리됩니다. 잘못된 정규식은 `BackendError::Pattern`을 반환합니다.
+    pub fn matcher(&self, pattern: &str) -> Result<GrepMatcher, BackendError> {
+        let source = if self.regex {
+            pattern.to_string()
+        } else {
+            regex::escape(pattern)
+        };
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multiline)
+            .build()
+            .map_err(|e| BackendError::Pattern(e.to_string()))?;
+
+        Ok(GrepMatcher {
+            regex,
+            multiline: self.multiline,
+            context_before: self.context_before,
+            context_after: self.context_after,
+        })
+    }
+}
+
+/// `GrepOptions::matcher`로 생성되는 매처
+///
+/// 백엔드 구현체가 파일 내용을 스캔할 때 사용합니다.
+#[derive(Debug, Clone)]
+pub struct GrepMatcher {
+    regex: Regex,
+    multiline: bool,
+    context_before: usize,
+    context_after: usize,
+}
+
+impl GrepMatcher {
+    /// 여러 줄 모드 여부 (true이면 라인 단위가 아닌 전체 내용으로 `search` 해야 함)
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    /// 컨텍스트 라인 요청 여부 (true이면 라인 스트리밍 대신 전체 내용으로 `search` 해야 함)
+    pub fn has_context(&self) -> bool {
+        self.context_before > 0 || self.context_after > 0
+    }
+
+    /// 매칭에 앞뒤 컨텍스트 라인 채우기
+    ///
+    /// `lines`는 파일 전체 라인이며, 범위는 파일 경계에서 잘립니다.
+    pub fn add_context<L: AsRef<str>>(&self, mut m: GrepMatch, lines: &[L]) -> GrepMatch {
+        if !self.has_context() {
+            return m;
+        }
+        let start = m.line.saturating_sub(1).min(lines.len());
+        // 여러 줄 매칭은 매칭에 걸친 마지막 라인 이후부터 after 컨텍스트
+        let end = (start + m.text.lines().count().max(1)).min(lines.len());
+        let to_owned = |l: &L| l.as_ref().trim_end_matches('\r').to_string();
+
+        m.context_before = lines[start.saturating_sub(self.context_before)..start]
+            .iter()
+            .map(to_owned)
+            .collect();
+        m.context_after = lines[end..(end + self.context_after).min(lines.len())]
+            .iter()
+            .map(to_owned)
+            .collect();
+        m
+    }
+
+    /// 단일 라인 매칭
+    pub fn is_match(&self, line: &str) -> bool {
+        self.regex.is_match(line)
+    }
+
+    /// 파일 내용 전체 검색
+    ///
+    /// 라인 모드에서는 매칭된 각 라인을, 여러 줄 모드에서는 매칭이 시작된 라인 번호와
+    /// 매칭에 걸친 라인 전체를 반환합니다.
+    pub fn search(&self, path: &str, content: &str) -> Vec<GrepMatch> {
+        let lines: Vec<&str> = content.lines().collect();
+        self.search_lines(path, content)
+            .into_iter()
+            .map(|m| self.add_context(m, &lines))
+            .collect()
+    }
+
+    /// 컨텍스트 없이 매칭만 찾기
+    fn search_lines(&self, path: &str, content: &str) -> Vec<GrepMatch> {
+        if !self.multiline {
+            return content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| self.is_match(line))
+                .map(|(i, line)| GrepMatch::new(path, i + 1, line))
+                .collect();
+        }
+
+        let mut results: Vec<GrepMatch> = Vec::new();
+        for m in self.regex.find_iter(content) {
+            let line = content[..m.start()].matches('\n').count() + 1;
+            if results.last().is_some_and(|last| last.line == line) {
+                continue;
+            }
+
+            let start = content[..m.start()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let end = content[m.end()..].find('\n').map(|i| m.end() + i).unwrap_or(content.len());
+            results.push(GrepMatch::new(path, line, content[start..end].trim_end_matches('\r')));
+        }
+        results
     }
 }
 
@@ -64,12 +249,56 @@ pub trait Backend: Send + Sync {
     /// Python: ls_info(path: str) -> list[FileInfo]
     async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError>;
 
+    /// 재귀 디렉토리 나열
+    ///
+    /// `depth` 단계까지 내려가며(`1`이면 `ls`와 같은 한 단계), `path` 기준 상대 경로의
+    /// `FileInfo`를 트리 순서로 반환합니다. 얕은 항목부터 채우며
+    /// 최대 [`LS_RECURSIVE_MAX_ENTRIES`]개에서 잘립니다.
+    /// 기본 구현은 `ls`를 반복 호출합니다.
+    async fn ls_recursive(&self, path: &str, depth: usize) -> Result<Vec<FileInfo>, BackendError> {
+        let base = path.trim_end_matches('/');
+        let prefix = format!("{}/", base);
+
+        let mut results = Vec::new();
+        let mut queue = VecDeque::from([(base.to_string(), 1)]);
+
+        'walk: while let Some((dir, level)) = queue.pop_front() {
+            let dir_path = if dir.is_empty() { "/" } else { dir.as_str() };
+            for entry in self.ls(dir_path).await? {
+                let absolute = entry.path.trim_end_matches('/').to_string();
+                let relative = absolute.strip_prefix(&prefix).unwrap_or(&absolute).to_string();
+
+                if entry.is_dir && level < depth {
+                    queue.push_back((absolute, level + 1));
+                }
+                results.push(FileInfo { path: relative, ..entry });
+
+                if results.len() >= LS_RECURSIVE_MAX_ENTRIES {
+                    break 'walk;
+                }
+            }
+        }
+
+        // 컴포넌트 단위 정렬로 디렉토리 바로 뒤에 그 내용이 오도록 함
+        results.sort_by(|a, b| a.path.split('/').cmp(b.path.split('/')));
+        Ok(results)
+    }
+
     /// 파일 읽기 (페이지네이션 지원)
     /// Python: read(file_path: str, offset: int, limit: int) -> str
     ///
     /// Returns: 라인 번호 포함된 포맷 (cat -n 스타일)
     async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError>;
 
+    /// 라인 범위 읽기
+    ///
+    /// `offset`(0-indexed)부터 최대 `limit`개 라인을 1-based 라인 번호와 함께 반환합니다 (cat -n 스타일).
+    /// 기본 구현은 `read`에 위임하며, 큰 파일을 전부 메모리에 올리지 않고
+    /// 필요한 범위만 읽을 수 있는 백엔드는 오버라이드합니다.
+    async fn read_file_range(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        self.read(path, offset, limit).await
+    }
+
     async fn read_plain(&self, path: &str) -> Result<String, BackendError> {
         let formatted = self.read(path, 0, 50_000).await?;
         Ok(strip_cat_n(&formatted))
@@ -79,6 +308,24 @@ pub trait Backend: Send + Sync {
     /// Python: write(file_path: str, content: str) -> WriteResult
     async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError>;
 
+    /// 바이너리 파일 읽기
+    ///
+    /// 기본 구현은 `read_plain`으로 읽은 내용을 base64 디코딩합니다.
+    /// (`write_bytes` 기본 구현과 짝을 이룸) 바이트를 직접 저장할 수 있는
+    /// 백엔드는 이 메서드를 오버라이드해야 합니다.
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        let encoded = self.read_plain(path).await?;
+        BASE64.decode(encoded.trim())
+            .map_err(|e| BackendError::Io(format!("Invalid base64 content in {}: {}", path, e)))
+    }
+
+    /// 바이너리 파일 쓰기 (새 파일 생성)
+    ///
+    /// 기본 구현은 base64로 인코딩하여 `write`에 위임합니다.
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        self.write(path, &BASE64.encode(content)).await
+    }
+
     /// 파일 편집 (문자열 교체)
     /// Python: edit(file_path: str, old_string: str, new_string: str, replace_all: bool) -> EditResult
     async fn edit(
@@ -105,27 +352,91 @@ pub trait Backend: Send + Sync {
     /// - **성능**: 정규식 컴파일 오버헤드 없음, 단순 substring 매칭
     /// - **단순성**: LLM 에이전트가 이해하기 쉬운 동작
     ///
-    /// 정규식이 필요한 경우:
-    /// - `regex` crate를 사용하는 `grep_regex` 메서드 추가 고려
-    /// - 또는 Backend 구현체에서 regex 기능 확장
+    /// 정규식이 필요한 경우 `GrepOptions::with_regex`로 명시적으로 켭니다.
     ///
     /// # Parameters
     ///
-    /// * `pattern` - 검색할 리터럴 문자열 (regex 아님!)
+    /// * `pattern` - 검색할 리터럴 문자열 (`GrepOptions::regex`가 켜져 있으면 정규식)
     /// * `path` - 검색 시작 디렉토리 (None이면 루트)
     /// * `glob_filter` - 파일 필터 패턴 (예: `**/*.rs`, `*.txt`)
+    /// * `options` - 대소문자 무시, 여러 줄 매칭 등 (`GrepOptions::matcher` 사용)
     async fn grep(
         &self,
         pattern: &str,
         path: Option<&str>,
         glob_filter: Option<&str>,
+        options: GrepOptions,
     ) -> Result<Vec<GrepMatch>, BackendError>;
 
+    /// glob에 매칭되는 모든 파일에서 한 번에 패턴 검색
+    ///
+    /// `glob`은 루트 기준 패턴입니다 (예: `**/*.rs`). 결과는 경로와 라인 순으로 정렬되며
+    /// 최대 [`GREP_GLOB_MAX_MATCHES`]개에서 잘립니다.
+    /// 기본 구현은 `grep`에 glob 필터를 넘겨 호출합니다.
+    async fn grep_glob(
+        &self,
+        pattern: &str,
+        glob: &str,
+        options: GrepOptions,
+    ) -> Result<Vec<GrepMatch>, BackendError> {
+        let mut matches = self.grep(pattern, None, Some(glob), options).await?;
+        matches.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        matches.truncate(GREP_GLOB_MAX_MATCHES);
+        Ok(matches)
+    }
+
     /// 파일 존재 여부 확인
     async fn exists(&self, path: &str) -> Result<bool, BackendError>;
 
     /// 파일 삭제
     async fn delete(&self, path: &str) -> Result<(), BackendError>;
+
+    /// 다중 파일 트랜잭션 시작 (선택 기능)
+    ///
+    /// 트랜잭션을 지원하는 백엔드는 쓰기를 버퍼링했다가 `commit()` 시 한 번에 반영하고,
+    /// 커밋 없이 drop되면 모든 변경을 폐기합니다.
+    /// 지원하지 않는 백엔드는 `None`을 반환하며, 호출자는 개별 `write`/`edit`으로 폴백합니다.
+    fn transaction(&self) -> Option<Box<dyn BackendTransaction + '_>> {
+        None
+    }
+
+    /// 로컬 디렉토리 루트 (선택 기능)
+    ///
+    /// 실제 디렉토리에 매핑되는 백엔드만 `Some`을 반환합니다.
+    /// `ShellTool`은 이 경로를 작업 디렉토리로 사용합니다.
+    fn root_dir(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// 다중 파일 트랜잭션 핸들
+///
+/// 여러 파일에 걸친 복합 편집이 중간에 실패하거나 실행기가 중단되어도
+/// 파일시스템이 반쯤 갱신된 상태로 남지 않도록 합니다.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if let Some(mut tx) = backend.transaction() {
+///     let content = tx.read_plain("/src/lib.rs").await?;
+///     tx.write("/src/lib.rs", &content.replace("old", "new")).await?;
+///     tx.write("/CHANGELOG.md", "- renamed old to new").await?;
+///     let files_update = tx.commit().await?;
+/// }
+/// ```
+#[async_trait]
+pub trait BackendTransaction: Send {
+    /// 파일 내용 읽기 (스테이징된 내용이 있으면 우선)
+    async fn read_plain(&self, path: &str) -> Result<String, BackendError>;
+
+    /// 파일 전체 내용을 스테이징 (생성 또는 덮어쓰기)
+    async fn write(&mut self, path: &str, content: &str) -> Result<(), BackendError>;
+
+    /// 스테이징된 모든 쓰기를 반영
+    ///
+    /// Returns: 체크포인트 백엔드는 `{path: FileData}` 상태 업데이트,
+    /// 외부 백엔드는 `None` (`WriteResult::files_update`와 동일한 규약)
+    async fn commit(self: Box<Self>) -> Result<Option<HashMap<String, FileData>>, BackendError>;
 }
 
 fn strip_cat_n(formatted: &str) -> String {
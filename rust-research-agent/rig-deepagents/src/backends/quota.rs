@@ -0,0 +1,254 @@
+// src/backends/quota.rs
+//! 용량 제한 래퍼 백엔드
+//!
+//! 장시간 리서치 실행 중 거대한 중간 파일이 컨테이너 디스크 한도를 넘지 않도록
+//! 전체 쓰기 용량과 파일별 크기를 제한합니다.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use super::path_utils::normalize_path;
+use super::protocol::{Backend, GrepOptions, FileInfo, GrepMatch};
+use crate::error::{BackendError, WriteResult, EditResult};
+
+/// 용량 제한 백엔드
+///
+/// 이 래퍼를 통해 쓰여진 파일들의 현재 크기 합계를 추적하고,
+/// `write`/`edit` 결과가 한도를 넘으면 `BackendError::QuotaExceeded`를 반환합니다.
+/// 읽기, `glob`, `grep`은 영향을 받지 않습니다.
+///
+/// 사용량은 래퍼를 통해 생성/수정된 파일만 집계합니다 (기존 파일은 수정 시점부터 집계).
+pub struct QuotaBackend {
+    inner: Arc<dyn Backend>,
+    max_total_bytes: usize,
+    max_file_bytes: Option<usize>,
+    /// 경로별 추적 크기 (쓰기 작업 직렬화용 잠금 겸용)
+    sizes: Mutex<HashMap<String, usize>>,
+    /// 현재 사용량 (동기 조회용)
+    used: AtomicUsize,
+}
+
+impl QuotaBackend {
+    /// 새 용량 제한 백엔드 생성
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - 실제 저장을 담당하는 백엔드
+    /// * `max_total_bytes` - 래퍼를 통해 쓰여진 전체 바이트 한도
+    /// * `max_file_bytes` - 단일 파일 크기 한도 (None이면 제한 없음)
+    pub fn new(inner: Arc<dyn Backend>, max_total_bytes: usize, max_file_bytes: Option<usize>) -> Self {
+        Self {
+            inner,
+            max_total_bytes,
+            max_file_bytes,
+            sizes: Mutex::new(HashMap::new()),
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// 현재 사용량 (바이트)
+    pub fn usage(&self) -> usize {
+        self.used.load(Ordering::SeqCst)
+    }
+
+    /// 전체 용량 한도
+    pub fn max_total_bytes(&self) -> usize {
+        self.max_total_bytes
+    }
+
+    /// 파일별 크기 한도
+    pub fn max_file_bytes(&self) -> Option<usize> {
+        self.max_file_bytes
+    }
+
+    /// 새 크기로 교체했을 때 한도 확인
+    fn check(&self, path: &str, previous: usize, new_size: usize) -> Result<(), BackendError> {
+        if let Some(max_file) = self.max_file_bytes {
+            if new_size > max_file {
+                return Err(BackendError::QuotaExceeded(format!(
+                    "{} would be {} bytes (per-file limit {} bytes)",
+                    path, new_size, max_file
+                )));
+            }
+        }
+
+        let projected = self.usage() - previous + new_size;
+        if projected > self.max_total_bytes {
+            return Err(BackendError::QuotaExceeded(format!(
+                "writing {} would use {} bytes (total limit {} bytes)",
+                path, projected, self.max_total_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn record(&self, sizes: &mut HashMap<String, usize>, path: String, new_size: usize) {
+        let previous = sizes.insert(path, new_size).unwrap_or(0);
+        self.used.fetch_sub(previous, Ordering::SeqCst);
+        self.used.fetch_add(new_size, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl Backend for QuotaBackend {
+    async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        self.inner.ls(path).await
+    }
+
+    async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        self.inner.read(path, offset, limit).await
+    }
+
+    async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError> {
+        let key = normalize_path(path)?;
+        let mut sizes = self.sizes.lock().await;
+
+        let previous = sizes.get(&key).copied().unwrap_or(0);
+        self.check(path, previous, content.len())?;
+
+        let result = self.inner.write(path, content).await?;
+        if result.is_ok() {
+            self.record(&mut sizes, key, content.len());
+        }
+        Ok(result)
+    }
+
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        self.inner.read_bytes(path).await
+    }
+
+    async fn write_bytes(&self, path: &str, content: &[u8]) -> Result<WriteResult, BackendError> {
+        let key = normalize_path(path)?;
+        let mut sizes = self.sizes.lock().await;
+
+        let previous = sizes.get(&key).copied().unwrap_or(0);
+        self.check(path, previous, content.len())?;
+
+        let result = self.inner.write_bytes(path, content).await?;
+        if result.is_ok() {
+            self.record(&mut sizes, key, content.len());
+        }
+        Ok(result)
+    }
+
+    async fn edit(
+        &self,
+        path: &str,
+        old_string: &str,
+        new_string: &str,
+        replace_all: bool
+    ) -> Result<EditResult, BackendError> {
+        let key = normalize_path(path)?;
+        let mut sizes = self.sizes.lock().await;
+
+        // 편집 후 크기를 미리 계산하여 한도 확인
+        let current = self.inner.read_plain(path).await?;
+        let occurrences = current.matches(old_string).count();
+        let replaced = if replace_all { occurrences } else { occurrences.min(1) };
+        let new_size = current.len() - replaced * old_string.len() + replaced * new_string.len();
+
+        let previous = sizes.get(&key).copied().unwrap_or(0);
+        self.check(path, previous, new_size)?;
+
+        let result = self.inner.edit(path, old_string, new_string, replace_all).await?;
+        if result.is_ok() {
+            self.record(&mut sizes, key, new_size);
+        }
+        Ok(result)
+    }
+
+    async fn glob(&self, pattern: &str, base_path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        self.inner.glob(pattern, base_path).await
+    }
+
+    async fn grep(
+        &self,
+        pattern: &str,
+        path: Option<&str>,
+        glob_filter: Option<&str>,
+        options: GrepOptions,
+    ) -> Result<Vec<GrepMatch>, BackendError> {
+        self.inner.grep(pattern, path, glob_filter, options).await
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, BackendError> {
+        self.inner.exists(path).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), BackendError> {
+        let key = normalize_path(path)?;
+        let mut sizes = self.sizes.lock().await;
+
+        self.inner.delete(path).await?;
+
+        if let Some(previous) = sizes.remove(&key) {
+            self.used.fetch_sub(previous, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+
+    fn quota(max_total: usize, max_file: Option<usize>) -> QuotaBackend {
+        QuotaBackend::new(Arc::new(MemoryBackend::new()), max_total, max_file)
+    }
+
+    #[tokio::test]
+    async fn test_quota_backend_tracks_usage() {
+        let backend = quota(100, None);
+
+        backend.write("/a.txt", "hello").await.unwrap();
+        backend.write("/b.txt", "world!").await.unwrap();
+        assert_eq!(backend.usage(), 11);
+
+        backend.edit("/a.txt", "hello", "hi", false).await.unwrap();
+        assert_eq!(backend.usage(), 8);
+
+        backend.delete("/b.txt").await.unwrap();
+        assert_eq!(backend.usage(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_quota_backend_rejects_total_overflow() {
+        let backend = quota(10, None);
+
+        backend.write("/a.txt", "12345678").await.unwrap();
+        let result = backend.write("/b.txt", "12345").await;
+        assert!(matches!(result, Err(BackendError::QuotaExceeded(_))));
+        assert!(!backend.exists("/b.txt").await.unwrap());
+
+        let result = backend.edit("/a.txt", "8", "8901", false).await;
+        assert!(matches!(result, Err(BackendError::QuotaExceeded(_))));
+        assert_eq!(backend.usage(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_quota_backend_rejects_large_file() {
+        let backend = quota(1_000, Some(4));
+
+        let result = backend.write("/big.txt", "too large").await;
+        assert!(matches!(result, Err(BackendError::QuotaExceeded(_))));
+        assert_eq!(backend.usage(), 0);
+
+        backend.write("/ok.txt", "tiny").await.unwrap();
+        assert_eq!(backend.usage(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_quota_backend_reads_unaffected() {
+        let backend = quota(5, Some(5));
+        backend.write("/a.txt", "hello").await.unwrap();
+
+        assert!(backend.read("/a.txt", 0, 10).await.unwrap().contains("hello"));
+        assert_eq!(backend.grep("hell", None, None, GrepOptions::default()).await.unwrap().len(), 1);
+        assert_eq!(backend.glob("*.txt", "/").await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+// src/backends/readonly.rs
+//! 읽기 전용 래퍼 백엔드
+//!
+//! 임의의 백엔드를 감싸 읽기 작업만 허용합니다.
+//! 파일을 절대 수정하면 안 되는 SubAgent에 제한된 파일시스템 뷰를 제공할 때 사용합니다.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::protocol::{Backend, GrepOptions, FileInfo, GrepMatch};
+use crate::error::{BackendError, WriteResult, EditResult};
+
+/// 읽기 전용 백엔드
+///
+/// `ls`, `read`, `glob`, `grep`, `exists`는 내부 백엔드로 전달하고,
+/// `write`, `edit`, `delete`는 `BackendError::ReadOnly`를 반환합니다.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let shared: Arc<dyn Backend> = Arc::new(FilesystemBackend::new("./workspace"));
+/// let restricted = Arc::new(ReadOnlyBackend::new(shared.clone()));
+/// ```
+pub struct ReadOnlyBackend {
+    inner: Arc<dyn Backend>,
+}
+
+impl ReadOnlyBackend {
+    pub fn new(inner: Arc<dyn Backend>) -> Self {
+        Self { inner }
+    }
+
+    /// 내부 백엔드 참조
+    pub fn inner(&self) -> &Arc<dyn Backend> {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl Backend for ReadOnlyBackend {
+    async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        self.inner.ls(path).await
+    }
+
+    async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        self.inner.read(path, offset, limit).await
+    }
+
+    async fn write(&self, path: &str, _content: &str) -> Result<WriteResult, BackendError> {
+        Err(BackendError::ReadOnly(path.to_string()))
+    }
+
+    async fn read_bytes(&self, path: &str) -> Result<Vec<u8>, BackendError> {
+        self.inner.read_bytes(path).await
+    }
+
+    async fn write_bytes(&self, path: &str, _content: &[u8]) -> Result<WriteResult, BackendError> {
+        Err(BackendError::ReadOnly(path.to_string()))
+    }
+
+    async fn edit(
+        &self,
+        path: &str,
+        _old_string: &str,
+        _new_string: &str,
+        _replace_all: bool
+    ) -> Result<EditResult, BackendError> {
+        Err(BackendError::ReadOnly(path.to_string()))
+    }
+
+    async fn glob(&self, pattern: &str, base_path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        self.inner.glob(pattern, base_path).await
+    }
+
+    async fn grep(
+        &self,
+        pattern: &str,
+        path: Option<&str>,
+        glob_filter: Option<&str>,
+        options: GrepOptions,
+    ) -> Result<Vec<GrepMatch>, BackendError> {
+        self.inner.grep(pattern, path, glob_filter, options).await
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, BackendError> {
+        self.inner.exists(path).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), BackendError> {
+        Err(BackendError::ReadOnly(path.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+
+    async fn seeded_backend() -> ReadOnlyBackend {
+        let inner = Arc::new(MemoryBackend::new());
+        inner.write("/docs/readme.md", "hello world").await.unwrap();
+        ReadOnlyBackend::new(inner)
+    }
+
+    #[tokio::test]
+    async fn test_readonly_backend_reads_pass_through() {
+        let backend = seeded_backend().await;
+
+        let content = backend.read("/docs/readme.md", 0, 100).await.unwrap();
+        assert!(content.contains("hello world"));
+
+        assert_eq!(backend.ls("/docs").await.unwrap().len(), 1);
+        assert_eq!(backend.glob("**/*.md", "/").await.unwrap().len(), 1);
+        assert_eq!(backend.grep("hello", None, None, GrepOptions::default()).await.unwrap().len(), 1);
+        assert!(backend.exists("/docs/readme.md").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_readonly_backend_rejects_writes() {
+        let backend = seeded_backend().await;
+
+        let result = backend.write("/new.txt", "content").await;
+        assert!(matches!(result, Err(BackendError::ReadOnly(ref p)) if p == "/new.txt"));
+
+        let result = backend.edit("/docs/readme.md", "hello", "bye", false).await;
+        assert!(matches!(result, Err(BackendError::ReadOnly(_))));
+
+        let result = backend.delete("/docs/readme.md").await;
+        assert!(matches!(result, Err(BackendError::ReadOnly(_))));
+
+        // 내부 백엔드는 변경되지 않아야 함
+        assert!(!backend.exists("/new.txt").await.unwrap());
+        let content = backend.read("/docs/readme.md", 0, 100).await.unwrap();
+        assert!(content.contains("hello world"));
+    }
+}
@@ -0,0 +1,623 @@
+// src/backends/s3.rs
+//! S3 호환 오브젝트 스토리지 백엔드 구현
+//!
+//! AWS S3, MinIO, Cloudflare R2 등 S3 API를 지원하는 모든 엔드포인트에서 동작합니다.
+//! 가상 경로는 설정된 prefix 아래의 오브젝트 키로 매핑됩니다.
+//!
+//! ```text
+//! prefix = "agents/run-1/"
+//! /notes/plan.md  →  s3://{bucket}/agents/run-1/notes/plan.md
+//! ```
+//!
+//! 자격 증명과 엔드포인트 설정은 호출자가 관리합니다 (이미 구성된 `Client`를 전달).
+
+use async_trait::async_trait;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::{ByteStream, DateTimeFormat};
+use aws_sdk_s3::types::Object;
+use aws_sdk_s3::Client;
+use tokio::io::AsyncBufReadExt;
+
+use super::path_utils::{normalize_path, GlobMatcher};
+use super::protocol::{Backend, GrepOptions, FileInfo, GrepMatch};
+use crate::error::{BackendError, EditResult, WriteResult};
+
+/// S3 호환 오브젝트 스토리지 백엔드
+///
+/// S3에는 실제 디렉토리가 없으므로 `ls`는 `ListObjectsV2`의 `/` delimiter를 사용해
+/// 공통 prefix를 의사 디렉토리(pseudo-directory)로 노출합니다.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = aws_config::load_from_env().await;
+/// let client = aws_sdk_s3::Client::new(&config);
+/// let backend = S3Backend::new(client, "research-bucket", "agents/run-1");
+/// ```
+pub struct S3Backend {
+    client: Client,
+    bucket: String,
+    /// 오브젝트 키 prefix (비어있거나 `/`로 끝남)
+    prefix: String,
+}
+
+impl S3Backend {
+    /// 새 S3 백엔드 생성
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - 자격 증명/엔드포인트가 구성된 S3 클라이언트
+    /// * `bucket` - 대상 버킷 이름
+    /// * `prefix` - 모든 가상 경로 앞에 붙는 키 prefix (빈 문자열이면 버킷 루트)
+    pub fn new(client: Client, bucket: impl Into<String>, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        let trimmed = prefix.trim_matches('/');
+        let prefix = if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", trimmed)
+        };
+
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix,
+        }
+    }
+
+    /// 버킷 이름
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// 키 prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// 가상 경로 → 오브젝트 키
+    fn key_for(&self, path: &str) -> Result<String, BackendError> {
+        let normalized = normalize_path(path)?;
+        Ok(format!("{}{}", self.prefix, normalized.trim_start_matches('/')))
+    }
+
+    /// 가상 디렉토리 경로 → 리스트용 키 prefix (`/`로 끝남)
+    fn dir_prefix_for(&self, path: &str) -> Result<String, BackendError> {
+        let key = self.key_for(path)?;
+        if key.is_empty() || key.ends_with('/') {
+            Ok(key)
+        } else {
+            Ok(format!("{}/", key))
+        }
+    }
+
+    /// 오브젝트 키 → 가상 경로
+    fn path_for(&self, key: &str) -> String {
+        let relative = key.strip_prefix(&self.prefix).unwrap_or(key);
+        format!("/{}", relative.trim_end_matches('/'))
+    }
+
+    fn file_info(&self, object: &Object) -> Option<FileInfo> {
+        let key = object.key()?;
+        // 디렉토리 마커 오브젝트 ("dir/")는 파일로 취급하지 않음
+        if key.ends_with('/') {
+            return None;
+        }
+
+        Some(FileInfo {
+            path: self.path_for(key),
+            is_dir: false,
+            size: object.size().map(|s| s.max(0) as u64),
+            modified_at: object
+                .last_modified()
+                .and_then(|t| t.fmt(DateTimeFormat::DateTime).ok()),
+        })
+    }
+
+    /// prefix 하위의 모든 오브젝트를 재귀적으로 나열
+    async fn list_all(&self, key_prefix: &str) -> Result<Vec<Object>, BackendError> {
+        let mut objects = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(key_prefix)
+            .into_paginator()
+            .send();
+
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(sdk_error)?;
+            objects.extend(page.contents().iter().cloned());
+        }
+
+        Ok(objects)
+    }
+
+    /// 오브젝트를 문자열로 읽기 (없으면 None)
+    async fn get_string(&self, key: &str) -> Result<Option<String>, BackendError> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_key()) => {
+                return Ok(None);
+            }
+            Err(e) => return Err(sdk_error(e)),
+        };
+
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| BackendError::Io(e.to_string()))?
+            .into_bytes();
+
+        String::from_utf8(bytes.to_vec())
+            .map(Some)
+            .map_err(|e| BackendError::Io(format!("Object {} is not valid UTF-8: {}", key, e)))
+    }
+
+    async fn put_string(&self, key: &str, content: &str) -> Result<(), BackendError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type("text/plain; charset=utf-8")
+            .body(ByteStream::from(content.as_bytes().to_vec()))
+            .send()
+            .await
+            .map_err(sdk_error)?;
+        Ok(())
+    }
+
+    async fn key_exists(&self, key: &str) -> Result<bool, BackendError> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().is_some_and(|se| se.is_not_found()) => Ok(false),
+            Err(e) => Err(sdk_error(e)),
+        }
+    }
+
+    fn format_with_line_numbers(content: &str, offset: usize) -> String {
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| format!("{}\t{}", offset + i + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn sdk_error<E, R>(err: aws_sdk_s3::error::SdkError<E, R>) -> BackendError
+where
+    E: std::error::Error + 'static,
+    R: std::fmt::Debug,
+{
+    BackendError::Io(format!("S3 error: {}", DisplayErrorContext(&err)))
+}
+
+#[async_trait]
+impl Backend for S3Backend {
+    async fn ls(&self, path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        let dir_prefix = self.dir_prefix_for(path)?;
+
+        let mut results = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&dir_prefix)
+            .delimiter("/")
+            .into_paginator()
+            .send();
+
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(sdk_error)?;
+
+            for common in page.common_prefixes() {
+                if let Some(p) = common.prefix() {
+                    results.push(FileInfo::dir(&format!("{}/", self.path_for(p))));
+                }
+            }
+
+            for object in page.contents() {
+                if let Some(info) = self.file_info(object) {
+                    results.push(info);
+                }
+            }
+        }
+
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(results)
+    }
+
+    async fn read(&self, path: &str, offset: usize, limit: usize) -> Result<String, BackendError> {
+        let key = self.key_for(path)?;
+        let content = self
+            .get_string(&key)
+            .await?
+            .ok_or_else(|| BackendError::FileNotFound(path.to_string()))?;
+
+        let selected = content
+            .lines()
+            .skip(offset)
+            .take(limit)
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Self::format_with_line_numbers(&selected, offset))
+    }
+
+    async fn write(&self, path: &str, content: &str) -> Result<WriteResult, BackendError> {
+        let key = self.key_for(path)?;
+
+        if self.key_exists(&key).await? {
+            return Ok(WriteResult::error(&format!(
+                "Cannot write to {} because it already exists. Read and then make an edit.",
+                path
+            )));
+        }
+
+        self.put_string(&key, content).await?;
+
+        // 외부 백엔드이므로 files_update = None
+        Ok(WriteResult::success_external(path))
+    }
+
+    async fn edit(
+        &self,
+        path: &str,
+        old_string: &str,
+        new_string: &str,
+        replace_all: bool
+    ) -> Result<EditResult, BackendError> {
+        let key = self.key_for(path)?;
+        let content = self
+            .get_string(&key)
+            .await?
+            .ok_or_else(|| BackendError::FileNotFound(path.to_string()))?;
+
+        let occurrences = content.matches(old_string).count();
+
+        if occurrences == 0 {
+            return Ok(EditResult::error(&format!("String '{}' not found in file", old_string)));
+        }
+
+        if !replace_all && occurrences > 1 {
+            return Ok(EditResult::error(&format!(
+                "String '{}' found {} times. Use replace_all=true or provide more context.",
+                old_string, occurrences
+            )));
+        }
+
+        let new_content = if replace_all {
+            content.replace(old_string, new_string)
+        } else {
+            content.replacen(old_string, new_string, 1)
+        };
+
+        self.put_string(&key, &new_content).await?;
+
+        let actual = if replace_all { occurrences } else { 1 };
+        Ok(EditResult::success_external(path, actual))
+    }
+
+    async fn glob(&self, pattern: &str, base_path: &str) -> Result<Vec<FileInfo>, BackendError> {
+        let glob_pattern = GlobMatcher::new(pattern)?;
+
+        let dir_prefix = self.dir_prefix_for(base_path)?;
+        let mut results = Vec::new();
+
+        for object in self.list_all(&dir_prefix).await? {
+            let Some(info) = self.file_info(&object) else {
+                continue;
+            };
+
+            // base_path 기준 상대 경로로 매칭 (FilesystemBackend와 동일)
+            let key = object.key().unwrap_or_default();
+            let rel_path = key.strip_prefix(&dir_prefix).unwrap_or(key);
+
+            if glob_pattern.matches(rel_path) {
+                results.push(info);
+            }
+        }
+
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(results)
+    }
+
+    /// 리터럴 텍스트 검색
+    ///
+    /// 매칭되는 오브젝트를 하나씩 스트리밍하며 라인 단위로 검사하므로
+    /// 큰 오브젝트도 메모리에 한 번에 올리지 않습니다.
+    async fn grep(
+        &self,
+        pattern: &str,
+        path: Option<&str>,
+        glob_filter: Option<&str>,
+        options: GrepOptions,
+    ) -> Result<Vec<GrepMatch>, BackendError> {
+        let dir_prefix = self.dir_prefix_for(path.unwrap_or("/"))?;
+
+        // glob 패턴 정규화: **로 시작하지 않으면 **/ 접두사 추가
+        let glob_pattern = glob_filter.map(|g| {
+            let normalized = if g.starts_with("**/") || g.starts_with('/') {
+                g.to_string()
+            } else {
+                format!("**/{}", g)
+            };
+            GlobMatcher::new(&normalized)
+        }).transpose()?;
+        let matcher = options.matcher(pattern)?;
+
+        let mut results = Vec::new();
+
+        for object in self.list_all(&dir_prefix).await? {
+            let Some(key) = object.key() else {
+                continue;
+            };
+            if key.ends_with('/') {
+                continue;
+            }
+
+            if let Some(ref gp) = glob_pattern {
+                let relative_path = key.strip_prefix(&dir_prefix).unwrap_or(key);
+                let filename = relative_path.rsplit('/').next().unwrap_or(relative_path);
+                if !gp.matches(relative_path) && !gp.matches(filename) {
+                    continue;
+                }
+            }
+
+            let output = match self
+                .client
+                .get_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(e) => {
+                    tracing::debug!(key, error = %DisplayErrorContext(&e), "Skipping object in grep due to read error");
+                    continue;
+                }
+            };
+
+            let virt_path = self.path_for(key);
+
+            // 여러 줄 매칭과 컨텍스트 라인은 전체 내용이 필요
+            if matcher.is_multiline() || matcher.has_context() {
+                match output.body.collect().await {
+                    Ok(body) => {
+                        let content = String::from_utf8_lossy(&body.into_bytes()).into_owned();
+                        results.extend(matcher.search(&virt_path, &content));
+                    }
+                    Err(e) => {
+                        tracing::debug!(key, error = %e, "Skipping object in grep due to read error");
+                    }
+                }
+                continue;
+            }
+
+            let mut lines = output.body.into_async_read().lines();
+            let mut line_num = 0;
+
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => {
+                        line_num += 1;
+                        if matcher.is_match(&line) {
+                            results.push(GrepMatch::new(&virt_path, line_num, &line));
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::debug!(key, error = %e, "Stopping grep on object due to read error");
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, BackendError> {
+        let key = self.key_for(path)?;
+        self.key_exists(&key).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), BackendError> {
+        let key = self.key_for(path)?;
+
+        if !self.key_exists(&key).await? {
+            return Err(BackendError::FileNotFound(path.to_string()));
+        }
+
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+            .map_err(sdk_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region, RequestChecksumCalculation};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::any;
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    const NO_SUCH_KEY: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+        <Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>";
+
+    /// 오브젝트를 메모리에 보관하는 목 S3 서버 (path-style GET/HEAD/PUT/DELETE)
+    #[derive(Clone, Default)]
+    struct InMemoryS3 {
+        /// `{bucket}/{key}` → 오브젝트 바이트
+        objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    }
+
+    impl InMemoryS3 {
+        fn object(&self, key: &str) -> Option<Vec<u8>> {
+            self.objects.lock().unwrap().get(key).cloned()
+        }
+    }
+
+    impl Respond for InMemoryS3 {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let key = request.url.path().trim_start_matches('/').to_string();
+            let mut objects = self.objects.lock().unwrap();
+
+            match (request.method.as_str(), objects.get(&key)) {
+                ("PUT", _) => {
+                    objects.insert(key, request.body.clone());
+                    ResponseTemplate::new(200)
+                }
+                ("DELETE", _) => {
+                    objects.remove(&key);
+                    ResponseTemplate::new(204)
+                }
+                ("HEAD", Some(body)) => ResponseTemplate::new(200)
+                    .insert_header("Content-Length", body.len().to_string().as_str()),
+                ("GET", Some(body)) => ResponseTemplate::new(200).set_body_bytes(body.clone()),
+                ("GET", None) => ResponseTemplate::new(404)
+                    .set_body_raw(NO_SUCH_KEY, "application/xml"),
+                _ => ResponseTemplate::new(404),
+            }
+        }
+    }
+
+    async fn mocked_backend() -> (MockServer, InMemoryS3, S3Backend) {
+        let server = MockServer::start().await;
+        let store = InMemoryS3::default();
+        Mock::given(any()).respond_with(store.clone()).mount(&server).await;
+
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+            .endpoint_url(server.uri())
+            .force_path_style(true)
+            .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+            .build();
+        let backend = S3Backend::new(Client::from_conf(config), "bucket", "agents");
+        (server, store, backend)
+    }
+
+    fn offline_client() -> Client {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .build();
+        Client::from_conf(config)
+    }
+
+    #[test]
+    fn test_s3_backend_prefix_normalization() {
+        assert_eq!(S3Backend::new(offline_client(), "b", "agents/run-1").prefix(), "agents/run-1/");
+        assert_eq!(S3Backend::new(offline_client(), "b", "/agents/run-1/").prefix(), "agents/run-1/");
+        assert_eq!(S3Backend::new(offline_client(), "b", "").prefix(), "");
+    }
+
+    #[test]
+    fn test_s3_backend_key_mapping() {
+        let backend = S3Backend::new(offline_client(), "bucket", "agents/run-1");
+
+        assert_eq!(backend.key_for("/notes/plan.md").unwrap(), "agents/run-1/notes/plan.md");
+        assert_eq!(backend.key_for("notes//plan.md").unwrap(), "agents/run-1/notes/plan.md");
+        assert_eq!(backend.dir_prefix_for("/notes").unwrap(), "agents/run-1/notes/");
+        assert_eq!(backend.dir_prefix_for("/").unwrap(), "agents/run-1/");
+
+        assert_eq!(backend.path_for("agents/run-1/notes/plan.md"), "/notes/plan.md");
+        assert_eq!(backend.path_for("agents/run-1/notes/"), "/notes");
+    }
+
+    #[test]
+    fn test_s3_backend_rejects_traversal() {
+        let backend = S3Backend::new(offline_client(), "bucket", "agents");
+        assert!(matches!(
+            backend.key_for("/../secrets.txt"),
+            Err(BackendError::PathTraversal(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_s3_backend_write_read_round_trip() {
+        let (_server, store, backend) = mocked_backend().await;
+
+        let result = backend.write("/notes/plan.md", "line one\nline two\n").await.unwrap();
+        assert!(result.is_ok());
+        assert!(result.files_update.is_none());
+        assert_eq!(
+            store.object("bucket/agents/notes/plan.md").as_deref(),
+            Some(&b"line one\nline two\n"[..])
+        );
+
+        let content = backend.read("/notes/plan.md", 0, 10).await.unwrap();
+        assert_eq!(content, "1\tline one\n2\tline two");
+        assert_eq!(backend.read("/notes/plan.md", 1, 1).await.unwrap(), "2\tline two");
+        assert!(backend.exists("/notes/plan.md").await.unwrap());
+
+        // 기존 오브젝트는 덮어쓰지 않음
+        let again = backend.write("/notes/plan.md", "clobber").await.unwrap();
+        assert!(!again.is_ok());
+        assert_eq!(
+            store.object("bucket/agents/notes/plan.md").as_deref(),
+            Some(&b"line one\nline two\n"[..])
+        );
+
+        assert!(matches!(
+            backend.read("/notes/missing.md", 0, 10).await,
+            Err(BackendError::FileNotFound(_))
+        ));
+        assert!(!backend.exists("/notes/missing.md").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_s3_backend_edit_round_trip() {
+        let (_server, store, backend) = mocked_backend().await;
+        backend.write("/draft.md", "alpha beta\nbeta gamma").await.unwrap();
+
+        let result = backend.edit("/draft.md", "alpha", "ALPHA", false).await.unwrap();
+        assert!(result.is_ok());
+        assert_eq!(result.occurrences, Some(1));
+
+        // 여러 번 매치되면 replace_all 없이는 거부
+        let ambiguous = backend.edit("/draft.md", "beta", "BETA", false).await.unwrap();
+        assert!(!ambiguous.is_ok());
+
+        let result = backend.edit("/draft.md", "beta", "BETA", true).await.unwrap();
+        assert_eq!(result.occurrences, Some(2));
+        assert_eq!(
+            store.object("bucket/agents/draft.md").as_deref(),
+            Some(&b"ALPHA BETA\nBETA gamma"[..])
+        );
+        assert_eq!(backend.read_plain("/draft.md").await.unwrap(), "ALPHA BETA\nBETA gamma");
+
+        let missing = backend.edit("/draft.md", "delta", "DELTA", false).await.unwrap();
+        assert!(!missing.is_ok());
+        assert!(matches!(
+            backend.edit("/nope.md", "a", "b", false).await,
+            Err(BackendError::FileNotFound(_))
+        ));
+    }
+}
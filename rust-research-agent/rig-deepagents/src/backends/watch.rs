@@ -0,0 +1,205 @@
+// src/backends/watch.rs
+//! 파일 변경 알림 (`watch` feature)
+//!
+//! `FilesystemBackend::subscribe()`가 반환하는 이벤트 스트림 구현입니다.
+//! `notify` crate로 루트 디렉토리를 재귀 감시하고,
+//! 이벤트를 가상 경로로 변환하여 `tokio::sync::broadcast` 채널로 전달합니다.
+//!
+//! # Debounce
+//!
+//! 편집기나 `fs::write`는 한 번의 저장에 여러 개의 원시 이벤트를 발생시키는 경우가 많습니다.
+//! 같은 경로에 대해 같은 종류의 이벤트가 [`DEFAULT_DEBOUNCE`] 이내에 반복되면
+//! 첫 번째 이벤트만 전달합니다 (leading-edge). 종류가 다른 이벤트
+//! (예: Created 직후 Modified)는 각각 전달됩니다.
+//!
+//! 트랜잭션 임시 파일(`.{name}.{uuid}.tmp`)에 대한 이벤트는 전달하지 않습니다.
+
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+use crate::error::BackendError;
+
+/// 같은 경로/종류의 반복 이벤트를 합치는 간격
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// broadcast 채널 용량 (느린 구독자는 `RecvError::Lagged`를 받음)
+const CHANNEL_CAPACITY: usize = 256;
+
+/// 파일 이벤트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileEventKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// 파일 변경 이벤트
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEvent {
+    pub kind: FileEventKind,
+    /// 백엔드 기준 경로 (가상 모드에서는 `/`로 시작하는 루트 상대 경로)
+    pub path: String,
+}
+
+/// 실행 중인 감시자
+///
+/// drop되면 감시가 중단됩니다.
+pub(crate) struct FileWatcher {
+    sender: broadcast::Sender<FileEvent>,
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// 루트 디렉토리 감시 시작
+    pub(crate) fn start(root: &Path, virtual_mode: bool) -> Result<Self, BackendError> {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        // 플랫폼에 따라 canonical 경로로 이벤트가 보고되므로 두 경로 모두 보관
+        let canonical_root = root.canonicalize()
+            .map_err(|e| BackendError::Io(e.to_string()))?;
+        let mapper = PathMapper {
+            roots: vec![canonical_root.clone(), root.to_path_buf()],
+            virtual_mode,
+        };
+
+        let tx = sender.clone();
+        let last_sent: Mutex<HashMap<(String, FileEventKind), Instant>> = Mutex::new(HashMap::new());
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else { return };
+
+            for (kind, path) in classify(&event) {
+                if is_transaction_temp(&path) {
+                    continue;
+                }
+
+                let path = mapper.to_backend_path(&path);
+                let now = Instant::now();
+                let mut last_sent = last_sent.lock().unwrap_or_else(|e| e.into_inner());
+                let key = (path.clone(), kind);
+
+                if last_sent.get(&key).is_some_and(|t| now.duration_since(*t) < DEFAULT_DEBOUNCE) {
+                    continue;
+                }
+                last_sent.insert(key, now);
+                last_sent.retain(|_, t| now.duration_since(*t) < DEFAULT_DEBOUNCE);
+
+                // 구독자가 없으면 에러지만 무시
+                let _ = tx.send(FileEvent { kind, path });
+            }
+        })
+        .map_err(|e| BackendError::Io(e.to_string()))?;
+
+        watcher.watch(&canonical_root, RecursiveMode::Recursive)
+            .map_err(|e| BackendError::Io(e.to_string()))?;
+
+        Ok(Self { sender, _watcher: watcher })
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<FileEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// 실제 경로 → 백엔드 경로 변환
+struct PathMapper {
+    roots: Vec<PathBuf>,
+    virtual_mode: bool,
+}
+
+impl PathMapper {
+    fn to_backend_path(&self, path: &Path) -> String {
+        if !self.virtual_mode {
+            return path.display().to_string();
+        }
+
+        self.roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .map(|p| format!("/{}", p.display()))
+            .unwrap_or_else(|| path.display().to_string())
+    }
+}
+
+/// notify 이벤트를 (종류, 경로) 목록으로 변환
+fn classify(event: &Event) -> Vec<(FileEventKind, PathBuf)> {
+    let kind = match event.kind {
+        EventKind::Create(_) => FileEventKind::Created,
+        EventKind::Remove(_) => FileEventKind::Deleted,
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => FileEventKind::Deleted,
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => FileEventKind::Created,
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            // paths = [from, to]
+            let mut events = Vec::new();
+            if let Some(from) = event.paths.first() {
+                events.push((FileEventKind::Deleted, from.clone()));
+            }
+            if let Some(to) = event.paths.get(1) {
+                events.push((FileEventKind::Created, to.clone()));
+            }
+            return events;
+        }
+        EventKind::Modify(_) => FileEventKind::Modified,
+        _ => return Vec::new(),
+    };
+
+    event.paths.iter().map(|p| (kind, p.clone())).collect()
+}
+
+/// 트랜잭션 임시 파일 여부 (`.{name}.{uuid}.tmp`)
+fn is_transaction_temp(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.') && n.ends_with(".tmp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{Backend, FilesystemBackend};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_subscribe_receives_write_event() {
+        let temp = TempDir::new().unwrap();
+        let backend = FilesystemBackend::new(temp.path());
+        let mut events = backend.subscribe().unwrap();
+
+        backend.write("/notes.txt", "hello").await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = events.recv().await.unwrap();
+                if event.path == "/notes.txt" {
+                    return event;
+                }
+            }
+        })
+        .await
+        .expect("no event for /notes.txt");
+
+        assert!(matches!(event.kind, FileEventKind::Created | FileEventKind::Modified));
+    }
+
+    #[test]
+    fn test_classify_rename_both() {
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/root/a.txt"))
+            .add_path(PathBuf::from("/root/b.txt"));
+
+        let classified = classify(&event);
+        assert_eq!(classified, vec![
+            (FileEventKind::Deleted, PathBuf::from("/root/a.txt")),
+            (FileEventKind::Created, PathBuf::from("/root/b.txt")),
+        ]);
+        assert!(is_transaction_temp(Path::new("/root/.a.txt.1234.tmp")));
+        assert!(!is_transaction_temp(Path::new("/root/a.tmp")));
+    }
+}
@@ -85,6 +85,8 @@ struct SkillMetadata {
     tags: Vec<String>,
     version: Option<String>,
     author: Option<String>,
+    #[serde(default)]
+    requires: Vec<String>,
 }
 
 fn main() -> ExitCode {
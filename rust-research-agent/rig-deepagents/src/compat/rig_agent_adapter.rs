@@ -40,23 +40,33 @@
 //!
 //! - Tool definitions passed to `complete()` are forwarded to Rig's completion API
 //!   so the model can emit tool calls, but execution remains external.
-//! - Streaming emits text chunks only; tool call streaming is ignored.
+//! - Streaming tool call deltas carry only the call ID and an argument fragment;
+//!   the tool name is known once the completed call arrives.
+//! - Rig doesn't normalize finish reasons, so they are read from the provider's
+//!   raw response (see `finish_from_raw`). Unrecognized shapes fall back to
+//!   `Stop`/`ToolCalls` based on the message.
 
 use async_trait::async_trait;
 use std::sync::Arc;
 
 use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
 
 use rig::agent::Agent;
 use rig::completion::{
-    Completion, CompletionModel, GetTokenUsage, Message as RigMessage, ToolDefinition as RigToolDefinition,
+    Completion, CompletionError, CompletionModel, CompletionRequestBuilder, GetTokenUsage, Message as RigMessage,
+    ToolDefinition as RigToolDefinition,
 };
 use rig::message::{AssistantContent, ToolCall as RigToolCall};
 use rig::streaming::StreamedAssistantContent;
 use rig::OneOrMany;
 
 use crate::error::DeepAgentError;
-use crate::llm::{LLMConfig, LLMProvider, LLMResponse, LLMResponseStream, MessageChunk, TokenUsage};
+use crate::llm::{
+    to_user_contents, FinishReason, LLMConfig, LLMProvider, LLMResponse, LLMResponseStream, MessageChunk,
+    TokenUsage, ToolCallDelta,
+};
 use crate::middleware::ToolDefinition;
 use crate::state::{Message, Role, ToolCall};
 
@@ -147,14 +157,7 @@ where
             builder = builder.preamble(preamble);
         }
 
-        if let Some(cfg) = config {
-            if let Some(temperature) = cfg.temperature {
-                builder = builder.temperature(temperature);
-            }
-            if let Some(max_tokens) = cfg.max_tokens {
-                builder = builder.max_tokens(max_tokens);
-            }
-        }
+        builder = apply_llm_config(builder, config);
 
         let rig_tools = to_rig_tool_definitions(tools);
         if !rig_tools.is_empty() {
@@ -174,6 +177,18 @@ where
             llm_response = llm_response.with_usage(usage);
         }
 
+        let raw = serde_json::to_value(&response.raw_response).unwrap_or_default();
+        let (finish_reason, refusal) = finish_from_raw(&raw);
+        match finish_reason {
+            // The Responses API reports `completed` even when the turn ends in tool calls
+            Some(FinishReason::Stop) if llm_response.message.has_tool_calls() => {}
+            Some(reason) => llm_response = llm_response.with_finish_reason(reason),
+            None => {}
+        }
+        if let Some(refusal) = refusal {
+            llm_response = llm_response.with_refusal(refusal);
+        }
+
         Ok(llm_response)
     }
 
@@ -198,14 +213,7 @@ where
             builder = builder.preamble(preamble);
         }
 
-        if let Some(cfg) = config {
-            if let Some(temperature) = cfg.temperature {
-                builder = builder.temperature(temperature);
-            }
-            if let Some(max_tokens) = cfg.max_tokens {
-                builder = builder.max_tokens(max_tokens);
-            }
-        }
+        builder = apply_llm_config(builder, config);
 
         let rig_tools = to_rig_tool_definitions(tools);
         if !rig_tools.is_empty() {
@@ -217,31 +225,7 @@ where
             .await
             .map_err(|e| DeepAgentError::LlmError(format!("Rig agent error: {}", e)))?;
 
-        let mapped = stream.filter_map(|item| async move {
-            match item {
-                Ok(StreamedAssistantContent::Text(text)) => Some(Ok(MessageChunk {
-                    content: text.text,
-                    is_final: false,
-                    usage: None,
-                })),
-                Ok(StreamedAssistantContent::Final(response)) => {
-                    let usage = response
-                        .token_usage()
-                        .map(|usage| TokenUsage::from_rig_usage(&usage))
-                        .filter(|usage| usage.total_tokens > 0);
-                    Some(Ok(MessageChunk {
-                        content: String::new(),
-                        is_final: true,
-                        usage,
-                    }))
-                }
-                Ok(_) => None,
-                Err(err) => Some(Err(DeepAgentError::LlmError(format!(
-                    "Rig agent error: {}",
-                    err
-                )))),
-            }
-        });
+        let mapped = stream.filter_map(|item| async move { to_message_chunk(item) });
 
         Ok(LLMResponseStream::new(mapped))
     }
@@ -255,6 +239,40 @@ where
     }
 }
 
+/// Apply per-call `LLMConfig` overrides to a Rig request builder.
+///
+/// Only `Some`/non-empty values override the agent's defaults. `top_p` and
+/// `stop` have no dedicated Rig builder method, so they are merged into the
+/// request's additional params using the OpenAI-compatible names.
+fn apply_llm_config<M: CompletionModel>(
+    mut builder: CompletionRequestBuilder<M>,
+    config: Option<&LLMConfig>,
+) -> CompletionRequestBuilder<M> {
+    let Some(cfg) = config else {
+        return builder;
+    };
+
+    if let Some(temperature) = cfg.temperature {
+        builder = builder.temperature(temperature);
+    }
+    if let Some(max_tokens) = cfg.max_tokens {
+        builder = builder.max_tokens(max_tokens);
+    }
+    if let Some(top_p) = cfg.top_p {
+        builder = builder.additional_params(serde_json::json!({ "top_p": top_p }));
+    }
+    if !cfg.stop.is_empty() {
+        builder = builder.additional_params(serde_json::json!({ "stop": cfg.stop }));
+    }
+    if let Some(format) = &cfg.response_format {
+        builder = builder.additional_params(serde_json::json!({
+            "response_format": format.to_request_param(),
+        }));
+    }
+
+    builder
+}
+
 struct RigConversation {
     prompt: RigMessage,
     history: Vec<RigMessage>,
@@ -272,7 +290,7 @@ fn build_rig_conversation(messages: &[Message]) -> RigConversation {
                     system_parts.push(message.content.clone());
                 }
             }
-            Role::User => rig_messages.push(RigMessage::user(message.content.clone())),
+            Role::User => rig_messages.push(convert_user_message(message)),
             Role::Assistant => rig_messages.push(convert_assistant_message(message)),
             Role::Tool => rig_messages.push(convert_tool_message(message)),
         }
@@ -295,6 +313,13 @@ fn build_rig_conversation(messages: &[Message]) -> RigConversation {
     }
 }
 
+fn convert_user_message(message: &Message) -> RigMessage {
+    match message.rich_content.as_ref().map(to_user_contents) {
+        Some(Ok(content)) => RigMessage::User { content },
+        _ => RigMessage::user(message.content.clone()),
+    }
+}
+
 fn convert_assistant_message(message: &Message) -> RigMessage {
     let mut contents = Vec::new();
 
@@ -334,7 +359,7 @@ fn to_rig_tool_definitions(tools: &[ToolDefinition]) -> Vec<RigToolDefinition> {
         .iter()
         .map(|tool| RigToolDefinition {
             name: tool.name.clone(),
-            description: tool.description.clone(),
+            description: tool.description_with_examples(),
             parameters: tool.parameters.clone(),
         })
         .collect()
@@ -364,6 +389,95 @@ fn message_from_rig_choice(choice: &OneOrMany<AssistantContent>) -> Message {
     }
 }
 
+/// Raw-response locations of the finish reason, in lookup order
+///
+/// Covers OpenAI Chat Completions, Anthropic, Gemini, and the OpenAI
+/// Responses API (`incomplete_details.reason` before the coarser `status`).
+const FINISH_REASON_POINTERS: &[&str] = &[
+    "/choices/0/finish_reason",
+    "/stop_reason",
+    "/candidates/0/finishReason",
+    "/incomplete_details/reason",
+    "/status",
+];
+
+/// Extract the finish reason and refusal text from a serialized raw response.
+fn finish_from_raw(raw: &Value) -> (Option<FinishReason>, Option<String>) {
+    let finish_reason = FINISH_REASON_POINTERS
+        .iter()
+        .find_map(|pointer| raw.pointer(pointer).and_then(Value::as_str))
+        .map(FinishReason::from_provider);
+
+    let refusal = raw
+        .pointer("/choices/0/message/refusal")
+        .and_then(Value::as_str)
+        .or_else(|| {
+            // Responses API: refusal is a content part of an output message
+            raw.get("output")?
+                .as_array()?
+                .iter()
+                .filter_map(|item| item.get("content")?.as_array())
+                .flatten()
+                .find(|part| part.get("type").and_then(Value::as_str) == Some("refusal"))?
+                .get("refusal")?
+                .as_str()
+        })
+        .map(str::to_string);
+
+    (finish_reason, refusal)
+}
+
+/// Map one Rig streaming item to a `MessageChunk`.
+///
+/// Reasoning items are dropped. The final item's raw response is probed for
+/// a finish reason the same way as non-streaming responses.
+fn to_message_chunk<R: GetTokenUsage + Serialize>(
+    item: Result<StreamedAssistantContent<R>, CompletionError>,
+) -> Option<Result<MessageChunk, DeepAgentError>> {
+    let chunk = |content: String| MessageChunk {
+        content,
+        is_final: false,
+        usage: None,
+        tool_calls: Vec::new(),
+        tool_call_delta: None,
+        finish_reason: None,
+    };
+
+    match item {
+        Ok(StreamedAssistantContent::Text(text)) => Some(Ok(chunk(text.text))),
+        Ok(StreamedAssistantContent::ToolCall(tool_call)) => Some(Ok(MessageChunk {
+            tool_calls: vec![convert_rig_tool_call(&tool_call)],
+            ..chunk(String::new())
+        })),
+        Ok(StreamedAssistantContent::ToolCallDelta { id, delta }) => Some(Ok(MessageChunk {
+            tool_call_delta: Some(ToolCallDelta {
+                id,
+                arguments_delta: delta,
+            }),
+            ..chunk(String::new())
+        })),
+        Ok(StreamedAssistantContent::Final(response)) => {
+            let usage = response
+                .token_usage()
+                .map(|usage| TokenUsage::from_rig_usage(&usage))
+                .filter(|usage| usage.total_tokens > 0);
+            let raw = serde_json::to_value(&response).unwrap_or_default();
+            let (finish_reason, _) = finish_from_raw(&raw);
+            Some(Ok(MessageChunk {
+                is_final: true,
+                usage,
+                finish_reason,
+                ..chunk(String::new())
+            }))
+        }
+        Ok(_) => None,
+        Err(err) => Some(Err(DeepAgentError::LlmError(format!(
+            "Rig agent error: {}",
+            err
+        )))),
+    }
+}
+
 fn convert_rig_tool_call(tool_call: &RigToolCall) -> ToolCall {
     ToolCall {
         id: tool_call.id.clone(),
@@ -388,6 +502,7 @@ where
 mod tests {
     use super::*;
     use rig::message::UserContent;
+    use crate::llm::ResponseFormat;
 
     fn rig_message_text(message: &RigMessage) -> Option<String> {
         match message {
@@ -402,6 +517,56 @@ mod tests {
         }
     }
 
+    fn request_builder() -> CompletionRequestBuilder<rig::providers::openai::responses_api::ResponsesCompletionModel> {
+        use rig::client::CompletionClient;
+        let client = rig::providers::openai::Client::new("test-key").unwrap();
+        CompletionRequestBuilder::new(client.completion_model("gpt-4.1"), "hello")
+    }
+
+    #[test]
+    fn test_apply_llm_config_forwards_sampling_options() {
+        let config = LLMConfig::new("gpt-4.1")
+            .with_temperature(0.2)
+            .with_max_tokens(512)
+            .with_top_p(0.8)
+            .with_stop("END");
+
+        let request = apply_llm_config(request_builder(), Some(&config)).build();
+
+        assert_eq!(request.temperature, Some(0.2));
+        assert_eq!(request.max_tokens, Some(512));
+        let params = request.additional_params.unwrap();
+        assert_eq!(params["top_p"], 0.8);
+        assert_eq!(params["stop"], serde_json::json!(["END"]));
+    }
+
+    #[test]
+    fn test_apply_llm_config_forwards_response_format() {
+        let schema = serde_json::json!({"type": "object"});
+        let config = LLMConfig::new("gpt-4.1")
+            .with_top_p(0.5)
+            .with_response_format(ResponseFormat::json_schema("Report", schema.clone()));
+
+        let request = apply_llm_config(request_builder(), Some(&config)).build();
+
+        let params = request.additional_params.unwrap();
+        assert_eq!(params["top_p"], 0.5);
+        assert_eq!(params["response_format"]["type"], "json_schema");
+        assert_eq!(params["response_format"]["json_schema"]["name"], "Report");
+        assert_eq!(params["response_format"]["json_schema"]["schema"], schema);
+    }
+
+    #[test]
+    fn test_apply_llm_config_keeps_agent_defaults_when_unset() {
+        let builder = request_builder().temperature(0.9).max_tokens(100);
+
+        let request = apply_llm_config(builder, Some(&LLMConfig::new("gpt-4.1"))).build();
+
+        assert_eq!(request.temperature, Some(0.9));
+        assert_eq!(request.max_tokens, Some(100));
+        assert!(request.additional_params.is_none());
+    }
+
     #[test]
     fn test_build_rig_conversation_history_and_preamble() {
         let messages = vec![
@@ -420,6 +585,24 @@ mod tests {
         assert_eq!(rig_message_text(&conversation.prompt).unwrap(), "next");
     }
 
+    #[test]
+    fn test_build_rig_conversation_with_image() {
+        use crate::state::MessageContent;
+
+        let messages = vec![Message::user_with_content(MessageContent::Parts(vec![
+            MessageContent::Text("look".to_string()),
+            MessageContent::image("https://example.com/a.webp", "image/webp"),
+        ]))];
+
+        let conversation = build_rig_conversation(&messages);
+
+        let RigMessage::User { content } = conversation.prompt else {
+            panic!("Expected User prompt");
+        };
+        assert_eq!(content.len(), 2);
+        assert!(matches!(content.iter().nth(1), Some(UserContent::Image(_))));
+    }
+
     #[test]
     fn test_message_from_rig_choice_with_tool_call() {
         let choice = OneOrMany::many(vec![
@@ -442,4 +625,134 @@ mod tests {
         assert_eq!(calls[0].id, "call_1");
         assert_eq!(calls[0].name, "search");
     }
+
+    #[test]
+    fn test_finish_from_raw_maps_provider_reasons() {
+        let cases = [
+            (serde_json::json!({"choices": [{"finish_reason": "length"}]}), FinishReason::Length),
+            (serde_json::json!({"choices": [{"finish_reason": "tool_calls"}]}), FinishReason::ToolCalls),
+            (serde_json::json!({"choices": [{"finish_reason": "content_filter"}]}), FinishReason::ContentFilter),
+            (serde_json::json!({"stop_reason": "end_turn"}), FinishReason::Stop),
+            (serde_json::json!({"stop_reason": "max_tokens"}), FinishReason::Length),
+            (serde_json::json!({"stop_reason": "tool_use"}), FinishReason::ToolCalls),
+            (serde_json::json!({"candidates": [{"finishReason": "MAX_TOKENS"}]}), FinishReason::Length),
+            (serde_json::json!({"candidates": [{"finishReason": "SAFETY"}]}), FinishReason::ContentFilter),
+            (
+                serde_json::json!({"status": "incomplete", "incomplete_details": {"reason": "max_output_tokens"}}),
+                FinishReason::Length,
+            ),
+            (serde_json::json!({"status": "completed", "incomplete_details": null}), FinishReason::Stop),
+        ];
+
+        for (raw, expected) in cases {
+            let (reason, _) = finish_from_raw(&raw);
+            assert_eq!(reason, Some(expected), "{}", raw);
+        }
+
+        assert_eq!(finish_from_raw(&serde_json::json!({"id": "x"})).0, None);
+        assert_eq!(finish_from_raw(&Value::Null).0, None);
+    }
+
+    #[test]
+    fn test_finish_from_raw_extracts_refusal() {
+        let chat = serde_json::json!({
+            "choices": [{
+                "finish_reason": "stop",
+                "message": {"content": null, "refusal": "I can't help with that."}
+            }]
+        });
+        assert_eq!(finish_from_raw(&chat).1.as_deref(), Some("I can't help with that."));
+
+        let responses = serde_json::json!({
+            "status": "completed",
+            "output": [{
+                "type": "message",
+                "content": [{"type": "refusal", "refusal": "Not able to do that."}]
+            }]
+        });
+        assert_eq!(finish_from_raw(&responses).1.as_deref(), Some("Not able to do that."));
+
+        let plain = serde_json::json!({"choices": [{"finish_reason": "stop", "message": {"content": "Hi"}}]});
+        assert_eq!(finish_from_raw(&plain).1, None);
+    }
+
+    #[derive(Clone, Serialize)]
+    struct MockStreamResponse;
+
+    impl GetTokenUsage for MockStreamResponse {
+        fn token_usage(&self) -> Option<rig::completion::Usage> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_maps_tool_call_deltas() {
+        let items: Vec<Result<StreamedAssistantContent<MockStreamResponse>, CompletionError>> = vec![
+            Ok(StreamedAssistantContent::text("Searching")),
+            Ok(StreamedAssistantContent::ToolCallDelta {
+                id: "call_1".to_string(),
+                delta: "{\"query\": \"ru".to_string(),
+            }),
+            Ok(StreamedAssistantContent::ToolCallDelta {
+                id: "call_1".to_string(),
+                delta: "st\"}".to_string(),
+            }),
+            Ok(StreamedAssistantContent::ToolCall(RigToolCall::new(
+                "call_1".to_string(),
+                rig::message::ToolFunction::new(
+                    "tavily_search".to_string(),
+                    serde_json::json!({"query": "rust"}),
+                ),
+            ))),
+            Ok(StreamedAssistantContent::final_response(MockStreamResponse)),
+        ];
+
+        let chunks: Vec<MessageChunk> = futures::stream::iter(items)
+            .filter_map(|item| async move { to_message_chunk(item) })
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunks[0].content, "Searching");
+        assert!(chunks[0].tool_call_delta.is_none());
+
+        let arguments: String = chunks[1..3]
+            .iter()
+            .map(|chunk| {
+                let delta = chunk.tool_call_delta.as_ref().unwrap();
+                assert_eq!(delta.id, "call_1");
+                delta.arguments_delta.as_str()
+            })
+            .collect();
+        assert_eq!(arguments, "{\"query\": \"rust\"}");
+
+        assert_eq!(chunks[3].tool_calls[0].name, "tavily_search");
+        assert!(chunks[3].tool_call_delta.is_none());
+        assert!(chunks[4].is_final);
+        assert_eq!(chunks[4].finish_reason, None);
+    }
+
+    #[derive(Clone, Serialize)]
+    struct MockFinalResponse {
+        stop_reason: &'static str,
+    }
+
+    impl GetTokenUsage for MockFinalResponse {
+        fn token_usage(&self) -> Option<rig::completion::Usage> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_stream_final_chunk_carries_finish_reason() {
+        let item: Result<StreamedAssistantContent<MockFinalResponse>, CompletionError> = Ok(
+            StreamedAssistantContent::final_response(MockFinalResponse { stop_reason: "max_tokens" }),
+        );
+
+        let chunk = to_message_chunk(item).unwrap().unwrap();
+
+        assert!(chunk.is_final);
+        assert_eq!(chunk.finish_reason, Some(FinishReason::Length));
+    }
 }
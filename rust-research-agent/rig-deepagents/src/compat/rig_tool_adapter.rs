@@ -49,7 +49,8 @@ use crate::runtime::ToolRuntime;
 ///
 /// - The `ToolRuntime` parameter is ignored since Rig tools don't use runtime context
 /// - Tool definition is cached at construction time for efficiency
-/// - Errors are converted to `MiddlewareError::ToolExecution`
+/// - Argument deserialization failures become `MiddlewareError::InvalidToolArguments`
+/// - Tool errors become `MiddlewareError::ToolFailed`, keeping the original error as its source
 pub struct RigToolAdapter<T>
 where
     T: rig::tool::Tool + Send + Sync,
@@ -94,6 +95,7 @@ where
             name: rig_def.name,
             description: rig_def.description,
             parameters: rig_def.parameters,
+            examples: Vec::new(),
         };
 
         Self {
@@ -118,6 +120,7 @@ where
             name: rig_def.name,
             description: rig_def.description,
             parameters: rig_def.parameters,
+            examples: Vec::new(),
         };
 
         Self {
@@ -157,16 +160,18 @@ where
     ) -> Result<ToolResult, MiddlewareError> {
         // Step 1: Deserialize JSON args to the tool's typed Args
         let typed_args: T::Args = serde_json::from_value(args).map_err(|e| {
-            MiddlewareError::ToolExecution(format!(
-                "Failed to deserialize args for tool '{}': {}",
-                T::NAME,
-                e
-            ))
+            MiddlewareError::InvalidToolArguments {
+                tool_name: T::NAME.to_string(),
+                message: e.to_string(),
+            }
         })?;
 
         // Step 2: Call the Rig tool
         let result = self.inner.call(typed_args).await.map_err(|e| {
-            MiddlewareError::ToolExecution(format!("Tool '{}' execution failed: {}", T::NAME, e))
+            MiddlewareError::ToolFailed {
+                tool_name: T::NAME.to_string(),
+                source: Box::new(e),
+            }
         })?;
 
         // Step 3: Serialize the output to JSON string
@@ -212,7 +217,7 @@ mod tests {
         y: i32,
     }
 
-    #[derive(Debug, thiserror::Error)]
+    #[derive(Debug, PartialEq, thiserror::Error)]
     #[error("Math error: {0}")]
     struct MathError(String);
 
@@ -245,7 +250,9 @@ mod tests {
         }
 
         async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-            Ok(args.x + args.y)
+            args.x
+                .checked_add(args.y)
+                .ok_or_else(|| MathError("overflow".to_string()))
         }
     }
 
@@ -298,7 +305,31 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.to_string().contains("deserialize"));
+        assert!(err.to_string().contains("missing field `y`"), "{}", err);
+        assert!(matches!(
+            err,
+            MiddlewareError::InvalidToolArguments { ref tool_name, .. } if tool_name == "add"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_adapter_execute_tool_error() {
+        let adapter = RigToolAdapter::new(Adder).await;
+        let runtime = create_test_runtime();
+
+        let err = adapter
+            .execute(serde_json::json!({"x": i32::MAX, "y": 1}), &runtime)
+            .await
+            .unwrap_err();
+
+        let MiddlewareError::ToolFailed { tool_name, source } = err else {
+            panic!("Expected ToolFailed, got {:?}", err);
+        };
+        assert_eq!(tool_name, "add");
+        assert_eq!(
+            source.downcast_ref::<MathError>(),
+            Some(&MathError("overflow".to_string()))
+        );
     }
 
     #[tokio::test]
@@ -10,6 +10,19 @@
 //! | `OPENAI_API_KEY` | OpenAI API authentication | Yes (for OpenAI) |
 //! | `ANTHROPIC_API_KEY` | Anthropic API authentication | Yes (for Anthropic) |
 //! | `TAVILY_API_KEY` | Tavily Search API authentication | Yes (for search) |
+//! | `OLLAMA_API_BASE_URL` | Ollama server URL | No (defaults to localhost) |
+//! | `OLLAMA_MODEL` | Ollama model name | No (defaults to `llama3.2`) |
+//! | `LLM_PROVIDER` | `openai`, `anthropic`/`claude` or `ollama` | No (defaults to `openai`) |
+//! | `LLM_MODEL` | Model name override | No |
+//! | `LLM_TEMPERATURE` | Sampling temperature | No |
+//! | `MAX_SEARCHES` | Research search budget | No |
+//! | `WORKFLOW_TIMEOUT` | Workflow timeout in seconds | No |
+//! | `PARALLELISM` | Parallel vertex limit | No |
+//! | `CHECKPOINT_INTERVAL` | Supersteps between checkpoints (0 = disabled) | No |
+//! | `SUMMARIZATION_TRIGGER_TOKENS` | Token count that triggers summarization | No |
+//! | `SUMMARIZATION_KEEP_MESSAGES` | Recent messages kept after summarizing | No |
+//!
+//! Keys set explicitly on [`ProductionConfig`] take precedence over the environment.
 //!
 //! # Example
 //!
@@ -26,20 +39,30 @@
 //!
 //! // Build workflow with production settings
 //! let workflow = config.build_research_workflow()?;
+//!
+//! // Or get a ready-to-run research agent in one call
+//! let executor = ProductionSetup::new(config).build_research_executor()?;
 //! ```
 
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
-use rig::client::{CompletionClient, ProviderClient};
+use rig::client::CompletionClient;
 
+use crate::backends::{Backend, MemoryBackend};
 use crate::compat::RigAgentAdapter;
 use crate::error::DeepAgentError;
+use crate::executor::AgentExecutor;
 use crate::llm::{LLMConfig, LLMProvider};
-use crate::middleware::{Tool, ToolDefinition};
+use crate::middleware::summarization::{KeepSize, SummarizationConfig, TriggerCondition};
+use crate::middleware::{
+    DynTool, FilesystemMiddleware, MiddlewareStack, SubAgentKind, SubAgentMiddleware,
+    SubAgentMiddlewareConfig, SubAgentSpec, SummarizationMiddleware, ToolDefinition,
+};
 use crate::pregel::config::ExecutionMode;
 use crate::pregel::PregelConfig;
-use crate::research::{ResearchConfig, ResearchWorkflowBuilder};
+use crate::research::{ResearchConfig, ResearchPrompts, ResearchWorkflowBuilder};
 use crate::tools::{TavilySearchTool, ThinkTool};
 use crate::workflow::graph::BuiltWorkflowGraph;
 use crate::ResearchState;
@@ -85,13 +108,94 @@ pub struct ProductionConfig {
 
     /// Tavily search timeout in seconds
     pub tavily_timeout_secs: u64,
+
+    /// API keys (unset keys are read from the environment)
+    pub api_keys: ApiKeys,
+
+    /// Token count that triggers summarization (model preset when unset)
+    pub summarization_trigger_tokens: Option<usize>,
+
+    /// Recent messages kept after summarizing (model preset when unset)
+    pub summarization_keep_messages: Option<usize>,
+}
+
+/// API keys for hosted services
+///
+/// `Debug` output redacts the key values.
+#[derive(Clone, Default)]
+pub struct ApiKeys {
+    /// OpenAI API key (`OPENAI_API_KEY`)
+    pub openai: Option<String>,
+    /// Anthropic API key (`ANTHROPIC_API_KEY`)
+    pub anthropic: Option<String>,
+    /// Tavily Search API key (`TAVILY_API_KEY`)
+    pub tavily: Option<String>,
+}
+
+impl ApiKeys {
+    /// Explicit key, falling back to the environment variable
+    fn resolve(key: &Option<String>, env_var: &str) -> Result<String, DeepAgentError> {
+        key.clone()
+            .or_else(|| std::env::var(env_var).ok())
+            .ok_or_else(|| DeepAgentError::Config(format!("{} not set", env_var)))
+    }
+}
+
+impl fmt::Debug for ApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redact = |key: &Option<String>| key.as_ref().map(|_| "<redacted>");
+        f.debug_struct("ApiKeys")
+            .field("openai", &redact(&self.openai))
+            .field("anthropic", &redact(&self.anthropic))
+            .field("tavily", &redact(&self.tavily))
+            .finish()
+    }
+}
+
+/// Default Ollama server URL
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// Default Ollama model (matches the standalone research binary)
+pub const DEFAULT_OLLAMA_MODEL: &str = "llama3.2";
+
+/// Parse an optional variable, recording an error when it is set but invalid
+fn parse_var<T: std::str::FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    errors: &mut Vec<String>,
+) -> Option<T> {
+    let raw = var(name)?;
+    match raw.trim().parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            errors.push(format!("{}: invalid value '{}'", name, raw));
+            None
+        }
+    }
 }
 
 /// Supported LLM provider types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LLMProviderType {
     OpenAI,
     Anthropic,
+    /// Local models served by Ollama (no API key needed)
+    Ollama {
+        /// Server URL, e.g. `http://localhost:11434`
+        host: String,
+        /// Model name, e.g. `llama3.2`
+        model: String,
+    },
+}
+
+impl LLMProviderType {
+    /// Ollama on the default local host
+    pub fn ollama(model: impl Into<String>) -> Self {
+        Self::Ollama {
+            host: DEFAULT_OLLAMA_HOST.to_string(),
+            model: model.into(),
+        }
+    }
 }
 
 impl Default for ProductionConfig {
@@ -110,6 +214,9 @@ impl Default for ProductionConfig {
             tracing_enabled: true,
             tavily_max_retries: 3,
             tavily_timeout_secs: 30,
+            api_keys: ApiKeys::default(),
+            summarization_trigger_tokens: None,
+            summarization_keep_messages: None,
         }
     }
 }
@@ -122,57 +229,103 @@ impl ProductionConfig {
 
     /// Load configuration from environment variables
     ///
-    /// Reads optional overrides from environment:
-    /// - `LLM_PROVIDER`: "openai" or "anthropic"
-    /// - `LLM_MODEL`: Model name
-    /// - `LLM_TEMPERATURE`: Temperature value
-    /// - `MAX_SEARCHES`: Research search budget
-    /// - `WORKFLOW_TIMEOUT`: Timeout in seconds
+    /// See the module docs for the variables read. Every missing or invalid
+    /// variable is reported in a single [`DeepAgentError::Config`], e.g.
+    /// `"invalid environment: OPENAI_API_KEY not set; MAX_SEARCHES: invalid value 'many'"`.
     pub fn from_env() -> Result<Self, DeepAgentError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Load configuration from an arbitrary variable lookup
+    ///
+    /// Same rules as [`from_env`](Self::from_env); useful for tests and for
+    /// configuration sources other than the process environment.
+    pub fn from_vars<F>(var: F) -> Result<Self, DeepAgentError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
         let mut config = Self::default();
+        let mut errors = Vec::new();
 
         // LLM provider selection
-        if let Ok(provider) = std::env::var("LLM_PROVIDER") {
-            config.llm_provider_type = match provider.to_lowercase().as_str() {
-                "anthropic" | "claude" => LLMProviderType::Anthropic,
-                _ => LLMProviderType::OpenAI,
-            };
-        }
-
-        // Model override
-        if let Ok(model) = std::env::var("LLM_MODEL") {
-            config.model = Some(model);
+        if let Some(provider) = var("LLM_PROVIDER") {
+            match provider.to_lowercase().as_str() {
+                "openai" => config.llm_provider_type = LLMProviderType::OpenAI,
+                "anthropic" | "claude" => config.llm_provider_type = LLMProviderType::Anthropic,
+                "ollama" => {
+                    config.llm_provider_type = LLMProviderType::Ollama {
+                        host: var("OLLAMA_API_BASE_URL")
+                            .unwrap_or_else(|| DEFAULT_OLLAMA_HOST.to_string()),
+                        model: var("OLLAMA_MODEL")
+                            .unwrap_or_else(|| DEFAULT_OLLAMA_MODEL.to_string()),
+                    }
+                }
+                _ => errors.push(format!(
+                    "LLM_PROVIDER: unsupported provider '{}' (expected openai, anthropic or ollama)",
+                    provider
+                )),
+            }
         }
 
-        // Temperature
-        if let Ok(temp) = std::env::var("LLM_TEMPERATURE") {
-            if let Ok(t) = temp.parse() {
-                config.temperature = t;
+        // API keys required by the selected provider and the search tool
+        config.api_keys = ApiKeys {
+            openai: var("OPENAI_API_KEY"),
+            anthropic: var("ANTHROPIC_API_KEY"),
+            tavily: var("TAVILY_API_KEY"),
+        };
+        match config.llm_provider_type {
+            LLMProviderType::OpenAI if config.api_keys.openai.is_none() => {
+                errors.push("OPENAI_API_KEY not set".to_string())
+            }
+            LLMProviderType::Anthropic if config.api_keys.anthropic.is_none() => {
+                errors.push("ANTHROPIC_API_KEY not set".to_string())
             }
+            _ => {}
+        }
+        if config.api_keys.tavily.is_none() {
+            errors.push("TAVILY_API_KEY not set".to_string());
         }
 
-        // Max searches
-        if let Ok(searches) = std::env::var("MAX_SEARCHES") {
-            if let Ok(s) = searches.parse() {
-                config.max_searches = s;
-            }
+        config.model = var("LLM_MODEL");
+
+        // Numeric settings: keep the default when unset, report when unparsable
+        if let Some(v) = parse_var(&var, "LLM_TEMPERATURE", &mut errors) {
+            config.temperature = v;
+        }
+        if let Some(v) = parse_var(&var, "MAX_SEARCHES", &mut errors) {
+            config.max_searches = v;
+        }
+        if let Some(v) = parse_var(&var, "WORKFLOW_TIMEOUT", &mut errors) {
+            config.workflow_timeout_secs = v;
+        }
+        if let Some(v) = parse_var(&var, "PARALLELISM", &mut errors) {
+            config.parallelism = v;
         }
 
-        // Workflow timeout
-        if let Ok(timeout) = std::env::var("WORKFLOW_TIMEOUT") {
-            if let Ok(t) = timeout.parse() {
-                config.workflow_timeout_secs = t;
-            }
+        // Checkpointing and summarization
+        if let Some(v) = parse_var(&var, "CHECKPOINT_INTERVAL", &mut errors) {
+            config.checkpoint_interval = v;
         }
+        config.summarization_trigger_tokens =
+            parse_var(&var, "SUMMARIZATION_TRIGGER_TOKENS", &mut errors);
+        config.summarization_keep_messages =
+            parse_var(&var, "SUMMARIZATION_KEEP_MESSAGES", &mut errors);
 
-        // Parallelism
-        if let Ok(par) = std::env::var("PARALLELISM") {
-            if let Ok(p) = par.parse() {
-                config.parallelism = p;
-            }
+        if config.parallelism == 0 {
+            errors.push("PARALLELISM: must be at least 1".to_string());
+        }
+        if config.summarization_trigger_tokens == Some(0) {
+            errors.push("SUMMARIZATION_TRIGGER_TOKENS: must be at least 1".to_string());
         }
 
-        Ok(config)
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(DeepAgentError::Config(format!(
+                "invalid environment: {}",
+                errors.join("; ")
+            )))
+        }
     }
 
     /// Set the LLM provider type
@@ -217,6 +370,36 @@ impl ProductionConfig {
         self
     }
 
+    /// Summarize once the conversation reaches this many tokens
+    pub fn with_summarization_trigger_tokens(mut self, tokens: usize) -> Self {
+        self.summarization_trigger_tokens = Some(tokens);
+        self
+    }
+
+    /// Keep this many recent messages when summarizing
+    pub fn with_summarization_keep_messages(mut self, messages: usize) -> Self {
+        self.summarization_keep_messages = Some(messages);
+        self
+    }
+
+    /// Set the OpenAI API key
+    pub fn with_openai_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.openai = Some(key.into());
+        self
+    }
+
+    /// Set the Anthropic API key
+    pub fn with_anthropic_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.anthropic = Some(key.into());
+        self
+    }
+
+    /// Set the Tavily Search API key
+    pub fn with_tavily_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.tavily = Some(key.into());
+        self
+    }
+
     /// Create the LLM provider based on configuration
     ///
     /// Uses `RigAgentAdapter` to wrap Rig's native providers for full
@@ -224,19 +407,31 @@ impl ProductionConfig {
     ///
     /// # Environment Variables
     ///
-    /// - `OPENAI_API_KEY` - Required for OpenAI provider
-    /// - `ANTHROPIC_API_KEY` - Required for Anthropic provider
+    /// - `OPENAI_API_KEY` - Required for OpenAI provider (unless set in `api_keys`)
+    /// - `ANTHROPIC_API_KEY` - Required for Anthropic provider (unless set in `api_keys`)
     pub fn llm_provider(&self) -> Result<Arc<dyn LLMProvider>, DeepAgentError> {
-        match self.llm_provider_type {
+        let client_error = |e| DeepAgentError::Config(format!("Failed to create LLM client: {}", e));
+
+        match &self.llm_provider_type {
             LLMProviderType::OpenAI => {
-                let client = rig::providers::openai::Client::from_env();
+                let api_key = ApiKeys::resolve(&self.api_keys.openai, "OPENAI_API_KEY")?;
+                let mut builder = rig::providers::openai::Client::builder().api_key(&api_key);
+                if let Ok(base_url) = std::env::var("OPENAI_BASE_URL") {
+                    builder = builder.base_url(&base_url);
+                }
+                let client: rig::providers::openai::Client = builder.build().map_err(client_error)?;
                 let model = self.model.clone().unwrap_or_else(|| "gpt-4.1".to_string());
                 let agent = client.agent(&model).build();
                 let adapter = RigAgentAdapter::with_names(agent, "openai", &model);
                 Ok(Arc::new(adapter))
             }
             LLMProviderType::Anthropic => {
-                let client = rig::providers::anthropic::Client::from_env();
+                let api_key = ApiKeys::resolve(&self.api_keys.anthropic, "ANTHROPIC_API_KEY")?;
+                let client: rig::providers::anthropic::Client =
+                    rig::providers::anthropic::Client::builder()
+                        .api_key(api_key)
+                        .build()
+                        .map_err(client_error)?;
                 let model = self.model.clone().unwrap_or_else(|| {
                     rig::providers::anthropic::completion::CLAUDE_3_5_SONNET.to_string()
                 });
@@ -244,15 +439,28 @@ impl ProductionConfig {
                 let adapter = RigAgentAdapter::with_names(agent, "anthropic", &model);
                 Ok(Arc::new(adapter))
             }
+            LLMProviderType::Ollama { host, model } => {
+                let client: rig::providers::ollama::Client =
+                    rig::providers::ollama::Client::builder()
+                        .api_key(rig::client::Nothing)
+                        .base_url(host)
+                        .build()
+                        .map_err(client_error)?;
+                let model = self.model.clone().unwrap_or_else(|| model.clone());
+                let agent = client.agent(&model).build();
+                let adapter = RigAgentAdapter::with_names(agent, "ollama", &model);
+                Ok(Arc::new(adapter))
+            }
         }
     }
 
     /// Create LLM configuration
     pub fn llm_config(&self) -> LLMConfig {
         let model = self.model.clone().unwrap_or_else(|| {
-            match self.llm_provider_type {
+            match &self.llm_provider_type {
                 LLMProviderType::OpenAI => "gpt-4.1".to_string(),
                 LLMProviderType::Anthropic => "claude-3-5-sonnet-latest".to_string(),
+                LLMProviderType::Ollama { model, .. } => model.clone(),
             }
         });
 
@@ -261,19 +469,41 @@ impl ProductionConfig {
             .with_max_tokens(self.max_tokens)
     }
 
+    /// Create summarization configuration
+    ///
+    /// Starts from the model preset and applies any configured thresholds.
+    pub fn summarization_config(&self) -> SummarizationConfig {
+        let mut config = SummarizationConfig::for_model(&self.llm_config().model);
+        if let Some(tokens) = self.summarization_trigger_tokens {
+            config.triggers = vec![TriggerCondition::Tokens(tokens)];
+        }
+        if let Some(messages) = self.summarization_keep_messages {
+            config.keep = KeepSize::Messages(messages);
+        }
+        config
+    }
+
     /// Create research tool definitions
     ///
     /// # Environment Variables
     ///
-    /// - `TAVILY_API_KEY` - Required for Tavily search
+    /// - `TAVILY_API_KEY` - Required for Tavily search (unless set in `api_keys`)
     pub fn research_tools(&self) -> Result<Vec<ToolDefinition>, DeepAgentError> {
-        let tavily = TavilySearchTool::from_env()?
+        Ok(self
+            .research_tool_impls()?
+            .iter()
+            .map(|tool| tool.definition())
+            .collect())
+    }
+
+    /// Create the research tools themselves (Tavily search and think)
+    pub fn research_tool_impls(&self) -> Result<Vec<DynTool>, DeepAgentError> {
+        let api_key = ApiKeys::resolve(&self.api_keys.tavily, "TAVILY_API_KEY")?;
+        let tavily = TavilySearchTool::new(api_key)
             .with_timeout(Duration::from_secs(self.tavily_timeout_secs))
             .with_max_retries(self.tavily_max_retries);
 
-        let think = ThinkTool;
-
-        Ok(vec![tavily.definition(), think.definition()])
+        Ok(vec![Arc::new(tavily), Arc::new(ThinkTool)])
     }
 
     /// Create Pregel runtime configuration
@@ -311,7 +541,7 @@ impl ProductionConfig {
 
     /// Create initial research state
     pub fn create_research_state(&self, query: impl Into<String>) -> ResearchState {
-        ResearchState::new(query).with_max_searches(self.max_searches)
+        self.research_config().initial_state(query)
     }
 }
 
@@ -320,6 +550,7 @@ pub struct ProductionSetup {
     config: ProductionConfig,
     llm: Option<Arc<dyn LLMProvider>>,
     tools: Vec<ToolDefinition>,
+    backend: Option<Arc<dyn Backend>>,
 }
 
 impl ProductionSetup {
@@ -329,9 +560,22 @@ impl ProductionSetup {
             config,
             llm: None,
             tools: vec![],
+            backend: None,
         }
     }
 
+    /// Use an existing LLM provider instead of creating one from the config
+    pub fn with_llm(mut self, llm: Arc<dyn LLMProvider>) -> Self {
+        self.llm = Some(llm);
+        self
+    }
+
+    /// Set the backend for agent file operations (default: in-memory)
+    pub fn with_backend(mut self, backend: Arc<dyn Backend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
     /// Initialize from environment
     pub fn from_env() -> Result<Self, DeepAgentError> {
         let config = ProductionConfig::from_env()?;
@@ -382,11 +626,82 @@ impl ProductionSetup {
     pub fn create_state(&self, query: impl Into<String>) -> ResearchState {
         self.config.create_research_state(query)
     }
+
+    /// Build a ready-to-run research agent
+    ///
+    /// The executor uses the planner prompt and comes with:
+    /// - filesystem tools (`FilesystemMiddleware`)
+    /// - `task` delegation to a `researcher` subagent and a general-purpose subagent
+    /// - summarization tuned for the configured model
+    /// - Tavily search and think tools
+    ///
+    /// The LLM is created from the config unless one was set with [`with_llm`](Self::with_llm).
+    pub fn build_research_executor(&self) -> Result<AgentExecutor, DeepAgentError> {
+        let llm = match &self.llm {
+            Some(llm) => llm.clone(),
+            None => self.config.llm_provider()?,
+        };
+        let backend = self
+            .backend
+            .clone()
+            .unwrap_or_else(|| Arc::new(MemoryBackend::new()));
+        let llm_config = self.config.llm_config();
+        let research_tools = self.config.research_tool_impls()?;
+
+        let researcher = SubAgentSpec::builder("researcher")
+            .description("Web researcher for focused questions; returns findings with sources")
+            .system_prompt(ResearchPrompts::researcher())
+            .tools(research_tools.clone())
+            .build();
+        let subagents = SubAgentMiddleware::new(
+            SubAgentMiddlewareConfig::new(llm.clone(), backend.clone())
+                .with_subagent(SubAgentKind::Spec(researcher))
+                .with_general_purpose(),
+        );
+
+        let middleware = MiddlewareStack::new()
+            .with_middleware(FilesystemMiddleware::new())
+            .with_middleware(subagents)
+            .with_middleware(
+                SummarizationMiddleware::for_model(llm.clone(), &llm_config.model)
+                    .with_config(self.config.summarization_config()),
+            );
+
+        Ok(AgentExecutor::new(llm, middleware, backend)
+            .with_config(llm_config)
+            .with_system_prompt(ResearchPrompts::planner())
+            .with_tools(research_tools))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::LLMResponse;
+    use crate::state::Message;
+    use async_trait::async_trait;
+
+    struct MockLLM;
+
+    #[async_trait]
+    impl LLMProvider for MockLLM {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            Ok(LLMResponse::new(Message::assistant("done")))
+        }
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+    }
 
     #[test]
     fn test_production_config_defaults() {
@@ -457,6 +772,147 @@ mod tests {
         assert_eq!(research.max_directions, 4);
     }
 
+    #[tokio::test]
+    async fn test_ollama_provider() {
+        let config = ProductionConfig::new().with_provider(LLMProviderType::Ollama {
+            host: "http://127.0.0.1:1".to_string(),
+            model: "qwen2.5:7b".to_string(),
+        });
+
+        // Building the adapter does not contact the server
+        let llm = config.llm_provider().unwrap();
+        assert_eq!(llm.name(), "ollama");
+        assert_eq!(llm.default_model(), "qwen2.5:7b");
+        assert_eq!(config.llm_config().model, "qwen2.5:7b");
+
+        // An explicit model override still wins
+        let llm = config.with_model("llama3.1").llm_provider().unwrap();
+        assert_eq!(llm.default_model(), "llama3.1");
+    }
+
+    #[test]
+    fn test_ollama_default_host() {
+        assert_eq!(
+            LLMProviderType::ollama("llama3.2"),
+            LLMProviderType::Ollama {
+                host: DEFAULT_OLLAMA_HOST.to_string(),
+                model: "llama3.2".to_string(),
+            }
+        );
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_from_vars_complete() {
+        let config = ProductionConfig::from_vars(env(&[
+            ("LLM_PROVIDER", "anthropic"),
+            ("LLM_MODEL", "claude-sonnet-4"),
+            ("LLM_TEMPERATURE", "0.3"),
+            ("ANTHROPIC_API_KEY", "sk-ant"),
+            ("TAVILY_API_KEY", "tvly"),
+            ("MAX_SEARCHES", "12"),
+            ("WORKFLOW_TIMEOUT", "600"),
+            ("PARALLELISM", "2"),
+            ("CHECKPOINT_INTERVAL", "0"),
+            ("SUMMARIZATION_TRIGGER_TOKENS", "50000"),
+            ("SUMMARIZATION_KEEP_MESSAGES", "8"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.llm_provider_type, LLMProviderType::Anthropic);
+        assert_eq!(config.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(config.temperature, 0.3);
+        assert_eq!(config.api_keys.anthropic.as_deref(), Some("sk-ant"));
+        assert_eq!(config.api_keys.tavily.as_deref(), Some("tvly"));
+        assert_eq!(config.max_searches, 12);
+        assert_eq!(config.workflow_timeout_secs, 600);
+        assert_eq!(config.parallelism, 2);
+        assert_eq!(config.checkpoint_interval, 0);
+        assert_eq!(config.summarization_trigger_tokens, Some(50000));
+        assert_eq!(config.summarization_keep_messages, Some(8));
+
+        let summarization = config.summarization_config();
+        assert!(matches!(
+            summarization.triggers.as_slice(),
+            [TriggerCondition::Tokens(50000)]
+        ));
+        assert_eq!(summarization.keep.message_count(), Some(8));
+    }
+
+    #[test]
+    fn test_from_vars_ollama_needs_no_llm_key() {
+        let config = ProductionConfig::from_vars(env(&[
+            ("LLM_PROVIDER", "ollama"),
+            ("OLLAMA_MODEL", "qwen2.5"),
+            ("TAVILY_API_KEY", "tvly"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.llm_provider_type, LLMProviderType::ollama("qwen2.5"));
+    }
+
+    #[test]
+    fn test_from_vars_aggregates_errors() {
+        let err = ProductionConfig::from_vars(env(&[
+            ("MAX_SEARCHES", "many"),
+            ("PARALLELISM", "0"),
+        ]))
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("OPENAI_API_KEY not set"), "{}", message);
+        assert!(message.contains("TAVILY_API_KEY not set"), "{}", message);
+        assert!(message.contains("MAX_SEARCHES: invalid value 'many'"), "{}", message);
+        assert!(message.contains("PARALLELISM: must be at least 1"), "{}", message);
+    }
+
+    #[test]
+    fn test_from_vars_rejects_unknown_provider() {
+        let err = ProductionConfig::from_vars(env(&[
+            ("LLM_PROVIDER", "gemini"),
+            ("TAVILY_API_KEY", "tvly"),
+        ]))
+        .unwrap_err();
+
+        assert!(err.to_string().contains("unsupported provider 'gemini'"));
+    }
+
+    #[test]
+    fn test_api_keys_debug_redacted() {
+        let config = ProductionConfig::new().with_tavily_api_key("secret-key");
+        let debug = format!("{:?}", config);
+
+        assert!(!debug.contains("secret-key"));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_build_research_executor() {
+        let config = ProductionConfig::new().with_tavily_api_key("test-key");
+        let setup = ProductionSetup::new(config)
+            .with_llm(Arc::new(MockLLM));
+
+        let executor = setup.build_research_executor().unwrap();
+        let mut names: Vec<_> = executor
+            .tool_definitions()
+            .into_iter()
+            .map(|def| def.name)
+            .collect();
+        names.sort();
+
+        assert_eq!(
+            names,
+            vec!["edit_file", "glob", "grep", "ls", "read_file", "task", "tavily_search", "think", "write_file"]
+        );
+    }
+
     #[test]
     fn test_create_research_state() {
         let config = ProductionConfig::new().with_max_searches(10);
@@ -34,6 +34,18 @@ pub enum BackendError {
 
     #[error("Pattern error: {0}")]
     Pattern(String),
+
+    #[error("Read-only filesystem: {0}")]
+    ReadOnly(String),
+
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("Path escapes sandbox root: {0}")]
+    PathEscape(String),
+
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
 }
 
 /// 미들웨어 에러
@@ -45,6 +57,19 @@ pub enum MiddlewareError {
     #[error("Tool execution error: {0}")]
     ToolExecution(String),
 
+    #[error("Invalid arguments for tool '{tool_name}': {message}")]
+    InvalidToolArguments {
+        tool_name: String,
+        message: String,
+    },
+
+    #[error("Tool '{tool_name}' execution failed: {source}")]
+    ToolFailed {
+        tool_name: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     #[error("State update error: {0}")]
     StateUpdate(String),
 
@@ -60,14 +85,53 @@ pub enum MiddlewareError {
     #[error("SubAgent not found: {0}")]
     SubAgentNotFound(String),
 
+    #[error("Middleware not found: {0}")]
+    MiddlewareNotFound(String),
+
     #[error("Recursion limit exceeded: {0}")]
     RecursionLimit(String),
 
+    #[error("SubAgent recursion depth limit ({limit}) exceeded")]
+    RecursionLimitExceeded {
+        limit: usize,
+    },
+
     #[error("SubAgent '{subagent_id}' timed out after {duration_secs}s")]
     SubAgentTimeout {
         subagent_id: String,
         duration_secs: u64,
     },
+
+    #[error("Tool '{tool_name}' timed out after {timeout:?}")]
+    ToolTimeout {
+        tool_name: String,
+        timeout: std::time::Duration,
+    },
+
+    #[error("Skill load error: {0}")]
+    SkillLoad(#[from] crate::skills::SkillLoadError),
+
+    #[error("{}old_string must match exactly once but matched {matches} time(s). Provide more context or set replace_all=true.", edit_label(.edit))]
+    AmbiguousEdit {
+        matches: usize,
+        /// 다중 편집에서 실패한 편집 번호 (1-based)
+        edit: Option<usize>,
+    },
+
+    #[error("{}old_string '{old_string}' not found in file", edit_label(.edit))]
+    EditNotFound {
+        old_string: String,
+        /// 다중 편집에서 실패한 편집 번호 (1-based)
+        edit: Option<usize>,
+    },
+
+    #[error("File already exists: {0}. Set overwrite=true to replace it or append=true to add to it.")]
+    FileExists(String),
+}
+
+/// 다중 편집 에러 메시지의 편집 번호 접두사
+fn edit_label(edit: &Option<usize>) -> String {
+    edit.map(|n| format!("Edit #{} failed: ", n)).unwrap_or_default()
 }
 
 /// DeepAgent 최상위 에러
@@ -91,12 +155,98 @@ pub enum DeepAgentError {
     #[error("Message conversion error: {0}")]
     Conversion(String),
 
+    /// 구조화 출력이 요청한 스키마와 맞지 않음 (복구 재시도 소진 후)
+    #[error("Schema validation error: {0}")]
+    SchemaValidation(String),
+
     /// HumanInTheLoop 인터럽트 - 인간 승인 대기
     ///
     /// 이 에러는 실제 실패가 아니라 실행 일시 중단을 나타냅니다.
     /// 사용자가 결정을 제공하면 실행을 재개할 수 있습니다.
     #[error("Execution interrupted for human approval")]
     Interrupt(crate::middleware::InterruptRequest),
+
+    /// 에이전트 루프가 최대 반복 횟수 안에 끝나지 않음
+    ///
+    /// 모델이 계속 도구를 호출하는 경우 발생하며, 진행 상황을 확인할 수 있도록
+    /// 중단 시점의 상태를 함께 담습니다.
+    #[error("Agent exceeded the maximum of {limit} iterations")]
+    MaxIterationsExceeded {
+        limit: usize,
+        state: Box<crate::state::AgentState>,
+    },
+
+    /// 취소 토큰으로 실행이 중단됨
+    ///
+    /// 취소 시점까지 진행된 상태를 담습니다. 도구 배치 사이에서 취소되면
+    /// 일부 도구 호출에 결과 메시지가 없을 수 있습니다.
+    #[error("Agent execution cancelled")]
+    Cancelled {
+        state: Box<crate::state::AgentState>,
+    },
+}
+
+/// 일시적 LLM 오류를 나타내는 HTTP 상태 코드
+const RETRYABLE_STATUS_CODES: &[u16] = &[429, 500, 502, 503, 504, 529];
+
+/// 뒤따르는 숫자가 HTTP 상태 코드임을 나타내는 표현 (소문자 토큰)
+const STATUS_MARKERS: &[&str] = &["status", "http"];
+
+/// 일시적 LLM 오류를 나타내는 메시지 표현 (소문자)
+///
+/// `"timeout must be positive"`, `"invalid connection string"` 같은 설정 오류가
+/// 걸리지 않도록 "timeout"/"connection" 단독이 아닌 구체적인 표현만 사용합니다.
+const RETRYABLE_LLM_MESSAGES: &[&str] = &[
+    "rate limit",
+    "too many requests",
+    "overloaded",
+    "timed out",
+    "connection refused",
+    "connection reset",
+    "connection closed",
+    "service unavailable",
+    "bad gateway",
+    "internal server error",
+];
+
+impl DeepAgentError {
+    /// 재시도하면 성공할 수 있는 일시적 오류인지 확인
+    ///
+    /// 프로바이더 에러는 문자열로만 전달되므로 `LlmError` 메시지에서
+    /// rate limit(429), 5xx, 타임아웃, 연결 오류 표시를 찾습니다.
+    /// 상태 코드는 `"status 429"`, `"HTTP 503"`처럼 표시어 뒤에 올 때만 인정하므로
+    /// `"max_tokens must be below 500"` 같은 메시지의 숫자는 무시됩니다.
+    /// 그 외 변형(설정 오류, 인터럽트 등)은 재시도 대상이 아닙니다.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::LlmError(message) => {
+                let message = message.to_lowercase();
+                http_status(&message).is_some_and(|status| RETRYABLE_STATUS_CODES.contains(&status))
+                    || RETRYABLE_LLM_MESSAGES.iter().any(|marker| message.contains(marker))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 메시지에서 HTTP 상태 코드 추출 (`status`/`http` 표시어 뒤의 첫 세 자리 숫자)
+///
+/// `"HTTP/1.1 503"`의 버전 번호나 `"status code: 429"`의 `code`는 건너뜁니다.
+fn http_status(message: &str) -> Option<u16> {
+    let tokens: Vec<&str> = message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    tokens.iter().enumerate()
+        .filter(|(_, token)| STATUS_MARKERS.contains(*token))
+        .find_map(|(index, _)| {
+            tokens[index + 1..].iter()
+                .take(3)
+                .filter(|token| token.len() == 3)
+                .find_map(|token| token.parse::<u16>().ok())
+                .filter(|status| (100..600).contains(status))
+        })
 }
 
 /// 쓰기 작업 결과
@@ -145,6 +295,8 @@ pub struct EditResult {
     /// 체크포인트 백엔드를 위한 상태 업데이트
     pub files_update: Option<HashMap<String, FileData>>,
     pub occurrences: Option<usize>,
+    /// 다중 편집 시 각 편집별 교체 횟수 (순서대로)
+    pub edit_occurrences: Option<Vec<usize>>,
 }
 
 impl EditResult {
@@ -157,6 +309,7 @@ impl EditResult {
             path: Some(path.to_string()),
             files_update: Some(files),
             occurrences: Some(occurrences),
+            edit_occurrences: None,
         }
     }
 
@@ -167,11 +320,25 @@ impl EditResult {
             path: Some(path.to_string()),
             files_update: None,
             occurrences: Some(occurrences),
+            edit_occurrences: None,
         }
     }
 
+    /// 다중 편집 결과 요약 설정 (`occurrences`는 합계로 갱신)
+    pub fn with_edit_occurrences(mut self, edit_occurrences: Vec<usize>) -> Self {
+        self.occurrences = Some(edit_occurrences.iter().sum());
+        self.edit_occurrences = Some(edit_occurrences);
+        self
+    }
+
     pub fn error(msg: &str) -> Self {
-        Self { error: Some(msg.to_string()), path: None, files_update: None, occurrences: None }
+        Self {
+            error: Some(msg.to_string()),
+            path: None,
+            files_update: None,
+            occurrences: None,
+            edit_occurrences: None,
+        }
     }
 
     pub fn is_ok(&self) -> bool {
@@ -197,6 +364,31 @@ mod tests {
         assert!(matches!(middleware_err, MiddlewareError::Backend(_)));
     }
 
+    #[test]
+    fn test_deep_agent_error_is_retryable() {
+        let retryable = [
+            "Rig agent error: HTTP status 429 Too Many Requests",
+            "Rig agent error: 503 Service Unavailable",
+            "Rig agent error: HTTP/1.1 502",
+            "Rig agent error: status code: 529",
+            "Rig agent error: Anthropic API overloaded",
+            "Rig agent error: request timed out",
+            "Rig agent error: connection reset by peer",
+            "Rig agent error: tcp connect error: Connection refused (os error 111)",
+        ];
+        for message in retryable {
+            assert!(DeepAgentError::LlmError(message.to_string()).is_retryable(), "{}", message);
+        }
+
+        assert!(!DeepAgentError::LlmError("Rig agent error: 401 invalid api key".to_string()).is_retryable());
+        assert!(!DeepAgentError::LlmError("max_tokens must be below 5000".to_string()).is_retryable());
+        assert!(!DeepAgentError::LlmError("max_tokens must be below 500".to_string()).is_retryable());
+        assert!(!DeepAgentError::LlmError("Rig agent error: HTTP status 400: n must be 429 or less".to_string()).is_retryable());
+        assert!(!DeepAgentError::LlmError("Rig agent error: invalid connection string".to_string()).is_retryable());
+        assert!(!DeepAgentError::LlmError("Rig agent error: timeout must be positive".to_string()).is_retryable());
+        assert!(!DeepAgentError::Config("HTTP 503".to_string()).is_retryable());
+    }
+
     #[test]
     fn test_write_result_success() {
         let file_data = FileData::new("hello");
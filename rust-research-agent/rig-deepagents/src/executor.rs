@@ -2,16 +2,84 @@
 //!
 //! Python Reference: deepagents/graph.py
 
+use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::mpsc::{unbounded, UnboundedSender};
+use futures::{FutureExt, Stream, StreamExt};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 use crate::backends::Backend;
-use crate::error::DeepAgentError;
-use crate::llm::{LLMProvider, LLMConfig};
-use crate::middleware::{MiddlewareStack, DynTool, ModelRequest, ModelResponse, ModelControl, ToolResult};
-use crate::runtime::{RuntimeConfig, ToolRuntime};
-use crate::state::{AgentState, Message, ToolCall};
+use crate::error::{DeepAgentError, MiddlewareError};
+use crate::llm::{FinishReason, LLMProvider, LLMConfig, LLMRetryConfig, ResponseFormat, TokenUsage};
+use crate::middleware::{
+    MiddlewareStack, DynTool, ModelRequest, ModelResponse, ModelControl, ToolDefinition, ToolResult,
+    Decision, InterruptRequest, ResumeToken,
+};
+use crate::runtime::{
+    RuntimeConfig, SystemPromptLayout, ToolRuntime, DEFAULT_MAX_CONCURRENT_TOOLS, DEFAULT_MAX_ITERATIONS,
+};
+use crate::state::{AgentState, Message, Role, ToolCall};
 use crate::tool_result_eviction::{ToolResultEvictor, DEFAULT_TOOL_RESULT_TOKEN_LIMIT};
 
+/// 구조화 출력 파싱 실패 시 기본 복구 재시도 횟수
+pub const DEFAULT_SCHEMA_REPAIR_RETRIES: usize = 2;
+
+/// 출력 길이 제한으로 잘린 응답의 기본 이어쓰기 횟수
+pub const DEFAULT_MAX_LENGTH_CONTINUATIONS: usize = 2;
+
+/// 잘린 응답을 이어서 생성하도록 요청하는 메시지
+const LENGTH_CONTINUATION_PROMPT: &str =
+    "Your previous response was cut off by the output token limit. \
+     Continue exactly where it stopped, without repeating anything.";
+
+/// 실행 중 발생하는 이벤트 (스트리밍 실행용)
+///
+/// CLI/TUI가 토큰과 도구 호출 진행 상황을 실시간으로 표시할 수 있도록
+/// [`AgentExecutor::run_streaming`]이 순서대로 내보냅니다.
+#[derive(Debug, Clone)]
+pub enum ExecutorEvent {
+    /// LLM 응답 토큰 조각
+    TokenChunk(String),
+    /// 도구 호출 시작
+    ToolCallStarted(ToolCall),
+    /// 도구 실행 중간 진행 상황 (도구 결과 이전에 도착)
+    ToolProgress {
+        tool_call_id: String,
+        tool_name: String,
+        content: String,
+    },
+    /// 도구 실행 결과 (모델에 전달되는 메시지)
+    ToolResult {
+        tool_call_id: String,
+        tool_name: String,
+        content: String,
+    },
+    /// 어시스턴트 메시지 완성 (after_model 통과 후)
+    MessageComplete(Message),
+    /// 실행 종료 - 최종 상태 (항상 마지막 이벤트)
+    Done(Box<AgentState>),
+}
+
+/// [`ExecutorEvent`] 스트림
+pub type ExecutorEventStream<'a> =
+    Pin<Box<dyn Stream<Item = Result<ExecutorEvent, DeepAgentError>> + Send + 'a>>;
+
+type EventSender = UnboundedSender<Result<ExecutorEvent, DeepAgentError>>;
+
+/// 인터럽트에서 재개할 때 모델 호출 없이 처리할 응답
+struct ResumedTurn {
+    /// 대기 중이던 도구 호출이 담긴 어시스턴트 메시지 (결정 반영 후)
+    response: Message,
+    /// 거부된 도구 호출 (도구 호출 ID -> 거부 사유)
+    rejected_calls: HashMap<String, String>,
+}
+
 /// Agent Executor
 ///
 /// 에이전트 실행 루프를 관리합니다:
@@ -43,12 +111,24 @@ pub struct AgentExecutor {
     additional_tools: Vec<DynTool>,
     /// System prompt to prepend to messages
     system_prompt: Option<String>,
+    /// Ordering of the base prompt and middleware prompt sections
+    prompt_layout: SystemPromptLayout,
     /// Current recursion depth (for nested subagent calls)
     recursion_depth: usize,
     /// Maximum recursion depth
     max_recursion: usize,
     /// Tool result eviction token limit (None disables eviction)
     tool_result_token_limit_before_evict: Option<usize>,
+    /// Per-call tool execution timeout (None waits indefinitely)
+    tool_timeout: Option<Duration>,
+    /// Retry policy for transient LLM errors
+    llm_retry: LLMRetryConfig,
+    /// Maximum number of concurrent-safe tool calls run in parallel
+    max_concurrent_tools: usize,
+    /// Repair attempts when a typed run's final message fails to parse
+    schema_repair_retries: usize,
+    /// Follow-up calls allowed when a response is truncated by the token limit
+    max_length_continuations: usize,
 }
 
 impl AgentExecutor {
@@ -62,17 +142,36 @@ impl AgentExecutor {
             llm,
             middleware,
             backend,
-            max_iterations: 50,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
             config: None,
             additional_tools: Vec::new(),
             system_prompt: None,
+            prompt_layout: SystemPromptLayout::default(),
             recursion_depth: 0,
             max_recursion: 100,  // Default matches Python
             tool_result_token_limit_before_evict: Some(DEFAULT_TOOL_RESULT_TOKEN_LIMIT),
+            tool_timeout: None,
+            llm_retry: LLMRetryConfig::default(),
+            max_concurrent_tools: DEFAULT_MAX_CONCURRENT_TOOLS,
+            schema_repair_retries: DEFAULT_SCHEMA_REPAIR_RETRIES,
+            max_length_continuations: DEFAULT_MAX_LENGTH_CONTINUATIONS,
         }
     }
 
+    /// 모델에 노출되는 도구 정의 (미들웨어 도구 + 추가 도구)
+    pub fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        self.middleware
+            .collect_tools()
+            .iter()
+            .chain(self.additional_tools.iter())
+            .map(|t| t.definition_with_examples())
+            .collect()
+    }
+
     /// Set the maximum number of iterations for the agent loop
+    ///
+    /// A run still requesting tools after this many model turns fails with
+    /// [`DeepAgentError::MaxIterationsExceeded`] carrying the partial state.
     pub fn with_max_iterations(mut self, max: usize) -> Self {
         self.max_iterations = max;
         self
@@ -94,12 +193,22 @@ impl AgentExecutor {
 
     /// Set a system prompt to prepend to messages
     ///
-    /// This system message is added at the start of every execution.
+    /// This system message is added at the start of every execution, combined
+    /// with the prompt sections contributed by middleware.
     pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
         self.system_prompt = Some(prompt.into());
         self
     }
 
+    /// Set the order of system prompt sections
+    ///
+    /// By default the base prompt comes first, followed by middleware sections
+    /// in registration order. See [`SystemPromptLayout`].
+    pub fn with_prompt_layout(mut self, layout: SystemPromptLayout) -> Self {
+        self.prompt_layout = layout;
+        self
+    }
+
     /// Set recursion depth for nested subagent calls (H2 fix)
     ///
     /// This is propagated to the ToolRuntime so nested `task` calls
@@ -121,22 +230,265 @@ impl AgentExecutor {
         self
     }
 
+    /// Set a timeout for each tool call
+    ///
+    /// A tool that exceeds it is abandoned and the timeout error is returned
+    /// to the model as the tool result, so the agent can recover.
+    pub fn with_tool_timeout(mut self, timeout: Duration) -> Self {
+        self.tool_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the retry policy for transient LLM errors (429, 5xx, timeouts)
+    pub fn with_llm_retry(mut self, llm_retry: LLMRetryConfig) -> Self {
+        self.llm_retry = llm_retry;
+        self
+    }
+
+    /// Set how many concurrent-safe tool calls from one model turn run in parallel
+    ///
+    /// Only tools whose `is_concurrent_safe()` returns true are parallelized;
+    /// a value of 1 runs every call sequentially.
+    pub fn with_max_concurrent_tools(mut self, max: usize) -> Self {
+        self.max_concurrent_tools = max;
+        self
+    }
+
+    /// Set how many times [`run_typed`](Self::run_typed) asks the model to fix
+    /// a final answer that does not match the schema
+    pub fn with_schema_repair_retries(mut self, retries: usize) -> Self {
+        self.schema_repair_retries = retries;
+        self
+    }
+
+    /// Set how many times a response cut off by the output token limit
+    /// (`FinishReason::Length`) is continued with a follow-up call
+    ///
+    /// The pieces are merged into a single assistant message. A value of 0
+    /// keeps truncated responses as they are.
+    pub fn with_max_length_continuations(mut self, max: usize) -> Self {
+        self.max_length_continuations = max;
+        self
+    }
+
     /// 에이전트 실행
+    ///
+    /// [`run_streaming`](Self::run_streaming)의 이벤트를 소비하여 최종 상태를 반환합니다.
     pub async fn run(&self, initial_state: AgentState) -> Result<AgentState, DeepAgentError> {
+        self.run_with_config(initial_state, self.config.clone()).await
+    }
+
+    /// 구조화 출력 실행
+    ///
+    /// `T`의 JSON Schema를 `LLMConfig::response_format`으로 요청에 주입하고,
+    /// 마지막 어시스턴트 메시지를 `T`로 파싱합니다. 파싱에 실패하면 오류와 스키마를
+    /// 모델에 알려 최대 `schema_repair_retries`번 다시 실행하며, 그래도 실패하면
+    /// [`DeepAgentError::SchemaValidation`]을 반환합니다.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// #[derive(Deserialize, JsonSchema)]
+    /// struct Verdict { answer: String, confidence: f64 }
+    ///
+    /// let verdict: Verdict = executor.run_typed(initial_state).await?;
+    /// ```
+    #[doc(alias = "execute_typed")]
+    pub async fn run_typed<T>(&self, initial_state: AgentState) -> Result<T, DeepAgentError>
+    where
+        T: DeserializeOwned + JsonSchema,
+    {
+        let schema = serde_json::to_value(schemars::schema_for!(T))
+            .map_err(|e| DeepAgentError::SchemaValidation(format!("Invalid schema: {}", e)))?;
+        let config = self
+            .config
+            .clone()
+            .unwrap_or_default()
+            .with_response_format(ResponseFormat::json_schema(T::schema_name(), schema.clone()));
+
         let mut state = initial_state;
+        let mut attempt = 0;
+        loop {
+            state = self.run_with_config(state, Some(config.clone())).await?;
+
+            let error = match parse_typed::<T>(&state) {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if attempt >= self.schema_repair_retries {
+                return Err(DeepAgentError::SchemaValidation(format!(
+                    "{} (after {} attempts)",
+                    error,
+                    attempt + 1
+                )));
+            }
+
+            attempt += 1;
+            tracing::warn!(attempt, error = %error, "Final answer does not match schema; requesting repair");
+            self.strip_system_prompt(&mut state);
+            state.add_message(Message::user(&format!(
+                "Your previous answer did not match the required JSON schema: {}\n\n\
+                 Respond again with only a JSON value matching this schema:\n{}",
+                error, schema
+            )));
+        }
+    }
+
+    /// 취소 가능한 에이전트 실행
+    ///
+    /// 매 반복 시작과 도구 배치 사이에 `token`을 확인하고, 진행 중인 LLM 호출은
+    /// 취소되는 즉시 중단합니다. 취소되면 그 시점까지의 상태를 담은
+    /// [`DeepAgentError::Cancelled`]를 반환합니다 (예: 클라이언트 연결 종료).
+    #[doc(alias = "execute_with_cancel")]
+    pub async fn run_with_cancel(
+        &self,
+        initial_state: AgentState,
+        token: CancellationToken,
+    ) -> Result<AgentState, DeepAgentError> {
+        Self::final_state(self.stream_with_config(initial_state, self.config.clone(), None, Some(token))).await
+    }
+
+    /// 지정한 LLM 설정으로 실행하여 최종 상태 반환
+    async fn run_with_config(
+        &self,
+        initial_state: AgentState,
+        config: Option<LLMConfig>,
+    ) -> Result<AgentState, DeepAgentError> {
+        Self::final_state(self.stream_with_config(initial_state, config, None, None)).await
+    }
+
+    /// 이벤트 스트림을 끝까지 소비하여 최종 상태 반환
+    async fn final_state(mut events: ExecutorEventStream<'_>) -> Result<AgentState, DeepAgentError> {
+        while let Some(event) = events.next().await {
+            if let ExecutorEvent::Done(state) = event? {
+                return Ok(*state);
+            }
+        }
+
+        Err(DeepAgentError::AgentExecution(
+            "Event stream ended without a final state".to_string(),
+        ))
+    }
+
+    /// 에이전트 스트리밍 실행
+    ///
+    /// `LLMProvider::stream`으로 모델을 호출하며 토큰, 도구 호출, 완성된 메시지를
+    /// [`ExecutorEvent`]로 내보냅니다. 성공 시 마지막 이벤트는 `Done`이고,
+    /// 실패 시 에러 하나를 내보낸 뒤 종료됩니다.
+    /// 실행은 스트림을 폴링할 때만 진행됩니다.
+    #[doc(alias = "execute_streaming")]
+    pub fn run_streaming(&self, initial_state: AgentState) -> ExecutorEventStream<'_> {
+        self.stream_with_config(initial_state, self.config.clone(), None, None)
+    }
+
+    /// 인터럽트된 실행 재개
+    ///
+    /// [`InterruptRequest::to_resume_token`]으로 저장한 토큰과 사용자 결정으로
+    /// 대기 중인 도구 호출을 처리한 뒤 에이전트 루프를 이어갑니다.
+    /// - `Decision::Approve` - 원래 인자로 실행
+    /// - `Decision::Reject` - 실행하지 않고 거부 메시지를 도구 결과로 기록
+    /// - `Decision::Edit` - 토큰의 `ActionRequest::args`로 인자를 바꿔 실행
+    ///
+    /// 액션의 `ReviewConfig`가 허용하지 않는 결정은 거부로 처리됩니다.
+    ///
+    /// [`InterruptRequest::to_resume_token`]: crate::middleware::InterruptRequest::to_resume_token
+    pub async fn resume_from_interrupt(
+        &self,
+        token: ResumeToken,
+        decision: Decision,
+    ) -> Result<AgentState, DeepAgentError> {
+        let ResumeToken { action_requests, review_configs, mut state } = token;
+
+        // before_model 인터럽트 - 대기 중인 도구 호출 없이 그대로 재개
+        let pending = state
+            .messages
+            .last()
+            .is_some_and(|m| m.role == Role::Assistant && m.has_tool_calls());
+        if !pending {
+            return self.run(state).await;
+        }
+
+        let mut response = state.messages.pop().expect("pending assistant message");
+        let tool_calls = response.tool_calls.get_or_insert_with(Vec::new);
+        let mut rejected_calls = HashMap::new();
+
+        for (index, action) in action_requests.iter().enumerate() {
+            let Some(call) = tool_calls.iter_mut().find(|call| call.id == action.id) else {
+                return Err(DeepAgentError::AgentExecution(format!(
+                    "Resume token action '{}' does not match any pending tool call",
+                    action.id
+                )));
+            };
 
-        // Prepend system prompt if configured
-        if let Some(ref system_prompt) = self.system_prompt {
-            // Insert system message at the beginning
-            let system_msg = Message::system(system_prompt);
-            state.messages.insert(0, system_msg);
+            let allowed = review_configs
+                .get(index)
+                .is_none_or(|review| review.allowed_decisions.contains(&decision));
+            match if allowed { decision } else { Decision::Reject } {
+                Decision::Approve => {}
+                Decision::Reject => {
+                    rejected_calls.insert(
+                        action.id.clone(),
+                        format!("Tool '{}' was rejected by the reviewer", action.name),
+                    );
+                }
+                Decision::Edit => call.arguments = action.args.clone(),
+            }
         }
 
+        tracing::info!(?decision, actions = action_requests.len(), "Resuming interrupted execution");
+        let resumed = ResumedTurn { response, rejected_calls };
+        Self::final_state(self.stream_with_config(state, self.config.clone(), Some(resumed), None)).await
+    }
+
+    /// 지정한 LLM 설정으로 스트리밍 실행 (재개할 턴이 있으면 모델 호출 없이 먼저 처리)
+    fn stream_with_config(
+        &self,
+        initial_state: AgentState,
+        config: Option<LLMConfig>,
+        resumed: Option<ResumedTurn>,
+        cancel: Option<CancellationToken>,
+    ) -> ExecutorEventStream<'_> {
+        let (events, receiver) = unbounded();
+
+        let driver = async move {
+            let span = tracing::info_span!("agent_run", max_iterations = self.max_iterations);
+            let outcome = self
+                .drive(initial_state, config.as_ref(), resumed, cancel.as_ref(), &events)
+                .instrument(span)
+                .await
+                .map(|state| ExecutorEvent::Done(Box::new(state)));
+            let _ = events.unbounded_send(outcome);
+        };
+
+        // 드라이버 future를 이벤트 수신 스트림과 함께 폴링 (드라이버 자체는 아무것도 내보내지 않음)
+        let driver = driver
+            .into_stream()
+            .filter_map(|()| futures::future::ready(None));
+
+        Box::pin(futures::stream::select(driver, receiver))
+    }
+
+    /// 실행 루프 본체
+    async fn drive(
+        &self,
+        initial_state: AgentState,
+        config: Option<&LLMConfig>,
+        mut resumed: Option<ResumedTurn>,
+        cancel: Option<&CancellationToken>,
+        events: &EventSender,
+    ) -> Result<AgentState, DeepAgentError> {
+        let mut state = initial_state;
+
         // Create runtime with proper recursion configuration (H2 fix)
         let runtime_config = RuntimeConfig {
             debug: false,
             max_recursion: self.max_recursion,
             current_recursion: self.recursion_depth,
+            tool_timeout: self.tool_timeout,
+            llm_retry: self.llm_retry.clone(),
+            max_concurrent_tools: self.max_concurrent_tools,
+            max_iterations: self.max_iterations,
+            prompt_layout: self.prompt_layout.clone(),
         };
         let runtime = ToolRuntime::new(state.clone(), self.backend.clone())
             .with_config(runtime_config);
@@ -145,91 +497,58 @@ impl AgentExecutor {
         let _before_updates = self.middleware.before_agent(&mut state, &runtime).await
             .map_err(DeepAgentError::Middleware)?;
 
+        // Prepend the assembled system prompt (after before_agent, so sections
+        // that depend on it, like skill summaries, are populated)
+        if let Some(system_prompt) = self.assembled_system_prompt() {
+            state.messages.insert(0, Message::system(&system_prompt));
+        }
+
         // 도구 수집 (middleware tools + additional tools)
         let mut tools = self.middleware.collect_tools();
         tools.extend(self.additional_tools.iter().cloned());
         let tool_definitions: Vec<_> = tools.iter()
-            .map(|t| t.definition())
+            .map(|t| t.definition_with_examples())
             .collect();
 
         // 메인 실행 루프
-        for iteration in 0..self.max_iterations {
+        let max_iterations = runtime.config().max_iterations;
+        let mut finished = false;
+        for iteration in 0..max_iterations {
             tracing::debug!(iteration, "Agent iteration");
+            let iteration_span = tracing::info_span!("agent_iteration", iteration);
+            if cancel.is_some_and(|token| token.is_cancelled()) {
+                return Err(cancelled(state));
+            }
 
-            // =========================================================================
-            // before_model hook
-            // =========================================================================
-            let mut model_request = ModelRequest::new(
-                state.messages.clone(),
-                tool_definitions.clone(),
-            );
-            if let Some(ref config) = self.config {
-                model_request = model_request.with_config(config.clone());
-            }
-
-            let before_control = self.middleware.before_model(&mut model_request, &mut state, &runtime).await
-                .map_err(DeepAgentError::Middleware)?;
-
-            // before_model 제어 흐름 처리
-            let response = match before_control {
-                ModelControl::Continue => {
-                    // 정상 LLM 호출
-                    let llm_response = self.llm.complete(
-                        &model_request.messages,
-                        &model_request.tools,
-                        model_request.config.as_ref(),
-                    ).await?;
-                    llm_response.message
-                }
-                ModelControl::ModifyRequest(_) => {
-                    // 요청이 이미 수정됨, 수정된 요청으로 LLM 호출
-                    let llm_response = self.llm.complete(
-                        &model_request.messages,
-                        &model_request.tools,
-                        model_request.config.as_ref(),
-                    ).await?;
-                    llm_response.message
-                }
-                ModelControl::Skip(resp) => {
-                    // LLM 호출 건너뛰기, 제공된 응답 사용
-                    tracing::debug!("Skipping LLM call, using cached response");
-                    resp.message
-                }
-                ModelControl::Interrupt(interrupt) => {
-                    // 인터럽트 - 실행 중단
-                    tracing::info!("Execution interrupted in before_model");
-                    return Err(DeepAgentError::Interrupt(interrupt));
+            let (response, rejected_calls) = match resumed.take() {
+                // 인터럽트에서 재개 - 이미 검토된 응답이므로 모델 훅을 다시 거치지 않음
+                Some(turn) => (turn.response, turn.rejected_calls),
+                None => {
+                    let turn = self
+                        .model_turn(&mut state, &tool_definitions, config, &runtime, events)
+                        .instrument(iteration_span.clone());
+                    let outcome = match cancel {
+                        // 취소되면 진행 중인 LLM 호출을 버림
+                        Some(token) => tokio::select! {
+                            result = turn => Some(result),
+                            _ = token.cancelled() => None,
+                        },
+                        None => Some(turn.await),
+                    };
+                    match outcome {
+                        Some(result) => result?,
+                        None => return Err(cancelled(state)),
+                    }
                 }
             };
 
-            // =========================================================================
-            // after_model hook
-            // =========================================================================
-            let model_response = ModelResponse::new(response.clone());
-            let after_control = self.middleware.after_model(&model_response, &state, &runtime).await
-                .map_err(DeepAgentError::Middleware)?;
-
-            // after_model 제어 흐름 처리
-            match after_control {
-                ModelControl::Continue => {
-                    // 정상 진행
-                }
-                ModelControl::Interrupt(interrupt) => {
-                    // HumanInTheLoop 인터럽트 - 응답 저장 후 중단
-                    state.add_message(response.clone());
-                    tracing::info!("Execution interrupted in after_model (HumanInTheLoop)");
-                    return Err(DeepAgentError::Interrupt(interrupt));
-                }
-                _ => {
-                    // Skip/ModifyRequest는 after_model에서 무시됨
-                }
-            }
-
+            emit(events, ExecutorEvent::MessageComplete(response.clone()));
             state.add_message(response.clone());
 
             // 도구 호출이 없으면 종료
             if !response.has_tool_calls() {
                 tracing::debug!("No tool calls, finishing");
+                finished = true;
                 break;
             }
 
@@ -241,34 +560,105 @@ impl AgentExecutor {
                     .count();
                 let has_duplicate_write_todos = write_todos_count > 1;
 
-                for call in tool_calls {
-                    if has_duplicate_write_todos && call.name == "write_todos" {
-                        let result = ToolResult::new(
-                            "Error: multiple write_todos calls in a single response are not allowed",
-                        );
-                        let tool_message = Message::tool_with_status(&result.message, &call.id, "error");
-                        state.add_message(tool_message);
-                        continue;
+                let max_concurrent = runtime.config().max_concurrent_tools.max(1);
+                let mut remaining = tool_calls.as_slice();
+
+                while !remaining.is_empty() {
+                    if cancel.is_some_and(|token| token.is_cancelled()) {
+                        return Err(cancelled(state));
                     }
 
-                    let result = self
-                        .execute_tool_call(call, &tools, &state, runtime.config())
-                        .await;
+                    // 연속된 동시 실행 안전 호출은 한 배치로 병렬 실행, 그 외 호출은 단독 배치
+                    let batch_len = if max_concurrent > 1 {
+                        remaining
+                            .iter()
+                            .take_while(|call| is_concurrent_safe(call, &tools))
+                            .count()
+                            .max(1)
+                    } else {
+                        1
+                    };
+                    let (batch, rest) = remaining.split_at(batch_len);
+                    remaining = rest;
+
+                    for call in batch {
+                        emit(events, ExecutorEvent::ToolCallStarted(call.clone()));
+                    }
 
-                    let result = self
-                        .maybe_evict_tool_result(result, call)
+                    // 배치 내 호출은 같은 상태 스냅샷을 보며, 결과는 호출 순서대로 반영
+                    let executions: Vec<_> = batch
+                        .iter()
+                        .map(|call| {
+                            let rejection = if has_duplicate_write_todos && call.name == "write_todos" {
+                                Some("Error: multiple write_todos calls in a single response are not allowed".to_string())
+                            } else {
+                                rejected_calls
+                                    .get(&call.id)
+                                    .map(|reason| format!("Error: {}", reason))
+                            };
+                            let execution = self
+                                .execute_tool_call(call, &tools, &state, runtime.config(), events)
+                                .instrument(tracing::info_span!(
+                                    parent: &iteration_span,
+                                    "tool_call",
+                                    tool_name = %call.name,
+                                    tool_call_id = %call.id,
+                                ));
+                            async move {
+                                match rejection {
+                                    Some(reason) => Err(reason),
+                                    None => Ok(execution.await),
+                                }
+                            }
+                        })
+                        .collect();
+                    let results: Vec<Result<ToolResult, String>> = futures::stream::iter(executions)
+                        .buffered(max_concurrent)
+                        .collect()
                         .await;
 
-                    for update in &result.updates {
-                        update.apply(&mut state);
+                    for (call, result) in batch.iter().zip(results) {
+                        let mut result = match result {
+                            Ok(result) => result,
+                            Err(reason) => {
+                                let result = ToolResult::new(reason);
+                                emit_tool_result(events, call, &result);
+                                let tool_message = Message::tool_with_status(&result.message, &call.id, "error");
+                                state.add_message(tool_message);
+                                continue;
+                            }
+                        };
+
+                        self.middleware
+                            .after_tool(call, &mut result, &state, &runtime)
+                            .await
+                            .map_err(DeepAgentError::Middleware)?;
+
+                        let result = self
+                            .maybe_evict_tool_result(result, call)
+                            .await;
+
+                        for update in &result.updates {
+                            update.apply(&mut state);
+                        }
+
+                        emit_tool_result(events, call, &result);
+                        let tool_message = Message::tool(&result.message, &call.id);
+                        state.add_message(tool_message);
                     }
-
-                    let tool_message = Message::tool(&result.message, &call.id);
-                    state.add_message(tool_message);
                 }
             }
         }
 
+        if !finished {
+            // 모델이 계속 도구를 호출 - after_agent 훅 없이 중단 시점 상태 반환
+            tracing::warn!(max_iterations, "Agent loop exceeded max iterations");
+            return Err(DeepAgentError::MaxIterationsExceeded {
+                limit: max_iterations,
+                state: Box::new(state),
+            });
+        }
+
         // After hooks 실행 (미들웨어 스택이 내부적으로 상태 업데이트 적용)
         let _after_updates = self.middleware.after_agent(&mut state, &runtime).await
             .map_err(DeepAgentError::Middleware)?;
@@ -276,6 +666,258 @@ impl AgentExecutor {
         Ok(state)
     }
 
+    /// 모델 한 턴 실행 (before_model → LLM 호출 → after_model)
+    ///
+    /// 훅을 통과한 응답과 미들웨어가 거부한 도구 호출 목록을 반환합니다.
+    async fn model_turn(
+        &self,
+        state: &mut AgentState,
+        tool_definitions: &[ToolDefinition],
+        config: Option<&LLMConfig>,
+        runtime: &ToolRuntime,
+        events: &EventSender,
+    ) -> Result<(Message, HashMap<String, String>), DeepAgentError> {
+        // =========================================================================
+        // before_model hook
+        // =========================================================================
+        let mut model_request = ModelRequest::new(
+            state.messages.clone(),
+            tool_definitions.to_vec(),
+        );
+        if let Some(config) = config {
+            model_request = model_request.with_config(config.clone());
+        }
+
+        let before_control = self.middleware.before_model(&mut model_request, state, runtime).await
+            .map_err(DeepAgentError::Middleware)?;
+
+        // before_model 제어 흐름 처리
+        let response = match before_control {
+            // RejectToolCalls는 before_model 스택에서 걸러지므로 Continue와 동일
+            ModelControl::Continue | ModelControl::RejectToolCalls(_) => {
+                // 정상 LLM 호출
+                let (message, usage) = self
+                    .call_model_continuing(&model_request, &runtime.config().llm_retry, events)
+                    .await?;
+                state.usage.record(usage.as_ref());
+                message
+            }
+            ModelControl::ModifyRequest(_) => {
+                // 요청이 이미 수정됨, 수정된 요청으로 LLM 호출
+                let (message, usage) = self
+                    .call_model_continuing(&model_request, &runtime.config().llm_retry, events)
+                    .await?;
+                state.usage.record(usage.as_ref());
+                message
+            }
+            ModelControl::Skip(resp) => {
+                // LLM 호출 건너뛰기, 제공된 응답 사용
+                tracing::debug!("Skipping LLM call, using cached response");
+                resp.message
+            }
+            ModelControl::Respond(message) => {
+                // 미들웨어가 직접 답변 (예: 정책 위반 거절)
+                tracing::debug!("Skipping LLM call, middleware responded directly");
+                message
+            }
+            ModelControl::Interrupt(interrupt) => {
+                // 인터럽트 - 실행 중단
+                tracing::info!("Execution interrupted in before_model");
+                return Err(self.interrupted(interrupt, state));
+            }
+        };
+
+        // =========================================================================
+        // after_model hook
+        // =========================================================================
+        let model_response = ModelResponse::new(response.clone());
+        let after_control = self.middleware.after_model(&model_response, state, runtime).await
+            .map_err(DeepAgentError::Middleware)?;
+
+        // after_model 제어 흐름 처리
+        let mut rejected_calls = HashMap::new();
+        match after_control {
+            ModelControl::Continue => {
+                // 정상 진행
+            }
+            ModelControl::RejectToolCalls(rejections) => {
+                // 미들웨어 정책으로 거부된 호출 - 실행 대신 사유를 기록
+                tracing::info!(rejected = rejections.len(), "Tool calls rejected in after_model");
+                rejected_calls = rejections;
+            }
+            ModelControl::Interrupt(interrupt) => {
+                // HumanInTheLoop 인터럽트 - 응답 저장 후 중단
+                state.add_message(response.clone());
+                tracing::info!("Execution interrupted in after_model (HumanInTheLoop)");
+                return Err(self.interrupted(interrupt, state));
+            }
+            _ => {
+                // Skip/Respond/ModifyRequest는 after_model에서 무시됨
+            }
+        }
+
+        Ok((response, rejected_calls))
+    }
+
+    /// 인터럽트에 재개용 상태를 기록하여 에러로 변환
+    fn interrupted(&self, interrupt: InterruptRequest, state: &AgentState) -> DeepAgentError {
+        let mut state = state.clone();
+        self.strip_system_prompt(&mut state);
+        DeepAgentError::Interrupt(interrupt.with_state(state))
+    }
+
+    /// LLM 호출 후 출력 길이 제한으로 잘린 응답을 이어서 생성
+    ///
+    /// `FinishReason::Length`로 끝난 응답은 잘린 부분과 이어쓰기 요청을 덧붙여
+    /// 최대 `max_length_continuations`번 다시 호출하고, 조각을 하나의 메시지로 합칩니다.
+    /// 덧붙인 메시지는 요청에만 쓰이며 상태에는 합쳐진 메시지만 남습니다.
+    async fn call_model_continuing(
+        &self,
+        request: &ModelRequest,
+        retry: &LLMRetryConfig,
+        events: &EventSender,
+    ) -> Result<(Message, Option<TokenUsage>), DeepAgentError> {
+        let (mut message, mut usage, mut finish_reason) = self.call_model(request, retry, events).await?;
+
+        let mut continuation_request: Option<ModelRequest> = None;
+        let mut piece = message.content.clone();
+        let mut continuations = 0;
+        while finish_reason == Some(FinishReason::Length)
+            && !message.has_tool_calls()
+            && continuations < self.max_length_continuations
+        {
+            continuations += 1;
+            tracing::info!(continuations, "Response truncated by output token limit; continuing");
+            let next_request = continuation_request.get_or_insert_with(|| request.clone());
+            next_request.messages.push(Message::assistant(&piece));
+            next_request.messages.push(Message::user(LENGTH_CONTINUATION_PROMPT));
+
+            let (next, next_usage, next_reason) = self.call_model(next_request, retry, events).await?;
+            if let Some(next_usage) = next_usage {
+                *usage.get_or_insert_with(TokenUsage::default) += next_usage;
+            }
+
+            let content = format!("{}{}", message.content, next.content);
+            message = match next.tool_calls {
+                Some(tool_calls) if !tool_calls.is_empty() => {
+                    Message::assistant_with_tool_calls(&content, tool_calls)
+                }
+                _ => Message::assistant(&content),
+            };
+            piece = next.content;
+            finish_reason = next_reason;
+        }
+
+        Ok((message, usage))
+    }
+
+    /// 스트리밍 LLM 호출 (일시적 오류 재시도 포함)
+    ///
+    /// 토큰 조각을 `TokenChunk`로 내보내고, 조각과 도구 호출을 모아 어시스턴트 메시지를 만듭니다.
+    /// 청크에 보고된 토큰 사용량을 합산하고, 마지막으로 보고된 종료 사유와 함께 반환합니다.
+    /// 토큰을 이미 내보낸 뒤 발생한 오류는 중복 출력을 막기 위해 재시도하지 않습니다.
+    async fn call_model(
+        &self,
+        request: &ModelRequest,
+        retry: &LLMRetryConfig,
+        events: &EventSender,
+    ) -> Result<(Message, Option<TokenUsage>, Option<FinishReason>), DeepAgentError> {
+        let mut attempt = 0;
+        loop {
+            let mut emitted = false;
+            let span = tracing::info_span!(
+                "llm_call",
+                provider = self.llm.name(),
+                attempt,
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+                total_tokens = tracing::field::Empty,
+            );
+            let error = match self.stream_model(request, events, &mut emitted).instrument(span.clone()).await {
+                Ok((message, usage, finish_reason)) => {
+                    if let Some(usage) = &usage {
+                        span.record("input_tokens", usage.input_tokens);
+                        span.record("output_tokens", usage.output_tokens);
+                        span.record("total_tokens", usage.total_tokens);
+                    }
+                    return Ok((message, usage, finish_reason));
+                }
+                Err(error) => error,
+            };
+
+            if emitted || attempt >= retry.max_retries || !retry.should_retry(&error) {
+                return Err(error);
+            }
+
+            let delay = retry.delay_for(attempt);
+            attempt += 1;
+            tracing::warn!(attempt, ?delay, error = %error, "Retrying LLM call");
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// 기본 프롬프트와 미들웨어 섹션을 레이아웃대로 조합 (비어 있으면 None)
+    fn assembled_system_prompt(&self) -> Option<String> {
+        let base = self.system_prompt.as_deref().unwrap_or_default();
+        let prompt = self.middleware.assemble_system_prompt(base, &self.prompt_layout);
+        (!prompt.is_empty()).then_some(prompt)
+    }
+
+    /// 이전 실행에서 삽입된 시스템 프롬프트 제거 (재실행 시 중복 방지)
+    fn strip_system_prompt(&self, state: &mut AgentState) {
+        let Some(system_prompt) = self.assembled_system_prompt() else {
+            return;
+        };
+        let inserted = state
+            .messages
+            .first()
+            .is_some_and(|m| m.role == Role::System && m.content == system_prompt);
+        if inserted {
+            state.messages.remove(0);
+        }
+    }
+
+    /// LLM 스트림 한 번 소비
+    async fn stream_model(
+        &self,
+        request: &ModelRequest,
+        events: &EventSender,
+        emitted: &mut bool,
+    ) -> Result<(Message, Option<TokenUsage>, Option<FinishReason>), DeepAgentError> {
+        let mut stream = self.llm.stream(
+            &request.messages,
+            &request.tools,
+            request.config.as_ref(),
+        ).await?.into_inner();
+
+        let mut content = String::new();
+        let mut tool_calls = Vec::new();
+        let mut usage: Option<TokenUsage> = None;
+        let mut finish_reason = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            if let Some(chunk_usage) = chunk.usage {
+                *usage.get_or_insert_with(TokenUsage::default) += chunk_usage;
+            }
+            if chunk.finish_reason.is_some() {
+                finish_reason = chunk.finish_reason;
+            }
+            if !chunk.content.is_empty() {
+                content.push_str(&chunk.content);
+                *emitted = true;
+                emit(events, ExecutorEvent::TokenChunk(chunk.content));
+            }
+            tool_calls.extend(chunk.tool_calls);
+        }
+
+        let message = if tool_calls.is_empty() {
+            Message::assistant(&content)
+        } else {
+            Message::assistant_with_tool_calls(&content, tool_calls)
+        };
+        Ok((message, usage, finish_reason))
+    }
+
     /// 도구 호출 실행
     async fn execute_tool_call(
         &self,
@@ -283,16 +925,41 @@ impl AgentExecutor {
         tools: &[DynTool],
         state: &AgentState,
         runtime_config: &RuntimeConfig,
+        events: &EventSender,
     ) -> ToolResult {
         let tool = tools.iter().find(|t| t.definition().name == call.name);
 
         match tool {
             Some(t) => {
+                if let Err(e) = t.validate_args(&call.arguments) {
+                    return ToolResult::new(format!("Tool error: {}", e));
+                }
+
+                let progress_events = events.clone();
+                let (tool_call_id, tool_name) = (call.id.clone(), call.name.clone());
                 let runtime = ToolRuntime::new(state.clone(), self.backend.clone())
                     .with_tool_call_id(&call.id)
-                    .with_config(runtime_config.clone());
-
-                match t.execute(call.arguments.clone(), &runtime).await {
+                    .with_config(runtime_config.clone())
+                    .with_progress(Arc::new(move |content| {
+                        emit(&progress_events, ExecutorEvent::ToolProgress {
+                            tool_call_id: tool_call_id.clone(),
+                            tool_name: tool_name.clone(),
+                            content,
+                        });
+                    }));
+
+                let execution = t.execute(call.arguments.clone(), &runtime);
+                let outcome = match runtime_config.tool_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, execution)
+                        .await
+                        .unwrap_or_else(|_| Err(MiddlewareError::ToolTimeout {
+                            tool_name: call.name.clone(),
+                            timeout,
+                        })),
+                    None => execution.await,
+                };
+
+                match outcome {
                     Ok(result) => result,
                     Err(e) => ToolResult::new(format!("Tool error: {}", e)),
                 }
@@ -310,62 +977,77 @@ impl AgentExecutor {
 
 }
 
+/// 도구 호출을 다른 호출과 병렬 실행해도 되는지 확인 (알 수 없는 도구는 에러만 반환하므로 안전)
+fn is_concurrent_safe(call: &ToolCall, tools: &[DynTool]) -> bool {
+    tools
+        .iter()
+        .find(|t| t.definition().name == call.name)
+        .is_none_or(|t| t.is_concurrent_safe())
+}
+
+/// 취소 시점의 상태를 담은 에러
+fn cancelled(state: AgentState) -> DeepAgentError {
+    tracing::info!(messages = state.message_count(), "Agent execution cancelled");
+    DeepAgentError::Cancelled {
+        state: Box::new(state),
+    }
+}
+
+/// 이벤트 전송 (수신 측이 스트림을 버린 경우 무시)
+fn emit(events: &EventSender, event: ExecutorEvent) {
+    let _ = events.unbounded_send(Ok(event));
+}
+
+fn emit_tool_result(events: &EventSender, call: &ToolCall, result: &ToolResult) {
+    emit(events, ExecutorEvent::ToolResult {
+        tool_call_id: call.id.clone(),
+        tool_name: call.name.clone(),
+        content: result.message.clone(),
+    });
+}
+
+/// 마지막 어시스턴트 메시지를 `T`로 파싱 (마크다운 코드 펜스는 벗겨냄)
+fn parse_typed<T: DeserializeOwned>(state: &AgentState) -> Result<T, String> {
+    let content = state
+        .last_assistant_message()
+        .map(|m| m.content.trim())
+        .ok_or_else(|| "No final assistant message".to_string())?;
+
+    let json = content
+        .strip_prefix("```json")
+        .or_else(|| content.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(content);
+
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use async_trait::async_trait;
     use crate::backends::MemoryBackend;
     use crate::error::MiddlewareError;
-    use crate::llm::LLMResponse;
+    use crate::llm::{LLMResponse, LLMResponseStream, MessageChunk};
     use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
     use crate::state::{Todo, Role, ToolCall};
+    use crate::testing::MockLLMProvider;
 
-    /// Mock LLM for testing that implements the new LLMProvider trait
-    struct MockLLM {
-        responses: Vec<Message>,
-        call_count: std::sync::atomic::AtomicUsize,
-    }
-
-    impl MockLLM {
-        fn new(responses: Vec<Message>) -> Self {
-            Self {
-                responses,
-                call_count: std::sync::atomic::AtomicUsize::new(0),
-            }
-        }
-
-        fn simple() -> Self {
-            Self::new(vec![Message::assistant("Hello! I'm a mock assistant.")])
-        }
+    /// 스크립트된 응답 후 "Default response"를 반환하는 Mock LLM
+    fn mock_llm(responses: Vec<Message>) -> MockLLMProvider {
+        MockLLMProvider::new()
+            .with_messages(responses)
+            .with_fallback("Default response")
     }
 
-    #[async_trait]
-    impl LLMProvider for MockLLM {
-        async fn complete(
-            &self,
-            _messages: &[Message],
-            _tools: &[ToolDefinition],
-            _config: Option<&LLMConfig>,
-        ) -> Result<LLMResponse, DeepAgentError> {
-            let count = self.call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            let message = self.responses.get(count).cloned().unwrap_or_else(|| {
-                Message::assistant("Default response")
-            });
-            Ok(LLMResponse::new(message))
-        }
-
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        fn default_model(&self) -> &str {
-            "mock-model"
-        }
+    fn simple_llm() -> MockLLMProvider {
+        mock_llm(vec![Message::assistant("Hello! I'm a mock assistant.")])
     }
 
     #[tokio::test]
     async fn test_executor_basic() {
-        let llm = Arc::new(MockLLM::simple());
+        let llm = Arc::new(simple_llm());
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
@@ -396,7 +1078,7 @@ mod tests {
             Message::assistant("Done reading file."),
         ];
 
-        let llm = Arc::new(MockLLM::new(responses));
+        let llm = Arc::new(mock_llm(responses));
         let backend = Arc::new(MemoryBackend::new());
 
         // Pre-populate backend with test file
@@ -415,6 +1097,105 @@ mod tests {
         assert!(result.messages.len() >= 4);
     }
 
+    #[tokio::test]
+    async fn test_executor_accumulates_run_usage() {
+        let tool_call = ToolCall {
+            id: "call_1".to_string(),
+            name: "read_file".to_string(),
+            arguments: serde_json::json!({"file_path": "/test.txt"}),
+        };
+
+        let llm = Arc::new(
+            MockLLMProvider::new()
+                .with_message(Message::assistant_with_tool_calls("", vec![tool_call]))
+                .with_usage(TokenUsage::new(100, 20))
+                .with_response("Done.")
+                .with_usage(TokenUsage::new(150, 30)),
+        );
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/test.txt", "Hello World").await.unwrap();
+
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), backend);
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Read the file")]))
+            .await
+            .unwrap();
+
+        let usage = result.run_usage();
+        assert_eq!(usage.llm_calls, 2);
+        assert_eq!(usage.tokens, TokenUsage::new(250, 50));
+    }
+
+    #[derive(Debug, serde::Deserialize, JsonSchema)]
+    struct Verdict {
+        answer: String,
+        confidence: f64,
+    }
+
+    #[tokio::test]
+    async fn test_run_typed_parses_final_answer() {
+        let llm = Arc::new(mock_llm(vec![Message::assistant(
+            "```json\n{\"answer\": \"yes\", \"confidence\": 0.9}\n```",
+        )]));
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_config(LLMConfig::new("test-model").with_temperature(0.0));
+
+        let verdict: Verdict = executor
+            .run_typed(AgentState::with_messages(vec![Message::user("Is it?")]))
+            .await
+            .unwrap();
+
+        assert_eq!(verdict.answer, "yes");
+        assert_eq!(verdict.confidence, 0.9);
+
+        let requests = llm.requests();
+        let config = requests[0].config.as_ref().unwrap();
+        assert_eq!(config.temperature, Some(0.0));
+        match config.response_format.as_ref().unwrap() {
+            ResponseFormat::JsonSchema { name, schema } => {
+                assert_eq!(name, "Verdict");
+                assert!(schema["properties"]["confidence"].is_object());
+            }
+            other => panic!("unexpected response format: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_typed_repairs_invalid_json() {
+        let llm = Arc::new(mock_llm(vec![
+            Message::assistant("{\"answer\": \"yes\"}"),
+            Message::assistant("{\"answer\": \"yes\", \"confidence\": 0.5}"),
+        ]));
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_system_prompt("Answer in JSON.");
+
+        let verdict: Verdict = executor
+            .run_typed(AgentState::with_messages(vec![Message::user("Is it?")]))
+            .await
+            .unwrap();
+
+        assert_eq!(verdict.confidence, 0.5);
+        assert_eq!(llm.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_typed_reports_schema_validation_error() {
+        let llm = Arc::new(mock_llm(vec![
+            Message::assistant("not json"),
+            Message::assistant("still not json"),
+            Message::assistant("{\"answer\": \"too late\", \"confidence\": 1.0}"),
+        ]));
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_schema_repair_retries(1);
+
+        let result = executor
+            .run_typed::<Verdict>(AgentState::with_messages(vec![Message::user("Is it?")]))
+            .await;
+
+        assert!(matches!(result, Err(DeepAgentError::SchemaValidation(_))));
+        assert_eq!(llm.request_count(), 2);
+    }
+
     struct UpdateTodosTool;
 
     #[async_trait]
@@ -427,6 +1208,7 @@ mod tests {
                     "type": "object",
                     "properties": {}
                 }),
+                examples: Vec::new(),
             }
         }
 
@@ -453,7 +1235,7 @@ mod tests {
             Message::assistant("Done."),
         ];
 
-        let llm = Arc::new(MockLLM::new(responses));
+        let llm = Arc::new(mock_llm(responses));
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
@@ -490,7 +1272,7 @@ mod tests {
             Message::assistant("Done."),
         ];
 
-        let llm = Arc::new(MockLLM::new(responses));
+        let llm = Arc::new(mock_llm(responses));
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
@@ -518,20 +1300,326 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn test_executor_max_iterations() {
-        // Create LLM that always returns tool calls
-        let tool_call = ToolCall {
-            id: "call_1".to_string(),
-            name: "some_tool".to_string(),
-            arguments: serde_json::json!({}),
-        };
+    /// 금지어가 포함된 요청에 LLM 호출 없이 거절하는 가드레일
+    struct KeywordGuardMiddleware;
 
-        let responses: Vec<Message> = (0..100)
-            .map(|_| Message::assistant_with_tool_calls("", vec![tool_call.clone()]))
-            .collect();
+    #[async_trait]
+    impl crate::middleware::AgentMiddleware for KeywordGuardMiddleware {
+        fn name(&self) -> &str {
+            "keyword_guard"
+        }
+
+        async fn before_model(
+            &self,
+            request: &mut ModelRequest,
+            _state: &mut AgentState,
+            _runtime: &ToolRuntime,
+        ) -> Result<ModelControl, MiddlewareError> {
+            let banned = request
+                .messages
+                .iter()
+                .any(|m| m.role == Role::User && m.content.contains("exploit"));
+            if banned {
+                Ok(ModelControl::Respond(Message::assistant("I can't help with that request.")))
+            } else {
+                Ok(ModelControl::Continue)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_middleware_responds_without_model_call() {
+        let llm = Arc::new(simple_llm());
+        let executor = AgentExecutor::new(
+            llm.clone(),
+            MiddlewareStack::new().with_middleware(KeywordGuardMiddleware),
+            Arc::new(MemoryBackend::new()),
+        );
+
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Write an exploit")]))
+            .await
+            .unwrap();
+
+        assert_eq!(llm.request_count(), 0);
+        assert_eq!(result.message_count(), 2);
+        let reply = result.last_assistant_message().unwrap();
+        assert_eq!(reply.content, "I can't help with that request.");
 
-        let llm = Arc::new(MockLLM::new(responses));
+        // 금지어가 없으면 정상적으로 LLM 호출
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Summarize RAG papers")]))
+            .await
+            .unwrap();
+        assert_eq!(llm.request_count(), 1);
+        assert_eq!(
+            result.last_assistant_message().unwrap().content,
+            "Hello! I'm a mock assistant."
+        );
+    }
+
+    /// 파일에서 실수로 읽은 API 키를 흉내내는 도구
+    struct LeakyTool;
+
+    #[async_trait]
+    impl Tool for LeakyTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "read_env".to_string(),
+                description: "Test tool that leaks a secret.".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            Ok(ToolResult::new("OPENAI_API_KEY=sk-abc123XYZ\nDEBUG=true"))
+        }
+    }
+
+    /// 도구 결과의 API 키를 마스킹하는 미들웨어
+    struct RedactSecretsMiddleware;
+
+    #[async_trait]
+    impl crate::middleware::AgentMiddleware for RedactSecretsMiddleware {
+        fn name(&self) -> &str {
+            "redact_secrets"
+        }
+
+        async fn after_tool(
+            &self,
+            _call: &ToolCall,
+            result: &mut ToolResult,
+            _state: &AgentState,
+            _runtime: &ToolRuntime,
+        ) -> Result<(), MiddlewareError> {
+            let pattern = regex::Regex::new(r"sk-[A-Za-z0-9]+").unwrap();
+            result.message = pattern.replace_all(&result.message, "sk-***").into_owned();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_after_tool_redacts_result() {
+        let tool_call = ToolCall {
+            id: "call_env".to_string(),
+            name: "read_env".to_string(),
+            arguments: serde_json::json!({}),
+        };
+        let llm = Arc::new(mock_llm(vec![
+            Message::assistant_with_tool_calls("", vec![tool_call]),
+            Message::assistant("Done."),
+        ]));
+        let executor = AgentExecutor::new(
+            llm,
+            MiddlewareStack::new().with_middleware(RedactSecretsMiddleware),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_tools(vec![Arc::new(LeakyTool)]);
+
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Show the env")]))
+            .await
+            .unwrap();
+
+        let tool_message = result
+            .messages
+            .iter()
+            .find(|m| m.role == Role::Tool)
+            .unwrap();
+        assert_eq!(tool_message.content, "OPENAI_API_KEY=sk-***\nDEBUG=true");
+        assert!(result.messages.iter().all(|m| !m.content.contains("sk-abc123XYZ")));
+    }
+
+    #[tokio::test]
+    async fn test_executor_skips_policy_rejected_tool_calls() {
+        use crate::middleware::{HumanInTheLoopMiddleware, InterruptOnConfig};
+
+        let tool_call = ToolCall {
+            id: "call_1".to_string(),
+            name: "write_todos".to_string(),
+            arguments: serde_json::json!({"todos": [{"content": "Test todo", "status": "pending"}]}),
+        };
+
+        let responses = vec![
+            Message::assistant_with_tool_calls("", vec![tool_call]),
+            Message::assistant("Done."),
+        ];
+
+        let llm = Arc::new(mock_llm(responses));
+        let backend = Arc::new(MemoryBackend::new());
+        let middleware = MiddlewareStack::new().with_middleware(HumanInTheLoopMiddleware::for_tools(
+            vec!["write_todos".to_string()],
+            InterruptOnConfig::auto_reject(),
+        ));
+
+        let executor = AgentExecutor::new(llm, middleware, backend)
+            .with_tools(vec![Arc::new(crate::tools::WriteTodosTool)]);
+
+        let initial_state = AgentState::with_messages(vec![
+            Message::user("Update todos"),
+        ]);
+
+        let result = executor.run(initial_state).await.unwrap();
+
+        assert!(result.todos.is_empty());
+
+        let tool_message = result
+            .messages
+            .iter()
+            .find(|message| message.role == Role::Tool)
+            .unwrap();
+        assert_eq!(tool_message.status.as_deref(), Some("error"));
+        assert!(tool_message.content.contains("rejected by approval policy"));
+    }
+
+    #[tokio::test]
+    async fn test_system_prompt_sections_follow_layout() {
+        use crate::middleware::{FilesystemMiddleware, TodoListMiddleware};
+
+        let llm = Arc::new(mock_llm(vec![Message::assistant("Done.")]));
+        let executor = AgentExecutor::new(
+            llm.clone(),
+            MiddlewareStack::new()
+                .with_middleware(FilesystemMiddleware::with_system_prompt("Filesystem rules."))
+                .with_middleware(TodoListMiddleware::with_system_prompt("Todo rules.")),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_system_prompt("You are a planner.")
+        .with_prompt_layout(SystemPromptLayout::new(["todo_list", SystemPromptLayout::BASE]));
+
+        executor
+            .run(AgentState::with_messages(vec![Message::user("Plan")]))
+            .await
+            .unwrap();
+
+        let system = &llm.requests()[0].messages[0];
+        assert_eq!(system.role, Role::System);
+        assert_eq!(
+            system.content,
+            "Todo rules.\n\nYou are a planner.\n\nFilesystem rules."
+        );
+    }
+
+    fn write_todos_call(content: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".to_string(),
+            name: "write_todos".to_string(),
+            arguments: serde_json::json!({"todos": [{"content": content, "status": "pending"}]}),
+        }
+    }
+
+    /// write_todos 호출에서 인터럽트된 실행의 재개 토큰 (JSON 왕복 후)
+    async fn interrupted_write_todos_token() -> ResumeToken {
+        use crate::middleware::HumanInTheLoopMiddleware;
+
+        let llm = Arc::new(mock_llm(vec![Message::assistant_with_tool_calls(
+            "",
+            vec![write_todos_call("Original todo")],
+        )]));
+        let executor = AgentExecutor::new(
+            llm,
+            MiddlewareStack::new().with_middleware(HumanInTheLoopMiddleware::for_tool("write_todos")),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_system_prompt("You are a planner.");
+
+        let err = executor
+            .run(AgentState::with_messages(vec![Message::user("Plan")]))
+            .await
+            .unwrap_err();
+        let DeepAgentError::Interrupt(interrupt) = err else {
+            panic!("Expected Interrupt, got {:?}", err);
+        };
+
+        let json = serde_json::to_string(&interrupt.to_resume_token().unwrap()).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    /// 재개 전용 executor (다른 프로세스를 가정)
+    fn resuming_executor() -> AgentExecutor {
+        AgentExecutor::new(
+            Arc::new(mock_llm(vec![Message::assistant("Done.")])),
+            MiddlewareStack::new(),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_system_prompt("You are a planner.")
+        .with_tools(vec![Arc::new(crate::tools::WriteTodosTool)])
+    }
+
+    #[tokio::test]
+    async fn test_resume_token_round_trip_approve() {
+        let token = interrupted_write_todos_token().await;
+
+        assert_eq!(token.action_requests.len(), 1);
+        assert_eq!(token.action_requests[0].id, "call_1");
+        assert!(token.state.messages.iter().all(|m| m.role != Role::System));
+        assert!(token.state.messages.last().unwrap().has_tool_calls());
+
+        let result = resuming_executor()
+            .resume_from_interrupt(token, Decision::Approve)
+            .await
+            .unwrap();
+
+        assert_eq!(result.todos.len(), 1);
+        assert_eq!(result.todos[0].content, "Original todo");
+        assert_eq!(result.messages.iter().filter(|m| m.role == Role::System).count(), 1);
+        assert_eq!(result.last_assistant_message().unwrap().content, "Done.");
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_interrupt_reject() {
+        let token = interrupted_write_todos_token().await;
+
+        let result = resuming_executor()
+            .resume_from_interrupt(token, Decision::Reject)
+            .await
+            .unwrap();
+
+        assert!(result.todos.is_empty());
+        let tool_message = result.messages.iter().find(|m| m.role == Role::Tool).unwrap();
+        assert_eq!(tool_message.status.as_deref(), Some("error"));
+        assert!(tool_message.content.contains("rejected by the reviewer"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_interrupt_edit_uses_token_args() {
+        let mut token = interrupted_write_todos_token().await;
+        token.action_requests[0].args =
+            serde_json::json!({"todos": [{"content": "Edited todo", "status": "pending"}]});
+        // Edit은 허용된 결정이어야 함 (기본 설정은 승인/거부만 허용)
+        token.review_configs[0].allowed_decisions.push(Decision::Edit);
+
+        let result = resuming_executor()
+            .resume_from_interrupt(token, Decision::Edit)
+            .await
+            .unwrap();
+
+        assert_eq!(result.todos.len(), 1);
+        assert_eq!(result.todos[0].content, "Edited todo");
+    }
+
+    #[tokio::test]
+    async fn test_executor_max_iterations() {
+        // Create LLM that always returns tool calls
+        let tool_call = ToolCall {
+            id: "call_1".to_string(),
+            name: "some_tool".to_string(),
+            arguments: serde_json::json!({}),
+        };
+
+        let responses: Vec<Message> = (0..100)
+            .map(|_| Message::assistant_with_tool_calls("", vec![tool_call.clone()]))
+            .collect();
+
+        let llm = Arc::new(mock_llm(responses));
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
@@ -542,13 +1630,187 @@ mod tests {
             Message::user("Test")
         ]);
 
-        let result = executor.run(initial_state).await.unwrap();
+        let err = executor.run(initial_state).await.unwrap_err();
+        let DeepAgentError::MaxIterationsExceeded { limit, state } = err else {
+            panic!("Expected MaxIterationsExceeded, got {:?}", err);
+        };
+        assert_eq!(limit, 5);
 
-        // Should have stopped after max iterations
         // Each iteration adds: assistant (tool call) + tool result = 2 messages
         // Plus initial user message = 1
-        // So max of 5 iterations = 1 + (5 * 2) = 11 messages
-        assert!(result.messages.len() <= 11);
+        // So 5 iterations = 1 + (5 * 2) = 11 messages
+        assert_eq!(state.messages.len(), 11);
+        assert_eq!(state.messages[0].content, "Test");
+        assert!(state.messages[9].has_tool_calls());
+        assert_eq!(state.messages[10].role, Role::Tool);
+    }
+
+    /// 처음 두 번은 도구 호출을 반환하고 세 번째 호출에서 멈추는 LLM
+    struct HangingLLM {
+        call_count: std::sync::atomic::AtomicUsize,
+        hanging: Arc<tokio::sync::Notify>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for HangingLLM {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            let count = self.call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if count >= 2 {
+                self.hanging.notify_one();
+                futures::future::pending::<()>().await;
+            }
+            Ok(LLMResponse::new(Message::assistant_with_tool_calls(
+                "",
+                vec![ToolCall {
+                    id: format!("call_{}", count),
+                    name: "some_tool".to_string(),
+                    arguments: serde_json::json!({}),
+                }],
+            )))
+        }
+
+        fn name(&self) -> &str {
+            "hanging"
+        }
+
+        fn default_model(&self) -> &str {
+            "hanging-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_run_with_cancel_stops_mid_loop() {
+        let hanging = Arc::new(tokio::sync::Notify::new());
+        let llm = Arc::new(HangingLLM {
+            call_count: std::sync::atomic::AtomicUsize::new(0),
+            hanging: hanging.clone(),
+        });
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), Arc::new(MemoryBackend::new()));
+        let token = CancellationToken::new();
+
+        let canceller = {
+            let token = token.clone();
+            async move {
+                hanging.notified().await;
+                token.cancel();
+            }
+        };
+        let run = executor.run_with_cancel(AgentState::with_messages(vec![Message::user("Go")]), token);
+
+        let ((), result) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(canceller, run)
+        })
+        .await
+        .expect("cancelled run should terminate promptly");
+
+        let err = result.unwrap_err();
+        let DeepAgentError::Cancelled { state } = err else {
+            panic!("Expected Cancelled, got {:?}", err);
+        };
+        // user + 2 x (assistant tool call + tool result), hanging call discarded
+        assert_eq!(state.messages.len(), 5);
+        assert_eq!(state.messages[4].role, Role::Tool);
+
+        // 이미 취소된 토큰이면 LLM을 호출하지 않음
+        let token = CancellationToken::new();
+        token.cancel();
+        let llm = Arc::new(simple_llm());
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()));
+        let result = executor
+            .run_with_cancel(AgentState::with_messages(vec![Message::user("Go")]), token)
+            .await;
+        assert!(matches!(result, Err(DeepAgentError::Cancelled { .. })));
+        assert_eq!(llm.request_count(), 0);
+    }
+
+    /// 생성된 span의 (이름, 필드, 부모 이름)을 기록하는 레이어
+    struct SpanCapture(Arc<std::sync::Mutex<Vec<(String, String, Option<String>)>>>);
+
+    struct FieldCapture(String);
+
+    impl tracing::field::Visit for FieldCapture {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!("{}={:?} ", field.name(), value));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut fields = FieldCapture(String::new());
+            attrs.record(&mut fields);
+            let parent = ctx
+                .span(id)
+                .and_then(|span| span.parent())
+                .map(|parent| parent.name().to_string());
+            self.0
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_string(), fields.0, parent));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_emits_nested_spans() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let spans = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(SpanCapture(spans.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let responses = vec![
+            Message::assistant_with_tool_calls(
+                "",
+                vec![ToolCall {
+                    id: "call_1".to_string(),
+                    name: "write_todos".to_string(),
+                    arguments: serde_json::json!({"todos": [{"content": "Trace me"}]}),
+                }],
+            ),
+            Message::assistant("Done."),
+        ];
+        let executor = AgentExecutor::new(
+            Arc::new(mock_llm(responses)),
+            MiddlewareStack::new(),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_tools(vec![Arc::new(crate::tools::WriteTodosTool)]);
+
+        executor
+            .run(AgentState::with_messages(vec![Message::user("Plan")]))
+            .await
+            .unwrap();
+
+        let spans = spans.lock().unwrap();
+        let named = |name: &str| spans.iter().filter(|s| s.0 == name).collect::<Vec<_>>();
+
+        let tool_spans = named("tool_call");
+        assert_eq!(tool_spans.len(), 1);
+        assert!(tool_spans[0].1.contains("tool_name=write_todos"), "{}", tool_spans[0].1);
+        assert!(tool_spans[0].1.contains("tool_call_id=call_1"));
+        assert_eq!(tool_spans[0].2.as_deref(), Some("agent_iteration"));
+
+        let iterations = named("agent_iteration");
+        assert_eq!(iterations.len(), 2);
+        assert!(iterations[1].1.contains("iteration=1"));
+        assert!(iterations.iter().all(|s| s.2.as_deref() == Some("agent_run")));
+
+        let llm_calls = named("llm_call");
+        assert_eq!(llm_calls.len(), 2);
+        assert!(llm_calls[0].1.contains("provider=\"mock\""));
+        assert!(llm_calls.iter().all(|s| s.2.as_deref() == Some("agent_iteration")));
     }
 
     struct BigTool;
@@ -563,6 +1825,7 @@ mod tests {
                     "type": "object",
                     "properties": {}
                 }),
+                examples: Vec::new(),
             }
         }
 
@@ -592,7 +1855,7 @@ mod tests {
             Message::assistant("Done."),
         ];
 
-        let llm = Arc::new(MockLLM::new(responses));
+        let llm = Arc::new(mock_llm(responses));
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
@@ -616,9 +1879,458 @@ mod tests {
         assert!(result.files.contains_key("/large_tool_results/call_big"));
     }
 
+    struct SlowTool;
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "slow_tool".to_string(),
+                description: "Never finishes in time.".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(ToolResult::new("finished"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_tool_timeout_is_reported_to_model() {
+        let tool_call = ToolCall {
+            id: "call_slow".to_string(),
+            name: "slow_tool".to_string(),
+            arguments: serde_json::json!({}),
+        };
+
+        let responses = vec![
+            Message::assistant_with_tool_calls("", vec![tool_call]),
+            Message::assistant("Recovered."),
+        ];
+
+        let llm = Arc::new(mock_llm(responses));
+        let backend = Arc::new(MemoryBackend::new());
+
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), backend)
+            .with_tools(vec![Arc::new(SlowTool)])
+            .with_tool_timeout(Duration::from_millis(50));
+
+        let initial_state = AgentState::with_messages(vec![
+            Message::user("Run slow tool"),
+        ]);
+
+        let started = std::time::Instant::now();
+        let result = executor.run(initial_state).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let tool_message = result
+            .messages
+            .iter()
+            .find(|message| message.role == Role::Tool)
+            .expect("tool message missing");
+        assert!(tool_message.content.contains("Tool 'slow_tool' timed out after 50ms"));
+        assert_eq!(result.last_assistant_message().unwrap().content, "Recovered.");
+    }
+
+    #[tokio::test]
+    async fn test_executor_continues_truncated_response() {
+        let llm = Arc::new(
+            MockLLMProvider::new()
+                .with_response("The answer is ")
+                .with_finish_reason(FinishReason::Length)
+                .with_response("forty-two."),
+        );
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()));
+
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("What is the answer?")]))
+            .await
+            .unwrap();
+
+        assert_eq!(llm.request_count(), 2);
+        let continuation = &llm.requests()[1].messages;
+        let [.., partial, prompt] = continuation.as_slice() else {
+            panic!("continuation request too short");
+        };
+        assert_eq!(partial.role, Role::Assistant);
+        assert_eq!(partial.content, "The answer is ");
+        assert_eq!(prompt.content, LENGTH_CONTINUATION_PROMPT);
+
+        let assistant_messages: Vec<_> = result
+            .messages
+            .iter()
+            .filter(|message| message.role == Role::Assistant)
+            .collect();
+        assert_eq!(assistant_messages.len(), 1);
+        assert_eq!(assistant_messages[0].content, "The answer is forty-two.");
+    }
+
+    #[tokio::test]
+    async fn test_executor_length_continuations_are_capped() {
+        let llm = Arc::new(
+            MockLLMProvider::new()
+                .with_response("a")
+                .with_finish_reason(FinishReason::Length)
+                .with_response("b")
+                .with_finish_reason(FinishReason::Length)
+                .with_response("c"),
+        );
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_max_length_continuations(1);
+
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Go")]))
+            .await
+            .unwrap();
+
+        assert_eq!(llm.request_count(), 2);
+        assert_eq!(result.last_assistant_message().unwrap().content, "ab");
+    }
+
+    #[tokio::test]
+    async fn test_executor_rejects_arguments_violating_schema() {
+        let tool_call = ToolCall {
+            id: "call_todos".to_string(),
+            name: "write_todos".to_string(),
+            arguments: serde_json::json!({"items": []}),
+        };
+
+        let responses = vec![
+            Message::assistant_with_tool_calls("", vec![tool_call]),
+            Message::assistant("Fixed."),
+        ];
+
+        let executor = AgentExecutor::new(
+            Arc::new(mock_llm(responses)),
+            MiddlewareStack::new(),
+            Arc::new(MemoryBackend::new()),
+        )
+        .with_tools(vec![Arc::new(crate::tools::WriteTodosTool)]);
+
+        let mut initial_state = AgentState::with_messages(vec![Message::user("Plan")]);
+        initial_state.todos = vec![Todo::new("Keep me")];
+
+        let result = executor.run(initial_state).await.unwrap();
+
+        let tool_message = result
+            .messages
+            .iter()
+            .find(|message| message.role == Role::Tool)
+            .expect("tool message missing");
+        assert!(
+            tool_message.content.contains("Invalid arguments for tool 'write_todos'"),
+            "{}",
+            tool_message.content
+        );
+        assert!(tool_message.content.contains("\"todos\" is a required property"));
+        assert_eq!(result.todos.len(), 1);
+        assert_eq!(result.todos[0].content, "Keep me");
+    }
+
+    /// Mock provider that streams each scripted turn as several chunks
+    struct MockStreamingLLM {
+        turns: Vec<Vec<MessageChunk>>,
+        call_count: std::sync::atomic::AtomicUsize,
+    }
+
+    fn chunk(content: &str, tool_calls: Vec<ToolCall>) -> MessageChunk {
+        MessageChunk {
+            content: content.to_string(),
+            is_final: false,
+            usage: None,
+            tool_calls,
+            tool_call_delta: None,
+            finish_reason: None,
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockStreamingLLM {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            unreachable!("executor should use stream()")
+        }
+
+        async fn stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponseStream, DeepAgentError> {
+            let count = self.call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let chunks: Vec<_> = self.turns[count].iter().cloned().map(Ok).collect();
+            Ok(LLMResponseStream::new(futures::stream::iter(chunks)))
+        }
+
+        fn name(&self) -> &str {
+            "mock-streaming"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_run_streaming_events() {
+        let tool_call = ToolCall {
+            id: "call_update".to_string(),
+            name: "update_todos".to_string(),
+            arguments: serde_json::json!({}),
+        };
+        let llm = Arc::new(MockStreamingLLM {
+            turns: vec![
+                vec![chunk("Let me ", vec![]), chunk("plan.", vec![tool_call.clone()])],
+                vec![chunk("All ", vec![]), chunk("done.", vec![])],
+            ],
+            call_count: std::sync::atomic::AtomicUsize::new(0),
+        });
+
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_tools(vec![Arc::new(UpdateTodosTool)]);
+
+        let events: Vec<ExecutorEvent> = executor
+            .run_streaming(AgentState::with_messages(vec![Message::user("Plan")]))
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        let summary: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                ExecutorEvent::TokenChunk(text) => format!("token:{}", text),
+                ExecutorEvent::ToolCallStarted(call) => format!("start:{}", call.name),
+                ExecutorEvent::ToolProgress { tool_name, content, .. } => {
+                    format!("progress:{}:{}", tool_name, content)
+                }
+                ExecutorEvent::ToolResult { tool_name, content, .. } => {
+                    format!("result:{}:{}", tool_name, content)
+                }
+                ExecutorEvent::MessageComplete(message) => format!("message:{}", message.content),
+                ExecutorEvent::Done(_) => "done".to_string(),
+            })
+            .collect();
+
+        assert_eq!(summary, vec![
+            "token:Let me ",
+            "token:plan.",
+            "message:Let me plan.",
+            "start:update_todos",
+            "result:update_todos:Todos updated",
+            "token:All ",
+            "token:done.",
+            "message:All done.",
+            "done",
+        ]);
+
+        match events.last() {
+            Some(ExecutorEvent::Done(state)) => {
+                assert_eq!(state.todos.len(), 1);
+                assert_eq!(state.last_assistant_message().unwrap().content, "All done.");
+            }
+            other => panic!("Unexpected final event: {:?}", other),
+        }
+    }
+
+    /// Mock LLM that fails a fixed number of times before succeeding
+    struct FlakyLLM {
+        failures: usize,
+        error: &'static str,
+        call_count: std::sync::atomic::AtomicUsize,
+    }
+
+    impl FlakyLLM {
+        fn new(failures: usize, error: &'static str) -> Self {
+            Self {
+                failures,
+                error,
+                call_count: std::sync::atomic::AtomicUsize::new(0),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.call_count.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for FlakyLLM {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            let count = self.call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if count < self.failures {
+                return Err(DeepAgentError::LlmError(self.error.to_string()));
+            }
+            Ok(LLMResponse::new(Message::assistant("Recovered.")))
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_retries_transient_llm_errors() {
+        let llm = Arc::new(FlakyLLM::new(2, "HTTP 503 Service Unavailable"));
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_llm_retry(LLMRetryConfig::default().with_base_delay(Duration::from_millis(1)));
+
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Hi")]))
+            .await
+            .unwrap();
+
+        assert_eq!(llm.calls(), 3);
+        assert_eq!(result.last_assistant_message().unwrap().content, "Recovered.");
+    }
+
+    #[tokio::test]
+    async fn test_executor_does_not_retry_permanent_llm_errors() {
+        let llm = Arc::new(FlakyLLM::new(1, "HTTP 401 invalid api key"));
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_llm_retry(LLMRetryConfig::default().with_base_delay(Duration::from_millis(1)));
+
+        let err = executor
+            .run(AgentState::with_messages(vec![Message::user("Hi")]))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DeepAgentError::LlmError(_)));
+        assert_eq!(llm.calls(), 1);
+    }
+
+    /// Tool that sleeps and records the peak number of overlapping executions
+    struct SleepyTool {
+        name: &'static str,
+        concurrent_safe: bool,
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    impl SleepyTool {
+        fn new(name: &'static str, concurrent_safe: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                concurrent_safe,
+                in_flight: std::sync::atomic::AtomicUsize::new(0),
+                peak: std::sync::atomic::AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Tool for SleepyTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: self.name.to_string(),
+                description: "Sleeps briefly.".to_string(),
+                parameters: serde_json::json!({"type": "object", "properties": {}}),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            use std::sync::atomic::Ordering;
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(ToolResult::new(format!("slept {}", args["n"])))
+        }
+
+        fn is_concurrent_safe(&self) -> bool {
+            self.concurrent_safe
+        }
+    }
+
+    fn sleepy_calls(name: &str, count: usize) -> Vec<ToolCall> {
+        (0..count)
+            .map(|n| ToolCall {
+                id: format!("call_{}", n),
+                name: name.to_string(),
+                arguments: serde_json::json!({"n": n}),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_executor_runs_concurrent_safe_tools_in_parallel() {
+        let tool = SleepyTool::new("sleepy_search", true);
+        let llm = Arc::new(mock_llm(vec![
+            Message::assistant_with_tool_calls("", sleepy_calls("sleepy_search", 3)),
+            Message::assistant("Done."),
+        ]));
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_tools(vec![tool.clone()])
+            .with_max_concurrent_tools(4);
+
+        let started = std::time::Instant::now();
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Search")]))
+            .await
+            .unwrap();
+
+        // 순차 실행이면 600ms 이상 걸림
+        assert!(started.elapsed() < Duration::from_millis(500), "took {:?}", started.elapsed());
+        assert_eq!(tool.peak.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        let tool_results: Vec<&str> = result
+            .messages
+            .iter()
+            .filter(|message| message.role == Role::Tool)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(tool_results, vec!["slept 0", "slept 1", "slept 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_executor_runs_unsafe_tools_serially() {
+        let tool = SleepyTool::new("sleepy_writer", false);
+        let llm = Arc::new(mock_llm(vec![
+            Message::assistant_with_tool_calls("", sleepy_calls("sleepy_writer", 2)),
+            Message::assistant("Done."),
+        ]));
+        let executor = AgentExecutor::new(llm, MiddlewareStack::new(), Arc::new(MemoryBackend::new()))
+            .with_tools(vec![tool.clone()])
+            .with_max_concurrent_tools(4);
+
+        executor
+            .run(AgentState::with_messages(vec![Message::user("Write")]))
+            .await
+            .unwrap();
+
+        assert_eq!(tool.peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_executor_with_config() {
-        let llm = Arc::new(MockLLM::simple());
+        let llm = Arc::new(simple_llm());
         let backend = Arc::new(MemoryBackend::new());
         let middleware = MiddlewareStack::new();
 
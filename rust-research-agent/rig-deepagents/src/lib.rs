@@ -42,45 +42,50 @@ pub mod research;
 pub mod config;
 pub mod compat;
 pub mod tokenization;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 mod tool_result_eviction;
 
 // Re-exports for convenience
 pub use error::{BackendError, MiddlewareError, DeepAgentError, WriteResult, EditResult};
-pub use state::{AgentState, Message, Role, Todo, TodoStatus, FileData, ToolCall};
-pub use backends::{Backend, FileInfo, GrepMatch, MemoryBackend, FilesystemBackend, CompositeBackend};
+pub use state::{AgentState, Message, MessageContent, Role, Todo, TodoStatus, FileData, ToolCall};
+pub use backends::{Backend, BackendTransaction, FileInfo, GrepMatch, GrepOptions, MemoryBackend, MemorySnapshot, FilesystemBackend, CompositeBackend, ReadOnlyBackend, QuotaBackend, OverlayBackend};
 pub use middleware::{
     AgentMiddleware, MiddlewareStack, StateUpdate, Tool, ToolDefinition, ToolRegistry, ToolResult, DynTool,
-    FilesystemMiddleware, TodoListMiddleware,
+    FilesystemMiddleware, TodoListMiddleware, PromptSection,
 };
-pub use runtime::{ToolRuntime, RuntimeConfig};
+pub use runtime::{ToolRuntime, RuntimeConfig, SystemPromptLayout, ToolProgressFn};
 pub use tools::{
     ReadFileTool, WriteFileTool, EditFileTool,
     LsTool, GlobTool, GrepTool,
     WriteTodosTool, TaskTool,
     default_tools, all_tools,
     // Domain tools
-    TavilySearchTool, TavilyError, SearchDepth, Topic,
-    ThinkTool,
+    TavilySearchTool, TavilyError, SearchDepth, Topic, FetchUrlTool,
+    SearchProvider, SearchOptions, SearchResponse, SearchResult, WebSearchTool,
+    ThinkTool, LoggingThinkTool, ShellTool,
     research_tools, research_tools_with_tavily,
+    // Wrappers
+    CachingTool, CacheConfig,
 };
-pub use executor::AgentExecutor;
+pub use executor::{AgentExecutor, ExecutorEvent, ExecutorEventStream};
 
 // Research workflow exports
 pub use research::{
     ResearchState, ResearchUpdate, ResearchPhase,
-    ResearchDirection, Finding, Source, SourceAgreement,
+    ResearchDirection, Finding, Source, SourceAgreement, Conflict, CitationStyle,
     ResearchWorkflowBuilder, ResearchConfig,
     ResearchPrompts, PromptBuilder,
     can_continue_research, determine_next_phase, phase_transition_update,
 };
 
 // Production configuration exports
-pub use config::{ProductionConfig, ProductionSetup, LLMProviderType};
+pub use config::{ApiKeys, ProductionConfig, ProductionSetup, LLMProviderType};
 
 // LLM Provider exports
 pub use llm::{
-    LLMProvider, LLMResponse, LLMResponseStream, MessageChunk,
-    LLMConfig, TokenUsage,
+    LLMProvider, LLMResponse, LLMResponseStream, FinishReason, MessageChunk, ToolCallDelta, FallbackProvider, BatchingProvider,
+    LLMConfig, LLMRetryConfig, ResponseFormat, RunUsage, TokenUsage,
     MessageConverter, ToolConverter, convert_messages, convert_tools,
 };
 
@@ -0,0 +1,238 @@
+//! Request-coalescing provider
+//!
+//! Subagents launched together (e.g. by `task_parallel`) each run their own
+//! agent loop and call `complete()` independently. [`BatchingProvider`]
+//! collects calls that arrive within a short window and forwards them to the
+//! wrapped provider as a single [`LLMProvider::complete_batch`] call, so
+//! providers with a native batch endpoint can serve them together.
+
+use async_trait::async_trait;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use crate::error::DeepAgentError;
+use crate::middleware::{ModelRequest, ToolDefinition};
+use crate::state::Message;
+use super::config::LLMConfig;
+use super::provider::{LLMProvider, LLMResponse};
+
+/// A queued request waiting for its batch to be dispatched
+struct PendingRequest {
+    request: ModelRequest,
+    reply: oneshot::Sender<Result<LLMResponse, DeepAgentError>>,
+}
+
+/// LLM provider that coalesces concurrent completions into batches
+///
+/// The first call in a window starts a timer; every call arriving before it
+/// fires joins the same batch. A batch is dispatched early once it reaches
+/// `max_batch_size`. Streaming falls back to `complete()`, so streamed calls
+/// are batched too (and arrive as a single chunk).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let provider = BatchingProvider::new(Arc::new(openai), Duration::from_millis(20))
+///     .with_max_batch_size(8);
+/// ```
+pub struct BatchingProvider {
+    inner: Arc<dyn LLMProvider>,
+    window: Duration,
+    max_batch_size: usize,
+    pending: Arc<Mutex<Vec<PendingRequest>>>,
+}
+
+impl BatchingProvider {
+    /// Default upper bound on requests per batch
+    pub const DEFAULT_MAX_BATCH_SIZE: usize = 16;
+
+    /// Wrap a provider, batching calls that arrive within `window` of each other
+    pub fn new(inner: Arc<dyn LLMProvider>, window: Duration) -> Self {
+        Self {
+            inner,
+            window,
+            max_batch_size: Self::DEFAULT_MAX_BATCH_SIZE,
+            pending: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Dispatch a batch as soon as it holds this many requests
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// The wrapped provider
+    pub fn inner(&self) -> &Arc<dyn LLMProvider> {
+        &self.inner
+    }
+
+    /// Send a batch to the wrapped provider and hand each caller its result
+    async fn dispatch(inner: Arc<dyn LLMProvider>, batch: Vec<PendingRequest>) {
+        let (requests, replies): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .map(|pending| (pending.request, pending.reply))
+            .unzip();
+
+        tracing::debug!(batch_size = requests.len(), provider = inner.name(), "Dispatching LLM batch");
+
+        let mut results = inner.complete_batch(&requests).await.into_iter();
+        for reply in replies {
+            let result = results.next().unwrap_or_else(|| {
+                Err(DeepAgentError::LlmError(
+                    "Batch response is missing a result for this request".to_string(),
+                ))
+            });
+            // The caller may have given up (e.g. timed out); nothing to do then
+            let _ = reply.send(result);
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for BatchingProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        config: Option<&LLMConfig>,
+    ) -> Result<LLMResponse, DeepAgentError> {
+        let (reply, response) = oneshot::channel();
+        let request = ModelRequest {
+            messages: messages.to_vec(),
+            tools: tools.to_vec(),
+            config: config.cloned(),
+        };
+
+        let (full_batch, opens_window) = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.push(PendingRequest { request, reply });
+            if pending.len() >= self.max_batch_size {
+                (Some(mem::take(&mut *pending)), false)
+            } else {
+                (None, pending.len() == 1)
+            }
+        };
+
+        // Dispatch on a separate task so a cancelled caller can't strand the others
+        if let Some(batch) = full_batch {
+            tokio::spawn(Self::dispatch(self.inner.clone(), batch));
+        } else if opens_window {
+            let inner = self.inner.clone();
+            let pending = self.pending.clone();
+            let window = self.window;
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                let batch = mem::take(&mut *pending.lock().unwrap());
+                if !batch.is_empty() {
+                    Self::dispatch(inner, batch).await;
+                }
+            });
+        }
+
+        response.await.map_err(|_| {
+            DeepAgentError::LlmError("Batch dispatcher dropped the request".to_string())
+        })?
+    }
+
+    async fn complete_batch(
+        &self,
+        requests: &[ModelRequest],
+    ) -> Vec<Result<LLMResponse, DeepAgentError>> {
+        self.inner.complete_batch(requests).await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the size of every batch it serves
+    struct RecordingProvider {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for RecordingProvider {
+        async fn complete(
+            &self,
+            messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            Ok(LLMResponse::new(Message::assistant(&messages[0].content)))
+        }
+
+        async fn complete_batch(
+            &self,
+            requests: &[ModelRequest],
+        ) -> Vec<Result<LLMResponse, DeepAgentError>> {
+            self.batches.lock().unwrap().push(requests.len());
+            let mut results = Vec::new();
+            for request in requests {
+                results.push(self.complete(&request.messages, &request.tools, None).await);
+            }
+            results
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn default_model(&self) -> &str {
+            "recording-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_a_batch() {
+        let inner = Arc::new(RecordingProvider {
+            batches: Mutex::new(Vec::new()),
+        });
+        let provider = BatchingProvider::new(inner.clone(), Duration::from_millis(50));
+
+        let prompts = ["alpha", "beta", "gamma"];
+        let provider = &provider;
+        let results = futures::future::join_all(prompts.iter().map(|prompt| async move {
+            provider.complete(&[Message::user(prompt)], &[], None).await
+        }))
+        .await;
+
+        let contents: Vec<_> = results.into_iter().map(|r| r.unwrap().message.content).collect();
+        assert_eq!(contents, prompts);
+        assert_eq!(*inner.batches.lock().unwrap(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_dispatches_early() {
+        let inner = Arc::new(RecordingProvider {
+            batches: Mutex::new(Vec::new()),
+        });
+        // The window is far longer than the test may take
+        let provider =
+            BatchingProvider::new(inner.clone(), Duration::from_secs(60)).with_max_batch_size(2);
+
+        let (a, b) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(
+                provider.complete(&[Message::user("one")], &[], None),
+                provider.complete(&[Message::user("two")], &[], None),
+            )
+        })
+        .await
+        .expect("full batch should not wait for the window");
+
+        assert_eq!(a.unwrap().message.content, "one");
+        assert_eq!(b.unwrap().message.content, "two");
+        assert_eq!(*inner.batches.lock().unwrap(), vec![2]);
+    }
+}
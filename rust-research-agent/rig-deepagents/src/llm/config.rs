@@ -3,6 +3,9 @@
 //! Provides configuration and usage tracking types for LLM providers.
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::error::DeepAgentError;
 
 /// Token usage statistics from an LLM completion.
 ///
@@ -67,6 +70,48 @@ impl std::ops::AddAssign for TokenUsage {
     }
 }
 
+/// Token usage accumulated over an agent run.
+///
+/// Sums every LLM call made during `AgentExecutor::run`, including
+/// summarization and subagent calls, for cost accounting. Calls whose
+/// provider did not report usage still count towards `llm_calls`.
+///
+/// # Example
+///
+/// ```
+/// use rig_deepagents::llm::{RunUsage, TokenUsage};
+///
+/// let mut run = RunUsage::default();
+/// run.record(Some(&TokenUsage::new(100, 20)));
+/// run.record(None);
+///
+/// assert_eq!(run.llm_calls, 2);
+/// assert_eq!(run.tokens.total_tokens, 120);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RunUsage {
+    /// Summed token usage of all calls
+    pub tokens: TokenUsage,
+    /// Number of LLM calls made
+    pub llm_calls: u64,
+}
+
+impl RunUsage {
+    /// Record one LLM call
+    pub fn record(&mut self, usage: Option<&TokenUsage>) {
+        self.llm_calls += 1;
+        if let Some(usage) = usage {
+            self.tokens += usage.clone();
+        }
+    }
+
+    /// Add the usage of another run (e.g. a subagent)
+    pub fn merge(&mut self, other: &RunUsage) {
+        self.tokens += other.tokens.clone();
+        self.llm_calls += other.llm_calls;
+    }
+}
+
 /// LLM Provider configuration
 ///
 /// Controls how an LLM provider generates completions. Configuration
@@ -93,6 +138,15 @@ pub struct LLMConfig {
     pub temperature: Option<f64>,
     /// Maximum tokens to generate in the response
     pub max_tokens: Option<u64>,
+    /// Nucleus sampling probability mass (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Sequences that stop generation when produced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Constrain the response format (e.g., JSON matching a schema)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
     /// API key (optional, can use environment variable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
@@ -122,6 +176,24 @@ impl LLMConfig {
         self
     }
 
+    /// Set the nucleus sampling probability mass
+    pub fn with_top_p(mut self, top_p: f64) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Add a stop sequence
+    pub fn with_stop(mut self, sequence: impl Into<String>) -> Self {
+        self.stop.push(sequence.into());
+        self
+    }
+
+    /// Set the response format
+    pub fn with_response_format(mut self, format: ResponseFormat) -> Self {
+        self.response_format = Some(format);
+        self
+    }
+
     /// Set the API key explicitly
     pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
         self.api_key = Some(key.into());
@@ -135,6 +207,135 @@ impl LLMConfig {
     }
 }
 
+/// Structured output format requested from the model
+///
+/// Forwarded to providers as an OpenAI-style `response_format` parameter.
+///
+/// # Example
+///
+/// ```
+/// use rig_deepagents::llm::{LLMConfig, ResponseFormat};
+///
+/// let schema = serde_json::json!({"type": "object", "properties": {"title": {"type": "string"}}});
+/// let config = LLMConfig::new("gpt-4.1")
+///     .with_response_format(ResponseFormat::json_schema("Report", schema));
+///
+/// assert!(config.response_format.is_some());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Any valid JSON object
+    JsonObject,
+    /// JSON conforming to the given JSON Schema
+    JsonSchema {
+        /// Schema name reported to the provider
+        name: String,
+        /// JSON Schema the response must satisfy
+        schema: serde_json::Value,
+    },
+}
+
+impl ResponseFormat {
+    /// Request JSON conforming to a schema
+    pub fn json_schema(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self::JsonSchema {
+            name: name.into(),
+            schema,
+        }
+    }
+
+    /// Provider request parameter (`{"type": "json_schema", "json_schema": {...}}`)
+    pub fn to_request_param(&self) -> serde_json::Value {
+        match self {
+            Self::JsonObject => serde_json::json!({ "type": "json_object" }),
+            Self::JsonSchema { name, schema } => serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": name,
+                    "schema": schema,
+                    "strict": true,
+                },
+            }),
+        }
+    }
+}
+
+/// LLM 호출 재시도 설정
+///
+/// `AgentExecutor`는 `retryable`이 true를 반환하는 에러에 대해
+/// `base_delay * 2^attempt` 간격으로 최대 `max_retries`번 재시도합니다.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rig_deepagents::llm::LLMRetryConfig;
+///
+/// let retry = LLMRetryConfig::default()
+///     .with_max_retries(5)
+///     .with_base_delay(Duration::from_millis(200));
+///
+/// assert_eq!(retry.delay_for(2), Duration::from_millis(800));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LLMRetryConfig {
+    /// Maximum number of retries after the first attempt (0 disables retries)
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each subsequent retry
+    pub base_delay: Duration,
+    /// Classifies which errors are worth retrying
+    pub retryable: fn(&DeepAgentError) -> bool,
+}
+
+impl Default for LLMRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            retryable: DeepAgentError::is_retryable,
+        }
+    }
+}
+
+impl LLMRetryConfig {
+    /// Configuration that never retries
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of retries
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set a custom retry classifier
+    pub fn with_retryable(mut self, retryable: fn(&DeepAgentError) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Whether the error should be retried
+    pub fn should_retry(&self, error: &DeepAgentError) -> bool {
+        (self.retryable)(error)
+    }
+
+    /// Backoff delay before retry number `attempt` (0-based)
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(attempt))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +369,20 @@ mod tests {
         assert_eq!(usage.total_tokens, 225);
     }
 
+    #[test]
+    fn test_run_usage_merge() {
+        let mut parent = RunUsage::default();
+        parent.record(Some(&TokenUsage::new(10, 5)));
+
+        let mut child = RunUsage::default();
+        child.record(Some(&TokenUsage::new(7, 3)));
+        child.record(Some(&TokenUsage::new(1, 1)));
+
+        parent.merge(&child);
+        assert_eq!(parent.llm_calls, 3);
+        assert_eq!(parent.tokens, TokenUsage::new(18, 9));
+    }
+
     #[test]
     fn test_llm_config_builder() {
         let config = LLMConfig::new("gpt-4.1")
@@ -179,6 +394,21 @@ mod tests {
         assert_eq!(config.max_tokens, Some(16000));
     }
 
+    #[test]
+    fn test_llm_config_sampling_options() {
+        let config = LLMConfig::new("gpt-4.1")
+            .with_top_p(0.9)
+            .with_stop("END")
+            .with_stop("\n\nUser:");
+
+        assert_eq!(config.top_p, Some(0.9));
+        assert_eq!(config.stop, vec!["END".to_string(), "\n\nUser:".to_string()]);
+
+        let json = serde_json::to_string(&LLMConfig::new("gpt-4.1")).unwrap();
+        assert!(!json.contains("top_p"));
+        assert!(!json.contains("stop"));
+    }
+
     #[test]
     fn test_llm_config_with_api_key() {
         let config = LLMConfig::new("gpt-4.1")
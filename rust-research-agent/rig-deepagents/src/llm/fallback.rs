@@ -0,0 +1,212 @@
+//! Fallback provider chain
+//!
+//! Tries an ordered list of providers ("Anthropic, then OpenAI") and moves on
+//! to the next one when a call fails with a retryable error
+//! ([`DeepAgentError::is_retryable`]). Non-retryable errors such as an invalid
+//! request are returned immediately, since another provider would reject
+//! them too.
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::error::DeepAgentError;
+use crate::middleware::ToolDefinition;
+use crate::state::Message;
+use super::config::LLMConfig;
+use super::provider::{LLMProvider, LLMResponse, LLMResponseStream};
+
+/// LLM provider that falls back through an ordered chain of providers
+///
+/// Every call starts from the first provider. [`name`](LLMProvider::name)
+/// and [`default_model`](LLMProvider::default_model) report the provider that
+/// served (or last attempted) the most recent call.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let provider = FallbackProvider::new(vec![
+///     Arc::new(RigAgentAdapter::new(anthropic_agent)),
+///     Arc::new(RigAgentAdapter::new(openai_agent)),
+/// ])?;
+/// ```
+pub struct FallbackProvider {
+    providers: Vec<Arc<dyn LLMProvider>>,
+    /// Index of the provider used for the most recent call
+    active: AtomicUsize,
+}
+
+impl FallbackProvider {
+    /// Create a fallback chain (first provider is the primary)
+    pub fn new(providers: Vec<Arc<dyn LLMProvider>>) -> Result<Self, DeepAgentError> {
+        if providers.is_empty() {
+            return Err(DeepAgentError::Config(
+                "FallbackProvider requires at least one provider".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            providers,
+            active: AtomicUsize::new(0),
+        })
+    }
+
+    /// Providers in fallback order
+    pub fn providers(&self) -> &[Arc<dyn LLMProvider>] {
+        &self.providers
+    }
+
+    /// The provider used for the most recent call
+    pub fn active_provider(&self) -> &Arc<dyn LLMProvider> {
+        &self.providers[self.active.load(Ordering::SeqCst)]
+    }
+
+    /// Whether to move on to the next provider after this error
+    fn should_fall_back(&self, index: usize, error: &DeepAgentError) -> bool {
+        index + 1 < self.providers.len() && error.is_retryable()
+    }
+}
+
+#[async_trait]
+impl LLMProvider for FallbackProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        config: Option<&LLMConfig>,
+    ) -> Result<LLMResponse, DeepAgentError> {
+        for (index, provider) in self.providers.iter().enumerate() {
+            self.active.store(index, Ordering::SeqCst);
+            match provider.complete(messages, tools, config).await {
+                Ok(response) => return Ok(response),
+                Err(error) if self.should_fall_back(index, &error) => {
+                    tracing::warn!(provider = provider.name(), error = %error, "Falling back to next LLM provider");
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        unreachable!("FallbackProvider always has at least one provider")
+    }
+
+    async fn stream(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        config: Option<&LLMConfig>,
+    ) -> Result<LLMResponseStream, DeepAgentError> {
+        // Only failures to open the stream fall back; errors mid-stream are
+        // surfaced as-is because tokens may already have been consumed.
+        for (index, provider) in self.providers.iter().enumerate() {
+            self.active.store(index, Ordering::SeqCst);
+            match provider.stream(messages, tools, config).await {
+                Ok(stream) => return Ok(stream),
+                Err(error) if self.should_fall_back(index, &error) => {
+                    tracing::warn!(provider = provider.name(), error = %error, "Falling back to next LLM provider");
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        unreachable!("FallbackProvider always has at least one provider")
+    }
+
+    fn name(&self) -> &str {
+        self.active_provider().name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.active_provider().default_model()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provider that always succeeds or always fails with a fixed error
+    struct StubProvider {
+        name: &'static str,
+        error: Option<&'static str>,
+        calls: AtomicUsize,
+    }
+
+    impl StubProvider {
+        fn ok(name: &'static str) -> Arc<Self> {
+            Arc::new(Self { name, error: None, calls: AtomicUsize::new(0) })
+        }
+
+        fn failing(name: &'static str, error: &'static str) -> Arc<Self> {
+            Arc::new(Self { name, error: Some(error), calls: AtomicUsize::new(0) })
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for StubProvider {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.error {
+                Some(error) => Err(DeepAgentError::LlmError(error.to_string())),
+                None => Ok(LLMResponse::new(Message::assistant(&format!("from {}", self.name)))),
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn default_model(&self) -> &str {
+            "stub-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_secondary() {
+        let primary = StubProvider::failing("anthropic", "HTTP 529 overloaded");
+        let secondary = StubProvider::ok("openai");
+        let provider = FallbackProvider::new(vec![primary.clone(), secondary.clone()]).unwrap();
+
+        let response = provider.complete(&[Message::user("Hi")], &[], None).await.unwrap();
+
+        assert_eq!(response.message.content, "from openai");
+        assert_eq!(provider.name(), "openai");
+        assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(secondary.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_providers_fail_returns_last_error() {
+        let provider = FallbackProvider::new(vec![
+            StubProvider::failing("anthropic", "HTTP 503 from anthropic"),
+            StubProvider::failing("openai", "HTTP 503 from openai"),
+        ])
+        .unwrap();
+
+        let err = provider.complete(&[Message::user("Hi")], &[], None).await.unwrap_err();
+
+        assert!(err.to_string().contains("from openai"));
+        assert_eq!(provider.name(), "openai");
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_does_not_fall_back() {
+        let secondary = StubProvider::ok("openai");
+        let provider = FallbackProvider::new(vec![
+            StubProvider::failing("anthropic", "HTTP 400 invalid request"),
+            secondary.clone(),
+        ])
+        .unwrap();
+
+        assert!(provider.complete(&[Message::user("Hi")], &[], None).await.is_err());
+        assert_eq!(secondary.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(provider.name(), "anthropic");
+    }
+
+    #[test]
+    fn test_empty_chain_is_rejected() {
+        assert!(matches!(FallbackProvider::new(Vec::new()), Err(DeepAgentError::Config(_))));
+    }
+}
@@ -7,9 +7,16 @@
 //!
 //! DeepAgents uses a simple struct-based Message with a Role enum:
 //! ```text
-//! Message { role: Role, content: String, tool_call_id, tool_calls }
+//! Message { role: Role, content: String, rich_content, tool_call_id, tool_calls }
 //! ```
 //!
+//! Multimodal user messages carry a [`MessageContent`] in `rich_content`; its
+//! text and image parts become Rig `UserContent::Text` / `UserContent::Image`.
+//!
+//! `Message::metadata` (provenance tags such as the producing subagent or
+//! phase) stays in agent state only: Rig messages have no place for it, so it
+//! is dropped on the way out and comes back empty on the way in.
+//!
 //! Rig uses an enum-based Message with rich content types:
 //! ```text
 //! Message::User { content: OneOrMany<UserContent> }
@@ -18,12 +25,12 @@
 //!
 //! This module bridges these two representations.
 
-use crate::state::{Message, Role, ToolCall};
+use crate::state::{Message, MessageContent, Role, ToolCall};
 use crate::middleware::ToolDefinition;
 use crate::error::DeepAgentError;
 use rig::completion::message::{
-    AssistantContent, Message as RigMessage, Text, ToolResultContent,
-    UserContent,
+    AssistantContent, DocumentSourceKind, Image, ImageMediaType, Message as RigMessage,
+    MimeType, Text, ToolResultContent, UserContent,
 };
 use rig::completion::ToolDefinition as RigToolDefinition;
 use rig::OneOrMany;
@@ -52,9 +59,12 @@ pub trait ToolConverter {
 impl MessageConverter for Message {
     fn to_rig_message(&self) -> Result<RigMessage, DeepAgentError> {
         match self.role {
-            Role::User => {
-                Ok(RigMessage::user(&self.content))
-            }
+            Role::User => match &self.rich_content {
+                Some(content) => Ok(RigMessage::User {
+                    content: to_user_contents(content)?,
+                }),
+                None => Ok(RigMessage::user(&self.content)),
+            },
             Role::Assistant => {
                 if let Some(tool_calls) = &self.tool_calls {
                     // Assistant message with tool calls
@@ -107,6 +117,7 @@ impl FromRigMessage for Message {
             RigMessage::User { content } => {
                 // Extract text content, handling tool results specially
                 let mut text_parts = Vec::new();
+                let mut parts = Vec::new();
                 let mut tool_id = None;
                 let mut is_tool_result = false;
 
@@ -114,6 +125,12 @@ impl FromRigMessage for Message {
                     match item {
                         UserContent::Text(Text { text }) => {
                             text_parts.push(text.clone());
+                            parts.push(MessageContent::Text(text.clone()));
+                        }
+                        UserContent::Image(image) => {
+                            if let Some(part) = from_rig_image(image) {
+                                parts.push(part);
+                            }
                         }
                         UserContent::ToolResult(result) => {
                             is_tool_result = true;
@@ -125,7 +142,7 @@ impl FromRigMessage for Message {
                             }
                         }
                         _ => {
-                            // Skip other content types (images, etc.)
+                            // Skip other content types (audio, documents, etc.)
                         }
                     }
                 }
@@ -135,7 +152,7 @@ impl FromRigMessage for Message {
                 if is_tool_result {
                     Ok(Message::tool(&content, &tool_id.unwrap_or_default()))
                 } else {
-                    Ok(Message::user(&content))
+                    Ok(Message::user_with_content(MessageContent::Parts(parts)))
                 }
             }
             RigMessage::Assistant { id: _, content } => {
@@ -176,12 +193,67 @@ impl ToolConverter for ToolDefinition {
     fn to_rig_tool(&self) -> RigToolDefinition {
         RigToolDefinition {
             name: self.name.clone(),
-            description: self.description.clone(),
+            // Rig has no field for example invocations, so they go in the description
+            description: self.description_with_examples(),
             parameters: self.parameters.clone(),
         }
     }
 }
 
+/// Convert multimodal content into Rig user content parts
+pub(crate) fn to_user_contents(
+    content: &MessageContent,
+) -> Result<OneOrMany<UserContent>, DeepAgentError> {
+    let parts: Vec<UserContent> = content
+        .leaves()
+        .into_iter()
+        .filter_map(|part| match part {
+            MessageContent::Text(text) if text.is_empty() => None,
+            MessageContent::Text(text) => Some(UserContent::text(text)),
+            MessageContent::Image { url_or_b64, mime } => Some(to_rig_image(url_or_b64, mime)),
+            MessageContent::Parts(_) => None,
+        })
+        .collect();
+
+    OneOrMany::many(parts).map_err(|e| {
+        DeepAgentError::Conversion(format!("Failed to create user content: {}", e))
+    })
+}
+
+/// Build a Rig image from a URL, a `data:` URL, or raw base64 data
+fn to_rig_image(url_or_b64: &str, mime: &str) -> UserContent {
+    let media_type = ImageMediaType::from_mime_type(mime);
+
+    if let Some((_, data)) = url_or_b64
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+    {
+        return UserContent::image_base64(data, media_type, None);
+    }
+
+    if url_or_b64.starts_with("http://") || url_or_b64.starts_with("https://") {
+        UserContent::image_url(url_or_b64, media_type, None)
+    } else {
+        UserContent::image_base64(url_or_b64, media_type, None)
+    }
+}
+
+/// Convert a Rig image back into an image part (raw bytes are not supported)
+fn from_rig_image(image: &Image) -> Option<MessageContent> {
+    let url_or_b64 = match &image.data {
+        DocumentSourceKind::Url(url) => url.clone(),
+        DocumentSourceKind::Base64(data) => data.clone(),
+        _ => return None,
+    };
+    let mime = image
+        .media_type
+        .as_ref()
+        .map(|media_type| media_type.to_mime_type().to_string())
+        .unwrap_or_default();
+
+    Some(MessageContent::image(url_or_b64, mime))
+}
+
 /// Convert a slice of DeepAgents messages to Rig format
 ///
 /// Filters out messages that cannot be converted (e.g., system messages
@@ -233,6 +305,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mixed_text_and_image_conversion() {
+        let msg = Message::user_with_content(MessageContent::Parts(vec![
+            MessageContent::Text("What is in this screenshot?".to_string()),
+            MessageContent::image("https://example.com/shot.png", "image/png"),
+            MessageContent::image("data:image/jpeg;base64,AAAA", "image/jpeg"),
+        ]));
+        assert_eq!(msg.content, "What is in this screenshot?");
+
+        let rig_msg = msg.to_rig_message().unwrap();
+
+        let RigMessage::User { content } = rig_msg else {
+            panic!("Expected User message");
+        };
+        let parts: Vec<_> = content.iter().collect();
+        assert_eq!(parts.len(), 3);
+        assert!(matches!(parts[0], UserContent::Text(Text { text }) if text == "What is in this screenshot?"));
+        match parts[1] {
+            UserContent::Image(image) => {
+                assert_eq!(image.data, DocumentSourceKind::Url("https://example.com/shot.png".to_string()));
+                assert_eq!(image.media_type, Some(ImageMediaType::PNG));
+            }
+            other => panic!("Expected image, got {:?}", other),
+        }
+        match parts[2] {
+            UserContent::Image(image) => {
+                assert_eq!(image.data, DocumentSourceKind::Base64("AAAA".to_string()));
+                assert_eq!(image.media_type, Some(ImageMediaType::JPEG));
+            }
+            other => panic!("Expected image, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_image_message() {
+        let original = Message::user_with_content(MessageContent::Parts(vec![
+            MessageContent::Text("Describe".to_string()),
+            MessageContent::image("iVBORw0KGgo=", "image/png"),
+        ]));
+
+        let rig_msg = original.to_rig_message().unwrap();
+        let restored = Message::from_rig_message(&rig_msg).unwrap();
+
+        assert_eq!(restored.content, "Describe");
+        assert_eq!(restored.message_content(), original.message_content());
+    }
+
     #[test]
     fn test_assistant_message_conversion() {
         let msg = Message::assistant("I'm here to help!");
@@ -260,7 +379,7 @@ mod tests {
         match rig_msg {
             RigMessage::Assistant { id: _, content } => {
                 // Should have text + tool call
-                assert!(content.len() >= 1);
+                assert!(!content.is_empty());
             }
             _ => panic!("Expected Assistant message"),
         }
@@ -304,6 +423,22 @@ mod tests {
         assert_eq!(converted.content, "Test message");
     }
 
+    #[test]
+    fn test_metadata_not_sent_to_llm() {
+        let original = Message::assistant("Response text")
+            .with_metadata("subagent", "researcher")
+            .with_metadata("phase", "Directed");
+        let rig_msg = original.to_rig_message().unwrap();
+
+        let wire = serde_json::to_string(&rig_msg).unwrap();
+        assert!(!wire.contains("subagent"));
+        assert!(!wire.contains("researcher"));
+
+        let converted = Message::from_rig_message(&rig_msg).unwrap();
+        assert_eq!(converted.content, "Response text");
+        assert!(converted.metadata.is_empty());
+    }
+
     #[test]
     fn test_roundtrip_assistant_message() {
         let original = Message::assistant("Response text");
@@ -326,6 +461,7 @@ mod tests {
                 },
                 "required": ["path"]
             }),
+            examples: Vec::new(),
         };
 
         let rig_tool = tool.to_rig_tool();
@@ -334,6 +470,25 @@ mod tests {
         assert_eq!(rig_tool.description, "Read a file from disk");
     }
 
+    #[test]
+    fn test_tool_examples_propagate_to_converted_definition() {
+        let tool = ToolDefinition {
+            name: "read_file".to_string(),
+            description: "Read a file from disk".to_string(),
+            parameters: serde_json::json!({"type": "object", "properties": {}}),
+            examples: vec![serde_json::json!({"path": "/notes.md"})],
+        }
+        .with_examples([serde_json::json!({"path": "/report.md", "limit": 50})]);
+
+        let converted = convert_tools(&[tool]);
+
+        assert_eq!(converted.len(), 1);
+        let description = &converted[0].description;
+        assert!(description.starts_with("Read a file from disk"));
+        assert!(description.contains(r#"- {"path":"/notes.md"}"#));
+        assert!(description.contains(r#"- {"limit":50,"path":"/report.md"}"#));
+    }
+
     #[test]
     fn test_convert_messages() {
         let messages = vec![
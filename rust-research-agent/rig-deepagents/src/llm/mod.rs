@@ -71,10 +71,15 @@
 mod config;
 mod provider;
 mod message;
+mod fallback;
+mod batch;
 
-pub use config::{LLMConfig, TokenUsage};
-pub use provider::{LLMProvider, LLMResponse, LLMResponseStream, MessageChunk};
+pub use config::{LLMConfig, LLMRetryConfig, ResponseFormat, RunUsage, TokenUsage};
+pub use provider::{FinishReason, LLMProvider, LLMResponse, LLMResponseStream, MessageChunk, ToolCallDelta};
+pub use fallback::FallbackProvider;
+pub use batch::BatchingProvider;
 pub use message::{MessageConverter, ToolConverter, convert_messages, convert_tools};
 
 // Re-export message utilities
 pub use message::extract_system_preamble;
+pub(crate) use message::to_user_contents;
@@ -9,10 +9,43 @@ use std::pin::Pin;
 use futures::Stream;
 
 use crate::error::DeepAgentError;
-use crate::state::Message;
-use crate::middleware::ToolDefinition;
+use crate::state::{Message, ToolCall};
+use crate::middleware::{ModelRequest, ToolDefinition};
 use super::config::{LLMConfig, TokenUsage};
 
+/// Why the model stopped generating
+///
+/// Providers report this under different names (`finish_reason`,
+/// `stop_reason`, `finishReason`); [`FinishReason::from_provider`]
+/// normalizes the common spellings.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum FinishReason {
+    /// Natural end of the turn or a stop sequence
+    #[default]
+    Stop,
+    /// Output was cut off by the token limit
+    Length,
+    /// The model stopped to call tools
+    ToolCalls,
+    /// Output was blocked or refused by a safety filter
+    ContentFilter,
+    /// Any provider-specific reason not covered above
+    Other(String),
+}
+
+impl FinishReason {
+    /// Map a provider's raw finish reason string
+    pub fn from_provider(reason: &str) -> Self {
+        match reason.to_ascii_lowercase().as_str() {
+            "stop" | "end_turn" | "stop_sequence" | "completed" => Self::Stop,
+            "length" | "max_tokens" | "max_output_tokens" => Self::Length,
+            "tool_calls" | "tool_use" | "function_call" => Self::ToolCalls,
+            "content_filter" | "safety" | "recitation" | "refusal" => Self::ContentFilter,
+            _ => Self::Other(reason.to_string()),
+        }
+    }
+}
+
 /// LLM completion response
 ///
 /// Contains the assistant's response message along with optional
@@ -23,12 +56,29 @@ pub struct LLMResponse {
     pub message: Message,
     /// Token usage statistics (if available from provider)
     pub usage: Option<TokenUsage>,
+    /// Why the model stopped generating
+    pub finish_reason: FinishReason,
+    /// Refusal text, when the provider reports one separately from content
+    pub refusal: Option<String>,
 }
 
 impl LLMResponse {
     /// Create a new response with just a message
+    ///
+    /// The finish reason defaults to `ToolCalls` when the message calls
+    /// tools and `Stop` otherwise.
     pub fn new(message: Message) -> Self {
-        Self { message, usage: None }
+        let finish_reason = if message.has_tool_calls() {
+            FinishReason::ToolCalls
+        } else {
+            FinishReason::Stop
+        };
+        Self {
+            message,
+            usage: None,
+            finish_reason,
+            refusal: None,
+        }
     }
 
     /// Add token usage statistics to the response
@@ -36,6 +86,18 @@ impl LLMResponse {
         self.usage = Some(usage);
         self
     }
+
+    /// Set the reason the model stopped generating
+    pub fn with_finish_reason(mut self, finish_reason: FinishReason) -> Self {
+        self.finish_reason = finish_reason;
+        self
+    }
+
+    /// Attach the provider's refusal text
+    pub fn with_refusal(mut self, refusal: impl Into<String>) -> Self {
+        self.refusal = Some(refusal.into());
+        self
+    }
 }
 
 /// Streaming response chunk
@@ -50,6 +112,24 @@ pub struct MessageChunk {
     pub is_final: bool,
     /// Token usage (typically only in final chunk)
     pub usage: Option<TokenUsage>,
+    /// Tool calls completed in this chunk
+    pub tool_calls: Vec<ToolCall>,
+    /// Partial tool call arguments streamed before the call completes
+    pub tool_call_delta: Option<ToolCallDelta>,
+    /// Why the model stopped (typically only in final chunk)
+    pub finish_reason: Option<FinishReason>,
+}
+
+/// Incremental fragment of a tool call that is still being generated
+///
+/// Lets consumers show a pending tool call before it completes. The full
+/// call, including its name, arrives later in [`MessageChunk::tool_calls`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCallDelta {
+    /// Provider-assigned tool call ID (matches the completed call)
+    pub id: String,
+    /// Raw JSON fragment of the call's arguments
+    pub arguments_delta: String,
 }
 
 /// Streaming response wrapper
@@ -74,11 +154,13 @@ impl LLMResponseStream {
     ///
     /// Useful for providers that don't support streaming or as a fallback.
     pub fn from_complete(response: LLMResponse) -> Self {
-        let content = response.message.content.clone();
         let chunk = MessageChunk {
-            content,
+            content: response.message.content,
             is_final: true,
             usage: response.usage,
+            tool_calls: response.message.tool_calls.unwrap_or_default(),
+            tool_call_delta: None,
+            finish_reason: Some(response.finish_reason),
         };
         Self::new(futures::stream::once(async move { Ok(chunk) }))
     }
@@ -163,6 +245,22 @@ pub trait LLMProvider: Send + Sync {
         Ok(LLMResponseStream::from_complete(response))
     }
 
+    /// Generate completions for several independent requests
+    ///
+    /// Results are returned in request order, one per request, so a failure
+    /// in one request doesn't affect the others. The default implementation
+    /// issues the individual `complete()` calls concurrently. Override for
+    /// providers with a native batch endpoint.
+    async fn complete_batch(
+        &self,
+        requests: &[ModelRequest],
+    ) -> Vec<Result<LLMResponse, DeepAgentError>> {
+        futures::future::join_all(requests.iter().map(|request| {
+            self.complete(&request.messages, &request.tools, request.config.as_ref())
+        }))
+        .await
+    }
+
     /// Provider name for logging/debugging
     fn name(&self) -> &str;
 
@@ -249,6 +347,51 @@ mod tests {
         let _ = stream.into_inner();
     }
 
+    #[tokio::test]
+    async fn test_complete_batch_preserves_order_and_errors() {
+        struct PickyProvider;
+
+        #[async_trait]
+        impl LLMProvider for PickyProvider {
+            async fn complete(
+                &self,
+                messages: &[Message],
+                _tools: &[ToolDefinition],
+                _config: Option<&LLMConfig>,
+            ) -> Result<LLMResponse, DeepAgentError> {
+                let content = &messages[0].content;
+                if content == "fail" {
+                    return Err(DeepAgentError::LlmError("rejected".to_string()));
+                }
+                // Earlier requests finish last
+                let delay = 30 - content.len() as u64 * 5;
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                Ok(LLMResponse::new(Message::assistant(content)))
+            }
+
+            fn name(&self) -> &str {
+                "picky"
+            }
+
+            fn default_model(&self) -> &str {
+                "picky-model"
+            }
+        }
+
+        let requests: Vec<ModelRequest> = ["a", "fail", "abc", "abcde"]
+            .iter()
+            .map(|content| ModelRequest::new(vec![Message::user(content)], vec![]))
+            .collect();
+
+        let results = PickyProvider.complete_batch(&requests).await;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().message.content, "a");
+        assert!(matches!(&results[1], Err(DeepAgentError::LlmError(msg)) if msg == "rejected"));
+        assert_eq!(results[2].as_ref().unwrap().message.content, "abc");
+        assert_eq!(results[3].as_ref().unwrap().message.content, "abcde");
+    }
+
     #[test]
     fn test_llm_response_with_usage() {
         let message = Message::assistant("Hello");
@@ -259,12 +402,43 @@ mod tests {
         assert_eq!(response.usage, Some(usage));
     }
 
+    #[test]
+    fn test_llm_response_default_finish_reason() {
+        let text = LLMResponse::new(Message::assistant("Hello"));
+        assert_eq!(text.finish_reason, FinishReason::Stop);
+
+        let call = ToolCall {
+            id: "call_1".to_string(),
+            name: "think".to_string(),
+            arguments: serde_json::json!({}),
+        };
+        let tool = LLMResponse::new(Message::assistant_with_tool_calls("", vec![call]));
+        assert_eq!(tool.finish_reason, FinishReason::ToolCalls);
+    }
+
+    #[test]
+    fn test_finish_reason_from_provider() {
+        assert_eq!(FinishReason::from_provider("stop"), FinishReason::Stop);
+        assert_eq!(FinishReason::from_provider("end_turn"), FinishReason::Stop);
+        assert_eq!(FinishReason::from_provider("length"), FinishReason::Length);
+        assert_eq!(FinishReason::from_provider("MAX_TOKENS"), FinishReason::Length);
+        assert_eq!(FinishReason::from_provider("tool_use"), FinishReason::ToolCalls);
+        assert_eq!(FinishReason::from_provider("SAFETY"), FinishReason::ContentFilter);
+        assert_eq!(
+            FinishReason::from_provider("pause_turn"),
+            FinishReason::Other("pause_turn".to_string())
+        );
+    }
+
     #[test]
     fn test_message_chunk() {
         let chunk = MessageChunk {
             content: "Hello".to_string(),
             is_final: true,
             usage: Some(TokenUsage::new(5, 3)),
+            tool_calls: Vec::new(),
+            tool_call_delta: None,
+            finish_reason: Some(FinishReason::Stop),
         };
 
         assert_eq!(chunk.content, "Hello");
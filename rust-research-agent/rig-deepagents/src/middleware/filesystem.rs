@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::middleware::{AgentMiddleware, DynTool};
+use crate::middleware::{AgentMiddleware, DynTool, PromptSection};
 use crate::tools::{EditFileTool, GlobTool, GrepTool, LsTool, ReadFileTool, WriteFileTool};
 
 /// Default system prompt for filesystem tools.
@@ -14,21 +14,61 @@ pub const FILESYSTEM_SYSTEM_PROMPT: &str = "## Filesystem tools `ls`, `read_file
 You can access a filesystem with these tools. All file paths must start with `/`.\n\
 - ls: list directory contents (absolute path required)\n\
 - read_file: read file contents with optional pagination (offset/limit)\n\
-- write_file: create a new file (avoid overwriting existing files)\n\
+- write_file: create a new file (set append=true to add to an existing file, overwrite=true to replace it)\n\
 - edit_file: exact string replacement (read the file first)\n\
 - glob: find files by pattern (e.g., \"**/*.rs\")\n\
-- grep: literal text search within files";
+- grep: literal text search within files (set files=\"**/*.rs\" to scan every matching file at once)";
+
+/// Tools exposed by [`FilesystemMiddleware::read_only`].
+pub const READ_ONLY_FILESYSTEM_TOOLS: &[&str] = &["ls", "read_file", "glob", "grep"];
+
+/// Per-tool guidance lines, in the order of [`FILESYSTEM_SYSTEM_PROMPT`].
+const TOOL_GUIDANCE: &[(&str, &str)] = &[
+    ("ls", "list directory contents (absolute path required)"),
+    ("read_file", "read file contents with optional pagination (offset/limit)"),
+    ("write_file", "create a new file (set append=true to add to an existing file, overwrite=true to replace it)"),
+    ("edit_file", "exact string replacement (read the file first)"),
+    ("glob", "find files by pattern (e.g., \"**/*.rs\")"),
+    ("grep", "literal text search within files (set files=\"**/*.rs\" to scan every matching file at once)"),
+];
+
+/// Build the default prompt mentioning only the given tools.
+fn filesystem_prompt(names: &[&str]) -> String {
+    let enabled: Vec<_> = TOOL_GUIDANCE
+        .iter()
+        .filter(|(name, _)| names.contains(name))
+        .collect();
+    let header = enabled
+        .iter()
+        .map(|(name, _)| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut prompt = format!(
+        "## Filesystem tools {}\nYou can access a filesystem with these tools. All file paths must start with `/`.",
+        header
+    );
+    for (name, guidance) in enabled {
+        prompt.push_str(&format!("\n- {}: {}", name, guidance));
+    }
+    prompt
+}
 
 /// Middleware that injects filesystem tools and prompt guidance.
 pub struct FilesystemMiddleware {
     tools: Vec<DynTool>,
     system_prompt: String,
+    /// Whether `system_prompt` is the generated default (rebuilt when tools change)
+    default_prompt: bool,
 }
 
 impl FilesystemMiddleware {
     /// Create a FilesystemMiddleware with default prompt.
     pub fn new() -> Self {
-        Self::with_system_prompt(FILESYSTEM_SYSTEM_PROMPT)
+        Self {
+            default_prompt: true,
+            ..Self::with_system_prompt(FILESYSTEM_SYSTEM_PROMPT)
+        }
     }
 
     /// Create a FilesystemMiddleware with a custom system prompt.
@@ -43,8 +83,38 @@ impl FilesystemMiddleware {
                 Arc::new(GrepTool),
             ],
             system_prompt: prompt.into(),
+            default_prompt: false,
         }
     }
+
+    /// Create a FilesystemMiddleware without `write_file`/`edit_file`.
+    pub fn read_only() -> Self {
+        Self::new().with_tools(READ_ONLY_FILESYSTEM_TOOLS)
+    }
+
+    /// Keep only the named file tools.
+    ///
+    /// The default prompt is rebuilt to mention only the kept tools; a custom
+    /// prompt is left untouched. Unknown names are ignored.
+    pub fn with_tools(mut self, names: &[&str]) -> Self {
+        for name in names {
+            if !TOOL_GUIDANCE.iter().any(|(known, _)| known == name) {
+                tracing::warn!(tool = %name, "Unknown filesystem tool ignored");
+            }
+        }
+        self.tools
+            .retain(|tool| names.contains(&tool.definition().name.as_str()));
+        if self.default_prompt {
+            self.system_prompt = filesystem_prompt(names);
+        }
+        self
+    }
+}
+
+impl Default for FilesystemMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
@@ -64,6 +134,14 @@ impl AgentMiddleware for FilesystemMiddleware {
             format!("{}\n\n{}", prompt, self.system_prompt)
         }
     }
+
+    fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        if self.system_prompt.is_empty() {
+            vec![]
+        } else {
+            vec![PromptSection::new(self.name(), self.system_prompt.clone())]
+        }
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +178,40 @@ mod tests {
         assert!(prompt.contains("Base prompt"));
         assert!(prompt.contains("read_file"));
     }
+
+    #[test]
+    fn test_filesystem_default_prompt_matches_constant() {
+        let all: Vec<&str> = TOOL_GUIDANCE.iter().map(|(name, _)| *name).collect();
+        assert_eq!(filesystem_prompt(&all), FILESYSTEM_SYSTEM_PROMPT);
+    }
+
+    #[test]
+    fn test_filesystem_read_only_tools_and_prompt() {
+        let middleware = FilesystemMiddleware::read_only();
+        let mut names: Vec<_> = middleware
+            .tools()
+            .iter()
+            .map(|tool| tool.definition().name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["glob", "grep", "ls", "read_file"]);
+
+        let prompt = middleware.modify_system_prompt(String::new());
+        assert!(prompt.contains("read_file"));
+        assert!(!prompt.contains("write_file"));
+        assert!(!prompt.contains("edit_file"));
+    }
+
+    #[test]
+    fn test_filesystem_with_tools_keeps_custom_prompt() {
+        let middleware = FilesystemMiddleware::with_system_prompt("Use `ls` only.")
+            .with_tools(&["ls"]);
+        let names: Vec<_> = middleware
+            .tools()
+            .iter()
+            .map(|tool| tool.definition().name)
+            .collect();
+        assert_eq!(names, vec!["ls"]);
+        assert_eq!(middleware.modify_system_prompt("Base".to_string()), "Base\n\nUse `ls` only.");
+    }
 }
@@ -16,14 +16,28 @@
 //! let middleware = HumanInTheLoopMiddleware::new(interrupt_on);
 //! ```
 //!
+//! # Approval Policies
+//!
+//! 도구별로 [`ApprovalPolicy`]를 지정하면 호출마다 자동 승인/승인 요청/자동 거부를
+//! 결정합니다.
+//!
+//! ```rust,ignore
+//! let mut policies = HashMap::new();
+//! policies.insert("read_file".to_string(), ApprovalPolicy::AutoApprove);
+//! policies.insert("shell".to_string(), ApprovalPolicy::RequireApproval);
+//! policies.insert("delete_repo".to_string(), ApprovalPolicy::AutoReject);
+//!
+//! let middleware = HumanInTheLoopMiddleware::from_policies(policies);
+//! ```
+//!
 //! # Interrupt Flow
 //!
 //! 1. LLM이 응답 생성
 //! 2. `after_model` 훅에서 tool_calls 검사
 //! 3. 승인 필요한 도구가 있으면 `ModelControl::Interrupt` 반환
 //! 4. AgentExecutor가 `DeepAgentError::Interrupt` 반환
-//! 5. 외부 시스템이 사용자 결정 수집
-//! 6. 결정과 함께 실행 재개
+//! 5. 외부 시스템이 사용자 결정 수집 (`InterruptRequest::to_resume_token`으로 상태 저장 가능)
+//! 6. `AgentExecutor::resume_from_interrupt`로 결정과 함께 실행 재개
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -36,11 +50,25 @@ use crate::middleware::{
 use crate::runtime::ToolRuntime;
 use crate::state::AgentState;
 
+/// 도구 호출에 대한 승인 정책
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApprovalPolicy {
+    /// 승인 없이 바로 실행
+    AutoApprove,
+    /// 인간 승인을 위해 인터럽트 (기본값)
+    #[default]
+    RequireApproval,
+    /// 실행하지 않고 자동 거부
+    AutoReject,
+}
+
 /// 도구별 인터럽트 설정
 #[derive(Debug, Clone)]
 pub struct InterruptOnConfig {
     /// 인터럽트 활성화 여부
     pub enabled: bool,
+    /// 도구 호출 승인 정책
+    pub policy: ApprovalPolicy,
     /// 허용되는 결정 유형
     pub allowed_decisions: Vec<Decision>,
     /// 설명 생성 함수 (선택)
@@ -51,6 +79,7 @@ impl Default for InterruptOnConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            policy: ApprovalPolicy::RequireApproval,
             allowed_decisions: vec![Decision::Approve, Decision::Reject],
             description_fn: None,
         }
@@ -72,9 +101,8 @@ impl InterruptOnConfig {
     /// 모든 결정 허용 (Approve, Reject, Edit)
     pub fn allow_all() -> Self {
         Self {
-            enabled: true,
             allowed_decisions: vec![Decision::Approve, Decision::Reject, Decision::Edit],
-            description_fn: None,
+            ..Default::default()
         }
     }
 
@@ -83,6 +111,22 @@ impl InterruptOnConfig {
         Self::default()
     }
 
+    /// 승인 정책 설정
+    pub fn with_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 항상 자동 승인
+    pub fn auto_approve() -> Self {
+        Self::default().with_policy(ApprovalPolicy::AutoApprove)
+    }
+
+    /// 항상 자동 거부
+    pub fn auto_reject() -> Self {
+        Self::default().with_policy(ApprovalPolicy::AutoReject)
+    }
+
     /// 설명 생성 함수 설정
     pub fn with_description_fn(mut self, f: fn(&serde_json::Value) -> String) -> Self {
         self.description_fn = Some(f);
@@ -114,6 +158,15 @@ impl HumanInTheLoopMiddleware {
         Self { interrupt_on }
     }
 
+    /// 정책 맵으로부터 생성 (tool_name -> policy)
+    pub fn from_policies(policies: HashMap<String, ApprovalPolicy>) -> Self {
+        let interrupt_on = policies
+            .into_iter()
+            .map(|(name, policy)| (name, InterruptOnConfig::default().with_policy(policy)))
+            .collect();
+        Self { interrupt_on }
+    }
+
     /// 단일 도구에 대한 인터럽트 설정
     pub fn for_tool(tool_name: impl Into<String>) -> Self {
         let mut interrupt_on = HashMap::new();
@@ -130,13 +183,20 @@ impl HumanInTheLoopMiddleware {
         Self { interrupt_on }
     }
 
-    /// 도구가 인터럽트 필요한지 확인
-    fn should_interrupt(&self, tool_name: &str) -> Option<&InterruptOnConfig> {
+    /// 도구에 적용할 설정 조회 (비활성화된 설정은 제외)
+    fn config_for(&self, tool_name: &str) -> Option<&InterruptOnConfig> {
         self.interrupt_on
             .get(tool_name)
             .filter(|c| c.enabled)
     }
 
+    /// 도구에 적용할 승인 정책 (설정 없으면 자동 승인)
+    pub fn policy_for(&self, tool_name: &str) -> ApprovalPolicy {
+        self.config_for(tool_name)
+            .map(|c| c.policy)
+            .unwrap_or(ApprovalPolicy::AutoApprove)
+    }
+
     /// ActionRequest 생성
     fn create_action_request(
         &self,
@@ -180,28 +240,63 @@ impl AgentMiddleware for HumanInTheLoopMiddleware {
 
         let mut action_requests = Vec::new();
         let mut review_configs = Vec::new();
+        let mut rejected = Vec::new();
 
         for tc in tool_calls {
-            if let Some(config) = self.should_interrupt(&tc.name) {
-                let action = self.create_action_request(
-                    &tc.id,
-                    &tc.name,
-                    &tc.arguments,
-                    config,
-                );
-
-                let review = ReviewConfig::new(
-                    tc.name.clone(),
-                    config.allowed_decisions.clone(),
-                );
-
-                action_requests.push(action);
-                review_configs.push(review);
+            let Some(config) = self.config_for(&tc.name) else {
+                continue;
+            };
+
+            match config.policy {
+                ApprovalPolicy::AutoApprove => {}
+                ApprovalPolicy::RequireApproval => {
+                    let action = self.create_action_request(
+                        &tc.id,
+                        &tc.name,
+                        &tc.arguments,
+                        config,
+                    );
+
+                    let review = ReviewConfig::new(
+                        tc.name.clone(),
+                        config.allowed_decisions.clone(),
+                    );
+
+                    action_requests.push(action);
+                    review_configs.push(review);
+                }
+                ApprovalPolicy::AutoReject => rejected.push(tc),
             }
         }
 
         if action_requests.is_empty() {
-            return Ok(ModelControl::Continue);
+            if rejected.is_empty() {
+                return Ok(ModelControl::Continue);
+            }
+
+            tracing::info!(
+                rejected_count = rejected.len(),
+                tools = ?rejected.iter().map(|tc| &tc.name).collect::<Vec<_>>(),
+                "Auto-rejecting tool calls by policy"
+            );
+
+            let rejections = rejected
+                .into_iter()
+                .map(|tc| {
+                    (tc.id.clone(), format!("Tool '{}' was rejected by approval policy", tc.name))
+                })
+                .collect();
+            return Ok(ModelControl::RejectToolCalls(rejections));
+        }
+
+        // 인터럽트가 우선 - 자동 거부 대상은 거부만 허용하는 항목으로 함께 전달
+        for tc in rejected {
+            let description = format!("Tool '{}' is rejected by approval policy.", tc.name);
+            action_requests.push(
+                ActionRequest::new(&tc.id, &tc.name, tc.arguments.clone())
+                    .with_description(description),
+            );
+            review_configs.push(ReviewConfig::new(tc.name.clone(), vec![Decision::Reject]));
         }
 
         tracing::info!(
@@ -406,4 +501,116 @@ mod tests {
             _ => panic!("Expected Interrupt"),
         }
     }
+
+    fn policy_middleware() -> HumanInTheLoopMiddleware {
+        let mut policies = HashMap::new();
+        policies.insert("read_file".to_string(), ApprovalPolicy::AutoApprove);
+        policies.insert("shell".to_string(), ApprovalPolicy::RequireApproval);
+        policies.insert("delete_repo".to_string(), ApprovalPolicy::AutoReject);
+        HumanInTheLoopMiddleware::from_policies(policies)
+    }
+
+    fn tool_call(id: &str, name: &str, arguments: serde_json::Value) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_auto_approve_continues() {
+        let middleware = policy_middleware();
+        let runtime = create_runtime();
+        let state = AgentState::new();
+
+        let response = ModelResponse::new(Message::assistant_with_tool_calls(
+            "",
+            vec![tool_call("call_1", "read_file", serde_json::json!({"path": "/a"}))],
+        ));
+
+        let result = middleware.after_model(&response, &state, &runtime).await.unwrap();
+        assert!(matches!(result, ModelControl::Continue));
+        assert_eq!(middleware.policy_for("read_file"), ApprovalPolicy::AutoApprove);
+    }
+
+    #[tokio::test]
+    async fn test_policy_require_approval_includes_args() {
+        let middleware = policy_middleware();
+        let runtime = create_runtime();
+        let state = AgentState::new();
+
+        let args = serde_json::json!({"command": "ls -la"});
+        let response = ModelResponse::new(Message::assistant_with_tool_calls(
+            "",
+            vec![tool_call("call_1", "shell", args.clone())],
+        ));
+
+        let result = middleware.after_model(&response, &state, &runtime).await.unwrap();
+        match result {
+            ModelControl::Interrupt(req) => {
+                assert_eq!(req.action_requests.len(), 1);
+                assert_eq!(req.action_requests[0].name, "shell");
+                assert_eq!(req.action_requests[0].args, args);
+            }
+            _ => panic!("Expected Interrupt"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_auto_reject_rejects_call() {
+        let middleware = policy_middleware();
+        let runtime = create_runtime();
+        let state = AgentState::new();
+
+        let response = ModelResponse::new(Message::assistant_with_tool_calls(
+            "",
+            vec![
+                tool_call("call_1", "read_file", serde_json::json!({})),
+                tool_call("call_2", "delete_repo", serde_json::json!({"name": "main"})),
+            ],
+        ));
+
+        let result = middleware.after_model(&response, &state, &runtime).await.unwrap();
+        match result {
+            ModelControl::RejectToolCalls(rejections) => {
+                assert_eq!(rejections.len(), 1);
+                assert!(rejections["call_2"].contains("delete_repo"));
+            }
+            _ => panic!("Expected RejectToolCalls"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_mixed_interrupt_limits_rejected_to_reject() {
+        let middleware = policy_middleware();
+        let runtime = create_runtime();
+        let state = AgentState::new();
+
+        let response = ModelResponse::new(Message::assistant_with_tool_calls(
+            "",
+            vec![
+                tool_call("call_1", "shell", serde_json::json!({"command": "make"})),
+                tool_call("call_2", "delete_repo", serde_json::json!({"name": "main"})),
+            ],
+        ));
+
+        let result = middleware.after_model(&response, &state, &runtime).await.unwrap();
+        match result {
+            ModelControl::Interrupt(req) => {
+                assert_eq!(req.action_requests.len(), 2);
+                assert_eq!(req.action_requests[1].id, "call_2");
+                assert_eq!(req.action_requests[1].args, serde_json::json!({"name": "main"}));
+                assert_eq!(req.review_configs[1].allowed_decisions, vec![Decision::Reject]);
+            }
+            _ => panic!("Expected Interrupt"),
+        }
+    }
+
+    #[test]
+    fn test_unconfigured_tool_policy_defaults_to_auto_approve() {
+        let middleware = HumanInTheLoopMiddleware::for_tool("shell");
+        assert_eq!(middleware.policy_for("shell"), ApprovalPolicy::RequireApproval);
+        assert_eq!(middleware.policy_for("grep"), ApprovalPolicy::AutoApprove);
+    }
 }
@@ -21,38 +21,41 @@ pub mod human_in_the_loop;
 // Core traits and types
 pub use traits::{AgentMiddleware, DynTool, Tool, ToolDefinition, ToolRegistry, ToolResult, StateUpdate};
 pub use stack::MiddlewareStack;
-pub use filesystem::{FilesystemMiddleware, FILESYSTEM_SYSTEM_PROMPT};
+pub use filesystem::{FilesystemMiddleware, FILESYSTEM_SYSTEM_PROMPT, READ_ONLY_FILESYSTEM_TOOLS};
 pub use todo_list::{TodoListMiddleware, TODO_SYSTEM_PROMPT};
 
 // Model hook types (Python Parity - NEW)
 pub use traits::{
     ModelRequest, ModelResponse, ModelControl,
-    InterruptRequest, ActionRequest, ReviewConfig, Decision,
+    InterruptRequest, ActionRequest, ReviewConfig, Decision, ResumeToken,
+    PromptSection,
 };
 
 // Summarization middleware
 pub use summarization::{
-    SummarizationMiddleware, SummarizationConfig, SummarizationConfigBuilder,
+    SummarizationMiddleware, SummarizationConfig, SummarizationConfigBuilder, SummaryFormat,
     TriggerCondition, KeepSize,
     count_tokens_approximately, get_chars_per_token, TokenCounterConfig,
-    DEFAULT_CHARS_PER_TOKEN, CLAUDE_CHARS_PER_TOKEN, DEFAULT_SUMMARY_PROMPT,
+    DEFAULT_CHARS_PER_TOKEN, CLAUDE_CHARS_PER_TOKEN, DEFAULT_SUMMARY_PROMPT, DEFAULT_ARCHIVE_DIR,
 };
 
 // SubAgent types
 pub use subagent::{
     SubAgentSpec, SubAgentSpecBuilder, CompiledSubAgent, SubAgentKind,
     SubAgentRegistry, SubAgentResult, IsolatedState, IsolatedStateBuilder,
+    CompiledSubAgentExecutor, SubAgentEvent, SubAgentEventSender, SubAgentEventReceiver,
+    subagent_event_channel,
     EXCLUDED_STATE_KEYS, TASK_SYSTEM_PROMPT,
     // Executor types
     SubAgentExecutorFactory, SubAgentExecutorConfig, DefaultSubAgentExecutorFactory,
-    // Task tool
-    TaskTool, TaskArgs,
+    // Task tools
+    TaskTool, TaskArgs, ParallelTaskTool, ParallelTaskArgs, DEFAULT_MAX_PARALLEL_TASKS,
     // Middleware
     SubAgentMiddleware, SubAgentMiddlewareConfig, SubAgentMiddlewareBuilder,
 };
 
 // PatchToolCalls middleware (Python Parity - NEW)
-pub use patch_tool_calls::PatchToolCallsMiddleware;
+pub use patch_tool_calls::{PatchMode, PatchToolCallsMiddleware};
 
 // HumanInTheLoop middleware (Python Parity - NEW)
-pub use human_in_the_loop::{HumanInTheLoopMiddleware, InterruptOnConfig};
+pub use human_in_the_loop::{ApprovalPolicy, HumanInTheLoopMiddleware, InterruptOnConfig};
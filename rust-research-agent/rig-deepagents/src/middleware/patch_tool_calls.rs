@@ -16,6 +16,9 @@
 //! // 또는 커스텀 취소 메시지와 함께
 //! let middleware = PatchToolCallsMiddleware::new()
 //!     .with_message("도구 호출이 취소되었습니다");
+//! // 체크포인트에서 재개할 때: 고정 플레이스홀더 결과 삽입
+//! let middleware = PatchToolCallsMiddleware::for_resume()
+//!     .with_placeholder("tool result unavailable after resume");
 //! ```
 
 use async_trait::async_trait;
@@ -26,6 +29,19 @@ use crate::middleware::{AgentMiddleware, StateUpdate};
 use crate::runtime::ToolRuntime;
 use crate::state::{AgentState, Message, Role};
 
+/// 재개 모드에서 사용하는 기본 플레이스홀더 텍스트
+pub const DEFAULT_RESUME_PLACEHOLDER: &str = "Tool result unavailable after resume.";
+
+/// 댕글링 도구 호출에 삽입할 합성 ToolMessage의 형태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatchMode {
+    /// 도구 이름/ID와 취소 메시지를 포함한 취소 알림 (기본값)
+    #[default]
+    Cancel,
+    /// 체크포인트 재개용: 플레이스홀더 텍스트만 그대로 삽입
+    ResumePlaceholder,
+}
+
 /// 댕글링 도구 호출을 패치하는 미들웨어
 ///
 /// 에이전트 실행 전에 메시지 히스토리를 검사하여
@@ -34,12 +50,18 @@ use crate::state::{AgentState, Message, Role};
 pub struct PatchToolCallsMiddleware {
     /// 패치된 도구 응답에 사용할 메시지
     cancellation_message: String,
+    /// 합성 메시지 형태
+    mode: PatchMode,
+    /// `PatchMode::ResumePlaceholder`에서 사용할 텍스트
+    placeholder: String,
 }
 
 impl Default for PatchToolCallsMiddleware {
     fn default() -> Self {
         Self {
             cancellation_message: "Tool call was cancelled - another message arrived before completion.".to_string(),
+            mode: PatchMode::Cancel,
+            placeholder: DEFAULT_RESUME_PLACEHOLDER.to_string(),
         }
     }
 }
@@ -50,12 +72,40 @@ impl PatchToolCallsMiddleware {
         Self::default()
     }
 
+    /// 체크포인트 재개용 미들웨어 생성 (`PatchMode::ResumePlaceholder`)
+    pub fn for_resume() -> Self {
+        Self::default().with_mode(PatchMode::ResumePlaceholder)
+    }
+
     /// 커스텀 취소 메시지 설정
     pub fn with_message(mut self, msg: impl Into<String>) -> Self {
         self.cancellation_message = msg.into();
         self
     }
 
+    /// 패치 모드 설정
+    pub fn with_mode(mut self, mode: PatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 재개 플레이스홀더 텍스트 설정
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// 댕글링 도구 호출 하나에 대한 합성 ToolMessage 내용
+    fn patch_content(&self, tool_name: &str, tool_call_id: &str) -> String {
+        match self.mode {
+            PatchMode::Cancel => format!(
+                "Tool call '{}' (ID: {}) was cancelled. {}",
+                tool_name, tool_call_id, self.cancellation_message
+            ),
+            PatchMode::ResumePlaceholder => self.placeholder.clone(),
+        }
+    }
+
     /// 대응하는 ToolMessage가 없는 tool_calls 찾기
     ///
     /// Returns: Vec<(ai_msg_index, tool_call_id, tool_name)>
@@ -129,10 +179,7 @@ impl AgentMiddleware for PatchToolCallsMiddleware {
             // 이 AIMessage 다음에 합성 ToolMessage 삽입
             if let Some(calls) = dangling_by_index.get(&i) {
                 for (tool_call_id, tool_name) in calls {
-                    let content = format!(
-                        "Tool call '{}' (ID: {}) was cancelled. {}",
-                        tool_name, tool_call_id, self.cancellation_message
-                    );
+                    let content = self.patch_content(tool_name, tool_call_id);
                     patched.push(Message::tool(&content, tool_call_id));
 
                     tracing::debug!(
@@ -331,4 +378,35 @@ mod tests {
             panic!("Expected SetMessages");
         }
     }
+
+    #[tokio::test]
+    async fn test_resume_placeholder_for_orphaned_call() {
+        let middleware = PatchToolCallsMiddleware::for_resume()
+            .with_placeholder("tool result unavailable after resume");
+
+        let tool_call = ToolCall {
+            id: "call_orphan".to_string(),
+            name: "search".to_string(),
+            arguments: serde_json::json!({"query": "test"}),
+        };
+
+        // 체크포인트가 도구 실행 전에 저장되어 ToolMessage가 없는 상태
+        let mut state = AgentState::with_messages(vec![
+            Message::user("Search"),
+            Message::assistant_with_tool_calls("", vec![tool_call]),
+        ]);
+
+        let runtime = create_runtime(&state);
+        let result = middleware.before_agent(&mut state, &runtime).await.unwrap();
+
+        if let Some(StateUpdate::SetMessages(msgs)) = result {
+            assert_eq!(msgs.len(), 3);
+            assert_eq!(msgs[2].role, Role::Tool);
+            assert_eq!(msgs[2].tool_call_id.as_deref(), Some("call_orphan"));
+            assert_eq!(msgs[2].content, "tool result unavailable after resume");
+            assert!(PatchToolCallsMiddleware::find_dangling_tool_calls(&msgs).is_empty());
+        } else {
+            panic!("Expected SetMessages");
+        }
+    }
 }
@@ -2,12 +2,30 @@
 //! 미들웨어 스택
 //!
 //! 여러 미들웨어를 조합하여 순차적으로 실행합니다.
+//!
+//! # 실행 순서
+//!
+//! 훅 실행 순서는 등록 순서로 결정되며 항상 동일합니다.
+//!
+//! - `modify_system_prompt`, `before_agent`, `before_model`: 등록 순서 (앞에서 뒤로)
+//! - `after_model`, `after_agent`: 등록 역순 (뒤에서 앞으로)
+//!
+//! 시스템 프롬프트 섹션(`system_prompt_sections`)은 기본적으로 등록 순서를 따르며,
+//! `SystemPromptLayout`으로 섹션 이름 기준의 순서를 지정할 수 있습니다.
+//!
+//! 즉 먼저 등록된 미들웨어가 요청을 가장 먼저 보고 응답을 가장 나중에 봅니다.
+//! 예를 들어 요약 미들웨어가 patch-tool-calls 미들웨어보다 먼저 요청을 수정해야 하면
+//! 먼저 등록하거나 `insert_before`/`insert_after`로 위치를 지정합니다.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use crate::state::AgentState;
+use crate::state::{AgentState, ToolCall};
 use crate::error::MiddlewareError;
-use crate::runtime::ToolRuntime;
-use super::traits::{AgentMiddleware, DynTool, StateUpdate, ModelRequest, ModelResponse, ModelControl};
+use crate::runtime::{SystemPromptLayout, ToolRuntime};
+use super::traits::{
+    AgentMiddleware, DynTool, StateUpdate, ModelRequest, ModelResponse, ModelControl, ToolResult,
+    PromptSection,
+};
 
 /// 미들웨어 스택
 pub struct MiddlewareStack {
@@ -31,6 +49,44 @@ impl MiddlewareStack {
         self
     }
 
+    /// 이름이 `name`인 미들웨어 바로 앞에 삽입
+    ///
+    /// 삽입된 미들웨어의 `before_model`은 대상보다 먼저, `after_model`은 나중에 실행됩니다.
+    pub fn insert_before<M: AgentMiddleware + 'static>(
+        &mut self,
+        name: &str,
+        middleware: M,
+    ) -> Result<(), MiddlewareError> {
+        let index = self.position(name)?;
+        self.middlewares.insert(index, Arc::new(middleware));
+        Ok(())
+    }
+
+    /// 이름이 `name`인 미들웨어 바로 뒤에 삽입
+    ///
+    /// 삽입된 미들웨어의 `before_model`은 대상보다 나중에, `after_model`은 먼저 실행됩니다.
+    pub fn insert_after<M: AgentMiddleware + 'static>(
+        &mut self,
+        name: &str,
+        middleware: M,
+    ) -> Result<(), MiddlewareError> {
+        let index = self.position(name)?;
+        self.middlewares.insert(index + 1, Arc::new(middleware));
+        Ok(())
+    }
+
+    fn position(&self, name: &str) -> Result<usize, MiddlewareError> {
+        self.middlewares
+            .iter()
+            .position(|m| m.name() == name)
+            .ok_or_else(|| MiddlewareError::MiddlewareNotFound(name.to_string()))
+    }
+
+    /// 등록 순서대로의 미들웨어 이름
+    pub fn names(&self) -> Vec<&str> {
+        self.middlewares.iter().map(|m| m.name()).collect()
+    }
+
     /// 미들웨어 개수
     pub fn len(&self) -> usize {
         self.middlewares.len()
@@ -56,6 +112,19 @@ impl MiddlewareStack {
         )
     }
 
+    /// 모든 미들웨어의 시스템 프롬프트 섹션 수집 (등록 순서)
+    pub fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        self.middlewares
+            .iter()
+            .flat_map(|m| m.system_prompt_sections())
+            .collect()
+    }
+
+    /// 레이아웃에 따라 기본 프롬프트와 미들웨어 섹션을 조합
+    pub fn assemble_system_prompt(&self, base: &str, layout: &SystemPromptLayout) -> String {
+        layout.assemble(base, self.system_prompt_sections())
+    }
+
     /// before_agent 훅 실행 (순차)
     pub async fn before_agent(
         &self,
@@ -106,6 +175,7 @@ impl MiddlewareStack {
     /// - `ModelControl::Continue` - 모든 미들웨어가 Continue 반환
     /// - `ModelControl::ModifyRequest` - 마지막 수정된 요청 (request가 이미 수정됨)
     /// - `ModelControl::Skip(resp)` - LLM 호출 건너뛰기
+    /// - `ModelControl::Respond(msg)` - LLM 호출 없이 직접 답변
     /// - `ModelControl::Interrupt(req)` - 실행 인터럽트
     pub async fn before_model(
         &self,
@@ -128,6 +198,14 @@ impl MiddlewareStack {
                     );
                     return Ok(control);
                 }
+                control @ ModelControl::Respond(_) => {
+                    // 미들웨어가 직접 답변 - 이후 미들웨어와 LLM 호출 생략
+                    tracing::info!(
+                        middleware = middleware.name(),
+                        "Middleware responding without model call"
+                    );
+                    return Ok(control);
+                }
                 control @ ModelControl::Interrupt(_) => {
                     // 인터럽트 - 즉시 반환
                     tracing::info!(
@@ -136,6 +214,14 @@ impl MiddlewareStack {
                     );
                     return Ok(control);
                 }
+                // 거부할 도구 호출은 응답이 있어야 의미 있음 - 무시
+                ModelControl::RejectToolCalls(_) => {
+                    tracing::warn!(
+                        middleware = middleware.name(),
+                        "RejectToolCalls ignored in before_model (only valid in after_model)"
+                    );
+                    continue;
+                }
             }
         }
         Ok(ModelControl::Continue)
@@ -150,12 +236,14 @@ impl MiddlewareStack {
     ///
     /// - `ModelControl::Continue` - 모든 미들웨어가 Continue 반환
     /// - `ModelControl::Interrupt(req)` - 인간 승인 대기
+    /// - `ModelControl::RejectToolCalls(map)` - 모든 미들웨어의 거부 목록 병합
     pub async fn after_model(
         &self,
         response: &ModelResponse,
         state: &AgentState,
         runtime: &ToolRuntime,
     ) -> Result<ModelControl, MiddlewareError> {
+        let mut rejections = HashMap::new();
         for middleware in self.middlewares.iter().rev() {
             match middleware.after_model(response, state, runtime).await? {
                 ModelControl::Continue => continue,
@@ -167,17 +255,45 @@ impl MiddlewareStack {
                     );
                     return Ok(control);
                 }
-                // Skip과 ModifyRequest는 after_model에서 의미 없음 - 무시
-                ModelControl::Skip(_) | ModelControl::ModifyRequest(_) => {
+                ModelControl::RejectToolCalls(rejected) => {
+                    // 거부 목록 병합 - 이후 인터럽트가 발생하면 인터럽트 우선
+                    rejections.extend(rejected);
+                }
+                // Skip, Respond, ModifyRequest는 after_model에서 의미 없음 - 무시
+                ModelControl::Skip(_) | ModelControl::Respond(_) | ModelControl::ModifyRequest(_) => {
                     tracing::warn!(
                         middleware = middleware.name(),
-                        "Skip/ModifyRequest ignored in after_model (only valid in before_model)"
+                        "Skip/Respond/ModifyRequest ignored in after_model (only valid in before_model)"
                     );
                     continue;
                 }
             }
         }
-        Ok(ModelControl::Continue)
+        if rejections.is_empty() {
+            Ok(ModelControl::Continue)
+        } else {
+            Ok(ModelControl::RejectToolCalls(rejections))
+        }
+    }
+
+    // =========================================================================
+    // Tool Call Hooks
+    // =========================================================================
+
+    /// after_tool 훅 실행 (역순, 뒤에서 앞으로)
+    ///
+    /// 각 미들웨어가 같은 결과를 차례로 수정합니다.
+    pub async fn after_tool(
+        &self,
+        call: &ToolCall,
+        result: &mut ToolResult,
+        state: &AgentState,
+        runtime: &ToolRuntime,
+    ) -> Result<(), MiddlewareError> {
+        for middleware in self.middlewares.iter().rev() {
+            middleware.after_tool(call, result, state, runtime).await?;
+        }
+        Ok(())
     }
 
     // 상태 업데이트 적용은 StateUpdate::apply에 위임
@@ -229,6 +345,32 @@ mod tests {
         assert!(result.contains("Second addition"));
     }
 
+    #[test]
+    fn test_assemble_system_prompt_follows_layout() {
+        let stack = MiddlewareStack::new()
+            .with_middleware(TestMiddleware {
+                name: "filesystem".to_string(),
+                prompt_addition: "Filesystem rules".to_string()
+            })
+            .with_middleware(TestMiddleware {
+                name: "skills".to_string(),
+                prompt_addition: "Skill list".to_string()
+            })
+            .with_middleware(TestMiddleware {
+                name: "subagent".to_string(),
+                prompt_addition: "Delegate with task".to_string()
+            });
+
+        // 레이아웃이 없으면 기본 프롬프트 뒤에 등록 순서대로
+        let default = stack.assemble_system_prompt("Base", &SystemPromptLayout::default());
+        assert_eq!(default, "Base\n\nFilesystem rules\n\nSkill list\n\nDelegate with task");
+
+        // 나열된 섹션이 먼저, 나열되지 않은 섹션은 등록 순서대로 뒤에
+        let layout = SystemPromptLayout::new(["subagent", SystemPromptLayout::BASE]);
+        let ordered = stack.assemble_system_prompt("Base", &layout);
+        assert_eq!(ordered, "Delegate with task\n\nBase\n\nFilesystem rules\n\nSkill list");
+    }
+
     #[tokio::test]
     async fn test_middleware_stack_hooks() {
         let stack = MiddlewareStack::new()
@@ -260,4 +402,77 @@ mod tests {
         assert_eq!(stack.len(), 2);
         assert!(!stack.is_empty());
     }
+
+    struct RecordingMiddleware {
+        name: &'static str,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl AgentMiddleware for RecordingMiddleware {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn before_model(
+            &self,
+            _request: &mut ModelRequest,
+            _state: &mut AgentState,
+            _runtime: &ToolRuntime,
+        ) -> Result<ModelControl, MiddlewareError> {
+            self.calls.lock().unwrap().push(format!("before:{}", self.name));
+            Ok(ModelControl::Continue)
+        }
+
+        async fn after_model(
+            &self,
+            _response: &ModelResponse,
+            _state: &AgentState,
+            _runtime: &ToolRuntime,
+        ) -> Result<ModelControl, MiddlewareError> {
+            self.calls.lock().unwrap().push(format!("after:{}", self.name));
+            Ok(ModelControl::Continue)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_model_hook_order_with_insertion() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recording = |name| RecordingMiddleware { name, calls: calls.clone() };
+
+        let mut stack = MiddlewareStack::new()
+            .with_middleware(recording("summarization"))
+            .with_middleware(recording("logging"));
+        stack.insert_before("logging", recording("patch_tool_calls")).unwrap();
+        stack.insert_after("logging", recording("hitl")).unwrap();
+        assert!(matches!(
+            stack.insert_after("missing", recording("other")),
+            Err(MiddlewareError::MiddlewareNotFound(_))
+        ));
+        assert_eq!(
+            stack.names(),
+            vec!["summarization", "patch_tool_calls", "logging", "hitl"]
+        );
+
+        let mut state = AgentState::new();
+        let runtime = ToolRuntime::new(state.clone(), Arc::new(MemoryBackend::new()));
+        let mut request = ModelRequest::new(vec![], vec![]);
+        stack.before_model(&mut request, &mut state, &runtime).await.unwrap();
+        let response = ModelResponse::new(crate::state::Message::assistant("done"));
+        stack.after_model(&response, &state, &runtime).await.unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "before:summarization",
+                "before:patch_tool_calls",
+                "before:logging",
+                "before:hitl",
+                "after:hitl",
+                "after:logging",
+                "after:patch_tool_calls",
+                "after:summarization",
+            ]
+        );
+    }
 }
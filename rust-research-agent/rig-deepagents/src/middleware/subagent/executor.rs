@@ -12,20 +12,25 @@
 //!
 //! Python Reference: deepagents/middleware/subagents.py
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use tokio::time::timeout;
 
 use crate::backends::Backend;
 use crate::error::MiddlewareError;
 use crate::executor::AgentExecutor;
-use crate::llm::LLMProvider;
+use crate::llm::{BatchingProvider, LLMProvider};
 use crate::middleware::{AgentMiddleware, MiddlewareStack};
 use crate::runtime::ToolRuntime;
 
-use super::spec::{SubAgentKind, SubAgentResult, SubAgentSpec};
+use super::spec::{
+    subagent_event_channel, CompiledSubAgent, SubAgentEvent, SubAgentKind, SubAgentResult,
+    SubAgentSpec,
+};
 use super::state_isolation::IsolatedState;
 
 /// Factory trait for creating and executing SubAgents
@@ -90,6 +95,13 @@ pub struct SubAgentExecutorConfig {
 
     /// Maximum iterations for subagent execution
     pub max_iterations: usize,
+
+    /// Coalesce LLM calls from concurrently running subagents within this window
+    ///
+    /// When set, subagents sharing a model send their completions through one
+    /// [`BatchingProvider`], so parallel delegation (`task_parallel`) reaches
+    /// the provider as [`LLMProvider::complete_batch`] calls.
+    pub batch_window: Option<Duration>,
 }
 
 impl SubAgentExecutorConfig {
@@ -103,6 +115,7 @@ impl SubAgentExecutorConfig {
             default_middleware: Vec::new(),
             backend,
             max_iterations: 25,  // Reasonable default for subagents
+            batch_window: None,
         }
     }
 
@@ -117,6 +130,12 @@ impl SubAgentExecutorConfig {
         self.max_iterations = max;
         self
     }
+
+    /// Batch LLM calls from subagents that run at the same time
+    pub fn with_batching(mut self, window: Duration) -> Self {
+        self.batch_window = Some(window);
+        self
+    }
 }
 
 /// Default executor factory using AgentExecutor
@@ -125,12 +144,34 @@ impl SubAgentExecutorConfig {
 /// AgentExecutor infrastructure to run subagents.
 pub struct DefaultSubAgentExecutorFactory {
     config: SubAgentExecutorConfig,
+    /// Batching wrappers keyed by the address of the wrapped model
+    batched_models: Mutex<HashMap<usize, Arc<dyn LLMProvider>>>,
 }
 
 impl DefaultSubAgentExecutorFactory {
     /// Create a new factory with the given configuration
     pub fn new(config: SubAgentExecutorConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            batched_models: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Model a subagent runs on, shared through a batching wrapper if enabled
+    fn model_for(&self, spec: &SubAgentSpec) -> Arc<dyn LLMProvider> {
+        let model = spec.model.clone().unwrap_or_else(|| self.config.default_model.clone());
+        let Some(window) = self.config.batch_window else {
+            return model;
+        };
+
+        // Every subagent on the same model must share one wrapper for calls to coalesce
+        let key = Arc::as_ptr(&model) as *const () as usize;
+        self.batched_models
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| Arc::new(BatchingProvider::new(model, window)))
+            .clone()
     }
 
     /// Build middleware stack for a subagent
@@ -159,7 +200,7 @@ impl DefaultSubAgentExecutorFactory {
         runtime: &ToolRuntime,
     ) -> Result<SubAgentResult, MiddlewareError> {
         // Use spec's model or default
-        let model = spec.model.clone().unwrap_or_else(|| self.config.default_model.clone());
+        let model = self.model_for(spec);
 
         // Build middleware stack
         let middleware = self.build_middleware_stack(spec);
@@ -221,6 +262,7 @@ impl DefaultSubAgentExecutorFactory {
             final_message,
             files: result_state.files,
             success: true,
+            usage: result_state.usage,
         })
     }
 }
@@ -240,12 +282,46 @@ impl SubAgentExecutorFactory for DefaultSubAgentExecutorFactory {
             }
             SubAgentKind::Compiled(compiled) => {
                 // For compiled subagents, use their pre-built executor
-                compiled.executor.execute(prompt, state.files).await
+                execute_compiled(compiled, prompt, state, runtime).await
             }
         }
     }
 }
 
+/// Run a compiled subagent, forwarding its progress events to the tool runtime
+///
+/// Events arrive at the orchestrator as tool progress before the final result.
+async fn execute_compiled(
+    compiled: &CompiledSubAgent,
+    prompt: &str,
+    state: IsolatedState,
+    runtime: &ToolRuntime,
+) -> Result<SubAgentResult, MiddlewareError> {
+    // Compiled executors only take a prompt, so structured context travels in front of it
+    let prompt = match state.context_message() {
+        Some(context) => format!("{}\n\n{}", context, prompt),
+        None => prompt.to_string(),
+    };
+    let (sender, mut receiver) = subagent_event_channel();
+    let report = |event: SubAgentEvent| runtime.report_progress(format!("[SubAgent '{}'] {}", compiled.name, event));
+    let execution = compiled.executor.execute_streaming(&prompt, state.files, sender);
+    futures::pin_mut!(execution);
+
+    let result = loop {
+        tokio::select! {
+            result = &mut execution => break result,
+            Some(event) = receiver.next() => report(event),
+        }
+    };
+
+    // Don't wait for the channel to close: the executor may have handed a
+    // sender clone to a task that outlives the run. Forward what is queued.
+    while let Ok(Some(event)) = receiver.try_next() {
+        report(event);
+    }
+    result
+}
+
 /// Mock executor factory for testing
 ///
 /// Returns predefined responses without actually running an agent.
@@ -298,8 +374,9 @@ mod tests {
     use crate::backends::MemoryBackend;
     use crate::llm::LLMResponse;
     use crate::middleware::ToolDefinition;
-    use crate::state::{AgentState, Message};
+    use crate::state::{AgentState, FileData, Message};
     use crate::llm::LLMConfig;
+    use super::super::spec::{CompiledSubAgentExecutor, SubAgentEventSender};
 
     /// Mock LLM for testing
     struct MockLLM {
@@ -395,6 +472,57 @@ mod tests {
         assert!(result.final_message.contains("Research completed"));
     }
 
+    /// Compiled subagent whose sender clone outlives the run
+    struct LeakingSenderExecutor {
+        leaked: Mutex<Option<SubAgentEventSender>>,
+    }
+
+    #[async_trait]
+    impl CompiledSubAgentExecutor for LeakingSenderExecutor {
+        async fn execute(
+            &self,
+            _prompt: &str,
+            _files: HashMap<String, FileData>,
+        ) -> Result<SubAgentResult, MiddlewareError> {
+            Ok(SubAgentResult::success("Done"))
+        }
+
+        async fn execute_streaming(
+            &self,
+            prompt: &str,
+            files: HashMap<String, FileData>,
+            events: SubAgentEventSender,
+        ) -> Result<SubAgentResult, MiddlewareError> {
+            events.unbounded_send(SubAgentEvent::Phase("working".into())).unwrap();
+            *self.leaked.lock().unwrap() = Some(events);
+            self.execute(prompt, files).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compiled_subagent_returns_while_sender_is_alive() {
+        let compiled = CompiledSubAgent::new(
+            "leaky",
+            "Keeps its event sender",
+            Arc::new(LeakingSenderExecutor { leaked: Mutex::new(None) }),
+        );
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let sink = progress.clone();
+        let runtime = ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()))
+            .with_progress(Arc::new(move |message: String| sink.lock().unwrap().push(message)));
+
+        let result = timeout(
+            Duration::from_secs(1),
+            execute_compiled(&compiled, "Go", IsolatedState::new(), &runtime),
+        )
+        .await
+        .expect("execution must not wait for the leaked sender")
+        .unwrap();
+
+        assert_eq!(result.final_message, "Done");
+        assert_eq!(*progress.lock().unwrap(), vec!["[SubAgent 'leaky'] Phase: working"]);
+    }
+
     #[test]
     fn test_executor_config_builder() {
         let mock_llm = Arc::new(MockLLM::new("test"));
@@ -405,4 +533,63 @@ mod tests {
 
         assert_eq!(config.max_iterations, 10);
     }
+
+    /// Mock LLM that records how many requests each batch carried
+    struct BatchCountingLLM {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for BatchCountingLLM {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, crate::error::DeepAgentError> {
+            Ok(LLMResponse::new(Message::assistant("Done")))
+        }
+
+        async fn complete_batch(
+            &self,
+            requests: &[crate::middleware::ModelRequest],
+        ) -> Vec<Result<LLMResponse, crate::error::DeepAgentError>> {
+            self.batches.lock().unwrap().push(requests.len());
+            requests
+                .iter()
+                .map(|_| Ok(LLMResponse::new(Message::assistant("Done"))))
+                .collect()
+        }
+
+        fn name(&self) -> &str {
+            "batch-counting"
+        }
+
+        fn default_model(&self) -> &str {
+            "batch-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_subagents_share_llm_batches() {
+        let llm = Arc::new(BatchCountingLLM {
+            batches: Mutex::new(Vec::new()),
+        });
+        let backend = Arc::new(MemoryBackend::new());
+        let config = SubAgentExecutorConfig::new(llm.clone(), backend.clone())
+            .with_batching(Duration::from_millis(50));
+        let factory = DefaultSubAgentExecutorFactory::new(config);
+
+        let spec = SubAgentKind::Spec(SubAgentSpec::new("researcher", "Research agent"));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let (a, b) = tokio::join!(
+            factory.execute(&spec, "Topic A", IsolatedState::new(), &runtime),
+            factory.execute(&spec, "Topic B", IsolatedState::new(), &runtime),
+        );
+
+        assert_eq!(a.unwrap().final_message, "Done");
+        assert_eq!(b.unwrap().final_message, "Done");
+        assert_eq!(*llm.batches.lock().unwrap(), vec![2]);
+    }
 }
@@ -6,6 +6,7 @@
 //! # Features
 //!
 //! - Injects the `task` tool into the agent's tool set
+//! - Optionally injects the `task_parallel` tool for concurrent fan-out
 //! - Adds system prompt explaining task delegation patterns
 //! - Configurable subagent registry and executor factory
 //!
@@ -30,10 +31,11 @@ use async_trait::async_trait;
 
 use crate::backends::Backend;
 use crate::llm::LLMProvider;
-use crate::middleware::{AgentMiddleware, DynTool};
+use crate::middleware::{AgentMiddleware, DynTool, PromptSection};
 
 use super::executor::{DefaultSubAgentExecutorFactory, SubAgentExecutorConfig};
 use super::spec::{SubAgentKind, SubAgentRegistry};
+use super::parallel_task_tool::ParallelTaskTool;
 use super::task_tool::TaskTool;
 use super::TASK_SYSTEM_PROMPT;
 
@@ -60,6 +62,14 @@ pub struct SubAgentMiddlewareConfig {
 
     /// Default middleware for all subagents
     pub default_middleware: Vec<Arc<dyn AgentMiddleware>>,
+
+    /// Concurrency cap for the `task_parallel` tool (None disables the tool)
+    pub max_parallel_tasks: Option<usize>,
+
+    /// Maximum subagent nesting depth (None relies on the runtime recursion limit only)
+    ///
+    /// Delegation beyond the limit fails with `MiddlewareError::RecursionLimitExceeded`.
+    pub max_recursion_depth: Option<usize>,
 }
 
 impl SubAgentMiddlewareConfig {
@@ -73,6 +83,8 @@ impl SubAgentMiddlewareConfig {
             include_general_purpose: false,
             max_iterations: 25,
             default_middleware: Vec::new(),
+            max_parallel_tasks: None,
+            max_recursion_depth: None,
         }
     }
 
@@ -111,6 +123,18 @@ impl SubAgentMiddlewareConfig {
         self.default_middleware.push(middleware);
         self
     }
+
+    /// Enable the `task_parallel` tool, running at most `max_parallel` subagents at once
+    pub fn with_parallel_tasks(mut self, max_parallel: usize) -> Self {
+        self.max_parallel_tasks = Some(max_parallel);
+        self
+    }
+
+    /// Set the maximum subagent nesting depth
+    pub fn with_max_recursion_depth(mut self, limit: usize) -> Self {
+        self.max_recursion_depth = Some(limit);
+        self
+    }
 }
 
 /// Middleware that provides task delegation to sub-agents
@@ -121,6 +145,9 @@ pub struct SubAgentMiddleware {
     /// The task tool for delegation
     task_tool: Arc<TaskTool>,
 
+    /// The task_parallel tool for concurrent delegation (if enabled)
+    parallel_task_tool: Option<Arc<ParallelTaskTool>>,
+
     /// System prompt addition
     system_prompt: String,
 
@@ -164,8 +191,21 @@ impl SubAgentMiddleware {
         // Create executor factory
         let executor_factory = Arc::new(DefaultSubAgentExecutorFactory::new(executor_config));
 
-        // Create task tool
-        let task_tool = Arc::new(TaskTool::new(Arc::new(registry), executor_factory));
+        // Create task tools
+        let registry = Arc::new(registry);
+        let parallel_task_tool = config.max_parallel_tasks.map(|max_parallel| {
+            let mut tool = ParallelTaskTool::new(registry.clone(), executor_factory.clone())
+                .with_max_parallel(max_parallel);
+            if let Some(limit) = config.max_recursion_depth {
+                tool = tool.with_max_recursion_depth(limit);
+            }
+            Arc::new(tool)
+        });
+        let mut task_tool = TaskTool::new(registry, executor_factory);
+        if let Some(limit) = config.max_recursion_depth {
+            task_tool = task_tool.with_max_recursion_depth(limit);
+        }
+        let task_tool = Arc::new(task_tool);
 
         // Build system prompt
         let system_prompt = config
@@ -174,6 +214,7 @@ impl SubAgentMiddleware {
 
         Self {
             task_tool,
+            parallel_task_tool,
             system_prompt,
             has_subagents,
         }
@@ -201,7 +242,11 @@ impl AgentMiddleware for SubAgentMiddleware {
 
     fn tools(&self) -> Vec<DynTool> {
         if self.has_subagents {
-            vec![self.task_tool.clone()]
+            let mut tools: Vec<DynTool> = vec![self.task_tool.clone()];
+            if let Some(parallel) = &self.parallel_task_tool {
+                tools.push(parallel.clone());
+            }
+            tools
         } else {
             // Don't inject task tool if no subagents are registered
             vec![]
@@ -215,6 +260,14 @@ impl AgentMiddleware for SubAgentMiddleware {
             prompt
         }
     }
+
+    fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        if self.has_subagents {
+            vec![PromptSection::new(self.name(), self.system_prompt.clone())]
+        } else {
+            vec![]
+        }
+    }
 }
 
 /// Builder for SubAgentMiddleware
@@ -254,6 +307,18 @@ impl SubAgentMiddlewareBuilder {
         self
     }
 
+    /// Enable the `task_parallel` tool
+    pub fn with_parallel_tasks(mut self, max_parallel: usize) -> Self {
+        self.config = self.config.with_parallel_tasks(max_parallel);
+        self
+    }
+
+    /// Set the maximum subagent nesting depth
+    pub fn with_max_recursion_depth(mut self, limit: usize) -> Self {
+        self.config = self.config.with_max_recursion_depth(limit);
+        self
+    }
+
     /// Build the middleware
     pub fn build(self) -> SubAgentMiddleware {
         SubAgentMiddleware::new(self.config)
@@ -378,6 +443,206 @@ mod tests {
         assert!(tool_def.description.contains("general-purpose"));
     }
 
+    #[test]
+    fn test_middleware_with_parallel_tasks() {
+        use super::super::spec::SubAgentSpec;
+
+        let middleware = SubAgentMiddleware::new(
+            create_test_config()
+                .with_subagent(SubAgentKind::Spec(SubAgentSpec::new("researcher", "Research")))
+                .with_parallel_tasks(2),
+        );
+
+        let names: Vec<String> = middleware.tools().iter().map(|t| t.definition().name).collect();
+        assert_eq!(names, vec!["task", "task_parallel"]);
+    }
+
+    /// Mock LLM that replays scripted responses and records every prompt it sees
+    struct ScriptedLLM {
+        responses: Vec<Message>,
+        calls: std::sync::Mutex<Vec<Vec<Message>>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for ScriptedLLM {
+        async fn complete(
+            &self,
+            messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, crate::error::DeepAgentError> {
+            let mut calls = self.calls.lock().unwrap();
+            let response = self
+                .responses
+                .get(calls.len())
+                .cloned()
+                .unwrap_or_else(|| Message::assistant("Done"));
+            calls.push(messages.to_vec());
+            Ok(LLMResponse::new(response))
+        }
+
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn default_model(&self) -> &str {
+            "scripted-model"
+        }
+    }
+
+    fn task_call(id: &str, subagent_type: &str) -> Message {
+        Message::assistant_with_tool_calls(
+            "",
+            vec![crate::state::ToolCall {
+                id: id.to_string(),
+                name: "task".to_string(),
+                arguments: serde_json::json!({
+                    "subagent_type": subagent_type,
+                    "description": "Delegate further"
+                }),
+            }],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_nested_delegation_past_max_recursion_depth() {
+        use super::super::spec::SubAgentSpec;
+        use crate::executor::AgentExecutor;
+        use crate::state::{AgentState, Role};
+
+        let llm = Arc::new(ScriptedLLM {
+            responses: vec![
+                task_call("call_top", "delegator"),  // main agent (depth 0)
+                task_call("call_nested", "leaf"),    // delegator subagent (depth 1)
+                Message::assistant("Delegator done"),
+                Message::assistant("Top done"),
+            ],
+            calls: std::sync::Mutex::new(Vec::new()),
+        });
+        let backend = Arc::new(MemoryBackend::new());
+
+        // Middleware used inside the delegator subagent
+        let inner = SubAgentMiddleware::new(
+            SubAgentMiddlewareConfig::new(llm.clone(), backend.clone())
+                .with_subagent(SubAgentKind::Spec(SubAgentSpec::new("leaf", "Leaf agent")))
+                .with_max_recursion_depth(1),
+        );
+        let delegator = SubAgentSpec::builder("delegator")
+            .description("Delegates again")
+            .middleware(Arc::new(inner))
+            .build();
+        let outer = SubAgentMiddleware::new(
+            SubAgentMiddlewareConfig::new(llm.clone(), backend.clone())
+                .with_subagent(SubAgentKind::Spec(delegator))
+                .with_max_recursion_depth(1),
+        );
+
+        let executor = AgentExecutor::new(
+            llm.clone(),
+            crate::middleware::MiddlewareStack::new().with_middleware(outer),
+            backend,
+        );
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("Start")]))
+            .await
+            .unwrap();
+
+        assert_eq!(result.last_assistant_message().unwrap().content, "Top done");
+
+        // The delegator's nested task call was refused with the depth limit error
+        let calls = llm.calls.lock().unwrap();
+        let nested_result = calls[2]
+            .iter()
+            .find(|m| m.role == Role::Tool)
+            .expect("delegator should see its task result");
+        assert!(nested_result.content.contains("recursion depth limit (1) exceeded"));
+
+        // The main agent's delegation itself succeeded
+        let top_result = calls[3].iter().find(|m| m.role == Role::Tool).unwrap();
+        assert!(top_result.content.contains("[SubAgent 'delegator' completed]"));
+    }
+
+    /// Compiled subagent that reports progress before finishing
+    struct StreamingResearcher;
+
+    #[async_trait]
+    impl super::super::spec::CompiledSubAgentExecutor for StreamingResearcher {
+        async fn execute(
+            &self,
+            _prompt: &str,
+            _files: std::collections::HashMap<String, crate::state::FileData>,
+        ) -> Result<super::super::spec::SubAgentResult, crate::error::MiddlewareError> {
+            Ok(super::super::spec::SubAgentResult::success("Final report"))
+        }
+
+        async fn execute_streaming(
+            &self,
+            prompt: &str,
+            files: std::collections::HashMap<String, crate::state::FileData>,
+            events: super::super::spec::SubAgentEventSender,
+        ) -> Result<super::super::spec::SubAgentResult, crate::error::MiddlewareError> {
+            use super::super::spec::SubAgentEvent;
+
+            events.unbounded_send(SubAgentEvent::Phase("searching".into())).unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            events.unbounded_send(SubAgentEvent::PartialFinding("first source".into())).unwrap();
+            self.execute(prompt, files).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compiled_subagent_progress_streams_before_result() {
+        use super::super::spec::CompiledSubAgent;
+        use crate::executor::{AgentExecutor, ExecutorEvent};
+        use crate::state::AgentState;
+        use futures::StreamExt;
+
+        let llm = Arc::new(ScriptedLLM {
+            responses: vec![task_call("call_1", "researcher"), Message::assistant("Done")],
+            calls: std::sync::Mutex::new(Vec::new()),
+        });
+        let backend = Arc::new(MemoryBackend::new());
+        let middleware = SubAgentMiddleware::new(
+            SubAgentMiddlewareConfig::new(llm.clone(), backend.clone()).with_subagent(
+                SubAgentKind::Compiled(CompiledSubAgent::new(
+                    "researcher",
+                    "Streaming researcher",
+                    Arc::new(StreamingResearcher),
+                )),
+            ),
+        );
+        let executor = AgentExecutor::new(
+            llm,
+            crate::middleware::MiddlewareStack::new().with_middleware(middleware),
+            backend,
+        );
+
+        let events: Vec<ExecutorEvent> = executor
+            .run_streaming(AgentState::with_messages(vec![Message::user("Research")]))
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        let tool_events: Vec<(&str, &str)> = events
+            .iter()
+            .filter_map(|event| match event {
+                ExecutorEvent::ToolProgress { tool_call_id, content, .. } => {
+                    Some(("progress", content.as_str())).filter(|_| tool_call_id == "call_1")
+                }
+                ExecutorEvent::ToolResult { tool_call_id, content, .. } => {
+                    Some(("result", content.as_str())).filter(|_| tool_call_id == "call_1")
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(tool_events.len(), 3);
+        assert_eq!(tool_events[0], ("progress", "[SubAgent 'researcher'] Phase: searching"));
+        assert_eq!(tool_events[1], ("progress", "[SubAgent 'researcher'] Finding: first source"));
+        assert_eq!(tool_events[2].0, "result");
+        assert!(tool_events[2].1.contains("Final report"));
+    }
+
     #[test]
     fn test_middleware_name() {
         let config = create_test_config();
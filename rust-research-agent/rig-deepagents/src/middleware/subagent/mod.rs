@@ -28,6 +28,7 @@
 //! - [`CompiledSubAgent`]: Pre-compiled subagents ready for execution
 //! - [`SubAgentRegistry`]: Registry for looking up subagents by name
 //! - [`IsolatedState`]: State isolation for subagent contexts
+//! - [`ParallelTaskTool`]: Concurrent fan-out of independent tasks (`task_parallel`)
 //!
 //! # Example
 //!
@@ -72,18 +73,21 @@ pub mod spec;
 pub mod state_isolation;
 pub mod executor;
 pub mod task_tool;
+pub mod parallel_task_tool;
 pub mod middleware;
 
 // Re-export main types
 pub use spec::{
     CompiledSubAgent, CompiledSubAgentExecutor, SubAgentKind, SubAgentRegistry, SubAgentResult,
-    SubAgentSpec, SubAgentSpecBuilder,
+    SubAgentSpec, SubAgentSpecBuilder, SubAgentEvent, SubAgentEventSender, SubAgentEventReceiver,
+    subagent_event_channel,
 };
 pub use state_isolation::{IsolatedState, IsolatedStateBuilder, EXCLUDED_STATE_KEYS};
 pub use executor::{
     SubAgentExecutorFactory, SubAgentExecutorConfig, DefaultSubAgentExecutorFactory,
 };
 pub use task_tool::{TaskTool, TaskArgs};
+pub use parallel_task_tool::{ParallelTaskTool, ParallelTaskArgs, DEFAULT_MAX_PARALLEL_TASKS};
 pub use middleware::{SubAgentMiddleware, SubAgentMiddlewareConfig, SubAgentMiddlewareBuilder};
 
 /// System prompt addition for task tool usage
@@ -107,7 +111,8 @@ Use sub-agents when:
 
 1. **Bias toward single sub-agent**: Most tasks should use one sub-agent at a time
 2. **Parallelize only when beneficial**: Use multiple simultaneous tasks only for
-   clearly independent work (e.g., comparing two topics)
+   clearly independent work (e.g., comparing two topics). When the `task_parallel`
+   tool is available, pass such tasks to it in a single call
 3. **Provide clear descriptions**: Sub-agents only see your task description,
    not the full conversation history
 
@@ -0,0 +1,391 @@
+//! ParallelTaskTool implementation for concurrent SubAgent fan-out
+//!
+//! This module provides the `task_parallel` tool, which delegates several
+//! independent tasks to sub-agents at once (e.g., researching two topics
+//! that will later be compared).
+//!
+//! # How It Works
+//!
+//! 1. Agent calls `task_parallel(tasks=[{subagent_type, description}, ...])`
+//! 2. All subagent types are validated before anything runs
+//! 3. The whole batch is counted against the recursion depth budget
+//! 4. Each task runs in its own `IsolatedState`, at most `max_parallel` at a time
+//! 5. Results are joined in the order the tasks were given
+//!
+//! With [`SubAgentExecutorConfig::with_batching`](super::SubAgentExecutorConfig::with_batching),
+//! the concurrently running subagents' LLM calls reach the provider through
+//! `LLMProvider::complete_batch` instead of as separate requests.
+//!
+//! # Recursion Budget
+//!
+//! A batch of `n` tasks consumes `n` levels of the recursion budget, so
+//! fanning out cannot be used to multiply the number of nested delegations
+//! beyond what `max_recursion` allows for sequential `task` calls.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::MiddlewareError;
+use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
+use crate::runtime::ToolRuntime;
+
+use super::executor::SubAgentExecutorFactory;
+use super::spec::SubAgentRegistry;
+use super::state_isolation::IsolatedState;
+use super::task_tool::TaskArgs;
+
+/// Default number of subagents run concurrently by `task_parallel`
+pub const DEFAULT_MAX_PARALLEL_TASKS: usize = 4;
+
+/// Separator placed between individual task results
+const RESULT_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Arguments for the task_parallel tool
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ParallelTaskArgs {
+    /// Tasks to delegate concurrently
+    pub tasks: Vec<TaskArgs>,
+}
+
+/// Tool that delegates multiple independent tasks to sub-agents concurrently
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let tool = ParallelTaskTool::new(registry, executor_factory).with_max_parallel(2);
+///
+/// // In agent's tool call:
+/// // task_parallel(tasks=[
+/// //     {"subagent_type": "researcher", "description": "Research topic A"},
+/// //     {"subagent_type": "researcher", "description": "Research topic B"},
+/// // ])
+/// ```
+pub struct ParallelTaskTool {
+    /// Registry of available sub-agents
+    registry: Arc<SubAgentRegistry>,
+
+    /// Factory for executing sub-agents
+    executor_factory: Arc<dyn SubAgentExecutorFactory>,
+
+    /// Maximum number of subagents running at the same time
+    max_parallel: usize,
+
+    /// Maximum subagent nesting depth (None relies on the runtime recursion limit only)
+    max_recursion_depth: Option<usize>,
+}
+
+impl ParallelTaskTool {
+    /// Create a new ParallelTaskTool
+    pub fn new(
+        registry: Arc<SubAgentRegistry>,
+        executor_factory: Arc<dyn SubAgentExecutorFactory>,
+    ) -> Self {
+        Self {
+            registry,
+            executor_factory,
+            max_parallel: DEFAULT_MAX_PARALLEL_TASKS,
+            max_recursion_depth: None,
+        }
+    }
+
+    /// Limit how deeply subagents may delegate (the batch counts as a whole)
+    pub fn with_max_recursion_depth(mut self, limit: usize) -> Self {
+        self.max_recursion_depth = Some(limit);
+        self
+    }
+
+    /// Set the maximum number of subagents running concurrently (minimum 1)
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel.max(1);
+        self
+    }
+
+    /// Generate JSON schema for parameters
+    fn generate_parameters_schema(&self) -> serde_json::Value {
+        let mut subagent_type = serde_json::json!({
+            "type": "string",
+            "description": "The type of sub-agent to use for this task"
+        });
+        let agent_names = self.registry.agent_names();
+        if !agent_names.is_empty() {
+            subagent_type["enum"] = serde_json::json!(agent_names);
+        }
+
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tasks": {
+                    "type": "array",
+                    "description": "Independent tasks to run concurrently",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "subagent_type": subagent_type,
+                            "description": {
+                                "type": "string",
+                                "description": "Detailed task description for the sub-agent"
+                            },
+                            "context": {
+                                "type": "object",
+                                "description": "Optional structured context for the sub-agent"
+                            }
+                        },
+                        "required": ["subagent_type", "description"]
+                    }
+                }
+            },
+            "required": ["tasks"]
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for ParallelTaskTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "task_parallel".to_string(),
+            description: format!(
+                "Delegate several independent tasks to sub-agents at once. Tasks run \
+                 concurrently (up to {} at a time) and their results are returned in \
+                 the order given.\n\n{}",
+                self.max_parallel,
+                self.registry.format_descriptions()
+            ),
+            parameters: self.generate_parameters_schema(),
+            examples: Vec::new(),
+        }
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        let args: ParallelTaskArgs = serde_json::from_value(args).map_err(|e| {
+            MiddlewareError::ToolExecution(format!("Invalid task_parallel arguments: {}", e))
+        })?;
+
+        if args.tasks.is_empty() {
+            return Err(MiddlewareError::ToolExecution(
+                "task_parallel requires at least one task".to_string(),
+            ));
+        }
+
+        // The whole batch is charged against the depth budget
+        let batch_size = args.tasks.len();
+        let config = runtime.config();
+        if config.current_recursion + batch_size > config.max_recursion {
+            tracing::warn!(
+                current = config.current_recursion,
+                max = config.max_recursion,
+                batch_size,
+                "Recursion limit exceeded by parallel batch"
+            );
+            return Err(MiddlewareError::RecursionLimit(format!(
+                "Maximum recursion depth ({}) exceeded. Cannot delegate a batch of {} tasks at depth {}.",
+                config.max_recursion, batch_size, config.current_recursion
+            )));
+        }
+
+        // Validate every subagent before launching any of them
+        let subagents = args
+            .tasks
+            .iter()
+            .map(|task| {
+                self.registry.get(&task.subagent_type).ok_or_else(|| {
+                    MiddlewareError::SubAgentNotFound(format!(
+                        "Unknown sub-agent type: '{}'. Available: {:?}",
+                        task.subagent_type,
+                        self.registry.agent_names()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut child_config = config.clone();
+        child_config.current_recursion += batch_size;
+        let child_runtime = runtime.with_increased_recursion().with_config(child_config);
+        let depth = child_runtime.config().current_recursion;
+
+        if let Some(limit) = self.max_recursion_depth {
+            if depth > limit {
+                tracing::warn!(depth, limit, batch_size, "SubAgent recursion depth limit exceeded");
+                return Err(MiddlewareError::RecursionLimitExceeded { limit });
+            }
+        }
+
+        tracing::info!(
+            batch_size,
+            max_parallel = self.max_parallel,
+            recursion_depth = child_runtime.config().current_recursion,
+            "Executing task_parallel tool"
+        );
+
+        let executions: Vec<_> = args
+            .tasks
+            .iter()
+            .zip(subagents)
+            .map(|(task, subagent)| {
+                let isolated_state = IsolatedState::from_parent(runtime.state())
+                    .with_depth(depth)
+                    .with_context(task.context.clone());
+                self.executor_factory
+                    .execute(subagent, &task.description, isolated_state, &child_runtime)
+            })
+            .collect();
+
+        // `buffered` keeps results in task order regardless of completion order
+        let results: Vec<_> = futures::stream::iter(executions)
+            .buffered(self.max_parallel)
+            .collect()
+            .await;
+
+        let mut sections = Vec::with_capacity(batch_size);
+        let mut updates = Vec::new();
+        for (task, result) in args.tasks.iter().zip(results) {
+            match result {
+                Ok(result) => {
+                    let status = if result.success { "completed" } else { "failed" };
+                    sections.push(format!(
+                        "[SubAgent '{}' {}]\n\n{}",
+                        task.subagent_type, status, result.final_message
+                    ));
+                    updates.push(StateUpdate::RecordUsage(result.usage));
+                }
+                Err(e) => {
+                    tracing::warn!(subagent_type = %task.subagent_type, error = %e, "Parallel subagent failed");
+                    sections.push(format!(
+                        "[SubAgent '{}' failed]\n\nError: {}",
+                        task.subagent_type, e
+                    ));
+                }
+            }
+        }
+
+        Ok(ToolResult::new(sections.join(RESULT_SEPARATOR)).with_updates(updates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::backends::MemoryBackend;
+    use crate::middleware::subagent::spec::{SubAgentKind, SubAgentResult, SubAgentSpec};
+    use crate::runtime::RuntimeConfig;
+    use crate::state::AgentState;
+
+    /// Mock factory that records how many subagents run at the same time
+    #[derive(Default)]
+    struct ConcurrencyTrackingFactory {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SubAgentExecutorFactory for ConcurrencyTrackingFactory {
+        async fn execute(
+            &self,
+            subagent: &SubAgentKind,
+            prompt: &str,
+            _state: IsolatedState,
+            _runtime: &ToolRuntime,
+        ) -> Result<SubAgentResult, MiddlewareError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+
+            // The first subagent finishes last to check result ordering
+            let delay = if subagent.name() == "researcher" { 80 } else { 20 };
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(SubAgentResult::success(format!("{} done", prompt)))
+        }
+    }
+
+    fn create_test_registry() -> Arc<SubAgentRegistry> {
+        Arc::new(
+            SubAgentRegistry::new()
+                .with_agent(SubAgentKind::Spec(SubAgentSpec::new("researcher", "Research")))
+                .with_agent(SubAgentKind::Spec(SubAgentSpec::new("critic", "Critique"))),
+        )
+    }
+
+    fn two_tasks() -> serde_json::Value {
+        serde_json::json!({
+            "tasks": [
+                {"subagent_type": "researcher", "description": "Topic A"},
+                {"subagent_type": "critic", "description": "Topic B"}
+            ]
+        })
+    }
+
+    fn create_test_runtime() -> ToolRuntime {
+        ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()))
+    }
+
+    #[tokio::test]
+    async fn test_parallel_tasks_run_concurrently_in_stable_order() {
+        let factory = Arc::new(ConcurrencyTrackingFactory::default());
+        let tool = ParallelTaskTool::new(create_test_registry(), factory.clone());
+
+        let result = tool.execute(two_tasks(), &create_test_runtime()).await.unwrap();
+
+        assert_eq!(factory.peak.load(Ordering::SeqCst), 2);
+        let sections: Vec<&str> = result.message.split(RESULT_SEPARATOR).collect();
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].contains("[SubAgent 'researcher' completed]"));
+        assert!(sections[0].contains("Topic A done"));
+        assert!(sections[1].contains("[SubAgent 'critic' completed]"));
+        assert_eq!(result.updates.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_tasks_respect_max_parallel() {
+        let factory = Arc::new(ConcurrencyTrackingFactory::default());
+        let tool = ParallelTaskTool::new(create_test_registry(), factory.clone()).with_max_parallel(1);
+
+        tool.execute(two_tasks(), &create_test_runtime()).await.unwrap();
+
+        assert_eq!(factory.peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_batch_counts_against_recursion_budget() {
+        let factory = Arc::new(ConcurrencyTrackingFactory::default());
+        let tool = ParallelTaskTool::new(create_test_registry(), factory.clone());
+
+        // One level of budget left: a single task fits, a batch of two does not
+        let runtime = ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()))
+            .with_config(RuntimeConfig::with_max_recursion(2))
+            .with_increased_recursion();
+
+        let result = tool.execute(two_tasks(), &runtime).await;
+
+        assert!(matches!(result, Err(MiddlewareError::RecursionLimit(_))));
+        assert_eq!(factory.peak.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_unknown_agent_launches_nothing() {
+        let factory = Arc::new(ConcurrencyTrackingFactory::default());
+        let tool = ParallelTaskTool::new(create_test_registry(), factory.clone());
+
+        let args = serde_json::json!({
+            "tasks": [
+                {"subagent_type": "researcher", "description": "Topic A"},
+                {"subagent_type": "unknown", "description": "Topic B"}
+            ]
+        });
+        let result = tool.execute(args, &create_test_runtime()).await;
+
+        assert!(matches!(result, Err(MiddlewareError::SubAgentNotFound(_))));
+        assert_eq!(factory.peak.load(Ordering::SeqCst), 0);
+    }
+}
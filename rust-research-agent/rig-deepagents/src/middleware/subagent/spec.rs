@@ -6,9 +6,12 @@
 //! Python Reference: deepagents/middleware/subagents.py
 
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+
 use crate::llm::LLMProvider;
 use crate::middleware::{AgentMiddleware, DynTool};
 
@@ -162,6 +165,53 @@ pub trait CompiledSubAgentExecutor: Send + Sync {
         prompt: &str,
         files: HashMap<String, crate::state::FileData>,
     ) -> Result<SubAgentResult, crate::error::MiddlewareError>;
+
+    /// Execute the subagent, emitting progress events before the final result
+    ///
+    /// Events sent on `events` are forwarded by the task tool to the
+    /// orchestrator as tool progress updates while the run is in flight.
+    /// The default implementation emits nothing and delegates to [`execute`](Self::execute).
+    async fn execute_streaming(
+        &self,
+        prompt: &str,
+        files: HashMap<String, crate::state::FileData>,
+        events: SubAgentEventSender,
+    ) -> Result<SubAgentResult, crate::error::MiddlewareError> {
+        drop(events);
+        self.execute(prompt, files).await
+    }
+}
+
+/// Incremental update from a running subagent
+///
+/// The streaming counterpart of [`SubAgentResult`]: zero or more events are
+/// emitted during the run, followed by the final result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubAgentEvent {
+    /// The subagent moved to a new phase (e.g., "searching", "synthesizing")
+    Phase(String),
+    /// A partial finding available before the run completes
+    PartialFinding(String),
+}
+
+impl fmt::Display for SubAgentEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Phase(phase) => write!(f, "Phase: {}", phase),
+            Self::PartialFinding(finding) => write!(f, "Finding: {}", finding),
+        }
+    }
+}
+
+/// Sending half of a subagent progress channel
+pub type SubAgentEventSender = UnboundedSender<SubAgentEvent>;
+
+/// Receiving half of a subagent progress channel
+pub type SubAgentEventReceiver = UnboundedReceiver<SubAgentEvent>;
+
+/// Create a subagent progress channel
+pub fn subagent_event_channel() -> (SubAgentEventSender, SubAgentEventReceiver) {
+    futures::channel::mpsc::unbounded()
 }
 
 /// Pre-compiled subagent (for CompiledSubAgent pattern)
@@ -226,6 +276,9 @@ pub struct SubAgentResult {
 
     /// Whether the subagent completed successfully
     pub success: bool,
+
+    /// LLM token usage of the subagent run
+    pub usage: crate::llm::RunUsage,
 }
 
 impl SubAgentResult {
@@ -235,6 +288,7 @@ impl SubAgentResult {
             final_message: message.into(),
             files: HashMap::new(),
             success: true,
+            usage: Default::default(),
         }
     }
 
@@ -244,6 +298,7 @@ impl SubAgentResult {
             final_message: message.into(),
             files: HashMap::new(),
             success: false,
+            usage: Default::default(),
         }
     }
 
@@ -252,6 +307,12 @@ impl SubAgentResult {
         self.files = files;
         self
     }
+
+    /// Attach the subagent's token usage
+    pub fn with_usage(mut self, usage: crate::llm::RunUsage) -> Self {
+        self.usage = usage;
+        self
+    }
 }
 
 /// Unified SubAgent type (either spec or compiled)
@@ -10,6 +10,12 @@
 //!
 //! But it SHOULD see:
 //! - Parent's files (shared filesystem context)
+//! - Structured context explicitly handed over by the delegating agent
+//!
+//! The exclusion filter applies to the *parent's* state only. Context passed
+//! through `task(context=...)` is attached after filtering and delivered as a
+//! system message ahead of the task prompt, so it always reaches the subagent
+//! even though the parent's `messages` are excluded.
 //!
 //! Python Reference: deepagents/middleware/subagents.py (_EXCLUDED_STATE_KEYS)
 
@@ -34,6 +40,15 @@ pub const EXCLUDED_STATE_KEYS: &[&str] = &["messages", "todos", "structured_resp
 pub struct IsolatedState {
     /// Files carried over from parent (shared context)
     pub files: HashMap<String, FileData>,
+
+    /// Nesting depth of the subagent receiving this state (1 = direct child of the main agent)
+    pub depth: usize,
+
+    /// Structured context supplied by the delegating agent (files of interest, prior findings)
+    ///
+    /// Not subject to [`EXCLUDED_STATE_KEYS`]: it is chosen explicitly per task
+    /// rather than inherited from the parent state.
+    pub context: Option<serde_json::Value>,
 }
 
 impl IsolatedState {
@@ -58,13 +73,41 @@ impl IsolatedState {
     pub fn from_parent(parent: &AgentState) -> Self {
         Self {
             files: parent.files.clone(),
+            depth: 0,
+            context: None,
         }
     }
 
+    /// Set the nesting depth of the subagent receiving this state
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Attach structured context for the subagent
+    ///
+    /// A JSON `null` is treated as no context.
+    pub fn with_context(mut self, context: Option<serde_json::Value>) -> Self {
+        self.context = context.filter(|value| !value.is_null());
+        self
+    }
+
+    /// Render the structured context as the system message handed to the subagent
+    pub fn context_message(&self) -> Option<String> {
+        let context = self.context.as_ref()?;
+        let rendered =
+            serde_json::to_string_pretty(context).unwrap_or_else(|_| context.to_string());
+        Some(format!(
+            "Context provided by the delegating agent:\n```json\n{}\n```",
+            rendered
+        ))
+    }
+
     /// Convert to AgentState for subagent execution
     ///
     /// Creates a new AgentState with:
-    /// - A single HumanMessage containing the task prompt
+    /// - A system message carrying the structured context, if any
+    /// - A HumanMessage containing the task prompt
     /// - Files from the isolated state
     /// - Empty todos and no structured response
     ///
@@ -81,7 +124,13 @@ impl IsolatedState {
     /// // subagent_state.messages = [HumanMessage("Research quantum computing")]
     /// ```
     pub fn to_agent_state(self, prompt: &str) -> AgentState {
-        let mut state = AgentState::with_messages(vec![Message::user(prompt)]);
+        let mut messages = Vec::with_capacity(2);
+        if let Some(context) = self.context_message() {
+            messages.push(Message::system(&context));
+        }
+        messages.push(Message::user(prompt));
+
+        let mut state = AgentState::with_messages(messages);
         state.files = self.files;
         state
     }
@@ -160,6 +209,8 @@ impl IsolatedStateBuilder {
         Self {
             state: IsolatedState {
                 files: parent.files.clone(),
+                depth: 0,
+                context: None,
             },
             include_files: true,
         }
@@ -184,6 +235,12 @@ impl IsolatedStateBuilder {
         self
     }
 
+    /// Attach structured context for the subagent
+    pub fn with_context(mut self, context: serde_json::Value) -> Self {
+        self.state = self.state.with_context(Some(context));
+        self
+    }
+
     /// Build the isolated state
     pub fn build(self) -> IsolatedState {
         self.state
@@ -346,4 +403,31 @@ mod tests {
         assert_eq!(state.messages.len(), 1);
         assert!(state.files.is_empty());
     }
+
+    #[test]
+    fn test_to_agent_state_with_context() {
+        let parent = create_test_parent_state();
+        let isolated = IsolatedState::from_parent(&parent).with_context(Some(serde_json::json!({
+            "files": ["/notes.md"],
+            "finding": "Qubits decohere quickly"
+        })));
+
+        let subagent_state = isolated.to_agent_state("Verify the finding");
+
+        // Context system message precedes the task prompt; parent messages stay excluded
+        assert_eq!(subagent_state.messages.len(), 2);
+        assert_eq!(subagent_state.messages[0].role, crate::state::Role::System);
+        assert!(subagent_state.messages[0]
+            .content
+            .contains("Qubits decohere quickly"));
+        assert_eq!(subagent_state.messages[1].content, "Verify the finding");
+        assert!(subagent_state.todos.is_empty());
+    }
+
+    #[test]
+    fn test_null_context_is_ignored() {
+        let isolated = IsolatedState::new().with_context(Some(serde_json::Value::Null));
+        assert!(isolated.context.is_none());
+        assert_eq!(isolated.to_agent_state("Test prompt").messages.len(), 1);
+    }
 }
@@ -5,10 +5,11 @@
 //!
 //! # How It Works
 //!
-//! 1. Agent calls `task(subagent_type, description)`
+//! 1. Agent calls `task(subagent_type, description, context?)`
 //! 2. TaskTool validates the request and checks recursion limit
 //! 3. Looks up the subagent in the registry
-//! 4. Creates isolated state (no messages/todos from parent)
+//! 4. Creates isolated state (no messages/todos from parent), attaching any
+//!    structured `context` as a system message for the subagent
 //! 5. Executes subagent with the task description
 //! 6. Returns the subagent's response as a ToolMessage
 //!
@@ -20,7 +21,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::error::MiddlewareError;
-use crate::middleware::{Tool, ToolDefinition, ToolResult};
+use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
 
 use super::executor::SubAgentExecutorFactory;
@@ -35,6 +36,10 @@ pub struct TaskArgs {
 
     /// Task description for the subagent
     pub description: String,
+
+    /// Structured context for the subagent (e.g. files to inspect, a prior finding)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<serde_json::Value>,
 }
 
 /// Task tool for delegating work to sub-agents
@@ -59,6 +64,9 @@ pub struct TaskTool {
 
     /// Custom tool description (optional)
     custom_description: Option<String>,
+
+    /// Maximum subagent nesting depth (None relies on the runtime recursion limit only)
+    max_recursion_depth: Option<usize>,
 }
 
 impl TaskTool {
@@ -71,9 +79,19 @@ impl TaskTool {
             registry,
             executor_factory,
             custom_description: None,
+            max_recursion_depth: None,
         }
     }
 
+    /// Limit how deeply subagents may delegate to further subagents
+    ///
+    /// A limit of 1 lets the main agent delegate but stops its subagents
+    /// from delegating again.
+    pub fn with_max_recursion_depth(mut self, limit: usize) -> Self {
+        self.max_recursion_depth = Some(limit);
+        self
+    }
+
     /// Set a custom tool description
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.custom_description = Some(description.into());
@@ -110,6 +128,10 @@ impl TaskTool {
                     "description": {
                         "type": "string",
                         "description": "Detailed task description for the sub-agent"
+                    },
+                    "context": {
+                        "type": "object",
+                        "description": "Optional structured context for the sub-agent, such as relevant file paths or prior findings"
                     }
                 },
                 "required": ["subagent_type", "description"]
@@ -127,6 +149,10 @@ impl TaskTool {
                     "description": {
                         "type": "string",
                         "description": "Detailed task description for the sub-agent"
+                    },
+                    "context": {
+                        "type": "object",
+                        "description": "Optional structured context for the sub-agent, such as relevant file paths or prior findings"
                     }
                 },
                 "required": ["subagent_type", "description"]
@@ -142,6 +168,7 @@ impl Tool for TaskTool {
             name: "task".to_string(),
             description: self.generate_description(),
             parameters: self.generate_parameters_schema(),
+            examples: Vec::new(),
         }
     }
 
@@ -157,6 +184,7 @@ impl Tool for TaskTool {
         tracing::info!(
             subagent_type = %args.subagent_type,
             description_len = args.description.len(),
+            has_context = args.context.is_some(),
             "Executing task tool"
         );
 
@@ -184,12 +212,26 @@ impl Tool for TaskTool {
             ))
         })?;
 
-        // Create isolated state from parent
-        let isolated_state = IsolatedState::from_parent(runtime.state());
-
         // Create child runtime with increased recursion
         let child_runtime = runtime.with_increased_recursion();
 
+        // Create isolated state from parent, tracking the subagent's depth
+        let isolated_state = IsolatedState::from_parent(runtime.state())
+            .with_depth(child_runtime.config().current_recursion)
+            .with_context(args.context.clone());
+
+        if let Some(limit) = self.max_recursion_depth {
+            if isolated_state.depth > limit {
+                tracing::warn!(
+                    depth = isolated_state.depth,
+                    limit,
+                    subagent_type = %args.subagent_type,
+                    "SubAgent recursion depth limit exceeded"
+                );
+                return Err(MiddlewareError::RecursionLimitExceeded { limit });
+            }
+        }
+
         tracing::debug!(
             recursion_depth = child_runtime.config().current_recursion,
             "Executing subagent"
@@ -209,17 +251,12 @@ impl Tool for TaskTool {
         );
 
         // Format response
-        if result.success {
-            Ok(ToolResult::new(format!(
-                "[SubAgent '{}' completed]\n\n{}",
-                args.subagent_type, result.final_message
-            )))
-        } else {
-            Ok(ToolResult::new(format!(
-                "[SubAgent '{}' failed]\n\n{}",
-                args.subagent_type, result.final_message
-            )))
-        }
+        let status = if result.success { "completed" } else { "failed" };
+        Ok(ToolResult::new(format!(
+            "[SubAgent '{}' {}]\n\n{}",
+            args.subagent_type, status, result.final_message
+        ))
+        .with_update(StateUpdate::RecordUsage(result.usage)))
     }
 }
 
@@ -229,8 +266,9 @@ mod tests {
     use crate::backends::MemoryBackend;
     use crate::middleware::subagent::executor::MockSubAgentExecutorFactory;
     use crate::middleware::subagent::spec::{SubAgentKind, SubAgentSpec};
+    use crate::middleware::subagent::SubAgentResult;
     use crate::runtime::RuntimeConfig;
-    use crate::state::AgentState;
+    use crate::state::{AgentState, Message, Role};
 
     fn create_test_registry() -> SubAgentRegistry {
         SubAgentRegistry::new()
@@ -343,6 +381,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_task_tool_max_recursion_depth() {
+        let registry = Arc::new(create_test_registry());
+        let executor = Arc::new(MockSubAgentExecutorFactory::new("Result"));
+        let tool = TaskTool::new(registry, executor).with_max_recursion_depth(1);
+
+        let args = serde_json::json!({
+            "subagent_type": "researcher",
+            "description": "Research something"
+        });
+
+        // Main agent (depth 0) may delegate
+        let runtime = create_test_runtime();
+        assert!(tool.execute(args.clone(), &runtime).await.is_ok());
+
+        // A subagent (depth 1) may not delegate further
+        let nested = runtime.with_increased_recursion();
+        let result = tool.execute(args, &nested).await;
+        assert!(matches!(
+            result,
+            Err(MiddlewareError::RecursionLimitExceeded { limit: 1 })
+        ));
+    }
+
     #[tokio::test]
     async fn test_task_tool_invalid_args() {
         let registry = Arc::new(create_test_registry());
@@ -391,4 +453,59 @@ mod tests {
         assert!(definition.parameters["properties"]["subagent_type"].is_object());
         assert!(definition.description.contains("No subagents available"));
     }
+
+    /// Records the isolated state handed to the subagent
+    struct RecordingExecutorFactory {
+        received: std::sync::Mutex<Option<AgentState>>,
+    }
+
+    #[async_trait]
+    impl SubAgentExecutorFactory for RecordingExecutorFactory {
+        async fn execute(
+            &self,
+            _subagent: &SubAgentKind,
+            prompt: &str,
+            state: IsolatedState,
+            _runtime: &ToolRuntime,
+        ) -> Result<SubAgentResult, MiddlewareError> {
+            *self.received.lock().unwrap() = Some(state.to_agent_state(prompt));
+            Ok(SubAgentResult::success("Done"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_tool_passes_context_to_subagent() {
+        let registry = Arc::new(create_test_registry());
+        let executor = Arc::new(RecordingExecutorFactory {
+            received: std::sync::Mutex::new(None),
+        });
+        let tool = TaskTool::new(registry, executor.clone());
+
+        let mut parent = AgentState::new();
+        parent.messages.push(Message::user("Parent conversation"));
+        let runtime = ToolRuntime::new(parent, Arc::new(MemoryBackend::new()));
+
+        let args = serde_json::json!({
+            "subagent_type": "researcher",
+            "description": "Double-check the finding",
+            "context": {
+                "files": ["/notes.md"],
+                "prior_finding": "Error rates halve with surface codes"
+            }
+        });
+        tool.execute(args, &runtime).await.unwrap();
+
+        let received = executor.received.lock().unwrap().take().unwrap();
+        assert_eq!(received.messages.len(), 2);
+        assert_eq!(received.messages[0].role, Role::System);
+        assert!(received.messages[0].content.contains("/notes.md"));
+        assert!(received.messages[0]
+            .content
+            .contains("Error rates halve with surface codes"));
+        assert_eq!(received.messages[1].content, "Double-check the finding");
+        assert!(received
+            .messages
+            .iter()
+            .all(|m| m.content != "Parent conversation"));
+    }
 }
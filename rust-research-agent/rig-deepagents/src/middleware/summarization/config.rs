@@ -5,6 +5,9 @@
 use super::trigger::{KeepSize, TriggerCondition};
 use super::token_counter::DEFAULT_CHARS_PER_TOKEN;
 
+/// Conventional backend directory for archived (summarized-away) messages
+pub const DEFAULT_ARCHIVE_DIR: &str = "/summaries";
+
 /// Default summarization prompt (ported from LangChain DeepAgents)
 pub const DEFAULT_SUMMARY_PROMPT: &str = r#"<role>Context Extraction Assistant</role>
 
@@ -30,6 +33,70 @@ Respond ONLY with the extracted context. Do not include any additional commentar
 
 <conversation_to_summarize>"#;
 
+/// Shape of the generated summary.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let format = SummaryFormat::Sections(vec![
+///     "Decisions".into(),
+///     "Open Questions".into(),
+///     "Files Touched".into(),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SummaryFormat {
+    /// Free-form text, as produced by the summary prompt
+    #[default]
+    Freeform,
+
+    /// Markdown `## <name>` sections, in the given order
+    Sections(Vec<String>),
+}
+
+impl SummaryFormat {
+    /// Extra prompt instructions requesting the configured structure
+    pub fn prompt_instructions(&self) -> Option<String> {
+        match self {
+            SummaryFormat::Freeform => None,
+            SummaryFormat::Sections(sections) => {
+                let headings: Vec<String> =
+                    sections.iter().map(|name| format!("## {}", name)).collect();
+                Some(format!(
+                    "Structure your response using exactly these Markdown headings, in this order. \
+                     Write \"None\" under a heading with nothing to report.\n\n{}",
+                    headings.join("\n")
+                ))
+            }
+        }
+    }
+
+    /// Append any configured section the summarizer left out, so every
+    /// summary carries the full set of headings
+    pub fn ensure_sections(&self, mut summary: String) -> String {
+        let SummaryFormat::Sections(sections) = self else {
+            return summary;
+        };
+
+        for name in sections {
+            let present = summary.lines().any(|line| {
+                line.trim_start()
+                    .trim_start_matches('#')
+                    .trim()
+                    .eq_ignore_ascii_case(name)
+            });
+            if !present {
+                if !summary.is_empty() && !summary.ends_with('\n') {
+                    summary.push('\n');
+                }
+                summary.push_str(&format!("\n## {}\nNone\n", name));
+            }
+        }
+
+        summary
+    }
+}
+
 /// Configuration for the SummarizationMiddleware.
 ///
 /// Controls when summarization triggers and how much context to keep.
@@ -71,6 +138,16 @@ pub struct SummarizationConfig {
 
     /// Model's maximum input token limit
     pub max_input_tokens: usize,
+
+    /// Keep leading system messages out of the summary and ahead of it
+    pub preserve_system: bool,
+
+    /// Structure requested for the summary
+    pub format: SummaryFormat,
+
+    /// Backend directory where summarized-away messages are archived
+    /// (None, the default, keeps only the summary)
+    pub archive_dir: Option<String>,
 }
 
 impl Default for SummarizationConfig {
@@ -83,6 +160,9 @@ impl Default for SummarizationConfig {
             overhead_per_message: 3.0,
             summary_prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
             max_input_tokens: 128_000, // Default for GPT-4 Turbo
+            preserve_system: true,
+            format: SummaryFormat::Freeform,
+            archive_dir: None,
         }
     }
 }
@@ -139,6 +219,9 @@ pub struct SummarizationConfigBuilder {
     overhead_per_message: Option<f32>,
     summary_prompt: Option<String>,
     max_input_tokens: Option<usize>,
+    preserve_system: Option<bool>,
+    format: Option<SummaryFormat>,
+    archive_dir: Option<Option<String>>,
 }
 
 impl SummarizationConfigBuilder {
@@ -192,6 +275,24 @@ impl SummarizationConfigBuilder {
         self
     }
 
+    /// Set whether leading system messages survive summarization verbatim
+    pub fn preserve_system(mut self, preserve: bool) -> Self {
+        self.preserve_system = Some(preserve);
+        self
+    }
+
+    /// Set the summary format
+    pub fn format(mut self, format: SummaryFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set the archive directory for summarized-away messages (None disables archiving)
+    pub fn archive_dir(mut self, dir: Option<String>) -> Self {
+        self.archive_dir = Some(dir);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> SummarizationConfig {
         let default = SummarizationConfig::default();
@@ -208,6 +309,9 @@ impl SummarizationConfigBuilder {
                 .unwrap_or(default.overhead_per_message),
             summary_prompt: self.summary_prompt.unwrap_or(default.summary_prompt),
             max_input_tokens: self.max_input_tokens.unwrap_or(default.max_input_tokens),
+            preserve_system: self.preserve_system.unwrap_or(default.preserve_system),
+            format: self.format.unwrap_or(default.format),
+            archive_dir: self.archive_dir.unwrap_or(default.archive_dir),
         }
     }
 }
@@ -225,6 +329,21 @@ mod tests {
         assert!(matches!(config.keep, KeepSize::Fraction(f) if (f - 0.10).abs() < 0.001));
         assert_eq!(config.trim_tokens_to_summarize, 4000);
         assert_eq!(config.max_input_tokens, 128_000);
+        assert!(config.preserve_system);
+        assert!(config.archive_dir.is_none());
+    }
+
+    #[test]
+    fn test_summary_format_sections() {
+        assert!(SummaryFormat::Freeform.prompt_instructions().is_none());
+        assert_eq!(SummaryFormat::Freeform.ensure_sections("text".into()), "text");
+
+        let format = SummaryFormat::Sections(vec!["Decisions".into(), "Files Touched".into()]);
+        let instructions = format.prompt_instructions().unwrap();
+        assert!(instructions.contains("## Decisions\n## Files Touched"));
+
+        let summary = format.ensure_sections("# decisions\nShip it".into());
+        assert_eq!(summary, "# decisions\nShip it\n\n## Files Touched\nNone\n");
     }
 
     #[test]
@@ -269,6 +388,11 @@ mod tests {
         assert!(matches!(config.keep, KeepSize::Messages(6)));
         assert_eq!(config.max_input_tokens, 200_000);
         assert_eq!(config.chars_per_token, 3.3);
+
+        let config = SummarizationConfig::builder()
+            .archive_dir(Some(DEFAULT_ARCHIVE_DIR.to_string()))
+            .build();
+        assert_eq!(config.archive_dir.as_deref(), Some(DEFAULT_ARCHIVE_DIR));
     }
 
     #[test]
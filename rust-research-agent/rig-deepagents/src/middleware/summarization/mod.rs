@@ -11,7 +11,10 @@
 //! 2. Checks trigger conditions (token count, message count, or fraction of max)
 //! 3. If triggered, partitions messages into "to summarize" and "preserved"
 //! 4. Calls an LLM to generate a summary of the older messages
-//! 5. Replaces the conversation with: summary + preserved messages
+//! 5. If `archive_dir` is set (e.g. [`DEFAULT_ARCHIVE_DIR`]), archives the summarized
+//!    messages to `{archive_dir}/{timestamp}.json` so the full transcript survives
+//!    for auditing; archiving is off by default
+//! 6. Replaces the conversation with: system messages + summary + preserved messages
 //!
 //! # Example
 //!
@@ -45,15 +48,20 @@ pub use token_counter::{
     DEFAULT_CHARS_PER_TOKEN, CLAUDE_CHARS_PER_TOKEN, DEFAULT_OVERHEAD_PER_MESSAGE,
 };
 pub use trigger::{TriggerCondition, KeepSize};
-pub use config::{SummarizationConfig, SummarizationConfigBuilder, DEFAULT_SUMMARY_PROMPT};
+pub use config::{
+    SummarizationConfig, SummarizationConfigBuilder, SummaryFormat, DEFAULT_ARCHIVE_DIR,
+    DEFAULT_SUMMARY_PROMPT,
+};
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::{debug, info, warn};
 
-use crate::error::MiddlewareError;
-use crate::llm::LLMProvider;
-use crate::middleware::traits::{AgentMiddleware, DynTool, ModelControl, ModelRequest};
+use crate::backends::Backend;
+use crate::error::{BackendError, MiddlewareError};
+use crate::llm::{LLMProvider, TokenUsage};
+use crate::middleware::traits::{AgentMiddleware, DynTool, ModelControl, ModelRequest, StateUpdate};
 use crate::runtime::ToolRuntime;
 use crate::state::{AgentState, Message, Role};
 use crate::tokenization::{ApproxTokenCounter, TokenCounter};
@@ -108,8 +116,25 @@ impl SummarizationMiddleware {
     }
 
     /// Create with model-tuned configuration.
+    ///
+    /// With the `tokenizer-tiktoken` feature, tokens are counted with the
+    /// model's tiktoken encoding instead of the character approximation.
     pub fn for_model(llm_provider: Arc<dyn LLMProvider>, model: &str) -> Self {
-        Self::new(llm_provider, SummarizationConfig::for_model(model))
+        let config = SummarizationConfig::for_model(model);
+
+        #[cfg(feature = "tokenizer-tiktoken")]
+        match crate::tokenization::TiktokenTokenCounter::for_model(model) {
+            Ok(counter) => return Self::with_token_counter(llm_provider, config, Arc::new(counter)),
+            Err(e) => warn!("Falling back to approximate token counting for {}: {}", model, e),
+        }
+
+        Self::new(llm_provider, config)
+    }
+
+    /// Replace the configuration, keeping the token counter.
+    pub fn with_config(mut self, config: SummarizationConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Count tokens in the current messages.
@@ -122,10 +147,26 @@ impl SummarizationMiddleware {
         self.config.should_summarize(token_count, message_count)
     }
 
+    /// Number of leading system messages kept out of the summary.
+    ///
+    /// Always 0 when `preserve_system` is disabled.
+    fn system_prefix_len(&self, messages: &[Message]) -> usize {
+        if !self.config.preserve_system {
+            return 0;
+        }
+        messages
+            .iter()
+            .take_while(|m| m.role == Role::System)
+            .count()
+    }
+
     /// Partition messages into (to_summarize, preserved).
     ///
-    /// Respects AI/Tool message pair boundaries.
+    /// Respects AI/Tool message pair boundaries. Leading system messages are
+    /// excluded from both halves when `preserve_system` is enabled; callers
+    /// re-prepend them ahead of the summary.
     fn partition_messages(&self, messages: &[Message]) -> (Vec<Message>, Vec<Message>) {
+        let messages = &messages[self.system_prefix_len(messages)..];
         if messages.is_empty() {
             return (vec![], vec![]);
         }
@@ -185,26 +226,54 @@ impl SummarizationMiddleware {
 
     /// Find a safe cutoff point that doesn't split AI/Tool pairs.
     ///
-    /// If the initial cutoff lands on a Tool message, advance past all consecutive
-    /// Tool messages to keep the AI message with its responses.
+    /// Moves the cutoff backward until no preserved Tool message answers a
+    /// tool call issued by a summarized assistant message, matching results
+    /// to calls by `tool_call_id`. A cutoff landing on a Tool message also
+    /// moves back past it, so results without a known call stay with the
+    /// message before them.
     fn find_safe_cutoff(&self, messages: &[Message], initial_cutoff: usize) -> usize {
         if initial_cutoff >= messages.len() {
             return messages.len();
         }
 
+        // Index of the assistant message that issued each tool call
+        let call_sites: HashMap<&str, usize> = messages
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| m.tool_calls.as_ref().map(|calls| (i, calls)))
+            .flat_map(|(i, calls)| calls.iter().map(move |c| (c.id.as_str(), i)))
+            .collect();
+
         let mut cutoff = initial_cutoff;
 
-        while cutoff > 0 && messages[cutoff].role == Role::Tool {
-            cutoff -= 1;
-        }
+        loop {
+            while cutoff > 0 && messages[cutoff].role == Role::Tool {
+                cutoff -= 1;
+            }
+
+            let earliest_split_call = messages[cutoff..]
+                .iter()
+                .filter_map(|m| m.tool_call_id.as_deref())
+                .filter_map(|id| call_sites.get(id).copied())
+                .filter(|&site| site < cutoff)
+                .min();
 
-        cutoff
+            match earliest_split_call {
+                Some(site) => cutoff = site,
+                None => return cutoff,
+            }
+        }
     }
 
     /// Generate a summary of the messages.
-    async fn generate_summary(&self, messages: &[Message]) -> Result<String, MiddlewareError> {
+    ///
+    /// Returns the summary with the summarizer call's token usage (if reported).
+    async fn generate_summary(
+        &self,
+        messages: &[Message],
+    ) -> Result<(String, Option<TokenUsage>), MiddlewareError> {
         if messages.is_empty() {
-            return Ok(String::new());
+            return Ok((String::new(), None));
         }
 
         // Trim messages to fit summarizer's context
@@ -214,11 +283,15 @@ impl SummarizationMiddleware {
         let conversation_text = self.format_messages(&trimmed);
 
         // Build the summarization prompt
-        let prompt = format!(
+        let mut prompt = format!(
             "{}\n{}\n</conversation_to_summarize>",
             self.config.summary_prompt,
             conversation_text
         );
+        if let Some(instructions) = self.config.format.prompt_instructions() {
+            prompt.push_str("\n\n");
+            prompt.push_str(&instructions);
+        }
 
         // Create a simple request
         let request_messages = vec![Message::user(&prompt)];
@@ -235,7 +308,49 @@ impl SummarizationMiddleware {
             .await
             .map_err(|e| MiddlewareError::ToolExecution(format!("Summary generation failed: {}", e)))?;
 
-        Ok(response.message.content)
+        let summary = self.config.format.ensure_sections(response.message.content);
+        Ok((summary, response.usage))
+    }
+
+    /// Archive summarized-away messages to the backend.
+    ///
+    /// Returns the archive path, or None when archiving is disabled. Backends
+    /// that track files in state get the new file mirrored into `state.files`.
+    async fn archive_messages(
+        &self,
+        messages: &[Message],
+        summary: &str,
+        backend: &dyn Backend,
+        state: &mut AgentState,
+    ) -> Result<Option<String>, MiddlewareError> {
+        let Some(dir) = &self.config.archive_dir else {
+            return Ok(None);
+        };
+
+        let now = chrono::Utc::now();
+        let path = format!(
+            "{}/{}.json",
+            dir.trim_end_matches('/'),
+            now.format("%Y%m%dT%H%M%S%.6fZ")
+        );
+        let archive = serde_json::json!({
+            "summarized_at": now.to_rfc3339(),
+            "summary": summary,
+            "messages": messages,
+        });
+
+        let result = backend.write(&path, &serde_json::to_string_pretty(&archive)?).await?;
+        if let Some(error) = result.error {
+            return Err(MiddlewareError::Backend(BackendError::Io(error)));
+        }
+        if let Some(files_update) = result.files_update {
+            let updates = files_update
+                .into_iter()
+                .map(|(path, data)| (path, Some(data)))
+                .collect();
+            StateUpdate::UpdateFiles(updates).apply(state);
+        }
+        Ok(Some(path))
     }
 
     /// Trim messages to fit within the summarizer's token budget.
@@ -310,7 +425,7 @@ impl AgentMiddleware for SummarizationMiddleware {
         &self,
         request: &mut ModelRequest,
         state: &mut AgentState,
-        _runtime: &ToolRuntime,
+        runtime: &ToolRuntime,
     ) -> Result<ModelControl, MiddlewareError> {
         let token_count = self.count_tokens(&state.messages);
         let message_count = state.messages.len();
@@ -349,19 +464,43 @@ impl AgentMiddleware for SummarizationMiddleware {
 
         // Generate summary
         let summary = match self.generate_summary(&to_summarize).await {
-            Ok(s) => s,
+            Ok((summary, usage)) => {
+                state.usage.record(usage.as_ref());
+                summary
+            }
             Err(e) => {
                 warn!(error = %e, "Failed to generate summary, keeping original messages");
                 return Ok(ModelControl::Continue);
             }
         };
 
+        // Archive the originals before dropping them from the live history.
+        // The archive is best-effort: a failure only drops the pointer to it.
+        let archive_path = match self
+            .archive_messages(&to_summarize, &summary, runtime.backend().as_ref(), state)
+            .await
+        {
+            Ok(path) => path,
+            Err(e) => {
+                warn!(error = %e, "Failed to archive summarized messages, summarizing without archive");
+                None
+            }
+        };
+
         // Build new message list
-        let summary_message = format!(
+        let mut summary_message = format!(
             "Here is a summary of the conversation to date:\n\n{}",
             summary
         );
-        let mut new_messages = vec![Message::user(&summary_message)];
+        if let Some(path) = &archive_path {
+            summary_message.push_str(&format!(
+                "\n\nThe full text of the summarized messages is archived at {}.",
+                path
+            ));
+        }
+        let system_len = self.system_prefix_len(&state.messages);
+        let mut new_messages = state.messages[..system_len].to_vec();
+        new_messages.push(Message::user(&summary_message));
         new_messages.extend(preserved);
 
         let new_token_count = self.count_tokens(&new_messages);
@@ -391,46 +530,18 @@ impl std::fmt::Debug for SummarizationMiddleware {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::llm::{LLMConfig, LLMResponse};
     use crate::middleware::{ModelControl, ModelRequest};
     use crate::runtime::ToolRuntime;
+    use crate::testing::MockLLMProvider;
 
-    /// Mock LLM provider for testing
-    struct MockProvider {
-        summary_response: String,
-    }
-
-    impl MockProvider {
-        fn new(response: &str) -> Self {
-            Self {
-                summary_response: response.to_string(),
-            }
-        }
-    }
-
-    #[async_trait]
-    impl LLMProvider for MockProvider {
-        async fn complete(
-            &self,
-            _messages: &[Message],
-            _tools: &[crate::middleware::ToolDefinition],
-            _config: Option<&LLMConfig>,
-        ) -> Result<LLMResponse, crate::error::DeepAgentError> {
-            Ok(LLMResponse::new(Message::assistant(&self.summary_response)))
-        }
-
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        fn default_model(&self) -> &str {
-            "mock-model"
-        }
+    /// Mock LLM that answers every summarization request with `summary`
+    fn summary_llm(summary: &str) -> MockLLMProvider {
+        MockLLMProvider::new().with_fallback(summary)
     }
 
     #[test]
     fn test_partition_empty_messages() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::default();
         let middleware = SummarizationMiddleware::new(provider, config);
 
@@ -439,9 +550,24 @@ mod tests {
         assert!(preserved.is_empty());
     }
 
+    #[cfg(feature = "tokenizer-tiktoken")]
+    #[test]
+    fn test_for_model_prefers_tiktoken_counter() {
+        use crate::tokenization::TiktokenTokenCounter;
+
+        let provider = Arc::new(summary_llm("Summary"));
+        let middleware = SummarizationMiddleware::for_model(provider, "gpt-4o");
+        let messages = vec![Message::user("How many tokens is this sentence?")];
+
+        let expected = TiktokenTokenCounter::for_model("gpt-4o")
+            .unwrap()
+            .count_messages(&messages);
+        assert_eq!(middleware.count_tokens(&messages), expected);
+    }
+
     #[test]
     fn test_partition_respects_keep_size() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::builder()
             .keep(KeepSize::Messages(2))
             .build();
@@ -465,7 +591,7 @@ mod tests {
 
     #[test]
     fn test_safe_cutoff_moves_backward_for_tool_messages() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::builder()
             .keep(KeepSize::Messages(3))
             .build();
@@ -497,9 +623,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_safe_cutoff_keeps_interleaved_tool_results_with_calls() {
+        let provider = Arc::new(summary_llm("Summary"));
+        let config = SummarizationConfig::builder()
+            .keep(KeepSize::Messages(3))
+            .build();
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let call = |id: &str| crate::state::ToolCall {
+            id: id.to_string(),
+            name: "read_file".to_string(),
+            arguments: serde_json::json!({"path": id}),
+        };
+
+        // call_a's result arrives after an unrelated exchange, so the naive
+        // cutoff (index 5) would preserve it while summarizing its call
+        let messages = vec![
+            Message::user("Request"),
+            Message::assistant_with_tool_calls("Reading two files", vec![call("call_a"), call("call_b")]),
+            Message::tool("B contents", "call_b"),
+            Message::assistant_with_tool_calls("One more", vec![call("call_c")]),
+            Message::tool("C contents", "call_c"),
+            Message::user("Any update on A?"),
+            Message::tool("A contents", "call_a"),
+            Message::assistant("Done"),
+        ];
+
+        let (to_summarize, preserved) = middleware.partition_messages(&messages);
+
+        assert_eq!(to_summarize.len() + preserved.len(), messages.len());
+        assert_eq!(to_summarize.len(), 1);
+
+        // Every preserved tool result has its call in the preserved set
+        let preserved_calls: Vec<&str> = preserved
+            .iter()
+            .filter_map(|m| m.tool_calls.as_ref())
+            .flatten()
+            .map(|c| c.id.as_str())
+            .collect();
+        for msg in preserved.iter().filter(|m| m.role == Role::Tool) {
+            let id = msg.tool_call_id.as_deref().unwrap();
+            assert!(preserved_calls.contains(&id), "orphaned tool result {}", id);
+        }
+
+        // And no summarized call has a result left in the preserved set
+        let summarized_calls: Vec<&str> = to_summarize
+            .iter()
+            .filter_map(|m| m.tool_calls.as_ref())
+            .flatten()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert!(preserved
+            .iter()
+            .filter_map(|m| m.tool_call_id.as_deref())
+            .all(|id| !summarized_calls.contains(&id)));
+    }
+
     #[tokio::test]
     async fn test_before_model_summarizes_request_messages() {
-        let provider = Arc::new(MockProvider::new("Summary text"));
+        let provider = Arc::new(summary_llm("Summary text"));
         let config = SummarizationConfig::builder()
             .trigger(TriggerCondition::Messages(2))
             .keep(KeepSize::Messages(1))
@@ -528,11 +711,178 @@ mod tests {
         assert_eq!(request.messages[1].content, state.messages[1].content);
         assert_eq!(state.messages.len(), 2);
         assert!(state.messages[0].content.contains("Summary text"));
+        assert_eq!(state.run_usage().llm_calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_before_model_preserves_system_message() {
+        let provider = Arc::new(summary_llm("Summary text"));
+        let config = SummarizationConfig::builder()
+            .trigger(TriggerCondition::Messages(4))
+            .keep(KeepSize::Messages(2))
+            .build();
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let system_prompt = "You are a meticulous research assistant.";
+        let mut messages = vec![Message::system(system_prompt)];
+        for i in 0..10 {
+            messages.push(Message::user(&format!("Question {}", i)));
+            messages.push(Message::assistant(&format!("Answer {}", i)));
+        }
+        let mut state = AgentState::with_messages(messages);
+
+        let (to_summarize, _) = middleware.partition_messages(&state.messages);
+        assert!(to_summarize.iter().all(|m| m.role != Role::System));
+
+        let mut request = ModelRequest::new(state.messages.clone(), vec![]);
+        let backend = Arc::new(crate::backends::MemoryBackend::new());
+        let runtime = ToolRuntime::new(state.clone(), backend);
+
+        middleware
+            .before_model(&mut request, &mut state, &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(state.messages.len(), 4);
+        assert_eq!(state.messages[0].role, Role::System);
+        assert_eq!(state.messages[0].content, system_prompt);
+        assert!(state.messages[1].content.contains("Summary text"));
+        assert_eq!(state.messages[3].content, "Answer 9");
+        assert_eq!(request.messages[0].content, system_prompt);
+    }
+
+    #[tokio::test]
+    async fn test_before_model_archives_summarized_messages() {
+        let provider = Arc::new(summary_llm("Summary text"));
+        let config = SummarizationConfig::builder()
+            .trigger(TriggerCondition::Messages(2))
+            .keep(KeepSize::Messages(1))
+            .archive_dir(Some(DEFAULT_ARCHIVE_DIR.to_string()))
+            .build();
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let mut state = AgentState::with_messages(vec![
+            Message::user("First"),
+            Message::assistant("Second"),
+            Message::user("Third"),
+        ]);
+
+        let mut request = ModelRequest::new(state.messages.clone(), vec![]);
+        let backend = Arc::new(crate::backends::MemoryBackend::new());
+        let runtime = ToolRuntime::new(state.clone(), backend.clone());
+
+        middleware
+            .before_model(&mut request, &mut state, &runtime)
+            .await
+            .unwrap();
+
+        // Live history is trimmed to summary + kept message
+        assert_eq!(state.messages.len(), 2);
+        assert_eq!(state.messages[1].content, "Third");
+
+        let archives = backend.glob("**/*.json", DEFAULT_ARCHIVE_DIR).await.unwrap();
+        assert_eq!(archives.len(), 1);
+        let path = &archives[0].path;
+        assert!(state.messages[0].content.contains(path.as_str()));
+        assert!(state.files.contains_key(path));
+
+        let archive: serde_json::Value =
+            serde_json::from_str(&backend.read_plain(path).await.unwrap()).unwrap();
+        assert_eq!(archive["summary"], "Summary text");
+        let archived: Vec<Message> = serde_json::from_value(archive["messages"].clone()).unwrap();
+        let contents: Vec<&str> = archived.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["First", "Second"]);
+    }
+
+    #[tokio::test]
+    async fn test_before_model_default_config_writes_nothing() {
+        let provider = Arc::new(summary_llm("Summary text"));
+        // Only the trigger and keep size differ from the defaults
+        let config = SummarizationConfig {
+            triggers: vec![TriggerCondition::Messages(2)],
+            keep: KeepSize::Messages(1),
+            ..SummarizationConfig::default()
+        };
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let mut state = AgentState::with_messages(vec![
+            Message::user("First"),
+            Message::assistant("Second"),
+            Message::user("Third"),
+        ]);
+
+        let mut request = ModelRequest::new(state.messages.clone(), vec![]);
+        let backend = Arc::new(crate::backends::MemoryBackend::new());
+        let runtime = ToolRuntime::new(state.clone(), backend.clone());
+
+        middleware
+            .before_model(&mut request, &mut state, &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(state.messages.len(), 2);
+        assert!(!state.messages[0].content.contains("archived at"));
+        assert!(backend.glob("**/*", "/").await.unwrap().is_empty());
+        assert!(state.files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_before_model_summarizes_when_archive_fails() {
+        let provider = Arc::new(summary_llm("Summary text"));
+        let config = SummarizationConfig::builder()
+            .trigger(TriggerCondition::Messages(2))
+            .keep(KeepSize::Messages(1))
+            .archive_dir(Some(DEFAULT_ARCHIVE_DIR.to_string()))
+            .build();
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let mut state = AgentState::with_messages(vec![
+            Message::user("First"),
+            Message::assistant("Second"),
+            Message::user("Third"),
+        ]);
+
+        let mut request = ModelRequest::new(state.messages.clone(), vec![]);
+        let backend = Arc::new(crate::backends::ReadOnlyBackend::new(Arc::new(
+            crate::backends::MemoryBackend::new(),
+        )));
+        let runtime = ToolRuntime::new(state.clone(), backend);
+
+        middleware
+            .before_model(&mut request, &mut state, &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(state.messages.len(), 2);
+        assert!(state.messages[0].content.contains("Summary text"));
+        assert!(!state.messages[0].content.contains("archived at"));
+        assert!(state.files.is_empty());
+    }
+
+    #[test]
+    fn test_partition_summarizes_system_when_not_preserved() {
+        let provider = Arc::new(summary_llm("Summary"));
+        let config = SummarizationConfig::builder()
+            .keep(KeepSize::Messages(1))
+            .preserve_system(false)
+            .build();
+        let middleware = SummarizationMiddleware::new(provider, config);
+
+        let messages = vec![
+            Message::system("System"),
+            Message::user("First"),
+            Message::assistant("Second"),
+        ];
+
+        let (to_summarize, preserved) = middleware.partition_messages(&messages);
+        assert_eq!(to_summarize.len(), 2);
+        assert_eq!(to_summarize[0].role, Role::System);
+        assert_eq!(preserved.len(), 1);
     }
 
     #[test]
     fn test_format_messages() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::default();
         let middleware = SummarizationMiddleware::new(provider, config);
 
@@ -549,7 +899,7 @@ mod tests {
 
     #[test]
     fn test_should_summarize_token_trigger() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::builder()
             .trigger(TriggerCondition::Tokens(100))
             .max_input_tokens(200)
@@ -563,7 +913,7 @@ mod tests {
 
     #[test]
     fn test_should_summarize_fraction_trigger() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::builder()
             .trigger(TriggerCondition::Fraction(0.8))
             .max_input_tokens(100)
@@ -577,7 +927,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_generate_summary() {
-        let provider = Arc::new(MockProvider::new("This is the summary."));
+        let provider = Arc::new(summary_llm("This is the summary."));
         let config = SummarizationConfig::default();
         let middleware = SummarizationMiddleware::new(provider, config);
 
@@ -586,13 +936,45 @@ mod tests {
             Message::assistant("Hi!"),
         ];
 
-        let summary = middleware.generate_summary(&messages).await.unwrap();
+        let (summary, usage) = middleware.generate_summary(&messages).await.unwrap();
         assert_eq!(summary, "This is the summary.");
+        assert!(usage.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_generate_summary_with_sections() {
+        let provider = Arc::new(summary_llm(
+            "## Decisions\nUse Pregel runtime\n\n## Open Questions\nNone",
+        ));
+        let sections = vec![
+            "Decisions".to_string(),
+            "Open Questions".to_string(),
+            "Files Touched".to_string(),
+        ];
+        let config = SummarizationConfig::builder()
+            .format(SummaryFormat::Sections(sections.clone()))
+            .build();
+        let middleware = SummarizationMiddleware::new(provider.clone(), config);
+
+        let messages = vec![
+            Message::user("Edit src/lib.rs"),
+            Message::assistant("Done"),
+        ];
+
+        let (summary, _) = middleware.generate_summary(&messages).await.unwrap();
+
+        assert_eq!(provider.request_count(), 1);
+        let prompt = &provider.last_request().unwrap().messages[0].content;
+        for name in &sections {
+            assert!(prompt.contains(&format!("## {}", name)), "prompt missing {}", name);
+            assert!(summary.contains(&format!("## {}", name)), "summary missing {}", name);
+        }
+        assert!(summary.starts_with("## Decisions\nUse Pregel runtime"));
     }
 
     #[test]
     fn test_trim_for_summary() {
-        let provider = Arc::new(MockProvider::new("Summary"));
+        let provider = Arc::new(summary_llm("Summary"));
         let config = SummarizationConfig::builder()
             .trim_tokens_to_summarize(50)
             .chars_per_token(1.0) // 1 char = 1 token for easy testing
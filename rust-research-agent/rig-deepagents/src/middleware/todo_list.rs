@@ -6,13 +6,14 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::middleware::{AgentMiddleware, DynTool};
+use crate::middleware::{AgentMiddleware, DynTool, PromptSection};
 use crate::tools::{ReadTodosTool, WriteTodosTool};
 
 /// Default system prompt for todo planning.
 pub const TODO_SYSTEM_PROMPT: &str = "## Planning with `write_todos`\n\
 Use `write_todos` for multi-step tasks (3+ steps).\n\
 Each todo item has `content` and `status` (pending, in_progress, completed).\n\
+Give items an `id` and list prerequisite ids in `depends_on` when order matters.\n\
 Update the list as you work: mark items in_progress before starting and completed immediately after finishing.";
 
 /// Middleware that injects the write_todos tool and planning guidance.
@@ -36,6 +37,12 @@ impl TodoListMiddleware {
     }
 }
 
+impl Default for TodoListMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl AgentMiddleware for TodoListMiddleware {
     fn name(&self) -> &str {
@@ -53,6 +60,14 @@ impl AgentMiddleware for TodoListMiddleware {
             format!("{}\n\n{}", prompt, self.system_prompt)
         }
     }
+
+    fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        if self.system_prompt.is_empty() {
+            vec![]
+        } else {
+            vec![PromptSection::new(self.name(), self.system_prompt.clone())]
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4,12 +4,13 @@
 //! Python Reference: langchain/agents/middleware/types.py
 
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::collections::HashMap;
-use crate::state::{AgentState, Message, Todo, FileData};
+use serde::{Deserialize, Serialize};
+use crate::state::{AgentState, Message, Todo, FileData, ToolCall};
 use crate::error::MiddlewareError;
 use crate::runtime::ToolRuntime;
-use crate::llm::{LLMConfig, TokenUsage};
+use crate::llm::{LLMConfig, RunUsage, TokenUsage};
 
 /// 상태 업데이트 커맨드
 /// Python: langgraph.types.Command
@@ -23,6 +24,8 @@ pub enum StateUpdate {
     SetTodos(Vec<Todo>),
     /// 파일 업데이트 (None = 삭제)
     UpdateFiles(HashMap<String, Option<FileData>>),
+    /// 토큰 사용량 누적 (SubAgent 실행분)
+    RecordUsage(RunUsage),
     /// 복합 업데이트
     Batch(Vec<StateUpdate>),
 }
@@ -49,6 +52,9 @@ impl StateUpdate {
                     }
                 }
             }
+            StateUpdate::RecordUsage(usage) => {
+                state.usage.merge(usage);
+            }
             StateUpdate::Batch(updates) => {
                 for update in updates {
                     update.apply(state);
@@ -131,8 +137,18 @@ pub enum ModelControl {
     ModifyRequest(ModelRequest),
     /// Model 호출을 건너뛰고 이 응답 사용 (캐싱용)
     Skip(ModelResponse),
+    /// Model 호출 없이 미들웨어가 직접 답변 (가드레일/입력 필터링용)
+    ///
+    /// `before_model`에서만 의미가 있으며, 제공된 어시스턴트 메시지가
+    /// LLM 응답 대신 히스토리에 추가됩니다.
+    Respond(Message),
     /// 실행을 인터럽트하고 인간 승인 대기 (HumanInTheLoop)
     Interrupt(InterruptRequest),
+    /// 지정한 도구 호출을 실행하지 않고 거부 (도구 호출 ID -> 거부 사유)
+    ///
+    /// `after_model`에서만 의미가 있으며, 거부된 호출에는 사유를 담은
+    /// 에러 도구 메시지가 대신 기록됩니다.
+    RejectToolCalls(HashMap<String, String>),
 }
 
 // ============================================================================
@@ -148,25 +164,56 @@ pub struct InterruptRequest {
     pub action_requests: Vec<ActionRequest>,
     /// 각 액션에 대한 리뷰 설정
     pub review_configs: Vec<ReviewConfig>,
+    /// 인터럽트 시점의 에이전트 상태 (AgentExecutor가 채움)
+    pub state: Option<Box<AgentState>>,
 }
 
 impl InterruptRequest {
     /// 새 InterruptRequest 생성
     pub fn new(action_requests: Vec<ActionRequest>, review_configs: Vec<ReviewConfig>) -> Self {
-        Self { action_requests, review_configs }
+        Self { action_requests, review_configs, state: None }
     }
 
     /// 단일 액션으로 InterruptRequest 생성
     pub fn single(action: ActionRequest, config: ReviewConfig) -> Self {
-        Self {
-            action_requests: vec![action],
-            review_configs: vec![config],
-        }
+        Self::new(vec![action], vec![config])
+    }
+
+    /// 인터럽트 시점의 상태 설정
+    pub fn with_state(mut self, state: AgentState) -> Self {
+        self.state = Some(Box::new(state));
+        self
+    }
+
+    /// 다른 프로세스에서 재개할 수 있는 직렬화 가능한 토큰 생성
+    ///
+    /// 상태가 기록되지 않은 경우(AgentExecutor를 거치지 않은 인터럽트) `None`을 반환합니다.
+    pub fn to_resume_token(&self) -> Option<ResumeToken> {
+        Some(ResumeToken {
+            action_requests: self.action_requests.clone(),
+            review_configs: self.review_configs.clone(),
+            state: self.state.as_deref()?.clone(),
+        })
     }
 }
 
+/// 인터럽트된 실행을 재개하기 위한 토큰
+///
+/// 대기 중인 액션과 실행을 이어가는 데 필요한 상태를 담으며, JSON 등으로 저장한 뒤
+/// `AgentExecutor::resume_from_interrupt`로 재개합니다.
+/// `Decision::Edit`로 재개할 때는 `action_requests`의 `args`를 수정한 값이 사용됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeToken {
+    /// 승인 대기 중인 액션 목록
+    pub action_requests: Vec<ActionRequest>,
+    /// 각 액션에 대한 리뷰 설정
+    pub review_configs: Vec<ReviewConfig>,
+    /// 인터럽트 시점의 에이전트 상태 (대기 중인 도구 호출 메시지 포함)
+    pub state: AgentState,
+}
+
 /// 승인이 필요한 개별 액션
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionRequest {
     /// 도구 호출 ID
     pub id: String,
@@ -197,7 +244,7 @@ impl ActionRequest {
 }
 
 /// 리뷰 설정
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewConfig {
     /// 대상 액션 이름
     pub action_name: String,
@@ -226,7 +273,7 @@ impl ReviewConfig {
 }
 
 /// 사용자 결정 유형
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Decision {
     /// 액션 승인
     Approve,
@@ -236,12 +283,94 @@ pub enum Decision {
     Edit,
 }
 
+/// 이름이 붙은 시스템 프롬프트 조각
+///
+/// 미들웨어가 시스템 프롬프트에 기여하는 단위입니다. 최종 순서는
+/// [`SystemPromptLayout`](crate::runtime::SystemPromptLayout)이 이름을 기준으로 정합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSection {
+    /// 섹션 이름 (레이아웃에서 참조)
+    pub name: String,
+    /// 섹션 본문
+    pub content: String,
+}
+
+impl PromptSection {
+    pub fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// 도구 이름별로 캐시된 인자 검증기 (스키마, 컴파일 결과)
+///
+/// 컴파일할 수 없는 스키마는 `None`으로 캐시되어 경고가 한 번만 기록됩니다.
+type ValidatorCache = HashMap<String, (serde_json::Value, Option<Arc<jsonschema::Validator>>)>;
+
+/// 스키마에 대한 검증기를 캐시에서 가져오거나 컴파일
+///
+/// 같은 이름의 도구가 다른 스키마를 가지면 새로 컴파일해 교체합니다.
+fn cached_validator(
+    tool_name: &str,
+    schema: &serde_json::Value,
+) -> Option<Arc<jsonschema::Validator>> {
+    static VALIDATORS: OnceLock<Mutex<ValidatorCache>> = OnceLock::new();
+
+    let mut cache = VALIDATORS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((cached_schema, validator)) = cache.get(tool_name) {
+        if cached_schema == schema {
+            return validator.clone();
+        }
+    }
+
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => Some(Arc::new(validator)),
+        Err(e) => {
+            tracing::warn!(tool = %tool_name, error = %e, "Skipping argument validation: invalid schema");
+            None
+        }
+    };
+    cache.insert(tool_name.to_string(), (schema.clone(), validator.clone()));
+    validator
+}
+
 /// 도구 정의
 #[derive(Debug, Clone)]
 pub struct ToolDefinition {
     pub name: String,
     pub description: String,
     pub parameters: serde_json::Value,
+    /// 호출 예시 인자 (스키마만으로는 도구 호출이 불안정한 소형 모델용 few-shot 힌트)
+    pub examples: Vec<serde_json::Value>,
+}
+
+impl ToolDefinition {
+    /// 호출 예시 추가
+    pub fn with_examples(mut self, examples: impl IntoIterator<Item = serde_json::Value>) -> Self {
+        self.examples.extend(examples);
+        self
+    }
+
+    /// 호출 예시를 덧붙인 설명
+    ///
+    /// 예시 필드를 지원하지 않는 제공자에게는 설명 안에 예시를 인라인으로 전달합니다.
+    pub fn description_with_examples(&self) -> String {
+        if self.examples.is_empty() {
+            return self.description.clone();
+        }
+
+        let examples: Vec<String> = self
+            .examples
+            .iter()
+            .map(|example| format!("- {}", example))
+            .collect();
+        format!("{}\n\nExample arguments:\n{}", self.description, examples.join("\n"))
+    }
 }
 
 /// Tool execution result with optional state updates.
@@ -285,6 +414,60 @@ pub trait Tool: Send + Sync {
         args: serde_json::Value,
         runtime: &ToolRuntime,
     ) -> Result<ToolResult, MiddlewareError>;
+
+    /// 실행 전 인자 검증
+    ///
+    /// 기본 구현은 `definition().parameters`의 JSON Schema로 인자를 검증하고,
+    /// 위반 사항을 경로와 함께 `MiddlewareError::InvalidToolArguments`로 반환합니다.
+    /// 스키마 자체를 컴파일할 수 없으면 검증을 건너뜁니다.
+    /// 컴파일된 검증기는 도구 이름별로 캐시됩니다.
+    fn validate_args(&self, args: &serde_json::Value) -> Result<(), MiddlewareError> {
+        let definition = self.definition();
+        let Some(validator) = cached_validator(&definition.name, &definition.parameters) else {
+            return Ok(());
+        };
+
+        let violations: Vec<String> = validator
+            .iter_errors(args)
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{}: {}", path, error)
+                }
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(MiddlewareError::InvalidToolArguments {
+                tool_name: definition.name,
+                message: violations.join("; "),
+            })
+        }
+    }
+
+    /// 다른 도구 호출과 동시에 실행해도 안전한지 여부
+    ///
+    /// 상태나 파일을 변경하지 않는 읽기 전용 도구만 `true`를 반환해야 합니다.
+    /// 기본값 `false`인 도구는 `AgentExecutor`에서 항상 순차 실행됩니다.
+    fn is_concurrent_safe(&self) -> bool {
+        false
+    }
+
+    /// 호출 예시 인자
+    ///
+    /// 레지스트리와 `AgentExecutor`가 정의를 수집할 때 `ToolDefinition::examples`에 합쳐집니다.
+    fn examples(&self) -> Vec<serde_json::Value> {
+        Vec::new()
+    }
+
+    /// 호출 예시가 포함된 도구 정의
+    fn definition_with_examples(&self) -> ToolDefinition {
+        self.definition().with_examples(self.examples())
+    }
 }
 
 /// 동적 도구 타입
@@ -341,7 +524,7 @@ impl ToolRegistry {
 
     /// Get all tool definitions (schemas) for LLM
     pub fn definitions(&self) -> Vec<ToolDefinition> {
-        self.tools.values().map(|t| t.definition()).collect()
+        self.tools.values().map(|t| t.definition_with_examples()).collect()
     }
 
     /// Get all tool names
@@ -363,6 +546,76 @@ impl ToolRegistry {
     pub fn is_empty(&self) -> bool {
         self.tools.is_empty()
     }
+
+    /// Create a registry exposing only the named tools
+    ///
+    /// Names that are not registered are ignored.
+    pub fn subset(&self, names: &[&str]) -> Self {
+        let tools = names
+            .iter()
+            .filter_map(|&name| self.tools.get_key_value(name))
+            .map(|(name, tool)| (name.clone(), tool.clone()))
+            .collect();
+        Self { tools }
+    }
+
+    /// Namespace every tool name as `{prefix}_{name}`
+    ///
+    /// Lets two registries with overlapping tool names (e.g. two filesystem
+    /// backends) be merged without collisions. `definitions()` reports the
+    /// prefixed names, so the LLM calls the tools by their namespaced name.
+    pub fn with_prefix(self, prefix: &str) -> Self {
+        let tools = self
+            .tools
+            .into_values()
+            .map(|tool| {
+                let tool: DynTool = Arc::new(PrefixedTool::new(prefix, tool));
+                (tool.definition().name, tool)
+            })
+            .collect();
+        Self { tools }
+    }
+}
+
+/// Tool wrapper that exposes the inner tool under a prefixed name
+struct PrefixedTool {
+    name: String,
+    inner: DynTool,
+}
+
+impl PrefixedTool {
+    fn new(prefix: &str, inner: DynTool) -> Self {
+        Self {
+            name: format!("{}_{}", prefix, inner.definition().name),
+            inner,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PrefixedTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: self.name.clone(),
+            ..self.inner.definition()
+        }
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        self.inner.execute(args, runtime).await
+    }
+
+    fn is_concurrent_safe(&self) -> bool {
+        self.inner.is_concurrent_safe()
+    }
+
+    fn examples(&self) -> Vec<serde_json::Value> {
+        self.inner.examples()
+    }
 }
 
 impl std::fmt::Debug for ToolRegistry {
@@ -382,6 +635,7 @@ impl std::fmt::Debug for ToolRegistry {
 /// - modify_system_prompt(): 시스템 프롬프트 수정 (체이닝)
 /// - before_agent() / after_agent(): 에이전트 라이프사이클 훅
 /// - before_model() / after_model(): LLM 호출 전후 훅 (NEW)
+/// - after_tool(): 도구 결과가 메시지 히스토리에 추가되기 전 훅
 ///
 /// # Example
 ///
@@ -420,6 +674,20 @@ pub trait AgentMiddleware: Send + Sync {
         prompt
     }
 
+    /// 시스템 프롬프트에 기여할 이름 있는 섹션
+    ///
+    /// 기본 구현은 `modify_system_prompt`가 빈 프롬프트에 덧붙이는 내용을
+    /// 미들웨어 이름으로 된 섹션 하나로 감쌉니다.
+    fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        let content = self.modify_system_prompt(String::new());
+        let content = content.trim();
+        if content.is_empty() {
+            vec![]
+        } else {
+            vec![PromptSection::new(self.name(), content)]
+        }
+    }
+
     // =========================================================================
     // Agent Lifecycle Hooks
     // =========================================================================
@@ -455,6 +723,7 @@ pub trait AgentMiddleware: Send + Sync {
     /// 사용 사례:
     /// - 메시지/도구 수정 (`ModelControl::ModifyRequest`)
     /// - 캐시된 응답 반환 (`ModelControl::Skip`)
+    /// - 정책 위반 시 직접 답변 (`ModelControl::Respond`)
     /// - 요청 로깅/모니터링
     ///
     /// # Returns
@@ -462,6 +731,7 @@ pub trait AgentMiddleware: Send + Sync {
     /// - `ModelControl::Continue` - 정상 진행
     /// - `ModelControl::ModifyRequest(req)` - 수정된 요청으로 진행
     /// - `ModelControl::Skip(resp)` - LLM 호출 건너뛰고 이 응답 사용
+    /// - `ModelControl::Respond(msg)` - LLM 호출 없이 이 메시지로 답변
     /// - `ModelControl::Interrupt(req)` - 실행 인터럽트
     async fn before_model(
         &self,
@@ -483,6 +753,7 @@ pub trait AgentMiddleware: Send + Sync {
     ///
     /// - `ModelControl::Continue` - 정상 진행
     /// - `ModelControl::Interrupt(req)` - 인간 승인 대기
+    /// - `ModelControl::RejectToolCalls(map)` - 지정한 도구 호출 거부
     async fn after_model(
         &self,
         _response: &ModelResponse,
@@ -491,6 +762,25 @@ pub trait AgentMiddleware: Send + Sync {
     ) -> Result<ModelControl, MiddlewareError> {
         Ok(ModelControl::Continue)
     }
+
+    // =========================================================================
+    // Tool Call Hooks
+    // =========================================================================
+
+    /// 도구 실행 후 훅 - 결과가 메시지 히스토리에 추가되기 전에 호출
+    ///
+    /// 결과를 직접 수정할 수 있습니다 (예: 비밀값 마스킹).
+    /// 큰 결과의 파일 축출(eviction)보다 먼저 실행되므로 원본 전체가 전달됩니다.
+    /// 정책에 의해 거부되어 실행되지 않은 호출에는 호출되지 않습니다.
+    async fn after_tool(
+        &self,
+        _call: &ToolCall,
+        _result: &mut ToolResult,
+        _state: &AgentState,
+        _runtime: &ToolRuntime,
+    ) -> Result<(), MiddlewareError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -509,6 +799,7 @@ mod tests {
                     "type": "object",
                     "properties": {}
                 }),
+                examples: Vec::new(),
             }
         }
 
@@ -521,6 +812,102 @@ mod tests {
         }
     }
 
+    struct BoundedTool;
+
+    #[async_trait]
+    impl Tool for BoundedTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "bounded".to_string(),
+                description: "Tool with a bounded integer argument".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {"type": "string"},
+                        "max_results": {"type": "integer", "maximum": 10}
+                    },
+                    "required": ["query"]
+                }),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            Ok(ToolResult::new("ok"))
+        }
+    }
+
+    #[test]
+    fn test_validate_args_accepts_valid_arguments() {
+        let args = serde_json::json!({"query": "rust", "max_results": 10});
+        assert!(BoundedTool.validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_args_rejects_maximum_violation() {
+        let args = serde_json::json!({"query": "rust", "max_results": 11});
+        let err = BoundedTool.validate_args(&args).unwrap_err();
+
+        let message = err.to_string();
+        assert!(matches!(err, MiddlewareError::InvalidToolArguments { ref tool_name, .. } if tool_name == "bounded"));
+        assert!(message.contains("/max_results"), "{}", message);
+        assert!(message.contains("maximum of 10"), "{}", message);
+    }
+
+    #[test]
+    fn test_validate_args_rejects_missing_required() {
+        let args = serde_json::json!({"max_results": 3});
+        let message = BoundedTool.validate_args(&args).unwrap_err().to_string();
+
+        assert!(message.contains("\"query\" is a required property"), "{}", message);
+    }
+
+    #[test]
+    fn test_validate_args_skips_invalid_schema() {
+        struct BrokenSchemaTool;
+
+        #[async_trait]
+        impl Tool for BrokenSchemaTool {
+            fn definition(&self) -> ToolDefinition {
+                ToolDefinition {
+                    name: "broken".to_string(),
+                    description: "Schema with an invalid type keyword".to_string(),
+                    parameters: serde_json::json!({"type": 42}),
+                    examples: Vec::new(),
+                }
+            }
+
+            async fn execute(
+                &self,
+                _args: serde_json::Value,
+                _runtime: &ToolRuntime,
+            ) -> Result<ToolResult, MiddlewareError> {
+                Ok(ToolResult::new("ok"))
+            }
+        }
+
+        assert!(BrokenSchemaTool.validate_args(&serde_json::json!({})).is_ok());
+    }
+
+    #[test]
+    fn test_cached_validator_reuses_and_recompiles_on_schema_change() {
+        let loose = serde_json::json!({"type": "object"});
+        let strict = serde_json::json!({"type": "object", "required": ["query"]});
+
+        let first = cached_validator("cache_probe", &loose).unwrap();
+        let second = cached_validator("cache_probe", &loose).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // Same name with a different schema gets a fresh validator
+        let changed = cached_validator("cache_probe", &strict).unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+        assert!(!changed.is_valid(&serde_json::json!({})));
+    }
+
     struct MockMiddleware;
 
     #[async_trait]
@@ -546,6 +933,89 @@ mod tests {
         assert_eq!(tools[0].definition().name, "mock_tool");
     }
 
+    struct NamedTool(&'static str);
+
+    #[async_trait]
+    impl Tool for NamedTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: self.0.to_string(),
+                description: format!("{} tool", self.0),
+                parameters: serde_json::json!({"type": "object", "properties": {}}),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            Ok(ToolResult::new(self.0))
+        }
+
+        fn is_concurrent_safe(&self) -> bool {
+            true
+        }
+
+        fn examples(&self) -> Vec<serde_json::Value> {
+            vec![serde_json::json!({"path": "/notes.md"})]
+        }
+    }
+
+    fn registry_of(names: &[&'static str]) -> ToolRegistry {
+        let mut registry = ToolRegistry::new();
+        registry.register_all(names.iter().map(|&n| Arc::new(NamedTool(n)) as DynTool).collect());
+        registry
+    }
+
+    #[test]
+    fn test_tool_registry_subset() {
+        let registry = registry_of(&["read_file", "write_file", "grep"]);
+
+        let subset = registry.subset(&["read_file", "grep", "missing"]);
+        let mut names = subset.names();
+        names.sort();
+        assert_eq!(names, vec!["grep", "read_file"]);
+        assert_eq!(subset.definitions().len(), 2);
+        assert!(!subset.contains("write_file"));
+        // 원본 레지스트리는 그대로 유지
+        assert_eq!(registry.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_tool_registry_with_prefix_rewrites_schema_names() {
+        let mut merged = registry_of(&["read_file", "write_file"]).with_prefix("fs");
+        merged.register_all(
+            registry_of(&["read_file"])
+                .with_prefix("s3")
+                .subset(&["s3_read_file"])
+                .tools
+                .into_values()
+                .collect(),
+        );
+
+        let mut names = merged.names();
+        names.sort();
+        assert_eq!(names, vec!["fs_read_file", "fs_write_file", "s3_read_file"]);
+
+        let mut definitions = merged.definitions();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(definitions[0].name, "fs_read_file");
+        assert_eq!(definitions[0].description, "read_file tool");
+        // 래핑된 도구의 호출 예시도 정의에 포함
+        assert_eq!(definitions[0].examples, vec![serde_json::json!({"path": "/notes.md"})]);
+
+        let tool = merged.get("s3_read_file").unwrap();
+        assert!(tool.is_concurrent_safe());
+        let runtime = ToolRuntime::new(
+            AgentState::new(),
+            Arc::new(crate::backends::MemoryBackend::new()),
+        );
+        let result = tool.execute(serde_json::json!({}), &runtime).await.unwrap();
+        assert_eq!(result.message, "read_file");
+    }
+
     #[test]
     fn test_middleware_prompt_modification() {
         let middleware = MockMiddleware;
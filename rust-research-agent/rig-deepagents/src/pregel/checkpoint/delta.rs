@@ -0,0 +1,181 @@
+//! Structural JSON diffs for incremental checkpoints
+//!
+//! Delta checkpoints are computed on the serialized form of a checkpoint, so any
+//! state that already round-trips through serde can be stored incrementally
+//! without implementing a diff by hand. Objects are patched field by field and
+//! arrays that only grew (the common case for accumulated findings or messages)
+//! are stored as appends; everything else is replaced wholesale.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Difference between two JSON values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(super) enum JsonDelta {
+    /// Replace the value wholesale
+    Replace { value: Value },
+
+    /// Append items to an array whose existing prefix is unchanged
+    Append { items: Vec<Value> },
+
+    /// Patch individual fields of an object
+    Patch {
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        set: BTreeMap<String, JsonDelta>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        removed: Vec<String>,
+    },
+}
+
+impl JsonDelta {
+    /// Delta that leaves the value unchanged
+    pub(super) fn unchanged() -> Self {
+        JsonDelta::Patch {
+            set: BTreeMap::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Compute the delta turning `previous` into `next`
+    ///
+    /// Returns `None` when both values are equal.
+    pub(super) fn between(previous: &Value, next: &Value) -> Option<Self> {
+        if previous == next {
+            return None;
+        }
+
+        match (previous, next) {
+            (Value::Object(prev), Value::Object(next)) => {
+                let set = next
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let delta = match prev.get(key) {
+                            Some(old) => Self::between(old, value)?,
+                            None => JsonDelta::Replace { value: value.clone() },
+                        };
+                        Some((key.clone(), delta))
+                    })
+                    .collect();
+                let removed = prev
+                    .keys()
+                    .filter(|key| !next.contains_key(*key))
+                    .cloned()
+                    .collect();
+                Some(JsonDelta::Patch { set, removed })
+            }
+            (Value::Array(prev), Value::Array(next))
+                if next.len() > prev.len() && next[..prev.len()] == prev[..] =>
+            {
+                Some(JsonDelta::Append {
+                    items: next[prev.len()..].to_vec(),
+                })
+            }
+            _ => Some(JsonDelta::Replace { value: next.clone() }),
+        }
+    }
+
+    /// Apply this delta to `base` in place
+    pub(super) fn apply(self, base: &mut Value) -> Result<(), String> {
+        match self {
+            JsonDelta::Replace { value } => {
+                *base = value;
+                Ok(())
+            }
+            JsonDelta::Append { items } => match base {
+                Value::Array(array) => {
+                    array.extend(items);
+                    Ok(())
+                }
+                other => Err(format!("cannot append to non-array value {}", other)),
+            },
+            JsonDelta::Patch { set, removed } => {
+                if set.is_empty() && removed.is_empty() {
+                    return Ok(());
+                }
+                let object: &mut Map<String, Value> = base
+                    .as_object_mut()
+                    .ok_or_else(|| "cannot patch fields of a non-object value".to_string())?;
+                for key in removed {
+                    object.remove(&key);
+                }
+                for (key, delta) in set {
+                    let slot = object.entry(key.clone()).or_insert(Value::Null);
+                    delta.apply(slot).map_err(|e| format!("{}: {}", key, e))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// On-disk representation of a checkpoint written in delta mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(super) enum StoredCheckpoint {
+    /// Complete serialized checkpoint
+    Full { checkpoint: Value },
+
+    /// Changes relative to the checkpoint saved at superstep `base`
+    Delta { base: usize, delta: JsonDelta },
+}
+
+impl StoredCheckpoint {
+    /// Interpret a checkpoint file's contents
+    ///
+    /// Files written without delta mode hold a bare checkpoint and are read as full.
+    pub(super) fn from_value(value: Value) -> Result<Self, serde_json::Error> {
+        if value.get("kind").is_some() {
+            serde_json::from_value(value)
+        } else {
+            Ok(StoredCheckpoint::Full { checkpoint: value })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn roundtrip(previous: Value, next: Value) -> Option<JsonDelta> {
+        let delta = JsonDelta::between(&previous, &next);
+        let mut rebuilt = previous;
+        if let Some(delta) = delta.clone() {
+            delta.apply(&mut rebuilt).unwrap();
+        }
+        assert_eq!(rebuilt, next);
+        delta
+    }
+
+    #[test]
+    fn test_equal_values_have_no_delta() {
+        assert!(roundtrip(json!({"a": [1, 2]}), json!({"a": [1, 2]})).is_none());
+    }
+
+    #[test]
+    fn test_grown_array_is_appended() {
+        let delta = roundtrip(json!({"notes": ["a"]}), json!({"notes": ["a", "b", "c"]}));
+        let Some(JsonDelta::Patch { set, removed }) = delta else {
+            panic!("expected object patch");
+        };
+        assert!(removed.is_empty());
+        assert_eq!(
+            set["notes"],
+            JsonDelta::Append {
+                items: vec![json!("b"), json!("c")]
+            }
+        );
+    }
+
+    #[test]
+    fn test_nested_changes_and_removals() {
+        roundtrip(
+            json!({"phase": "explore", "meta": {"depth": 1, "old": true}, "gone": null}),
+            json!({"phase": "synthesize", "meta": {"depth": 2, "new": [1]}, "extra": null}),
+        );
+        roundtrip(json!([3, 2, 1]), json!([1, 2]));
+        roundtrip(json!({"a": 1}), json!("scalar"));
+    }
+}
@@ -1,7 +1,11 @@
 //! File-based Checkpointer Implementation
 //!
 //! Stores checkpoints as JSON files in a directory structure.
-//! Supports optional compression via zstd for reduced storage.
+//! Supports optional compression via zstd, at a configurable level, for reduced storage.
+//!
+//! In delta mode each checkpoint after the first stores only the changes against
+//! the previously saved one (see [`FileCheckpointer::with_delta`]). Loading walks
+//! back to the nearest full checkpoint and replays the deltas forward.
 //!
 //! # Directory Structure
 //!
@@ -15,12 +19,15 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-use super::{Checkpoint, Checkpointer};
+use super::delta::{JsonDelta, StoredCheckpoint};
+use super::{Checkpoint, Checkpointer, Compression};
 use crate::pregel::error::PregelError;
 use crate::pregel::state::WorkflowState;
 
@@ -32,8 +39,21 @@ use crate::pregel::state::WorkflowState;
 pub struct FileCheckpointer {
     /// Workflow-specific subdirectory
     workflow_path: PathBuf,
-    /// Whether to compress checkpoints with zstd
-    compression: bool,
+    /// Compression applied when writing checkpoints
+    compression: Compression,
+    /// Store checkpoints as deltas against the previous one
+    delta: bool,
+    /// Last checkpoint written in delta mode, used as the base of the next delta
+    last_saved: Mutex<Option<SavedCheckpoint>>,
+}
+
+/// Serialized form of the most recently saved checkpoint
+#[derive(Debug)]
+struct SavedCheckpoint {
+    superstep: usize,
+    value: Value,
+    /// Number of deltas since the last full checkpoint
+    chain_len: usize,
 }
 
 impl FileCheckpointer {
@@ -43,20 +63,43 @@ impl FileCheckpointer {
     ///
     /// * `base_path` - Base directory for storing checkpoints
     /// * `workflow_id` - Unique identifier for this workflow
-    /// * `compression` - Whether to compress checkpoint data
-    pub fn new(base_path: impl Into<PathBuf>, workflow_id: impl AsRef<str>, compression: bool) -> Self {
+    /// * `compression` - Compression to apply; a `bool` selects zstd at the default level
+    pub fn new(
+        base_path: impl Into<PathBuf>,
+        workflow_id: impl AsRef<str>,
+        compression: impl Into<Compression>,
+    ) -> Self {
         let base_path = base_path.into();
         let workflow_path = base_path.join(workflow_id.as_ref());
 
         Self {
             workflow_path,
-            compression,
+            compression: compression.into(),
+            delta: false,
+            last_saved: Mutex::new(None),
         }
     }
 
+    /// Longest run of delta checkpoints before a full checkpoint is written again
+    ///
+    /// Bounds the number of files read when loading a checkpoint.
+    pub const MAX_DELTA_CHAIN: usize = 16;
+
+    /// Enable or disable delta checkpoints
+    ///
+    /// In delta mode the first checkpoint written by this instance is stored in
+    /// full and every later one only records what changed since the previous
+    /// save. A full checkpoint is written again every [`Self::MAX_DELTA_CHAIN`]
+    /// saves, or whenever a superstep is saved out of order (e.g. after resuming
+    /// from an earlier checkpoint).
+    pub fn with_delta(mut self, delta: bool) -> Self {
+        self.delta = delta;
+        self
+    }
+
     /// Get the file path for a checkpoint at a given superstep
     fn checkpoint_path(&self, superstep: usize) -> PathBuf {
-        let filename = if self.compression {
+        let filename = if self.compression.is_enabled() {
             format!("checkpoint_{:05}.json.zst", superstep)
         } else {
             format!("checkpoint_{:05}.json", superstep)
@@ -77,9 +120,9 @@ impl FileCheckpointer {
             .map_err(|e| PregelError::checkpoint_error(format!("Failed to create directory: {}", e)))
     }
 
-    /// Compress data using zstd
-    fn compress(data: &[u8]) -> Result<Vec<u8>, PregelError> {
-        let mut encoder = zstd::stream::Encoder::new(Vec::new(), 3)
+    /// Compress data using zstd at the given level
+    fn compress(data: &[u8], level: i32) -> Result<Vec<u8>, PregelError> {
+        let mut encoder = zstd::stream::Encoder::new(Vec::new(), level)
             .map_err(|e| PregelError::checkpoint_error(format!("Compression init failed: {}", e)))?;
         encoder
             .write_all(data)
@@ -111,55 +154,19 @@ impl FileCheckpointer {
         num_part.parse().ok()
     }
 
-    /// List all superstep numbers (non-generic helper method)
-    async fn list_supersteps(&self) -> Result<Vec<usize>, PregelError> {
-        if !self.workflow_path.exists() {
-            return Ok(Vec::new());
-        }
-
-        let mut entries = fs::read_dir(&self.workflow_path)
-            .await
-            .map_err(|e| PregelError::checkpoint_error(format!("Failed to read directory: {}", e)))?;
-
-        let mut supersteps = Vec::new();
-
-        while let Some(entry) = entries
-            .next_entry()
-            .await
-            .map_err(|e| PregelError::checkpoint_error(format!("Failed to read entry: {}", e)))?
-        {
-            if let Some(superstep) = Self::parse_superstep(&entry.path()) {
-                supersteps.push(superstep);
-            }
-        }
-
-        supersteps.sort();
-        Ok(supersteps)
-    }
-}
-
-#[async_trait]
-impl<S> Checkpointer<S> for FileCheckpointer
-where
-    S: WorkflowState + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de>,
-{
-    async fn save(&self, checkpoint: &Checkpoint<S>) -> Result<(), PregelError> {
+    /// Write checkpoint data atomically, compressing it if configured
+    async fn write_atomic(&self, superstep: usize, json: Vec<u8>) -> Result<(), PregelError> {
         self.ensure_dir().await?;
 
-        // Serialize checkpoint
-        let json = serde_json::to_vec_pretty(checkpoint)
-            .map_err(|e| PregelError::checkpoint_error(format!("Serialization failed: {}", e)))?;
-
         // Optionally compress
-        let data = if self.compression {
-            Self::compress(&json)?
-        } else {
-            json
+        let data = match self.compression {
+            Compression::Zstd(level) => Self::compress(&json, level)?,
+            Compression::None => json,
         };
 
         // Write to temp file first (atomic write pattern)
-        let temp_path = self.temp_path(checkpoint.superstep);
-        let final_path = self.checkpoint_path(checkpoint.superstep);
+        let temp_path = self.temp_path(superstep);
+        let final_path = self.checkpoint_path(superstep);
 
         let mut file = fs::File::create(&temp_path)
             .await
@@ -181,7 +188,8 @@ where
         Ok(())
     }
 
-    async fn load(&self, superstep: usize) -> Result<Option<Checkpoint<S>>, PregelError> {
+    /// Read and decompress a checkpoint file, returning `None` if it doesn't exist
+    async fn read_json(&self, superstep: usize) -> Result<Option<Vec<u8>>, PregelError> {
         let path = self.checkpoint_path(superstep);
 
         if !path.exists() {
@@ -198,13 +206,176 @@ where
             .map_err(|e| PregelError::checkpoint_error(format!("Failed to read file: {}", e)))?;
 
         // Decompress if needed
-        let json = if self.compression {
-            Self::decompress(&data)?
+        if self.compression.is_enabled() {
+            Self::decompress(&data).map(Some)
         } else {
-            data
+            Ok(Some(data))
+        }
+    }
+
+    /// Read a checkpoint file as stored, without resolving deltas
+    async fn read_stored(&self, superstep: usize) -> Result<Option<StoredCheckpoint>, PregelError> {
+        let Some(json) = self.read_json(superstep).await? else {
+            return Ok(None);
         };
+        let value: Value = serde_json::from_slice(&json)
+            .map_err(|e| PregelError::checkpoint_error(format!("Deserialization failed: {}", e)))?;
+        StoredCheckpoint::from_value(value)
+            .map(Some)
+            .map_err(|e| PregelError::checkpoint_error(format!("Deserialization failed: {}", e)))
+    }
+
+    /// Load the serialized checkpoint at `superstep`, replaying any deltas
+    async fn load_value(&self, superstep: usize) -> Result<Option<Value>, PregelError> {
+        let mut deltas = Vec::new();
+        let mut current = superstep;
+
+        let mut value = loop {
+            match self.read_stored(current).await? {
+                None if deltas.is_empty() => return Ok(None),
+                None => {
+                    return Err(PregelError::checkpoint_error(format!(
+                        "Delta checkpoint chain for superstep {} is missing base checkpoint {}",
+                        superstep, current
+                    )))
+                }
+                Some(StoredCheckpoint::Full { checkpoint }) => break checkpoint,
+                Some(StoredCheckpoint::Delta { base, delta }) => {
+                    if base >= current {
+                        return Err(PregelError::checkpoint_error(format!(
+                            "Delta checkpoint {} has invalid base {}",
+                            current, base
+                        )));
+                    }
+                    deltas.push(delta);
+                    current = base;
+                }
+            }
+        };
+
+        for delta in deltas.into_iter().rev() {
+            delta.apply(&mut value).map_err(|e| {
+                PregelError::checkpoint_error(format!("Failed to apply checkpoint delta: {}", e))
+            })?;
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Rewrite deltas based on `superstep` as full checkpoints so it can be deleted
+    async fn detach_dependents(&self, superstep: usize) -> Result<(), PregelError> {
+        for later in self.list_supersteps().await? {
+            if later <= superstep {
+                continue;
+            }
+            if let Some(StoredCheckpoint::Delta { base, .. }) = self.read_stored(later).await? {
+                if base == superstep {
+                    if let Some(checkpoint) = self.load_value(later).await? {
+                        self.write_stored(later, &StoredCheckpoint::Full { checkpoint })
+                            .await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize and write a delta-mode checkpoint file
+    async fn write_stored(
+        &self,
+        superstep: usize,
+        stored: &StoredCheckpoint,
+    ) -> Result<(), PregelError> {
+        let json = serde_json::to_vec_pretty(stored)
+            .map_err(|e| PregelError::checkpoint_error(format!("Serialization failed: {}", e)))?;
+        self.write_atomic(superstep, json).await
+    }
+
+    /// Decide whether the next delta-mode checkpoint is stored in full or as a delta
+    fn encode(&self, superstep: usize, value: &Value) -> (StoredCheckpoint, usize) {
+        let last = self.last_saved.lock().unwrap();
+        match last.as_ref() {
+            Some(prev) if prev.superstep < superstep && prev.chain_len < Self::MAX_DELTA_CHAIN => {
+                let delta = JsonDelta::between(&prev.value, value).unwrap_or_else(JsonDelta::unchanged);
+                (
+                    StoredCheckpoint::Delta {
+                        base: prev.superstep,
+                        delta,
+                    },
+                    prev.chain_len + 1,
+                )
+            }
+            _ => (
+                StoredCheckpoint::Full {
+                    checkpoint: value.clone(),
+                },
+                0,
+            ),
+        }
+    }
+
+    /// List all superstep numbers (non-generic helper method)
+    async fn list_supersteps(&self) -> Result<Vec<usize>, PregelError> {
+        if !self.workflow_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = fs::read_dir(&self.workflow_path)
+            .await
+            .map_err(|e| PregelError::checkpoint_error(format!("Failed to read directory: {}", e)))?;
 
-        let checkpoint: Checkpoint<S> = serde_json::from_slice(&json)
+        let mut supersteps = Vec::new();
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| PregelError::checkpoint_error(format!("Failed to read entry: {}", e)))?
+        {
+            if let Some(superstep) = Self::parse_superstep(&entry.path()) {
+                supersteps.push(superstep);
+            }
+        }
+
+        supersteps.sort();
+        Ok(supersteps)
+    }
+}
+
+#[async_trait]
+impl<S> Checkpointer<S> for FileCheckpointer
+where
+    S: WorkflowState + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de>,
+{
+    async fn save(&self, checkpoint: &Checkpoint<S>) -> Result<(), PregelError> {
+        if !self.delta {
+            // Serialize checkpoint
+            let json = serde_json::to_vec_pretty(checkpoint).map_err(|e| {
+                PregelError::checkpoint_error(format!("Serialization failed: {}", e))
+            })?;
+            return self.write_atomic(checkpoint.superstep, json).await;
+        }
+
+        let value = serde_json::to_value(checkpoint)
+            .map_err(|e| PregelError::checkpoint_error(format!("Serialization failed: {}", e)))?;
+        let (stored, chain_len) = self.encode(checkpoint.superstep, &value);
+        self.write_stored(checkpoint.superstep, &stored).await?;
+
+        // Only advance the delta base once the write has succeeded
+        *self.last_saved.lock().unwrap() = Some(SavedCheckpoint {
+            superstep: checkpoint.superstep,
+            value,
+            chain_len,
+        });
+
+        Ok(())
+    }
+
+    async fn load(&self, superstep: usize) -> Result<Option<Checkpoint<S>>, PregelError> {
+        let Some(value) = self.load_value(superstep).await? else {
+            return Ok(None);
+        };
+
+        let checkpoint: Checkpoint<S> = serde_json::from_value(value)
             .map_err(|e| PregelError::checkpoint_error(format!("Deserialization failed: {}", e)))?;
 
         Ok(Some(checkpoint))
@@ -224,6 +395,15 @@ where
     }
 
     async fn delete(&self, superstep: usize) -> Result<(), PregelError> {
+        if self.delta {
+            // Later deltas must not lose their base
+            self.detach_dependents(superstep).await?;
+            let mut last = self.last_saved.lock().unwrap();
+            if last.as_ref().is_some_and(|prev| prev.superstep == superstep) {
+                *last = None;
+            }
+        }
+
         let path = self.checkpoint_path(superstep);
 
         if path.exists() {
@@ -239,7 +419,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pregel::state::UnitState;
+    use crate::pregel::state::{UnitState, UnitUpdate};
     use crate::pregel::vertex::{VertexId, VertexState};
     use std::collections::HashMap;
     use tempfile::tempdir;
@@ -294,6 +474,29 @@ mod tests {
         assert_eq!(loaded.vertex_states.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_file_checkpointer_with_compression_level() {
+        let temp_dir = tempdir().unwrap();
+        let checkpointer =
+            FileCheckpointer::new(temp_dir.path(), "level-workflow", Compression::Zstd(19));
+
+        let vertex_states: HashMap<_, _> = (0..200)
+            .map(|i| (VertexId::new(format!("vertex_{}", i)), VertexState::Halted))
+            .collect();
+        let checkpoint = Checkpoint::new("level-workflow", 7, UnitState, vertex_states, HashMap::new());
+
+        checkpointer.save(&checkpoint).await.unwrap();
+
+        let path = temp_dir.path().join("level-workflow/checkpoint_00007.json.zst");
+        let compressed_len = std::fs::metadata(&path).unwrap().len() as usize;
+        let json_len = serde_json::to_vec_pretty(&checkpoint).unwrap().len();
+        assert!(compressed_len < json_len);
+
+        let loaded: Checkpoint<UnitState> = checkpointer.load(7).await.unwrap().unwrap();
+        assert_eq!(loaded.superstep, 7);
+        assert_eq!(loaded.vertex_states, checkpoint.vertex_states);
+    }
+
     #[tokio::test]
     async fn test_file_checkpointer_load_nonexistent() {
         let temp_dir = tempdir().unwrap();
@@ -435,4 +638,100 @@ mod tests {
             None
         );
     }
+
+    /// State that accumulates notes, like a research workflow
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct NotesState {
+        phase: String,
+        notes: Vec<String>,
+    }
+
+    impl WorkflowState for NotesState {
+        type Update = UnitUpdate;
+
+        fn apply_update(&self, _update: Self::Update) -> Self {
+            self.clone()
+        }
+
+        fn merge_updates(_updates: Vec<Self::Update>) -> Self::Update {
+            UnitUpdate
+        }
+    }
+
+    fn notes_checkpoint(superstep: usize) -> Checkpoint<NotesState> {
+        let state = NotesState {
+            phase: if superstep < 4 { "explore" } else { "synthesize" }.to_string(),
+            notes: (0..superstep * 20).map(|i| format!("finding number {}", i)).collect(),
+        };
+        let vertex_states = HashMap::from([(
+            VertexId::new("researcher"),
+            if superstep % 2 == 0 { VertexState::Active } else { VertexState::Halted },
+        )]);
+        Checkpoint::new("delta-workflow", superstep, state, vertex_states, HashMap::new())
+            .with_metadata("step", superstep.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpointer_delta_matches_full() {
+        let temp_dir = tempdir().unwrap();
+        let full = FileCheckpointer::new(temp_dir.path().join("full"), "delta-workflow", false);
+        let delta = FileCheckpointer::new(temp_dir.path().join("delta"), "delta-workflow", false)
+            .with_delta(true);
+
+        for superstep in 1..=6 {
+            let checkpoint = notes_checkpoint(superstep);
+            full.save(&checkpoint).await.unwrap();
+            delta.save(&checkpoint).await.unwrap();
+        }
+
+        for superstep in 1..=6 {
+            let expected: Checkpoint<NotesState> = full.load(superstep).await.unwrap().unwrap();
+            let rebuilt: Checkpoint<NotesState> = delta.load(superstep).await.unwrap().unwrap();
+            assert_eq!(
+                serde_json::to_value(&rebuilt).unwrap(),
+                serde_json::to_value(&expected).unwrap()
+            );
+        }
+
+        // Later checkpoints only store what changed
+        let file_len = |dir: &str| {
+            std::fs::metadata(temp_dir.path().join(dir).join("delta-workflow/checkpoint_00006.json"))
+                .unwrap()
+                .len()
+        };
+        assert!(file_len("delta") * 3 < file_len("full"));
+
+        let latest: Checkpoint<NotesState> = delta.latest().await.unwrap().unwrap();
+        assert_eq!(latest.state.notes.len(), 120);
+        assert_eq!(latest.state.phase, "synthesize");
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpointer_delta_survives_prune() {
+        let temp_dir = tempdir().unwrap();
+        let checkpointer =
+            FileCheckpointer::new(temp_dir.path(), "delta-workflow", true).with_delta(true);
+
+        for superstep in 1..=5 {
+            checkpointer.save(&notes_checkpoint(superstep)).await.unwrap();
+        }
+
+        // Pruning removes the full base checkpoint the deltas were built on
+        let deleted =
+            <FileCheckpointer as Checkpointer<NotesState>>::prune(&checkpointer, 2).await.unwrap();
+        assert_eq!(deleted, 3);
+
+        for superstep in [4, 5] {
+            let loaded: Checkpoint<NotesState> = checkpointer.load(superstep).await.unwrap().unwrap();
+            let expected = notes_checkpoint(superstep);
+            assert_eq!(loaded.superstep, superstep);
+            assert_eq!(loaded.state.notes, expected.state.notes);
+            assert_eq!(loaded.vertex_states, expected.vertex_states);
+        }
+
+        // Saving continues as deltas on top of the surviving chain
+        checkpointer.save(&notes_checkpoint(6)).await.unwrap();
+        let loaded: Checkpoint<NotesState> = checkpointer.load(6).await.unwrap().unwrap();
+        assert_eq!(loaded.state.notes.len(), 120);
+    }
 }
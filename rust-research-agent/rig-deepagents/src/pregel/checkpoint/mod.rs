@@ -28,7 +28,8 @@
 //! // Create a file-based checkpointer
 //! let config = CheckpointerConfig::File {
 //!     path: PathBuf::from("./checkpoints"),
-//!     compression: true,
+//!     compression: Compression::Zstd(3),
+//!     delta: false,
 //! };
 //! let checkpointer = create_checkpointer::<MyState>(config)?;
 //!
@@ -41,6 +42,7 @@
 //! }
 //! ```
 
+mod delta;
 mod file;
 #[cfg(feature = "checkpointer-sqlite")]
 mod sqlite;
@@ -106,6 +108,11 @@ where
     #[serde(default)]
     pub retry_counts: HashMap<VertexId, usize>,
 
+    /// Random number generator state per vertex (for deterministic replay of
+    /// randomized decisions such as weighted routing)
+    #[serde(default)]
+    pub rng_states: HashMap<VertexId, u64>,
+
     /// When this checkpoint was created
     pub timestamp: DateTime<Utc>,
 
@@ -133,6 +140,7 @@ where
             vertex_states,
             pending_messages,
             retry_counts: HashMap::new(),
+            rng_states: HashMap::new(),
             timestamp: Utc::now(),
             metadata: HashMap::new(),
         }
@@ -157,11 +165,18 @@ where
             vertex_states,
             pending_messages,
             retry_counts,
+            rng_states: HashMap::new(),
             timestamp: Utc::now(),
             metadata: HashMap::new(),
         }
     }
 
+    /// Attach per-vertex random number generator state
+    pub fn with_rng_states(mut self, rng_states: HashMap<VertexId, u64>) -> Self {
+        self.rng_states = rng_states;
+        self
+    }
+
     /// Add metadata to this checkpoint
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.metadata.insert(key.into(), value.into());
@@ -247,8 +262,14 @@ pub enum CheckpointerConfig {
     File {
         /// Directory to store checkpoint files
         path: PathBuf,
-        /// Whether to compress checkpoint data (uses zstd)
-        compression: bool,
+        /// Checkpoint compression (`true.into()` selects zstd at the default level)
+        compression: Compression,
+        /// Store each checkpoint as a diff against the previous one
+        ///
+        /// Worthwhile for large states that change little between supersteps.
+        /// Diffs are computed on the serialized state, so no extra trait bounds
+        /// are needed beyond serde.
+        delta: bool,
     },
 
     /// SQLite-based checkpointing (requires `checkpointer-sqlite` feature)
@@ -275,6 +296,36 @@ pub enum CheckpointerConfig {
     },
 }
 
+/// Compression applied to serialized checkpoint data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Store checkpoints uncompressed
+    #[default]
+    None,
+
+    /// Compress with zstd at the given level (1-22; higher is smaller but slower)
+    Zstd(i32),
+}
+
+impl Compression {
+    /// zstd level used when compression is enabled without an explicit level
+    pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+    /// Check whether any compression is applied
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Compression::None)
+    }
+}
+
+impl From<bool> for Compression {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Compression::Zstd(Self::DEFAULT_ZSTD_LEVEL)
+        } else {
+            Compression::None
+        }
+    }
+}
 
 /// In-memory checkpointer for testing.
 ///
@@ -349,7 +400,8 @@ where
 /// ```ignore
 /// let config = CheckpointerConfig::File {
 ///     path: PathBuf::from("./checkpoints"),
-///     compression: true,
+///     compression: Compression::Zstd(19),
+///     delta: true,
 /// };
 /// let checkpointer = create_checkpointer::<MyState>(config, "workflow-123")?;
 /// ```
@@ -365,8 +417,13 @@ where
     match config {
         CheckpointerConfig::Memory => Ok(Box::new(MemoryCheckpointer::<S>::new())),
 
-        CheckpointerConfig::File { path, compression } => {
-            let checkpointer = FileCheckpointer::new(path, workflow_id, compression);
+        CheckpointerConfig::File {
+            path,
+            compression,
+            delta,
+        } => {
+            let checkpointer =
+                FileCheckpointer::new(path, workflow_id, compression).with_delta(delta);
             Ok(Box::new(checkpointer))
         }
 
@@ -415,6 +472,17 @@ mod tests {
     use super::*;
     use crate::pregel::state::UnitState;
 
+    #[test]
+    fn test_compression_from_bool() {
+        assert_eq!(Compression::from(false), Compression::None);
+        assert_eq!(
+            Compression::from(true),
+            Compression::Zstd(Compression::DEFAULT_ZSTD_LEVEL)
+        );
+        assert!(!Compression::default().is_enabled());
+        assert!(Compression::Zstd(19).is_enabled());
+    }
+
     #[test]
     fn test_checkpoint_creation() {
         let checkpoint = Checkpoint::new(
@@ -1,20 +1,19 @@
 //! PostgreSQL-based Checkpointer Implementation
 //!
-//! Stores checkpoints in a PostgreSQL database for enterprise-grade persistence.
-//! Supports connection pooling and ACID transactions.
+//! Stores checkpoints in a PostgreSQL database for durable, queryable persistence.
+//! Each row holds the serialized `Checkpoint<S>` as JSONB, so checkpoints can be
+//! inspected and audited with plain SQL.
 //!
 //! # Schema
 //!
 //! ```sql
 //! CREATE TABLE IF NOT EXISTS checkpoints (
-//!     id SERIAL PRIMARY KEY,
 //!     workflow_id TEXT NOT NULL,
-//!     superstep INTEGER NOT NULL,
-//!     data BYTEA NOT NULL,
+//!     superstep BIGINT NOT NULL,
+//!     data JSONB NOT NULL,
 //!     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-//!     UNIQUE(workflow_id, superstep)
+//!     PRIMARY KEY (workflow_id, superstep)
 //! );
-//! CREATE INDEX IF NOT EXISTS idx_workflow_superstep ON checkpoints(workflow_id, superstep);
 //! ```
 //!
 //! # Usage
@@ -27,6 +26,15 @@
 //!     "my-workflow"
 //! ).await?;
 //! ```
+//!
+//! # Testing
+//!
+//! Integration tests run against the database in `DATABASE_URL` and are
+//! skipped when it is unset:
+//!
+//! ```text
+//! DATABASE_URL=postgres://localhost/test cargo test --features checkpointer-postgres postgres
+//! ```
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -46,8 +54,6 @@ pub struct PostgresCheckpointer {
     pool: PgPool,
     /// Workflow identifier for isolation
     workflow_id: String,
-    /// Whether to use compression
-    compression: bool,
 }
 
 impl PostgresCheckpointer {
@@ -69,15 +75,6 @@ impl PostgresCheckpointer {
     pub async fn new(
         url: impl AsRef<str>,
         workflow_id: impl Into<String>,
-    ) -> Result<Self, PregelError> {
-        Self::with_compression(url, workflow_id, false).await
-    }
-
-    /// Create a new PostgreSQL checkpointer with compression option.
-    pub async fn with_compression(
-        url: impl AsRef<str>,
-        workflow_id: impl Into<String>,
-        compression: bool,
     ) -> Result<Self, PregelError> {
         let pool = PgPoolOptions::new()
             .max_connections(5)
@@ -85,18 +82,24 @@ impl PostgresCheckpointer {
             .await
             .map_err(|e| PregelError::checkpoint_error(format!("Failed to connect to PostgreSQL: {}", e)))?;
 
-        let workflow_id = workflow_id.into();
+        Self::from_pool(pool, workflow_id).await
+    }
 
-        // Initialize schema
+    /// Create a checkpointer on an existing connection pool.
+    ///
+    /// Useful when the application already manages a pool for other tables.
+    pub async fn from_pool(
+        pool: PgPool,
+        workflow_id: impl Into<String>,
+    ) -> Result<Self, PregelError> {
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS checkpoints (
-                id SERIAL PRIMARY KEY,
                 workflow_id TEXT NOT NULL,
-                superstep INTEGER NOT NULL,
-                data BYTEA NOT NULL,
+                superstep BIGINT NOT NULL,
+                data JSONB NOT NULL,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-                UNIQUE(workflow_id, superstep)
+                PRIMARY KEY (workflow_id, superstep)
             )
             "#,
         )
@@ -104,40 +107,24 @@ impl PostgresCheckpointer {
         .await
         .map_err(|e| PregelError::checkpoint_error(format!("Failed to create schema: {}", e)))?;
 
-        sqlx::query(
-            r#"
-            CREATE INDEX IF NOT EXISTS idx_workflow_superstep
-                ON checkpoints(workflow_id, superstep)
-            "#,
-        )
-        .execute(&pool)
-        .await
-        .map_err(|e| PregelError::checkpoint_error(format!("Failed to create index: {}", e)))?;
-
         Ok(Self {
             pool,
-            workflow_id,
-            compression,
+            workflow_id: workflow_id.into(),
         })
     }
 
-    /// Compress data using zstd
-    fn compress(data: &[u8]) -> Result<Vec<u8>, PregelError> {
-        use std::io::Write;
-        let mut encoder = zstd::stream::Encoder::new(Vec::new(), 3)
-            .map_err(|e| PregelError::checkpoint_error(format!("Compression init failed: {}", e)))?;
-        encoder
-            .write_all(data)
-            .map_err(|e| PregelError::checkpoint_error(format!("Compression write failed: {}", e)))?;
-        encoder
-            .finish()
-            .map_err(|e| PregelError::checkpoint_error(format!("Compression finish failed: {}", e)))
+    /// Get the workflow ID
+    pub fn workflow_id(&self) -> &str {
+        &self.workflow_id
     }
 
-    /// Decompress data using zstd
-    fn decompress(data: &[u8]) -> Result<Vec<u8>, PregelError> {
-        zstd::stream::decode_all(data)
-            .map_err(|e| PregelError::checkpoint_error(format!("Decompression failed: {}", e)))
+    /// Deserialize a checkpoint from its JSONB text representation
+    fn decode<S>(data: &str) -> Result<Checkpoint<S>, PregelError>
+    where
+        S: WorkflowState + for<'de> Deserialize<'de>,
+    {
+        serde_json::from_str(data)
+            .map_err(|e| PregelError::checkpoint_error(format!("Deserialization failed: {}", e)))
     }
 }
 
@@ -147,29 +134,21 @@ where
     S: WorkflowState + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
     async fn save(&self, checkpoint: &Checkpoint<S>) -> Result<(), PregelError> {
-        // Serialize checkpoint
-        let json = serde_json::to_vec(checkpoint)
+        let json = serde_json::to_string(checkpoint)
             .map_err(|e| PregelError::checkpoint_error(format!("Serialization failed: {}", e)))?;
 
-        // Optionally compress
-        let data = if self.compression {
-            Self::compress(&json)?
-        } else {
-            json
-        };
-
         // Upsert using ON CONFLICT
         sqlx::query(
             r#"
             INSERT INTO checkpoints (workflow_id, superstep, data)
-            VALUES ($1, $2, $3)
+            VALUES ($1, $2, $3::jsonb)
             ON CONFLICT (workflow_id, superstep)
             DO UPDATE SET data = EXCLUDED.data, created_at = NOW()
             "#,
         )
         .bind(&self.workflow_id)
-        .bind(checkpoint.superstep as i32)
-        .bind(&data)
+        .bind(checkpoint.superstep as i64)
+        .bind(json)
         .execute(&self.pool)
         .await
         .map_err(|e| PregelError::checkpoint_error(format!("Failed to save checkpoint: {}", e)))?;
@@ -178,51 +157,37 @@ where
     }
 
     async fn load(&self, superstep: usize) -> Result<Option<Checkpoint<S>>, PregelError> {
-        let row: Option<(Vec<u8>,)> = sqlx::query_as(
-            "SELECT data FROM checkpoints WHERE workflow_id = $1 AND superstep = $2",
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT data::text FROM checkpoints WHERE workflow_id = $1 AND superstep = $2",
         )
         .bind(&self.workflow_id)
-        .bind(superstep as i32)
+        .bind(superstep as i64)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| PregelError::checkpoint_error(format!("Failed to load checkpoint: {}", e)))?;
 
-        match row {
-            Some((data,)) => {
-                // Decompress if needed
-                let json = if self.compression {
-                    Self::decompress(&data)?
-                } else {
-                    data
-                };
-
-                let checkpoint: Checkpoint<S> = serde_json::from_slice(&json).map_err(|e| {
-                    PregelError::checkpoint_error(format!("Deserialization failed: {}", e))
-                })?;
-
-                Ok(Some(checkpoint))
-            }
-            None => Ok(None),
-        }
+        row.map(|(data,)| Self::decode(&data)).transpose()
     }
 
     async fn latest(&self) -> Result<Option<Checkpoint<S>>, PregelError> {
-        let row: Option<(i32,)> = sqlx::query_as(
-            "SELECT MAX(superstep) FROM checkpoints WHERE workflow_id = $1",
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"
+            SELECT data::text FROM checkpoints
+            WHERE workflow_id = $1
+            ORDER BY superstep DESC
+            LIMIT 1
+            "#,
         )
         .bind(&self.workflow_id)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| PregelError::checkpoint_error(format!("Failed to get latest: {}", e)))?;
 
-        match row {
-            Some((superstep,)) => self.load(superstep as usize).await,
-            None => Ok(None),
-        }
+        row.map(|(data,)| Self::decode(&data)).transpose()
     }
 
     async fn list(&self) -> Result<Vec<usize>, PregelError> {
-        let rows: Vec<(i32,)> = sqlx::query_as(
+        let rows: Vec<(i64,)> = sqlx::query_as(
             "SELECT superstep FROM checkpoints WHERE workflow_id = $1 ORDER BY superstep ASC",
         )
         .bind(&self.workflow_id)
@@ -236,23 +201,115 @@ where
     async fn delete(&self, superstep: usize) -> Result<(), PregelError> {
         sqlx::query("DELETE FROM checkpoints WHERE workflow_id = $1 AND superstep = $2")
             .bind(&self.workflow_id)
-            .bind(superstep as i32)
+            .bind(superstep as i64)
             .execute(&self.pool)
             .await
             .map_err(|e| PregelError::checkpoint_error(format!("Failed to delete checkpoint: {}", e)))?;
 
         Ok(())
     }
+
+    async fn prune(&self, keep: usize) -> Result<usize, PregelError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM checkpoints
+            WHERE workflow_id = $1
+              AND superstep NOT IN (
+                  SELECT superstep FROM checkpoints
+                  WHERE workflow_id = $1
+                  ORDER BY superstep DESC
+                  LIMIT $2
+              )
+            "#,
+        )
+        .bind(&self.workflow_id)
+        .bind(keep as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PregelError::checkpoint_error(format!("Failed to prune checkpoints: {}", e)))?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn clear(&self) -> Result<(), PregelError> {
+        sqlx::query("DELETE FROM checkpoints WHERE workflow_id = $1")
+            .bind(&self.workflow_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PregelError::checkpoint_error(format!("Failed to clear checkpoints: {}", e)))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    // PostgreSQL tests require a running database, so they are marked as ignored
-    // Run with: cargo test --features checkpointer-postgres -- --ignored
+    // Integration tests require a running database. They connect to
+    // `DATABASE_URL` and return early when it is unset.
+    use super::*;
+    use crate::pregel::state::UnitState;
+    use crate::pregel::vertex::{VertexId, VertexState};
+    use std::collections::HashMap;
+
+    /// Connect with a unique workflow ID, or `None` when `DATABASE_URL` is unset
+    async fn checkpointer() -> Option<PostgresCheckpointer> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let workflow_id = format!("test-{}", uuid::Uuid::new_v4());
+        Some(PostgresCheckpointer::new(url, workflow_id).await.unwrap())
+    }
+
+    fn checkpoint(workflow_id: &str, superstep: usize) -> Checkpoint<UnitState> {
+        Checkpoint::new(workflow_id, superstep, UnitState, HashMap::new(), HashMap::new())
+    }
+
+    #[tokio::test]
+    async fn test_postgres_checkpointer_save_load() {
+        let Some(cp) = checkpointer().await else { return };
+
+        let mut vertex_states = HashMap::new();
+        vertex_states.insert(VertexId::new("vertex1"), VertexState::Halted);
+        let saved = Checkpoint::new(cp.workflow_id(), 3, UnitState, vertex_states, HashMap::new());
+
+        cp.save(&saved).await.unwrap();
+        let loaded: Checkpoint<UnitState> = cp.load(3).await.unwrap().unwrap();
+
+        assert_eq!(loaded.superstep, 3);
+        assert_eq!(loaded.workflow_id, cp.workflow_id());
+        assert_eq!(loaded.vertex_states.len(), 1);
+        assert!(Checkpointer::<UnitState>::load(&cp, 4).await.unwrap().is_none());
+
+        Checkpointer::<UnitState>::clear(&cp).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_postgres_checkpointer_latest_and_list() {
+        let Some(cp) = checkpointer().await else { return };
+        assert!(Checkpointer::<UnitState>::latest(&cp).await.unwrap().is_none());
+
+        for superstep in [2, 10, 5] {
+            cp.save(&checkpoint(cp.workflow_id(), superstep)).await.unwrap();
+        }
+
+        let latest: Checkpoint<UnitState> = cp.latest().await.unwrap().unwrap();
+        assert_eq!(latest.superstep, 10);
+        assert_eq!(Checkpointer::<UnitState>::list(&cp).await.unwrap(), vec![2, 5, 10]);
+
+        Checkpointer::<UnitState>::clear(&cp).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_postgres_checkpointer_prune_and_clear() {
+        let Some(cp) = checkpointer().await else { return };
+
+        for superstep in 1..=5 {
+            cp.save(&checkpoint(cp.workflow_id(), superstep)).await.unwrap();
+        }
+
+        let deleted = Checkpointer::<UnitState>::prune(&cp, 2).await.unwrap();
+        assert_eq!(deleted, 3);
+        assert_eq!(Checkpointer::<UnitState>::list(&cp).await.unwrap(), vec![4, 5]);
 
-    #[test]
-    fn test_postgres_checkpointer_compiles() {
-        // Basic compile-time check that the module is valid
-        assert!(true);
+        Checkpointer::<UnitState>::clear(&cp).await.unwrap();
+        assert!(Checkpointer::<UnitState>::list(&cp).await.unwrap().is_empty());
     }
 }
@@ -4,8 +4,11 @@
 //! parallelism, timeouts, checkpointing, and retry policies.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
+use super::vertex::VertexId;
+
 /// Execution mode for the Pregel runtime
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExecutionMode {
@@ -49,6 +52,28 @@ pub struct PregelConfig {
 
     /// Execution mode controlling vertex activation and edge routing
     pub execution_mode: ExecutionMode,
+
+    /// Per-vertex timeout/retry overrides (take precedence over the global defaults)
+    #[serde(default)]
+    pub vertex_overrides: HashMap<VertexId, VertexRuntimeConfig>,
+
+    /// Maximum compute activations for any single vertex (None = unlimited)
+    #[serde(default)]
+    pub max_activations_per_vertex: Option<usize>,
+
+    /// Seed for randomized decisions such as weighted routing (None = nondeterministic)
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// Apply vertex updates and route messages in sorted `VertexId` order
+    ///
+    /// Vertices still run concurrently, but their results are collected in
+    /// id order instead of hash-map order, so order-sensitive merges are
+    /// reproducible. The cost is a sort of the active set each superstep,
+    /// and results of fast vertices wait behind slower ones with smaller ids
+    /// before they are processed.
+    #[serde(default)]
+    pub deterministic: bool,
 }
 
 impl Default for PregelConfig {
@@ -62,6 +87,10 @@ impl Default for PregelConfig {
             tracing_enabled: true,
             retry_policy: RetryPolicy::default(),
             execution_mode: ExecutionMode::default(),
+            vertex_overrides: HashMap::new(),
+            max_activations_per_vertex: None,
+            seed: None,
+            deterministic: false,
         }
     }
 }
@@ -120,6 +149,50 @@ impl PregelConfig {
         self
     }
 
+    /// Limit how many times any single vertex may be computed in one run
+    pub fn with_max_activations_per_vertex(mut self, limit: usize) -> Self {
+        self.max_activations_per_vertex = Some(limit);
+        self
+    }
+
+    /// Make randomized decisions reproducible across runs
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Process vertex results in sorted `VertexId` order (see [`PregelConfig::deterministic`])
+    pub fn with_deterministic(mut self, enabled: bool) -> Self {
+        self.deterministic = enabled;
+        self
+    }
+
+    /// Override timeout/retry settings for a single vertex
+    pub fn with_vertex_override(
+        mut self,
+        vertex_id: impl Into<VertexId>,
+        overrides: VertexRuntimeConfig,
+    ) -> Self {
+        self.vertex_overrides.insert(vertex_id.into(), overrides);
+        self
+    }
+
+    /// Effective compute timeout for a vertex (override, then global default)
+    pub fn vertex_timeout_for(&self, vertex_id: &VertexId) -> Duration {
+        self.vertex_overrides
+            .get(vertex_id)
+            .and_then(|o| o.timeout)
+            .unwrap_or(self.vertex_timeout)
+    }
+
+    /// Effective retry policy for a vertex (override, then global default)
+    pub fn retry_policy_for(&self, vertex_id: &VertexId) -> &RetryPolicy {
+        self.vertex_overrides
+            .get(vertex_id)
+            .and_then(|o| o.retry_policy.as_ref())
+            .unwrap_or(&self.retry_policy)
+    }
+
     /// Check if checkpointing is enabled
     pub fn checkpointing_enabled(&self) -> bool {
         self.checkpoint_interval > 0
@@ -132,6 +205,53 @@ impl PregelConfig {
     }
 }
 
+/// Per-vertex runtime overrides
+///
+/// Unset fields fall back to the global [`PregelConfig`] values, so a slow
+/// LLM node can get a long timeout while tool nodes keep a short one.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rig_deepagents::pregel::{PregelConfig, VertexId, VertexRuntimeConfig};
+///
+/// let config = PregelConfig::default()
+///     .with_vertex_override("synthesizer", VertexRuntimeConfig::new().with_timeout(Duration::from_secs(120)))
+///     .with_vertex_override("web_search", VertexRuntimeConfig::new().with_timeout(Duration::from_secs(10)));
+///
+/// assert_eq!(config.vertex_timeout_for(&VertexId::new("web_search")), Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VertexRuntimeConfig {
+    /// Timeout for this vertex's computation
+    #[serde(default, with = "humantime_serde")]
+    pub timeout: Option<Duration>,
+
+    /// Retry policy for this vertex
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+impl VertexRuntimeConfig {
+    /// Create overrides with nothing set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the vertex timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the vertex retry policy
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+}
+
 /// Retry policy for failed vertex computations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetryPolicy {
@@ -239,6 +359,30 @@ mod tests {
         assert!(!disabled.checkpointing_enabled());
     }
 
+    #[test]
+    fn test_vertex_overrides_fall_back_to_defaults() {
+        let config = PregelConfig::default()
+            .with_vertex_timeout(Duration::from_secs(30))
+            .with_vertex_override(
+                "synthesizer",
+                VertexRuntimeConfig::new().with_timeout(Duration::from_secs(120)),
+            )
+            .with_vertex_override(
+                "web_search",
+                VertexRuntimeConfig::new().with_retry_policy(RetryPolicy::no_retry()),
+            );
+
+        let synthesizer = VertexId::new("synthesizer");
+        let web_search = VertexId::new("web_search");
+        let other = VertexId::new("other");
+
+        assert_eq!(config.vertex_timeout_for(&synthesizer), Duration::from_secs(120));
+        assert_eq!(config.vertex_timeout_for(&web_search), Duration::from_secs(30));
+        assert_eq!(config.vertex_timeout_for(&other), Duration::from_secs(30));
+        assert_eq!(config.retry_policy_for(&synthesizer).max_retries, 3);
+        assert_eq!(config.retry_policy_for(&web_search).max_retries, 0);
+    }
+
     #[test]
     fn test_should_checkpoint() {
         let config = PregelConfig::default().with_checkpoint_interval(5);
@@ -17,12 +17,23 @@ pub enum PregelError {
     VertexTimeout(VertexId),
 
     /// Error during vertex computation
-    #[error("Vertex error in {vertex_id:?}: {message}")]
+    ///
+    /// `superstep` and `inbox` are filled in by the runtime when the error
+    /// leaves the vertex (see [`PregelError::with_vertex_context`]).
+    #[error(
+        "Vertex error in {vertex_id:?}{}: {message}{}",
+        superstep_context(.superstep),
+        inbox_context(.inbox)
+    )]
     VertexError {
         vertex_id: VertexId,
         message: String,
         #[source]
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
+        /// Superstep in which the vertex failed
+        superstep: Option<usize>,
+        /// Truncated summary of the messages the vertex had received
+        inbox: Option<String>,
     },
 
     /// Error during routing decision
@@ -67,7 +78,17 @@ pub enum PregelError {
 
     /// Maximum retry attempts exceeded for a vertex
     #[error("Max retries exceeded for vertex {vertex_id:?}: {attempts} attempts")]
-    MaxRetriesExceeded { vertex_id: VertexId, attempts: usize },
+    MaxRetriesExceeded {
+        vertex_id: VertexId,
+        attempts: usize,
+        /// Error from the final attempt
+        #[source]
+        last_error: Option<Box<PregelError>>,
+    },
+
+    /// A vertex was activated more times than allowed
+    #[error("Vertex activation limit reached for {vertex_id:?}: limit {limit}")]
+    VertexActivationLimit { vertex_id: VertexId, limit: usize },
 
     /// Checkpoint workflow_id mismatch
     #[error("Checkpoint workflow mismatch: expected {expected}, found {found}")]
@@ -81,6 +102,8 @@ impl PregelError {
             vertex_id: vertex_id.into(),
             message: message.into(),
             source: None,
+            superstep: None,
+            inbox: None,
         }
     }
 
@@ -94,6 +117,8 @@ impl PregelError {
             vertex_id: vertex_id.into(),
             message: message.into(),
             source: Some(Box::new(source)),
+            superstep: None,
+            inbox: None,
         }
     }
 
@@ -114,6 +139,19 @@ impl PregelError {
         }
     }
 
+    /// Attach the superstep and inbox summary to a vertex error
+    ///
+    /// Context already set by the vertex is kept; other variants are returned unchanged.
+    pub fn with_vertex_context(mut self, at_superstep: usize, inbox_summary: Option<String>) -> Self {
+        if let Self::VertexError { superstep, inbox, .. } = &mut self {
+            superstep.get_or_insert(at_superstep);
+            if inbox.is_none() {
+                *inbox = inbox_summary;
+            }
+        }
+        self
+    }
+
     /// Check if the error is recoverable
     pub fn is_recoverable(&self) -> bool {
         matches!(
@@ -159,6 +197,15 @@ impl PregelError {
         Self::MaxRetriesExceeded {
             vertex_id: vertex_id.into(),
             attempts,
+            last_error: None,
+        }
+    }
+
+    /// Create a vertex activation limit error
+    pub fn vertex_activation_limit(vertex_id: impl Into<VertexId>, limit: usize) -> Self {
+        Self::VertexActivationLimit {
+            vertex_id: vertex_id.into(),
+            limit,
         }
     }
 
@@ -171,6 +218,14 @@ impl PregelError {
     }
 }
 
+fn superstep_context(superstep: &Option<usize>) -> String {
+    superstep.map(|s| format!(" at superstep {}", s)).unwrap_or_default()
+}
+
+fn inbox_context(inbox: &Option<String>) -> String {
+    inbox.as_ref().map(|i| format!(" (inbox: {})", i)).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     // Ensure errors are Send + Sync (compile-time check)
@@ -191,10 +246,14 @@ mod tests {
                 vertex_id,
                 message,
                 source,
+                superstep,
+                inbox,
             } => {
                 assert_eq!(vertex_id.0, "node1");
                 assert_eq!(message, "computation failed");
                 assert!(source.is_none());
+                assert!(superstep.is_none());
+                assert!(inbox.is_none());
             }
             _ => panic!("Wrong error type"),
         }
@@ -252,4 +311,19 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<PregelError>();
     }
+
+    #[test]
+    fn test_vertex_error_context_display() {
+        let err = PregelError::vertex_error("node1", "computation failed")
+            .with_vertex_context(4, Some("2 message(s) from planner".to_string()));
+        assert_eq!(
+            err.to_string(),
+            "Vertex error in VertexId(\"node1\") at superstep 4: computation failed \
+             (inbox: 2 message(s) from planner)"
+        );
+
+        // Context set by the vertex itself wins
+        let err = err.with_vertex_context(9, None);
+        assert!(err.to_string().contains("at superstep 4"));
+    }
 }
@@ -43,6 +43,17 @@ impl Source {
     }
 }
 
+/// Where a delivered message came from
+///
+/// Not to be confused with [`Source`], which cites research material.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MessageOrigin {
+    /// Sent by this vertex, or an edge activation triggered by it halting
+    Vertex(VertexId),
+    /// Injected by the runtime itself (e.g. restored from a checkpoint)
+    Runtime,
+}
+
 /// Standard message types for workflow coordination
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WorkflowMessage {
@@ -42,17 +42,23 @@ pub mod config;
 pub mod error;
 pub mod state;
 pub mod runtime;
+pub mod observer;
 pub mod checkpoint;
 pub mod visualization;
 
 // Re-exports
 pub use vertex::{
     BoxedVertex, ComputeContext, ComputeResult, StateUpdate, Vertex, VertexId, VertexState,
+    OUTPUT_VERTEX,
 };
-pub use message::{Priority, Source, VertexMessage, WorkflowMessage};
-pub use config::{ExecutionMode, PregelConfig, RetryPolicy};
+pub use message::{MessageOrigin, Priority, Source, VertexMessage, WorkflowMessage};
+pub use config::{ExecutionMode, PregelConfig, RetryPolicy, VertexRuntimeConfig};
 pub use error::PregelError;
+pub use observer::WorkflowObserver;
 pub use state::{UnitState, UnitUpdate, WorkflowState};
-pub use runtime::{CheckpointingRuntime, EdgeMetadata, PregelRuntime, WorkflowResult};
-pub use checkpoint::{Checkpoint, Checkpointer, CheckpointerConfig, MemoryCheckpointer, FileCheckpointer, create_checkpointer};
+pub use runtime::{
+    CheckpointingRuntime, ConditionalEdge, EdgeMetadata, EdgePredicate, PregelRuntime, WorkflowMetrics,
+    WorkflowResult,
+};
+pub use checkpoint::{Checkpoint, Checkpointer, CheckpointerConfig, Compression, MemoryCheckpointer, FileCheckpointer, create_checkpointer};
 pub use visualization::{sanitize_id, render_node, render_node_with_state, render_edge};
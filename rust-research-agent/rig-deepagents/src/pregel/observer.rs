@@ -0,0 +1,52 @@
+//! Workflow observers for monitoring Pregel execution
+//!
+//! A [`WorkflowObserver`] receives synchronous callbacks as the runtime moves
+//! through each superstep, making it possible to export metrics or traces
+//! (Prometheus counters, OpenTelemetry spans, structured logs) without
+//! modifying the runtime itself.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use rig_deepagents::pregel::{PregelRuntime, WorkflowObserver, VertexId};
+//!
+//! struct LoggingObserver;
+//!
+//! impl WorkflowObserver for LoggingObserver {
+//!     fn on_vertex_complete(&self, superstep: usize, vertex_id: &VertexId, elapsed: Duration) {
+//!         println!("superstep {superstep}: {vertex_id} took {elapsed:?}");
+//!     }
+//! }
+//!
+//! let runtime = PregelRuntime::new().with_observer(Arc::new(LoggingObserver));
+//! ```
+
+use std::time::Duration;
+
+use super::error::PregelError;
+use super::vertex::VertexId;
+
+/// Callbacks invoked by the Pregel runtime during execution
+///
+/// All methods have no-op default implementations, so observers only need to
+/// implement the events they care about. Callbacks run inline on the runtime's
+/// task and should return quickly.
+pub trait WorkflowObserver: Send + Sync {
+    /// Called before any vertex is computed in a superstep
+    ///
+    /// `active_vertices` lists the vertices scheduled for computation.
+    fn on_superstep_start(&self, _superstep: usize, _active_vertices: &[VertexId]) {}
+
+    /// Called after a vertex computes successfully
+    fn on_vertex_complete(&self, _superstep: usize, _vertex_id: &VertexId, _elapsed: Duration) {}
+
+    /// Called when a vertex computation fails (including attempts that will be retried)
+    fn on_vertex_error(&self, _superstep: usize, _vertex_id: &VertexId, _error: &PregelError) {}
+
+    /// Called after all messages of a superstep have been routed
+    ///
+    /// Not called when the superstep aborts with an error.
+    fn on_superstep_end(&self, _superstep: usize, _elapsed: Duration) {}
+}
@@ -3,17 +3,19 @@
 //! The runtime executes workflows through synchronized supersteps.
 //! Each superstep follows the sequence: Deliver → Compute → Collect → Route.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
 use tokio::time::timeout;
 
 use super::checkpoint::{Checkpoint, Checkpointer};
 use super::config::{ExecutionMode, PregelConfig};
 use super::error::PregelError;
-use super::message::{VertexMessage, WorkflowMessage};
+use super::message::{MessageOrigin, VertexMessage, WorkflowMessage};
+use super::observer::WorkflowObserver;
 use super::state::WorkflowState;
-use super::vertex::{BoxedVertex, ComputeContext, ComputeResult, VertexId, VertexState};
+use super::vertex::{BoxedVertex, ComputeContext, ComputeResult, VertexId, VertexState, OUTPUT_VERTEX};
 
 /// Metadata for an edge between vertices
 #[derive(Debug, Clone, Default)]
@@ -22,6 +24,32 @@ pub struct EdgeMetadata {
     pub label: Option<String>,
 }
 
+/// Predicate evaluated against the workflow state to choose a conditional edge branch
+pub type EdgePredicate<S> = Arc<dyn Fn(&S) -> bool + Send + Sync>;
+
+/// Two-way edge whose target is chosen by a predicate when the source halts
+///
+/// The predicate sees the workflow state after the superstep's updates are
+/// applied. A `None` target ends that branch without activating anything.
+#[derive(Clone)]
+pub struct ConditionalEdge<S> {
+    /// Branch selector
+    pub predicate: EdgePredicate<S>,
+    /// Target when the predicate holds
+    pub if_true: Option<VertexId>,
+    /// Target when the predicate does not hold
+    pub if_false: Option<VertexId>,
+}
+
+impl<S> std::fmt::Debug for ConditionalEdge<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConditionalEdge")
+            .field("if_true", &self.if_true)
+            .field("if_false", &self.if_false)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Result of a workflow execution
 #[derive(Debug, Clone)]
 pub struct WorkflowResult<S: WorkflowState> {
@@ -33,6 +61,74 @@ pub struct WorkflowResult<S: WorkflowState> {
     pub completed: bool,
     /// Final states of all vertices
     pub vertex_states: HashMap<VertexId, VertexState>,
+    /// Timing metrics collected during the run
+    pub metrics: WorkflowMetrics,
+}
+
+/// Timing metrics collected while running a workflow
+///
+/// Only supersteps executed by the current run are recorded; a run resumed
+/// from a checkpoint starts with empty metrics.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowMetrics {
+    /// Wall-clock duration of each executed superstep, in execution order
+    pub superstep_durations: Vec<Duration>,
+    /// Cumulative compute time per vertex (including retried attempts)
+    pub vertex_compute_time: HashMap<VertexId, Duration>,
+}
+
+impl WorkflowMetrics {
+    /// Total wall-clock time across all recorded supersteps
+    pub fn total_duration(&self) -> Duration {
+        self.superstep_durations.iter().sum()
+    }
+
+    /// Vertex with the largest cumulative compute time
+    pub fn slowest_vertex(&self) -> Option<(&VertexId, Duration)> {
+        self.vertex_compute_time
+            .iter()
+            .max_by_key(|(_, elapsed)| **elapsed)
+            .map(|(id, elapsed)| (id, *elapsed))
+    }
+
+    fn record_vertex(&mut self, vertex_id: &VertexId, elapsed: Duration) {
+        *self
+            .vertex_compute_time
+            .entry(vertex_id.clone())
+            .or_default() += elapsed;
+    }
+}
+
+/// Messages delivered to a vertex for one superstep, with their origins (same order)
+type Inbox<M> = (Vec<MessageOrigin>, Vec<M>);
+
+/// Most senders named in an inbox summary attached to vertex errors
+const INBOX_SUMMARY_MAX_SENDERS: usize = 5;
+
+/// Short description of a vertex's inbox for error context (`None` if empty)
+fn summarize_inbox(origins: &[MessageOrigin]) -> Option<String> {
+    if origins.is_empty() {
+        return None;
+    }
+
+    let mut senders: Vec<String> = Vec::new();
+    for origin in origins {
+        let sender = match origin {
+            MessageOrigin::Vertex(id) => id.to_string(),
+            MessageOrigin::Runtime => "runtime".to_string(),
+        };
+        if !senders.contains(&sender) {
+            senders.push(sender);
+        }
+    }
+
+    let hidden = senders.len().saturating_sub(INBOX_SUMMARY_MAX_SENDERS);
+    senders.truncate(INBOX_SUMMARY_MAX_SENDERS);
+    let mut summary = format!("{} message(s) from {}", origins.len(), senders.join(", "));
+    if hidden > 0 {
+        summary.push_str(&format!(" (+{} more)", hidden));
+    }
+    Some(summary)
 }
 
 /// Pregel Runtime for executing workflow graphs
@@ -50,12 +146,22 @@ where
     vertices: HashMap<VertexId, BoxedVertex<S, M>>,
     /// Current state of each vertex
     vertex_states: HashMap<VertexId, VertexState>,
-    /// Pending messages for each vertex (delivered at start of next superstep)
-    message_queues: HashMap<VertexId, Vec<M>>,
+    /// Pending messages for each vertex with their origin (delivered at start of next superstep)
+    message_queues: HashMap<VertexId, Vec<(MessageOrigin, M)>>,
     /// Edges defining message routing (source -> targets with optional metadata)
     edges: HashMap<VertexId, Vec<(VertexId, Option<EdgeMetadata>)>>,
+    /// Predicate-routed edges (source -> branches), evaluated after state updates
+    conditional_edges: HashMap<VertexId, Vec<ConditionalEdge<S>>>,
+    /// Sources with conditional edges that halted in the current superstep
+    pending_conditional: Vec<VertexId>,
     /// Retry attempt counts per vertex (for retry policy enforcement)
     retry_counts: HashMap<VertexId, usize>,
+    /// Compute activations per vertex (for `max_activations_per_vertex` enforcement)
+    activation_counts: HashMap<VertexId, usize>,
+    /// Optional observer notified of superstep and vertex events
+    observer: Option<Arc<dyn WorkflowObserver>>,
+    /// Timing metrics for the current run
+    metrics: WorkflowMetrics,
     /// Entry vertex ID (for EdgeDriven mode reference)
     entry_vertex: Option<VertexId>,
     /// Unique identifier for this workflow instance (used for checkpointing)
@@ -82,7 +188,12 @@ where
             vertex_states: HashMap::new(),
             message_queues: HashMap::new(),
             edges: HashMap::new(),
+            conditional_edges: HashMap::new(),
+            pending_conditional: Vec::new(),
             retry_counts: HashMap::new(),
+            activation_counts: HashMap::new(),
+            observer: None,
+            metrics: WorkflowMetrics::default(),
             entry_vertex: None,
             workflow_id: uuid::Uuid::new_v4().to_string(),
             _state_marker: std::marker::PhantomData,
@@ -98,6 +209,12 @@ where
         self
     }
 
+    /// Attach an observer that receives superstep and vertex callbacks
+    pub fn with_observer(mut self, observer: Arc<dyn WorkflowObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
     /// Get the workflow ID
     pub fn workflow_id(&self) -> &str {
         &self.workflow_id
@@ -145,6 +262,24 @@ where
         self
     }
 
+    /// Add a conditional edge (EdgeDriven mode only)
+    ///
+    /// When `from` halts, `predicate` is evaluated against the updated workflow
+    /// state and the chosen target is activated. `None` activates nothing.
+    pub fn add_conditional_edge(
+        &mut self,
+        from: impl Into<VertexId>,
+        predicate: EdgePredicate<S>,
+        if_true: Option<VertexId>,
+        if_false: Option<VertexId>,
+    ) -> &mut Self {
+        self.conditional_edges
+            .entry(from.into())
+            .or_default()
+            .push(ConditionalEdge { predicate, if_true, if_false });
+        self
+    }
+
     /// Set the entry point (activate this vertex on start)
     pub fn set_entry(&mut self, entry: impl Into<VertexId>) -> &mut Self {
         let entry_id = entry.into();
@@ -188,6 +323,7 @@ where
     async fn run_inner(&mut self, initial_state: S) -> Result<WorkflowResult<S>, PregelError> {
         let mut state = initial_state;
         let mut superstep = 0;
+        self.metrics = WorkflowMetrics::default();
 
         loop {
             // Check max supersteps limit
@@ -202,14 +338,18 @@ where
                     supersteps: superstep,
                     completed: true,
                     vertex_states: self.vertex_states.clone(),
+                    metrics: std::mem::take(&mut self.metrics),
                 });
             }
 
             // Execute one superstep
+            let started = Instant::now();
             let updates = self.execute_superstep(superstep, &state).await?;
 
             // Apply state updates
             state = state.apply_updates(updates);
+            self.route_conditional_edges(&state);
+            self.metrics.superstep_durations.push(started.elapsed());
 
             superstep += 1;
         }
@@ -242,11 +382,13 @@ where
         superstep: usize,
         state: &S,
     ) -> Result<Vec<S::Update>, PregelError> {
+        let started = Instant::now();
+
         // 1. Deliver messages - move pending messages to vertex inboxes
         let inboxes = self.deliver_messages();
 
         // 2. Reactivate halted vertices that received messages
-        for (vertex_id, messages) in &inboxes {
+        for (vertex_id, (_, messages)) in &inboxes {
             if !messages.is_empty() {
                 if let Some(vertex_state) = self.vertex_states.get_mut(vertex_id) {
                     if vertex_state.is_halted() {
@@ -262,25 +404,24 @@ where
         let (updates, outboxes, newly_halted) = self.compute_vertices(superstep, state, &inboxes).await?;
 
         // 4. Route explicit messages from vertex outboxes
-        self.route_messages(outboxes);
+        let forwarded = self.route_messages(outboxes);
 
         // 5. C2 Fix: Route automatic edge messages for newly halted vertices
-        self.route_edge_messages(&newly_halted);
+        self.route_edge_messages(&newly_halted, &forwarded);
+
+        if let Some(observer) = &self.observer {
+            observer.on_superstep_end(superstep, started.elapsed());
+        }
 
         Ok(updates)
     }
 
-    /// Deliver pending messages to vertex inboxes
-    fn deliver_messages(&mut self) -> HashMap<VertexId, Vec<M>> {
-        let mut inboxes = HashMap::new();
-        for (vertex_id, queue) in &mut self.message_queues {
-            if !queue.is_empty() {
-                inboxes.insert(vertex_id.clone(), std::mem::take(queue));
-            } else {
-                inboxes.insert(vertex_id.clone(), Vec::new());
-            }
-        }
-        inboxes
+    /// Deliver pending messages to vertex inboxes as `(origins, messages)`
+    fn deliver_messages(&mut self) -> HashMap<VertexId, Inbox<M>> {
+        self.message_queues
+            .iter_mut()
+            .map(|(vertex_id, queue)| (vertex_id.clone(), std::mem::take(queue).into_iter().unzip()))
+            .collect()
     }
 
     /// Compute all active vertices in parallel
@@ -289,21 +430,43 @@ where
         &mut self,
         superstep: usize,
         state: &S,
-        inboxes: &HashMap<VertexId, Vec<M>>,
+        inboxes: &HashMap<VertexId, Inbox<M>>,
     ) -> Result<(Vec<S::Update>, HashMap<VertexId, HashMap<VertexId, Vec<M>>>, Vec<VertexId>), PregelError> {
         let semaphore = Arc::new(Semaphore::new(self.config.parallelism));
         let updates = Arc::new(Mutex::new(Vec::new()));
         let outboxes = Arc::new(Mutex::new(HashMap::new()));
-        let vertex_timeout = self.config.vertex_timeout;
 
         // Collect active vertices to compute
-        let active_vertices: Vec<_> = self
+        let mut active_vertices: Vec<_> = self
             .vertex_states
             .iter()
             .filter(|(_, state)| state.is_active())
             .map(|(id, _)| id.clone())
             .collect();
 
+        // Results are collected in spawn order, so sorting fixes the update order
+        if self.config.deterministic {
+            active_vertices.sort();
+        }
+
+        if let Some(observer) = &self.observer {
+            observer.on_superstep_start(superstep, &active_vertices);
+        }
+
+        // Enforce the per-vertex activation limit before spawning any work
+        for vertex_id in &active_vertices {
+            if !self.vertices.contains_key(vertex_id) {
+                continue;
+            }
+            let count = self.activation_counts.entry(vertex_id.clone()).or_insert(0);
+            if let Some(limit) = self.config.max_activations_per_vertex {
+                if *count >= limit {
+                    return Err(PregelError::vertex_activation_limit(vertex_id.clone(), limit));
+                }
+            }
+            *count += 1;
+        }
+
         // Execute vertices in parallel
         let mut handles = Vec::new();
 
@@ -312,17 +475,21 @@ where
                 Some(v) => Arc::clone(v),
                 None => continue,
             };
-            let messages = inboxes.get(&vertex_id).cloned().unwrap_or_default();
+            let (origins, messages) = inboxes.get(&vertex_id).cloned().unwrap_or_default();
             let state_clone = state.clone();
             let sem_clone = Arc::clone(&semaphore);
             let vid = vertex_id.clone();
+            let vertex_timeout = self.config.vertex_timeout_for(&vertex_id);
 
             let handle = tokio::spawn(async move {
                 // Acquire semaphore permit for parallelism control
                 let _permit = sem_clone.acquire().await.unwrap();
 
+                let started = Instant::now();
+
                 // Create compute context
-                let mut ctx = ComputeContext::new(vid.clone(), &messages, superstep, &state_clone);
+                let mut ctx = ComputeContext::new(vid.clone(), &messages, superstep, &state_clone)
+                    .with_origins(&origins);
 
                 // Execute with timeout
                 let result: Result<ComputeResult<S::Update>, PregelError> = match timeout(
@@ -336,8 +503,10 @@ where
                 };
 
                 let outbox = ctx.into_outbox();
+                let result =
+                    result.map_err(|e| e.with_vertex_context(superstep, summarize_inbox(&origins)));
 
-                (vid, result, outbox)
+                (vid, result, outbox, started.elapsed())
             });
 
             handles.push(handle);
@@ -348,16 +517,20 @@ where
         let mut newly_halted = Vec::new();
 
         for handle in handles {
-            let (vid, result, outbox) = handle.await.map_err(|e| {
+            let (vid, result, outbox, elapsed) = handle.await.map_err(|e| {
                 PregelError::vertex_error_with_source(
                     "unknown",
                     "task join error",
                     std::io::Error::other(e.to_string()),
                 )
             })?;
+            self.metrics.record_vertex(&vid, elapsed);
 
             match result {
                 Ok(compute_result) => {
+                    if let Some(observer) = &self.observer {
+                        observer.on_vertex_complete(superstep, &vid, elapsed);
+                    }
                     // Success: reset retry count for this vertex
                     self.retry_counts.remove(&vid);
                     updates.lock().await.push(compute_result.update);
@@ -369,15 +542,19 @@ where
                     outboxes.lock().await.insert(vid, outbox);
                 }
                 Err(e) => {
+                    if let Some(observer) = &self.observer {
+                        observer.on_vertex_error(superstep, &vid, &e);
+                    }
                     if e.is_recoverable() {
                         // C3 Fix: Track retry attempts and enforce max_retries
                         // retry_count tracks how many retries we've already attempted
                         let retry_count = self.retry_counts.entry(vid.clone()).or_insert(0);
+                        let retry_policy = self.config.retry_policy_for(&vid);
 
                         // Check if we can retry BEFORE incrementing
-                        if self.config.retry_policy.should_retry(*retry_count) {
+                        if retry_policy.should_retry(*retry_count) {
                             // Apply backoff delay before next retry
-                            let delay = self.config.retry_policy.delay_for_attempt(*retry_count);
+                            let delay = retry_policy.delay_for_attempt(*retry_count);
                             tokio::time::sleep(delay).await;
                             // Track this retry attempt
                             *retry_count += 1;
@@ -388,6 +565,7 @@ where
                             return Err(PregelError::MaxRetriesExceeded {
                                 vertex_id: vid,
                                 attempts: *retry_count + 1, // +1 for the current failed attempt
+                                last_error: Some(Box::new(e)),
                             });
                         }
                     } else {
@@ -417,35 +595,103 @@ where
     }
 
     /// Route outgoing messages to target vertex queues
-    fn route_messages(&mut self, outboxes: HashMap<VertexId, HashMap<VertexId, Vec<M>>>) {
-        for (_source, outbox) in outboxes {
+    ///
+    /// In EdgeDriven mode, messages sent to [`OUTPUT_VERTEX`] (when no vertex has
+    /// that id) are forwarded to each plain-edge successor of the sender.
+    /// Returns the `(source, successor)` pairs that received forwarded output.
+    fn route_messages(
+        &mut self,
+        outboxes: HashMap<VertexId, HashMap<VertexId, Vec<M>>>,
+    ) -> HashSet<(VertexId, VertexId)> {
+        let mut forwarded = HashSet::new();
+        let forward_outputs = self.config.execution_mode == ExecutionMode::EdgeDriven
+            && !self.message_queues.contains_key(&VertexId::new(OUTPUT_VERTEX));
+
+        let mut outboxes: Vec<_> = outboxes
+            .into_iter()
+            .map(|(source, outbox)| (source, outbox.into_iter().collect::<Vec<_>>()))
+            .collect();
+        if self.config.deterministic {
+            outboxes.sort_by(|a, b| a.0.cmp(&b.0));
+            for (_, outbox) in &mut outboxes {
+                outbox.sort_by(|a, b| a.0.cmp(&b.0));
+            }
+        }
+
+        for (source, outbox) in outboxes {
+            let origin = MessageOrigin::Vertex(source.clone());
             for (target, messages) in outbox {
-                if let Some(queue) = self.message_queues.get_mut(&target) {
-                    queue.extend(messages);
+                if forward_outputs && target.as_str() == OUTPUT_VERTEX {
+                    for (successor, _metadata) in self.edges.get(&source).into_iter().flatten() {
+                        if let Some(queue) = self.message_queues.get_mut(successor) {
+                            queue.extend(messages.iter().map(|m| (origin.clone(), m.clone())));
+                            forwarded.insert((source.clone(), successor.clone()));
+                        }
+                    }
+                } else if let Some(queue) = self.message_queues.get_mut(&target) {
+                    queue.extend(messages.into_iter().map(|m| (origin.clone(), m)));
                 }
             }
         }
+        forwarded
     }
 
     /// Route automatic activation messages when vertices halt (EdgeDriven mode only)
-    fn route_edge_messages(&mut self, newly_halted: &[VertexId]) {
+    ///
+    /// Successors that already received the source's forwarded output are
+    /// activated by that output and get no extra activation message.
+    fn route_edge_messages(
+        &mut self,
+        newly_halted: &[VertexId],
+        forwarded: &HashSet<(VertexId, VertexId)>,
+    ) {
         if self.config.execution_mode != ExecutionMode::EdgeDriven {
             return;
         }
 
         for source_id in newly_halted {
+            // Conditional edges wait for the updated state (see route_conditional_edges)
+            if self.conditional_edges.contains_key(source_id) {
+                self.pending_conditional.push(source_id.clone());
+            }
+
             // Get edge targets for this source
             if let Some(targets) = self.edges.get(source_id) {
                 for (target_id, _metadata) in targets {
+                    if forwarded.contains(&(source_id.clone(), target_id.clone())) {
+                        continue;
+                    }
                     // Send Activate message to each edge target
                     if let Some(queue) = self.message_queues.get_mut(target_id) {
-                        queue.push(M::activation_message());
+                        queue.push((MessageOrigin::Vertex(source_id.clone()), M::activation_message()));
                     }
                 }
             }
         }
     }
 
+    /// Activate conditional edge targets for sources that halted this superstep
+    ///
+    /// Called after the superstep's updates are applied so predicates see the
+    /// state produced by the halting vertex.
+    pub(crate) fn route_conditional_edges(&mut self, state: &S) {
+        for source_id in std::mem::take(&mut self.pending_conditional) {
+            let Some(branches) = self.conditional_edges.get(&source_id) else {
+                continue;
+            };
+            for branch in branches {
+                let target = if (branch.predicate)(state) {
+                    &branch.if_true
+                } else {
+                    &branch.if_false
+                };
+                if let Some(queue) = target.as_ref().and_then(|t| self.message_queues.get_mut(t)) {
+                    queue.push((MessageOrigin::Vertex(source_id.clone()), M::activation_message()));
+                }
+            }
+        }
+    }
+
     // =========================================================================
     // Visualization Methods
     // =========================================================================
@@ -573,6 +819,15 @@ where
                 writeln!(output, "{}", render_edge(from, to, label)).unwrap();
             }
         }
+        for (from, branches) in &self.conditional_edges {
+            for branch in branches {
+                for (to, label) in [(&branch.if_true, "true"), (&branch.if_false, "false")] {
+                    if let Some(to) = to {
+                        writeln!(output, "{}", render_edge(from, to, Some(label))).unwrap();
+                    }
+                }
+            }
+        }
 
         // Style definitions for state visualization
         if include_state {
@@ -587,10 +842,11 @@ where
         self.vertices
             .keys()
             .filter(|id| {
-                match self.edges.get(*id) {
+                let no_edges = match self.edges.get(*id) {
                     None => true,
                     Some(targets) => targets.is_empty(),
-                }
+                };
+                no_edges && !self.conditional_edges.contains_key(*id)
             })
             .collect()
     }
@@ -727,6 +983,7 @@ where
     ) -> Result<WorkflowResult<S>, PregelError> {
         let mut state = initial_state;
         let mut superstep = start_superstep;
+        self.runtime.metrics = WorkflowMetrics::default();
 
         loop {
             // Check max supersteps limit (adjusted for resume)
@@ -741,14 +998,18 @@ where
                     supersteps: superstep,
                     completed: true,
                     vertex_states: self.runtime.vertex_states.clone(),
+                    metrics: std::mem::take(&mut self.runtime.metrics),
                 });
             }
 
             // Execute one superstep
+            let started = Instant::now();
             let updates = self.runtime.execute_superstep(superstep, &state).await?;
 
             // Apply state updates
             state = state.apply_updates(updates);
+            self.runtime.route_conditional_edges(&state);
+            self.runtime.metrics.superstep_durations.push(started.elapsed());
 
             superstep += 1;
 
@@ -814,6 +1075,7 @@ where
     /// - Vertex states (Active, Halted, Completed)
     /// - Pending message queues (with proper clear/overwrite to prevent state leak)
     /// - Retry counts (from metadata) - prevents retry count reset on resume
+    /// - Vertex RNG state - keeps weighted routing deterministic across resumes
     ///
     /// The method also validates topology compatibility between checkpoint and runtime.
     fn restore_from_checkpoint(&mut self, checkpoint: &Checkpoint<S>) -> Result<(), PregelError> {
@@ -862,7 +1124,8 @@ where
         // Clear/overwrite ALL message queues to prevent stale message leak
         for (vid, queue) in &mut self.runtime.message_queues {
             if let Some(msgs) = checkpoint.pending_messages.get(vid) {
-                *queue = msgs.clone();
+                // Checkpoints don't record senders
+                *queue = msgs.iter().map(|m| (MessageOrigin::Runtime, m.clone())).collect();
             } else {
                 // Clear queues for vertices not in checkpoint to prevent state leak
                 queue.clear();
@@ -873,6 +1136,13 @@ where
         // Now stored as first-class field instead of metadata JSON
         self.runtime.retry_counts = checkpoint.retry_counts.clone();
 
+        // Restore RNG state so randomized routing replays the same choices
+        for (vid, rng_state) in &checkpoint.rng_states {
+            if let Some(vertex) = self.runtime.vertices.get(vid) {
+                vertex.restore_rng_state(*rng_state);
+            }
+        }
+
         tracing::info!(
             workflow_id = %checkpoint.workflow_id,
             superstep = checkpoint.superstep,
@@ -896,7 +1166,14 @@ where
             .runtime
             .message_queues
             .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
+            .map(|(k, v)| (k.clone(), v.iter().map(|(_, m)| m.clone()).collect()))
+            .collect();
+
+        let rng_states: HashMap<VertexId, u64> = self
+            .runtime
+            .vertices
+            .iter()
+            .filter_map(|(vid, vertex)| vertex.rng_state().map(|rng| (vid.clone(), rng)))
             .collect();
 
         Checkpoint::with_retry_counts(
@@ -907,6 +1184,7 @@ where
             pending_messages,
             self.runtime.retry_counts.clone(),
         )
+        .with_rng_states(rng_states)
     }
 
     /// Save a checkpoint
@@ -1104,6 +1382,58 @@ mod tests {
         assert!(result.supersteps >= 1);
     }
 
+    #[tokio::test]
+    async fn test_runtime_messages_with_source() {
+        use std::sync::Mutex as StdMutex;
+
+        // Records which sender each received message came from
+        struct OriginRecorderVertex {
+            id: VertexId,
+            origins: Arc<StdMutex<Vec<MessageOrigin>>>,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for OriginRecorderVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                let mut origins = self.origins.lock().unwrap();
+                origins.extend(ctx.messages_with_source().map(|(origin, _)| origin.clone()));
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        let origins = Arc::new(StdMutex::new(Vec::new()));
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> = PregelRuntime::new();
+        for sender in ["left", "right"] {
+            runtime.add_vertex(Arc::new(MessageSenderVertex {
+                id: VertexId::new(sender),
+                target: VertexId::new("receiver"),
+            }));
+        }
+        runtime.add_vertex(Arc::new(OriginRecorderVertex {
+            id: VertexId::new("receiver"),
+            origins: Arc::clone(&origins),
+        }));
+
+        runtime.run(TestState::default()).await.unwrap();
+
+        let mut origins = origins.lock().unwrap().clone();
+        origins.sort_by_key(|origin| format!("{:?}", origin));
+        assert_eq!(
+            origins,
+            vec![
+                MessageOrigin::Vertex(VertexId::new("left")),
+                MessageOrigin::Vertex(VertexId::new("right")),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_runtime_termination_all_halted() {
         let mut runtime: PregelRuntime<TestState, WorkflowMessage> = PregelRuntime::new();
@@ -1163,6 +1493,48 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_vertex_activation_limit_exceeded() {
+        // Halts every superstep but reactivates itself via a self-message
+        struct SelfLoopVertex {
+            id: VertexId,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for SelfLoopVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                ctx.send_message(self.id.clone(), WorkflowMessage::Activate);
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        let config = PregelConfig::default()
+            .with_max_supersteps(50)
+            .with_max_activations_per_vertex(3);
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config);
+
+        runtime.add_vertex(Arc::new(SelfLoopVertex {
+            id: VertexId::new("loop"),
+        }));
+
+        let result = runtime.run(TestState::default()).await;
+        match result {
+            Err(PregelError::VertexActivationLimit { vertex_id, limit }) => {
+                assert_eq!(vertex_id, VertexId::new("loop"));
+                assert_eq!(limit, 3);
+            }
+            other => panic!("Expected VertexActivationLimit, got {:?}", other.map(|r| r.supersteps)),
+        }
+    }
+
     #[tokio::test]
     async fn test_runtime_terminal_state() {
         struct CounterVertex {
@@ -1254,6 +1626,89 @@ mod tests {
         assert!(elapsed < Duration::from_millis(150));
     }
 
+    #[tokio::test]
+    async fn test_deterministic_update_order() {
+        /// State whose merge is order-sensitive: updates are concatenated
+        #[derive(Clone, Debug, Default)]
+        struct LogState {
+            log: Vec<String>,
+        }
+
+        #[derive(Clone, Debug)]
+        struct LogUpdate(Vec<String>);
+
+        impl StateUpdate for LogUpdate {
+            fn empty() -> Self {
+                LogUpdate(Vec::new())
+            }
+
+            fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+        }
+
+        impl WorkflowState for LogState {
+            type Update = LogUpdate;
+
+            fn apply_update(&self, update: Self::Update) -> Self {
+                let mut log = self.log.clone();
+                log.extend(update.0);
+                LogState { log }
+            }
+
+            fn merge_updates(updates: Vec<Self::Update>) -> Self::Update {
+                LogUpdate(updates.into_iter().flat_map(|u| u.0).collect())
+            }
+
+            fn is_terminal(&self) -> bool {
+                false
+            }
+        }
+
+        /// Appends its id after a delay, so completion order differs from id order
+        struct LogVertex {
+            id: VertexId,
+            delay: Duration,
+        }
+
+        #[async_trait]
+        impl Vertex<LogState, WorkflowMessage> for LogVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                _ctx: &mut ComputeContext<'_, LogState, WorkflowMessage>,
+            ) -> Result<ComputeResult<LogUpdate>, PregelError> {
+                tokio::time::sleep(self.delay).await;
+                Ok(ComputeResult::halt(LogUpdate(vec![self.id.as_str().to_string()])))
+            }
+        }
+
+        let ids = ["delta", "alpha", "echo", "charlie", "bravo"];
+        let mut logs = Vec::new();
+        for _ in 0..5 {
+            let config = PregelConfig::default().with_parallelism(8).with_deterministic(true);
+            let mut runtime: PregelRuntime<LogState, WorkflowMessage> =
+                PregelRuntime::with_config(config);
+            for (i, id) in ids.iter().enumerate() {
+                runtime.add_vertex(Arc::new(LogVertex {
+                    id: VertexId::new(*id),
+                    delay: Duration::from_millis(5 * (ids.len() - i) as u64),
+                }));
+            }
+
+            let result = runtime.run(LogState::default()).await.unwrap();
+            logs.push(result.state.log);
+        }
+
+        let expected = vec!["alpha", "bravo", "charlie", "delta", "echo"];
+        for log in logs {
+            assert_eq!(log, expected);
+        }
+    }
+
     #[tokio::test]
     async fn test_runtime_add_edge() {
         let mut runtime: PregelRuntime<TestState, WorkflowMessage> = PregelRuntime::new();
@@ -1444,6 +1899,160 @@ mod tests {
         assert_eq!(FAIL_COUNT.load(Ordering::SeqCst), 4); // 1 initial + 3 retries
     }
 
+    #[tokio::test]
+    async fn test_vertex_override_timeout() {
+        use super::super::config::{RetryPolicy, VertexRuntimeConfig};
+
+        // Vertex that takes 50ms and then halts
+        struct SleepyVertex {
+            id: VertexId,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for SleepyVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                _ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        // Global timeout would fail both vertices; overrides decide instead
+        let config = PregelConfig::default()
+            .with_vertex_timeout(Duration::from_millis(10))
+            .with_vertex_override(
+                "synthesizer",
+                VertexRuntimeConfig::new().with_timeout(Duration::from_secs(5)),
+            )
+            .with_vertex_override(
+                "web_search",
+                VertexRuntimeConfig::new()
+                    .with_timeout(Duration::from_millis(20))
+                    .with_retry_policy(RetryPolicy::no_retry()),
+            );
+
+        // Only the synthesizer: its override is long enough
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config.clone());
+        runtime.add_vertex(Arc::new(SleepyVertex { id: VertexId::new("synthesizer") }));
+        assert!(runtime.run(TestState::default()).await.is_ok());
+
+        // Adding web_search: its override times out and it is not retried
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config);
+        runtime.add_vertex(Arc::new(SleepyVertex { id: VertexId::new("synthesizer") }));
+        runtime.add_vertex(Arc::new(SleepyVertex { id: VertexId::new("web_search") }));
+
+        let err = runtime.run(TestState::default()).await.unwrap_err();
+        match err {
+            PregelError::MaxRetriesExceeded { vertex_id, attempts, .. } => {
+                assert_eq!(vertex_id, VertexId::new("web_search"));
+                assert_eq!(attempts, 1);
+            }
+            other => panic!("Expected MaxRetriesExceeded, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vertex_error_reports_superstep() {
+        use super::super::config::RetryPolicy;
+
+        // Vertex that pings itself each superstep and fails on the third
+        struct LateFailVertex {
+            id: VertexId,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for LateFailVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                if ctx.superstep == 2 {
+                    return Err(PregelError::vertex_error(self.id.clone(), "late failure"));
+                }
+                ctx.send_message(self.id.clone(), WorkflowMessage::Activate);
+                Ok(ComputeResult::active(TestUpdate::empty()))
+            }
+        }
+
+        let config = PregelConfig::default().with_retry_policy(RetryPolicy::no_retry());
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config);
+        runtime.add_vertex(Arc::new(LateFailVertex { id: VertexId::new("worker") }));
+
+        let err = runtime.run(TestState::default()).await.unwrap_err();
+        let message = match err {
+            PregelError::MaxRetriesExceeded { last_error: Some(last_error), .. } => {
+                last_error.to_string()
+            }
+            other => panic!("Expected MaxRetriesExceeded with the last error, got {:?}", other),
+        };
+        assert!(message.contains("at superstep 2"), "{}", message);
+        assert!(message.contains("inbox: 1 message(s) from worker"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_edge_sees_updated_state() {
+        use super::super::config::ExecutionMode;
+
+        // Vertex that halts with a fixed update
+        struct DeltaVertex {
+            id: VertexId,
+            update: TestUpdate,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for DeltaVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                _ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                Ok(ComputeResult::halt(self.update.clone()))
+            }
+        }
+
+        let vertex = |id: &str, counter_delta, messages_delta| {
+            Arc::new(DeltaVertex {
+                id: VertexId::new(id),
+                update: TestUpdate { counter_delta, messages_delta },
+            })
+        };
+
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> = PregelRuntime::with_config(config);
+        runtime.add_vertex(vertex("check", 0, 1));
+        runtime.add_vertex(vertex("yes", 1, 0));
+        runtime.add_vertex(vertex("no", 2, 0));
+        runtime.set_entry("check");
+        // "check" sets messages_received in the same superstep it halts
+        runtime.add_conditional_edge(
+            "check",
+            Arc::new(|s: &TestState| s.messages_received > 0),
+            Some(VertexId::new("yes")),
+            Some(VertexId::new("no")),
+        );
+
+        let result = runtime.run(TestState::default()).await.unwrap();
+
+        assert_eq!(result.state.counter, 1);
+        assert_eq!(result.supersteps, 2);
+    }
+
     #[tokio::test]
     async fn test_edge_driven_only_entry_active() {
         use super::super::config::ExecutionMode;
@@ -1564,7 +2173,7 @@ mod tests {
 
         // Use thread-local to avoid test interference
         thread_local! {
-            static EXECUTION_ORDER: AtomicUsize = AtomicUsize::new(0);
+            static EXECUTION_ORDER: AtomicUsize = const { AtomicUsize::new(0) };
         }
 
         struct OrderedVertex {
@@ -1610,6 +2219,190 @@ mod tests {
         assert_eq!(EXECUTION_ORDER.with(|c| c.load(Ordering::SeqCst)), 3, "All 3 vertices should execute");
     }
 
+    #[tokio::test]
+    async fn test_edge_driven_forwards_output_payload() {
+        use super::super::config::ExecutionMode;
+        use std::sync::Mutex as StdMutex;
+
+        struct ProducerVertex {
+            id: VertexId,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for ProducerVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                ctx.send_output(WorkflowMessage::data("answer", 42));
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        struct ConsumerVertex {
+            id: VertexId,
+            received: Arc<StdMutex<Vec<WorkflowMessage>>>,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for ConsumerVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                assert_eq!(ctx.payload("answer"), Some(&serde_json::json!(42)));
+                self.received.lock().unwrap().extend(ctx.messages.iter().cloned());
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        let received = Arc::new(StdMutex::new(Vec::new()));
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config);
+
+        runtime
+            .add_vertex(Arc::new(ProducerVertex { id: VertexId::new("producer") }))
+            .add_vertex(Arc::new(ConsumerVertex {
+                id: VertexId::new("consumer"),
+                received: Arc::clone(&received),
+            }))
+            .set_entry("producer")
+            .add_edge("producer", "consumer");
+
+        runtime.run(TestState::default()).await.unwrap();
+
+        // The payload replaces the plain activation message
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(matches!(
+            &received[0],
+            WorkflowMessage::Data { key, .. } if key == "answer"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_observer_records_two_vertex_chain() {
+        use super::super::config::ExecutionMode;
+        use std::sync::Mutex as StdMutex;
+
+        #[derive(Default)]
+        struct RecordingObserver {
+            events: StdMutex<Vec<String>>,
+        }
+
+        impl WorkflowObserver for RecordingObserver {
+            fn on_superstep_start(&self, superstep: usize, active_vertices: &[VertexId]) {
+                let ids: Vec<_> = active_vertices.iter().map(|v| v.to_string()).collect();
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("start:{}:{}", superstep, ids.join(",")));
+            }
+
+            fn on_vertex_complete(&self, superstep: usize, vertex_id: &VertexId, _elapsed: std::time::Duration) {
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("complete:{}:{}", superstep, vertex_id));
+            }
+
+            fn on_vertex_error(&self, superstep: usize, vertex_id: &VertexId, _error: &PregelError) {
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("error:{}:{}", superstep, vertex_id));
+            }
+
+            fn on_superstep_end(&self, superstep: usize, _elapsed: std::time::Duration) {
+                self.events.lock().unwrap().push(format!("end:{}", superstep));
+            }
+        }
+
+        let observer = Arc::new(RecordingObserver::default());
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config).with_observer(observer.clone());
+
+        runtime
+            .add_vertex(Arc::new(MessageReceiverVertex { id: VertexId::new("a") }))
+            .add_vertex(Arc::new(MessageReceiverVertex { id: VertexId::new("b") }))
+            .set_entry("a")
+            .add_edge("a", "b");
+
+        let result = runtime.run(TestState::default()).await.unwrap();
+        assert!(result.completed);
+
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                "start:0:a",
+                "complete:0:a",
+                "end:0",
+                "start:1:b",
+                "complete:1:b",
+                "end:1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workflow_metrics_record_superstep_and_vertex_time() {
+        use super::super::config::ExecutionMode;
+
+        struct SleepingVertex {
+            id: VertexId,
+        }
+
+        #[async_trait]
+        impl Vertex<TestState, WorkflowMessage> for SleepingVertex {
+            fn id(&self) -> &VertexId {
+                &self.id
+            }
+
+            async fn compute(
+                &self,
+                _ctx: &mut ComputeContext<'_, TestState, WorkflowMessage>,
+            ) -> Result<ComputeResult<TestUpdate>, PregelError> {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(ComputeResult::halt(TestUpdate::empty()))
+            }
+        }
+
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+        let mut runtime: PregelRuntime<TestState, WorkflowMessage> =
+            PregelRuntime::with_config(config);
+        runtime
+            .add_vertex(Arc::new(SleepingVertex { id: VertexId::new("a") }))
+            .add_vertex(Arc::new(SleepingVertex { id: VertexId::new("b") }))
+            .set_entry("a")
+            .add_edge("a", "b");
+
+        let result = runtime.run(TestState::default()).await.unwrap();
+        let metrics = &result.metrics;
+
+        assert_eq!(result.supersteps, 2);
+        assert_eq!(metrics.superstep_durations.len(), 2);
+        assert!(metrics
+            .superstep_durations
+            .iter()
+            .all(|d| *d >= Duration::from_millis(20)));
+        assert_eq!(metrics.vertex_compute_time.len(), 2);
+        assert!(metrics.vertex_compute_time[&VertexId::new("a")] >= Duration::from_millis(20));
+        assert!(metrics.vertex_compute_time[&VertexId::new("b")] >= Duration::from_millis(20));
+        assert!(metrics.total_duration() >= Duration::from_millis(40));
+        assert!(metrics.slowest_vertex().is_some());
+    }
+
     // =========================================================================
     // Visualization Integration Tests
     // =========================================================================
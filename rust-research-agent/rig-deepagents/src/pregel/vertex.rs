@@ -10,7 +10,13 @@ use std::hash::Hash;
 use std::sync::Arc;
 
 use super::error::PregelError;
-use super::message::VertexMessage;
+use super::message::{MessageOrigin, VertexMessage, WorkflowMessage};
+
+/// Pseudo-target for a vertex's results
+///
+/// In `EdgeDriven` mode the runtime delivers messages sent here to every
+/// plain-edge successor of the sender (unless a real vertex has this id).
+pub const OUTPUT_VERTEX: &str = "output";
 
 /// Unique identifier for a vertex in the workflow graph
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -104,6 +110,8 @@ pub struct ComputeContext<'a, S, M: VertexMessage> {
     pub superstep: usize,
     /// Read-only access to workflow state
     pub state: &'a S,
+    /// Origin of each entry in `messages` (same order; may be empty)
+    origins: &'a [MessageOrigin],
     /// Outgoing messages (target vertex -> messages)
     outbox: HashMap<VertexId, Vec<M>>,
     /// Current vertex ID
@@ -117,11 +125,29 @@ impl<'a, S, M: VertexMessage> ComputeContext<'a, S, M> {
             messages,
             superstep,
             state,
+            origins: &[],
             outbox: HashMap::new(),
             vertex_id,
         }
     }
 
+    /// Attach the origin of each received message (parallel to `messages`)
+    pub fn with_origins(mut self, origins: &'a [MessageOrigin]) -> Self {
+        self.origins = origins;
+        self
+    }
+
+    /// Received messages paired with where they came from
+    ///
+    /// Messages without a recorded origin report [`MessageOrigin::Runtime`].
+    pub fn messages_with_source(&self) -> impl Iterator<Item = (&MessageOrigin, &M)> {
+        const UNKNOWN: &MessageOrigin = &MessageOrigin::Runtime;
+        self.messages
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| (self.origins.get(i).unwrap_or(UNKNOWN), msg))
+    }
+
     /// Get the current vertex ID
     pub fn id(&self) -> &VertexId {
         &self.vertex_id
@@ -135,6 +161,11 @@ impl<'a, S, M: VertexMessage> ComputeContext<'a, S, M> {
         self.outbox.entry(target).or_default().push(message);
     }
 
+    /// Send a result to this vertex's edge successors (see [`OUTPUT_VERTEX`])
+    pub fn send_output(&mut self, message: M) {
+        self.send_message(OUTPUT_VERTEX, message);
+    }
+
     /// Send a message to multiple targets
     pub fn broadcast(&mut self, targets: impl IntoIterator<Item = impl Into<VertexId>>, message: M) {
         for target in targets {
@@ -163,6 +194,21 @@ impl<'a, S, M: VertexMessage> ComputeContext<'a, S, M> {
     }
 }
 
+impl<S> ComputeContext<'_, S, WorkflowMessage> {
+    /// Data payloads received this superstep, as `(key, value)` pairs
+    pub fn payloads(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.messages.iter().filter_map(|msg| match msg {
+            WorkflowMessage::Data { key, value } => Some((key.as_str(), value)),
+            _ => None,
+        })
+    }
+
+    /// Last received payload with the given key
+    pub fn payload(&self, key: &str) -> Option<&serde_json::Value> {
+        self.payloads().filter(|(k, _)| *k == key).map(|(_, v)| v).last()
+    }
+}
+
 use super::state::WorkflowState;
 
 /// The core vertex trait for Pregel computation
@@ -233,6 +279,17 @@ where
     fn on_reactivation(&self, _messages: &[M]) -> VertexState {
         VertexState::Active
     }
+
+    /// Snapshot of the vertex's random number generator, if it has one
+    ///
+    /// Saved into checkpoints so that randomized decisions (e.g. weighted
+    /// routing) replay identically after a resume.
+    fn rng_state(&self) -> Option<u64> {
+        None
+    }
+
+    /// Restore the random number generator from a checkpoint snapshot
+    fn restore_rng_state(&self, _state: u64) {}
 }
 
 /// Result of a vertex computation
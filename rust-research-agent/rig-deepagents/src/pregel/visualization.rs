@@ -13,6 +13,7 @@
 //! | Tool        | Subroutine        | `id[[label]]`  |
 //! | Router      | Diamond           | `id{label}`    |
 //! | SubAgent    | Cylinder          | `id[(label)]`  |
+//! | SubWorkflow | Hexagon           | `id{{label}}`  |
 //! | FanOut      | Parallelogram     | `id[/label\]`  |
 //! | FanIn       | Reverse Para.     | `id[\label/]`  |
 //! | Passthrough | Rounded Rectangle | `id(label)`    |
@@ -59,6 +60,7 @@ pub fn render_node(id: &VertexId, kind: Option<&NodeKind>) -> String {
         Some(NodeKind::Tool(_)) => format!("    {}[[{}]]", safe_id, label),
         Some(NodeKind::Router(_)) => format!("    {}{{{}}}", safe_id, label),
         Some(NodeKind::SubAgent(_)) => format!("    {}[({})]", safe_id, label),
+        Some(NodeKind::SubWorkflow(_)) => format!("    {}{{{{{}}}}}", safe_id, label),
         Some(NodeKind::FanOut(_)) => format!("    {}[/{}\\]", safe_id, label),
         Some(NodeKind::FanIn(_)) => format!("    {}[\\{}/]", safe_id, label),
         Some(NodeKind::Passthrough) => format!("    {}({})", safe_id, label),
@@ -185,6 +187,14 @@ mod tests {
         assert_eq!(result, "    researcher[(researcher)]");
     }
 
+    #[test]
+    fn test_render_node_subworkflow() {
+        let id = VertexId::new("nested");
+        let kind = NodeKind::SubWorkflow(Default::default());
+        let result = render_node(&id, Some(&kind));
+        assert_eq!(result, "    nested{{nested}}");
+    }
+
     #[test]
     fn test_render_node_fanout() {
         let id = VertexId::new("split");
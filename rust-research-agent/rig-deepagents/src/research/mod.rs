@@ -65,8 +65,8 @@ pub mod workflow;
 
 // Re-exports for convenience
 pub use state::{
-    Finding, ResearchDirection, ResearchPhase, ResearchState, ResearchUpdate, Source,
-    SourceAgreement,
+    source_id, CitationStyle, Conflict, Finding, ResearchDirection, ResearchPhase, ResearchState,
+    ResearchUpdate, Source, SourceAgreement,
 };
 pub use prompts::{PromptBuilder, ResearchPrompts};
 pub use workflow::{
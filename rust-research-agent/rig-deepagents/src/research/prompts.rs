@@ -10,6 +10,8 @@
 
 use chrono::Utc;
 
+use super::state::{source_id, ResearchState};
+
 /// Prompt templates for the research workflow
 pub struct ResearchPrompts;
 
@@ -404,7 +406,19 @@ Your job is to take multiple sources of information and synthesize them into:
     }
 }
 
+/// Citation rules appended to the synthesis prompt
+///
+/// Sources are cited by id so [`ResearchState::render_report`] can number
+/// them and build the References section in the requested style.
+const SYNTHESIS_CITATION_INSTRUCTIONS: &str = "## Citations
+
+Cite sources inline by their id exactly as listed above, e.g. [S1] or [S1, S3].
+Do not renumber sources and do not write a Sources or References section;
+the references are generated from the ids you cite.
+";
+
 /// Prompt builder for dynamic template substitution
+#[derive(Debug, Clone)]
 pub struct PromptBuilder {
     template: String,
 }
@@ -426,6 +440,70 @@ impl PromptBuilder {
         self
     }
 
+    /// Synthesis-phase prompt for a research state
+    ///
+    /// Appends the query, the `max_findings` most confident findings and the
+    /// source list to [`ResearchPrompts::synthesizer`], so low-confidence
+    /// findings are dropped first when the prompt must stay within budget.
+    pub fn synthesis(state: &ResearchState, max_findings: usize) -> Self {
+        let findings = state
+            .top_findings(max_findings)
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let sources = if f.source_indices.is_empty() {
+                    String::new()
+                } else {
+                    let refs: Vec<_> = f.source_indices.iter().map(|&idx| format!("[{}]", source_id(idx))).collect();
+                    format!(" {}", refs.join(""))
+                };
+                format!(
+                    "{}. **{}** (confidence {:.2}){}\n{}",
+                    i + 1,
+                    f.title,
+                    f.confidence,
+                    sources,
+                    f.content
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let conflicts = &state.conflicts;
+        let conflicts_section = if conflicts.is_empty() {
+            String::new()
+        } else {
+            let listing = conflicts
+                .iter()
+                .map(|c| format!("- **{}**: {} vs {}", c.topic, c.source_a, c.source_b))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!(
+                "\n\n## Conflicts to Address\n\nThese sources contradict each other. \
+                 Explicitly address each conflict in the Contradictions & Uncertainties section.\n\n{}",
+                listing
+            )
+        };
+
+        let sources = state
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("[{}] {}: {}", source_id(i), s.title, s.url))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self::new(format!(
+            "{}\n## Research Query\n\n{{query}}\n\n## Findings\n\n{{findings}}{{conflicts}}\n\n## Sources\n\n{{sources}}\n\n{}",
+            ResearchPrompts::synthesizer(),
+            SYNTHESIS_CITATION_INSTRUCTIONS
+        ))
+        .with("conflicts", conflicts_section)
+        .with("findings", findings)
+        .with("sources", sources)
+        .with("query", &state.query)
+    }
+
     /// Build the final prompt string
     pub fn build(self) -> String {
         self.template
@@ -502,4 +580,45 @@ mod tests {
 
         assert_eq!(prompt, "2 + 2 = 4");
     }
+
+    #[test]
+    fn test_synthesis_prompt_includes_top_findings_only() {
+        use crate::research::{Finding, ResearchPhase, Source};
+
+        let mut state = ResearchState::new("What is context engineering?");
+        state.sources = vec![Source::new("https://a.com", "Source A", 0.9)];
+        state.findings = vec![
+            Finding::new("Weak", "Barely supported", 0.2, ResearchPhase::Exploratory),
+            Finding::new("Strong", "Well supported", 0.9, ResearchPhase::Directed)
+                .with_sources(vec![0]),
+            Finding::new("Medium", "Partly supported", 0.6, ResearchPhase::Directed),
+        ];
+
+        let prompt = PromptBuilder::synthesis(&state, 2).build();
+
+        assert!(prompt.contains("# Synthesis Specialist"));
+        assert!(prompt.contains("What is context engineering?"));
+        assert!(prompt.contains("1. **Strong** (confidence 0.90) [S1]"));
+        assert!(prompt.contains("2. **Medium**"));
+        assert!(!prompt.contains("Weak"));
+        assert!(prompt.contains("[S1] Source A: https://a.com"));
+        assert!(prompt.contains("Cite sources inline by their id"));
+        assert!(!prompt.contains("## Conflicts to Address"));
+    }
+
+    #[test]
+    fn test_synthesis_prompt_addresses_conflicts() {
+        use crate::research::Conflict;
+
+        let mut state = ResearchState::new("test");
+        state.conflicts.push(Conflict {
+            topic: "adoption trend".to_string(),
+            source_a: "https://a.com".to_string(),
+            source_b: "https://b.com".to_string(),
+        });
+
+        let prompt = PromptBuilder::synthesis(&state, 5).build();
+        assert!(prompt.contains("## Conflicts to Address"));
+        assert!(prompt.contains("- **adoption trend**: https://a.com vs https://b.com"));
+    }
 }
@@ -7,11 +7,15 @@
 //!
 //! Python Reference: research_agent/researcher/prompts.py
 
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use crate::error::DeepAgentError;
+use crate::llm::LLMProvider;
 use crate::pregel::state::WorkflowState;
 use crate::pregel::vertex::StateUpdate;
+use crate::state::Message;
 
 /// Research workflow phases following the "breadth-first, then depth" pattern
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -80,6 +84,12 @@ pub struct Source {
     pub relevance: f32,
     /// Optional snippet/summary from the source
     pub snippet: Option<String>,
+    /// Author or publisher (used by [`CitationStyle::AuthorDate`])
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Publication date, `YYYY` or `YYYY-MM-DD` (used by [`CitationStyle::AuthorDate`])
+    #[serde(default)]
+    pub published: Option<String>,
 }
 
 impl Source {
@@ -90,6 +100,8 @@ impl Source {
             title: title.into(),
             relevance: relevance.clamp(0.0, 1.0),
             snippet: None,
+            author: None,
+            published: None,
         }
     }
 
@@ -98,6 +110,100 @@ impl Source {
         self.snippet = Some(snippet.into());
         self
     }
+
+    /// Set the author or publisher
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Set the publication date (`YYYY` or `YYYY-MM-DD`)
+    pub fn with_published(mut self, published: impl Into<String>) -> Self {
+        self.published = Some(published.into());
+        self
+    }
+
+    /// Author label for author-date citations, falling back to the title
+    fn author_label(&self) -> &str {
+        self.author.as_deref().unwrap_or(&self.title)
+    }
+
+    /// Publication year for author-date citations ("n.d." when unknown)
+    fn year_label(&self) -> &str {
+        self.published
+            .as_deref()
+            .and_then(|date| date.get(..4))
+            .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+            .unwrap_or("n.d.")
+    }
+
+    /// URL used as the deduplication key
+    ///
+    /// Lowercases the scheme and host, and drops the fragment and any trailing
+    /// slash, so `https://Example.com/a/#intro` and `https://example.com/a`
+    /// are treated as the same source.
+    pub fn normalized_url(&self) -> String {
+        normalize_url(&self.url)
+    }
+
+    /// Merge metadata from a duplicate of this source
+    ///
+    /// Keeps the original title and URL, takes the higher relevance, and
+    /// fills in the snippet, author and date if this source has none.
+    pub fn merge(&mut self, other: Source) {
+        self.relevance = self.relevance.max(other.relevance);
+        if self.snippet.is_none() {
+            self.snippet = other.snippet;
+        }
+        if self.author.is_none() {
+            self.author = other.author;
+        }
+        if self.published.is_none() {
+            self.published = other.published;
+        }
+    }
+}
+
+/// Citation style for [`ResearchState::render_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CitationStyle {
+    /// `[1]` markers numbered by first citation; references listed by number
+    #[default]
+    Numbered,
+    /// `(Author, Year)` markers; references sorted by author then year
+    AuthorDate,
+}
+
+/// Identifier the model uses to cite `sources[index]` (e.g. `S1`)
+pub fn source_id(index: usize) -> String {
+    format!("S{}", index + 1)
+}
+
+/// Matches citation markers like `[S1]` or `[S1, S3]`
+const CITATION_PATTERN: &str = r"\[(S\d+(?:\s*,\s*S\d+)*)\]";
+
+/// Normalize a URL for source deduplication (see [`Source::normalized_url`])
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split_once('#').map_or(url, |(base, _)| base);
+
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme.to_lowercase()), rest),
+        None => (None, url),
+    };
+    let (host, path) = match rest.find(['/', '?']) {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, ""),
+    };
+    let path = match path.split_once('?') {
+        Some((path, query)) => format!("{}?{}", path.trim_end_matches('/'), query),
+        None => path.trim_end_matches('/').to_string(),
+    };
+
+    match scheme {
+        Some(scheme) => format!("{}://{}{}", scheme, host.to_lowercase(), path),
+        None => format!("{}{}", host.to_lowercase(), path),
+    }
 }
 
 /// A research finding with supporting sources
@@ -157,6 +263,32 @@ pub struct SourceAgreement {
     pub disagreement: Vec<String>,
 }
 
+/// Two sources that make opposing claims about a topic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// Topic the sources disagree on
+    pub topic: String,
+    /// First source (URL, or finding title when the finding has no source)
+    pub source_a: String,
+    /// Second source (URL, or finding title when the finding has no source)
+    pub source_b: String,
+}
+
+/// Topic used for findings that are not tied to a research direction
+const GENERAL_TOPIC: &str = "general";
+
+/// Prompt for the contradiction detection step
+const CONFLICT_DETECTION_PROMPT: &str = r#"You are checking research findings for contradictions.
+
+Below are findings about the topic "{topic}", each with the sources that support it.
+Identify pairs of sources that make opposing or incompatible claims.
+
+{findings}
+
+Respond with only a JSON array, using the source identifiers exactly as given:
+[{"topic": "<what they disagree on>", "source_a": "<source>", "source_b": "<source>"}]
+Respond with [] if the findings do not contradict each other."#;
+
 /// The complete research workflow state
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResearchState {
@@ -178,18 +310,46 @@ pub struct ResearchState {
     /// Source agreement analysis (Phase 3 output)
     pub agreement: SourceAgreement,
 
+    /// Pairs of sources making opposing claims (see [`ResearchState::detect_conflicts`])
+    #[serde(default)]
+    pub conflicts: Vec<Conflict>,
+
     /// Search count (for enforcing limits)
     pub search_count: usize,
 
     /// Maximum allowed searches (default: 6)
     pub max_searches: usize,
 
+    /// Optional per-phase search caps, applied on top of `max_searches`
+    #[serde(default)]
+    pub phase_budgets: HashMap<ResearchPhase, usize>,
+
+    /// Searches performed in each phase
+    #[serde(default)]
+    pub phase_search_counts: HashMap<ResearchPhase, usize>,
+
     /// Queries that have been executed (for deduplication)
     pub executed_queries: HashSet<String>,
 
     /// Any errors encountered during research
     pub errors: Vec<String>,
 
+    /// Synthesized report text, citing sources by id (Phase 3 output)
+    #[serde(default)]
+    pub synthesis: Option<String>,
+
+    /// Set when synthesis found gaps that need another directed pass
+    #[serde(default)]
+    pub needs_more_research: bool,
+
+    /// Number of Synthesis → Directed loop-backs taken so far
+    #[serde(default)]
+    pub research_loops: usize,
+
+    /// Maximum Synthesis → Directed loop-backs (default: 1)
+    #[serde(default = "default_max_research_loops")]
+    pub max_research_loops: usize,
+
     /// Whether research can continue (computed field for router decisions)
     /// This is automatically updated after each state update.
     #[serde(default = "default_can_continue")]
@@ -201,6 +361,11 @@ fn default_can_continue() -> bool {
     true
 }
 
+/// Default value for max_research_loops
+fn default_max_research_loops() -> usize {
+    1
+}
+
 impl ResearchState {
     /// Create a new research state for a query
     pub fn new(query: impl Into<String>) -> Self {
@@ -208,6 +373,7 @@ impl ResearchState {
             query: query.into(),
             phase: ResearchPhase::Exploratory,
             max_searches: 6,
+            max_research_loops: default_max_research_loops(),
             can_continue: true, // New states can always continue
             ..Default::default()
         }
@@ -236,11 +402,22 @@ impl ResearchState {
             return false;
         }
 
+        // A loop-back beyond the cap (e.g. a forced transition) ends the research
+        if self.research_loops > self.max_research_loops {
+            return false;
+        }
+
         // Check if all directions have been explored in Directed phase
         if self.phase == ResearchPhase::Directed && self.unexplored_directions().is_empty() {
             return false;
         }
 
+        // Directed is the last searching phase, so its own budget ends the research.
+        // An exhausted exploratory budget only means it is time to move on.
+        if self.phase == ResearchPhase::Directed && !self.can_search() {
+            return false;
+        }
+
         true
     }
 
@@ -250,14 +427,53 @@ impl ResearchState {
         self
     }
 
-    /// Check if more searches are allowed
+    /// Configure how many times synthesis may send the research back to Directed
+    pub fn with_max_research_loops(mut self, max: usize) -> Self {
+        self.max_research_loops = max;
+        self
+    }
+
+    /// Check if synthesis may loop back to the directed phase
+    ///
+    /// Requires a pending `needs_more_research` request, a loop-back left
+    /// under `max_research_loops`, and search budget left for the directed phase.
+    pub fn can_loop_back(&self) -> bool {
+        let directed_budget_left = match self.phase_budgets.get(&ResearchPhase::Directed) {
+            Some(&budget) => self.phase_searches(ResearchPhase::Directed) < budget,
+            None => true,
+        };
+
+        self.needs_more_research
+            && self.research_loops < self.max_research_loops
+            && self.search_count < self.max_searches
+            && directed_budget_left
+    }
+
+    /// Cap the number of searches allowed while in `phase`
+    pub fn with_phase_budget(mut self, phase: ResearchPhase, max: usize) -> Self {
+        self.phase_budgets.insert(phase, max);
+        self
+    }
+
+    /// Check if more searches are allowed in the current phase
     pub fn can_search(&self) -> bool {
-        self.search_count < self.max_searches
+        self.remaining_searches() > 0
     }
 
-    /// Get remaining search budget
+    /// Get remaining search budget for the current phase
+    ///
+    /// The smaller of the global budget and the current phase's budget.
     pub fn remaining_searches(&self) -> usize {
-        self.max_searches.saturating_sub(self.search_count)
+        let global = self.max_searches.saturating_sub(self.search_count);
+        match self.phase_budgets.get(&self.phase) {
+            Some(&budget) => global.min(budget.saturating_sub(self.phase_searches(self.phase))),
+            None => global,
+        }
+    }
+
+    /// Number of searches performed while in `phase`
+    pub fn phase_searches(&self, phase: ResearchPhase) -> usize {
+        self.phase_search_counts.get(&phase).copied().unwrap_or(0)
     }
 
     /// Check if a query has already been executed
@@ -268,7 +484,7 @@ impl ResearchState {
     /// Get unexplored directions sorted by priority
     pub fn unexplored_directions(&self) -> Vec<&ResearchDirection> {
         let mut dirs: Vec<_> = self.directions.iter().filter(|d| !d.explored).collect();
-        dirs.sort_by(|a, b| b.priority.cmp(&a.priority));
+        dirs.sort_by_key(|d| std::cmp::Reverse(d.priority));
         dirs
     }
 
@@ -280,6 +496,72 @@ impl ResearchState {
             .collect()
     }
 
+    /// Get the `n` most confident findings
+    ///
+    /// Sorted by confidence (highest first); ties go to the more recent finding.
+    pub fn top_findings(&self, n: usize) -> Vec<&Finding> {
+        let mut ranked: Vec<_> = self.findings.iter().enumerate().collect();
+        ranked.sort_by(|(ia, a), (ib, b)| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then_with(|| ib.cmp(ia))
+        });
+        ranked.into_iter().take(n).map(|(_, f)| f).collect()
+    }
+
+    /// Find pairs of sources with opposing claims
+    ///
+    /// Findings are grouped by topic (their research direction, or "general"),
+    /// and each group with at least two findings is checked with one call to
+    /// `llm`. Store the result with [`ResearchUpdate::with_conflicts`] so the
+    /// synthesis prompt can address it.
+    pub async fn detect_conflicts(
+        &self,
+        llm: &dyn LLMProvider,
+    ) -> Result<Vec<Conflict>, DeepAgentError> {
+        let mut topics: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+        for finding in &self.findings {
+            let topic = finding.direction.as_deref().unwrap_or(GENERAL_TOPIC);
+            topics.entry(topic).or_default().push(finding);
+        }
+
+        let mut conflicts = Vec::new();
+        for (topic, findings) in topics {
+            if findings.len() < 2 {
+                continue;
+            }
+
+            let listing = findings
+                .iter()
+                .map(|f| format!("- {}\n  Sources: {}", f.content, self.source_labels(f).join(", ")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let prompt = CONFLICT_DETECTION_PROMPT
+                .replace("{topic}", topic)
+                .replace("{findings}", &listing);
+
+            let response = llm.complete(&[Message::user(&prompt)], &[], None).await?;
+            conflicts.extend(parse_conflicts(&response.message.content)?);
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Identifiers for a finding's sources, falling back to the finding title
+    fn source_labels(&self, finding: &Finding) -> Vec<String> {
+        let labels: Vec<_> = finding
+            .source_indices
+            .iter()
+            .filter_map(|&idx| self.sources.get(idx))
+            .map(|s| s.url.clone())
+            .collect();
+        if labels.is_empty() {
+            vec![finding.title.clone()]
+        } else {
+            labels
+        }
+    }
+
     /// Get findings from the exploratory phase
     pub fn exploratory_findings(&self) -> Vec<&Finding> {
         self.findings
@@ -288,6 +570,18 @@ impl ResearchState {
             .collect()
     }
 
+    /// Sources with duplicate URLs removed (first occurrence wins)
+    ///
+    /// `apply_update` already deduplicates on insert; this also covers
+    /// sources assigned directly to `sources`.
+    pub fn unique_sources(&self) -> Vec<&Source> {
+        let mut seen = HashSet::new();
+        self.sources
+            .iter()
+            .filter(|s| seen.insert(s.normalized_url()))
+            .collect()
+    }
+
     /// Generate a formatted source list for citations
     pub fn format_sources(&self) -> String {
         self.sources
@@ -297,6 +591,126 @@ impl ResearchState {
             .collect::<Vec<_>>()
             .join("\n")
     }
+
+    /// Render the final report with formatted citations
+    ///
+    /// Takes the synthesis text (or, before synthesis, the findings with
+    /// their sources) and replaces source-id markers such as `[S2]` or
+    /// `[S1, S3]` with citations in `style`, then appends a References
+    /// section listing every cited source. Markers naming unknown sources
+    /// are left as written.
+    pub fn render_report(&self, style: CitationStyle) -> String {
+        let body = match &self.synthesis {
+            Some(synthesis) => synthesis.trim_end().to_string(),
+            None => self.findings_report(),
+        };
+
+        // Sources in order of first citation
+        let mut cited: Vec<usize> = Vec::new();
+        let pattern = Regex::new(CITATION_PATTERN).expect("valid citation pattern");
+        let body = pattern.replace_all(&body, |caps: &Captures| {
+            let indices: Vec<usize> = caps[1]
+                .split(',')
+                .filter_map(|id| id.trim()[1..].parse::<usize>().ok())
+                .filter(|n| (1..=self.sources.len()).contains(n))
+                .map(|n| n - 1)
+                .collect();
+            if indices.is_empty() {
+                return caps[0].to_string();
+            }
+
+            for &idx in &indices {
+                if !cited.contains(&idx) {
+                    cited.push(idx);
+                }
+            }
+            match style {
+                CitationStyle::Numbered => {
+                    let numbers: Vec<_> = indices
+                        .iter()
+                        .map(|idx| (cited.iter().position(|c| c == idx).unwrap() + 1).to_string())
+                        .collect();
+                    format!("[{}]", numbers.join(", "))
+                }
+                CitationStyle::AuthorDate => {
+                    let labels: Vec<_> = indices
+                        .iter()
+                        .map(|&idx| {
+                            let source = &self.sources[idx];
+                            format!("{}, {}", source.author_label(), source.year_label())
+                        })
+                        .collect();
+                    format!("({})", labels.join("; "))
+                }
+            }
+        });
+
+        if cited.is_empty() {
+            return format!("{}\n", body);
+        }
+
+        let references = match style {
+            CitationStyle::Numbered => cited
+                .iter()
+                .enumerate()
+                .map(|(n, &idx)| {
+                    let source = &self.sources[idx];
+                    format!("[{}] {}: {}", n + 1, source.title, source.url)
+                })
+                .collect::<Vec<_>>(),
+            CitationStyle::AuthorDate => {
+                let mut sources: Vec<&Source> = cited.iter().map(|&idx| &self.sources[idx]).collect();
+                sources.sort_by(|a, b| {
+                    a.author_label()
+                        .to_lowercase()
+                        .cmp(&b.author_label().to_lowercase())
+                        .then_with(|| a.year_label().cmp(b.year_label()))
+                });
+                sources
+                    .iter()
+                    .map(|s| format!("{} ({}). {}. {}", s.author_label(), s.year_label(), s.title, s.url))
+                    .collect()
+            }
+        };
+
+        format!("{}\n\n## References\n\n{}\n", body, references.join("\n"))
+    }
+
+    /// Report body built from the findings when no synthesis text exists
+    fn findings_report(&self) -> String {
+        let sections = self.findings.iter().map(|f| {
+            let markers: String = f
+                .source_indices
+                .iter()
+                .map(|&idx| format!("[{}]", source_id(idx)))
+                .collect();
+            if markers.is_empty() {
+                format!("## {}\n\n{}", f.title, f.content)
+            } else {
+                format!("## {}\n\n{} {}", f.title, f.content, markers)
+            }
+        });
+
+        std::iter::once(format!("# {}", self.query))
+            .chain(sections)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Parse the JSON conflict list from an LLM response (markdown code fences are stripped)
+fn parse_conflicts(content: &str) -> Result<Vec<Conflict>, DeepAgentError> {
+    let content = content.trim();
+    let json = content
+        .strip_prefix("```json")
+        .or_else(|| content.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(content);
+
+    serde_json::from_str(json).map_err(|e| {
+        DeepAgentError::AgentExecution(format!("Invalid conflict detection response: {}", e))
+    })
 }
 
 /// Update to the research state
@@ -326,6 +740,18 @@ pub struct ResearchUpdate {
     /// Source agreement update
     pub agreement_update: Option<SourceAgreement>,
 
+    /// Detected source conflicts (replaces the current list)
+    #[serde(default)]
+    pub conflicts_update: Option<Vec<Conflict>>,
+
+    /// Synthesized report text
+    #[serde(default)]
+    pub synthesis: Option<String>,
+
+    /// Synthesis found gaps that need another directed pass
+    #[serde(default)]
+    pub needs_more_research: bool,
+
     /// Errors encountered
     pub errors: Vec<String>,
 }
@@ -378,6 +804,24 @@ impl ResearchUpdate {
         self
     }
 
+    /// Set the detected source conflicts
+    pub fn with_conflicts(mut self, conflicts: Vec<Conflict>) -> Self {
+        self.conflicts_update = Some(conflicts);
+        self
+    }
+
+    /// Ask for another directed pass (see [`ResearchState::can_loop_back`])
+    pub fn with_more_research(mut self) -> Self {
+        self.needs_more_research = true;
+        self
+    }
+
+    /// Set the synthesized report text
+    pub fn with_synthesis(mut self, synthesis: impl Into<String>) -> Self {
+        self.synthesis = Some(synthesis.into());
+        self
+    }
+
     /// Add an error
     pub fn with_error(mut self, error: impl Into<String>) -> Self {
         self.errors.push(error.into());
@@ -399,6 +843,9 @@ impl StateUpdate for ResearchUpdate {
             && self.searches_performed == 0
             && self.phase_transition.is_none()
             && self.agreement_update.is_none()
+            && self.conflicts_update.is_none()
+            && self.synthesis.is_none()
+            && !self.needs_more_research
             && self.errors.is_empty()
     }
 }
@@ -412,10 +859,16 @@ impl WorkflowState for ResearchState {
         // Add new findings
         new_state.findings.extend(update.new_findings);
 
-        // Add new sources (dedup by URL)
+        // Add new sources (dedup by normalized URL, merging duplicates)
         for source in update.new_sources {
-            if !new_state.sources.iter().any(|s| s.url == source.url) {
-                new_state.sources.push(source);
+            let key = source.normalized_url();
+            match new_state
+                .sources
+                .iter_mut()
+                .find(|s| s.normalized_url() == key)
+            {
+                Some(existing) => existing.merge(source),
+                None => new_state.sources.push(source),
             }
         }
 
@@ -444,11 +897,26 @@ impl WorkflowState for ResearchState {
         // Record executed queries
         new_state.executed_queries.extend(update.executed_queries);
 
-        // Update search count
+        // Update search count (attributed to the phase before any transition)
         new_state.search_count += update.searches_performed;
+        if update.searches_performed > 0 {
+            *new_state.phase_search_counts.entry(self.phase).or_default() +=
+                update.searches_performed;
+        }
+
+        if update.needs_more_research {
+            new_state.needs_more_research = true;
+        }
 
         // Apply phase transition
         if let Some(new_phase) = update.phase_transition {
+            // Leaving synthesis consumes any pending loop-back request
+            if self.phase == ResearchPhase::Synthesis && new_phase != ResearchPhase::Synthesis {
+                if new_phase == ResearchPhase::Directed {
+                    new_state.research_loops += 1;
+                }
+                new_state.needs_more_research = false;
+            }
             new_state.phase = new_phase;
         }
 
@@ -457,6 +925,14 @@ impl WorkflowState for ResearchState {
             new_state.agreement = agreement;
         }
 
+        if let Some(conflicts) = update.conflicts_update {
+            new_state.conflicts = conflicts;
+        }
+
+        if let Some(synthesis) = update.synthesis {
+            new_state.synthesis = Some(synthesis);
+        }
+
         // Collect errors
         new_state.errors.extend(update.errors);
 
@@ -476,6 +952,7 @@ impl WorkflowState for ResearchState {
             merged.explored_directions.extend(update.explored_directions);
             merged.executed_queries.extend(update.executed_queries);
             merged.searches_performed += update.searches_performed;
+            merged.needs_more_research |= update.needs_more_research;
             merged.errors.extend(update.errors);
 
             // Last phase transition wins
@@ -487,6 +964,16 @@ impl WorkflowState for ResearchState {
             if update.agreement_update.is_some() {
                 merged.agreement_update = update.agreement_update;
             }
+
+            // Last conflict list wins
+            if update.conflicts_update.is_some() {
+                merged.conflicts_update = update.conflicts_update;
+            }
+
+            // Last synthesis wins
+            if update.synthesis.is_some() {
+                merged.synthesis = update.synthesis;
+            }
         }
 
         merged
@@ -500,6 +987,40 @@ impl WorkflowState for ResearchState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::{LLMConfig, LLMResponse};
+    use crate::middleware::ToolDefinition;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Mock provider returning a fixed response and recording prompts
+    struct MockProvider {
+        response: String,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockProvider {
+        async fn complete(
+            &self,
+            messages: &[Message],
+            _tools: &[ToolDefinition],
+            _config: Option<&LLMConfig>,
+        ) -> Result<LLMResponse, DeepAgentError> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .extend(messages.iter().map(|m| m.content.clone()));
+            Ok(LLMResponse::new(Message::assistant(&self.response)))
+        }
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn default_model(&self) -> &str {
+            "mock-model"
+        }
+    }
 
     #[test]
     fn test_research_phase_progression() {
@@ -542,6 +1063,45 @@ mod tests {
         assert_eq!(state.remaining_searches(), 0);
     }
 
+    #[test]
+    fn test_research_state_phase_budgets() {
+        let state = ResearchState::new("test")
+            .with_max_searches(6)
+            .with_phase_budget(ResearchPhase::Exploratory, 2)
+            .with_phase_budget(ResearchPhase::Directed, 3);
+
+        assert_eq!(state.remaining_searches(), 2);
+
+        // Exhaust the exploratory budget
+        let state = state.apply_update(ResearchUpdate::empty().with_search("q1").with_search("q2"));
+        assert_eq!(state.phase_searches(ResearchPhase::Exploratory), 2);
+        assert!(!state.can_search());
+        assert_eq!(state.remaining_searches(), 0);
+        // Running out of exploratory searches must not end the research
+        assert!(state.can_continue);
+
+        // The directed budget is untouched
+        let state = state.apply_update(ResearchUpdate::transition_to(ResearchPhase::Directed));
+        assert!(state.can_search());
+        assert_eq!(state.remaining_searches(), 3);
+
+        let state = state.apply_update(ResearchUpdate::empty().with_search("q3"));
+        assert_eq!(state.phase_searches(ResearchPhase::Directed), 1);
+        assert_eq!(state.phase_searches(ResearchPhase::Exploratory), 2);
+        assert_eq!(state.remaining_searches(), 2);
+    }
+
+    #[test]
+    fn test_research_state_phase_budget_capped_by_global() {
+        let mut state = ResearchState::new("test")
+            .with_max_searches(3)
+            .with_phase_budget(ResearchPhase::Directed, 5);
+        state.phase = ResearchPhase::Directed;
+        state.search_count = 2;
+
+        assert_eq!(state.remaining_searches(), 1);
+    }
+
     #[test]
     fn test_research_direction() {
         let dir = ResearchDirection::new("AI Safety", "Important emerging field", 5);
@@ -653,6 +1213,45 @@ mod tests {
         assert_eq!(state.sources[0].title, "A"); // Original kept
     }
 
+    #[test]
+    fn test_research_state_source_dedup_merges_metadata() {
+        let state = ResearchState::new("test");
+
+        let update1 = ResearchUpdate {
+            new_sources: vec![Source::new("https://Example.com/paper/", "Paper", 0.6)],
+            ..Default::default()
+        };
+        let update2 = ResearchUpdate {
+            new_sources: vec![Source::new("https://example.com/paper#results", "Paper again", 0.9)
+                .with_snippet("Key result")],
+            ..Default::default()
+        };
+
+        let state = state.apply_update(update1).apply_update(update2);
+
+        assert_eq!(state.sources.len(), 1);
+        let source = &state.sources[0];
+        assert_eq!(source.title, "Paper");
+        assert_eq!(source.url, "https://Example.com/paper/");
+        assert_eq!(source.relevance, 0.9);
+        assert_eq!(source.snippet.as_deref(), Some("Key result"));
+    }
+
+    #[test]
+    fn test_unique_sources() {
+        let mut state = ResearchState::new("test");
+        state.sources = vec![
+            Source::new("https://a.com/", "A", 0.9),
+            Source::new("https://b.com?q=1", "B", 0.8),
+            Source::new("HTTPS://A.COM", "A again", 0.7),
+            Source::new("https://b.com?q=2", "B2", 0.6),
+        ];
+
+        let unique = state.unique_sources();
+        let titles: Vec<_> = unique.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["A", "B", "B2"]);
+    }
+
     #[test]
     fn test_research_state_merge_updates() {
         let updates = vec![
@@ -719,6 +1318,85 @@ mod tests {
         assert_eq!(unexplored[1].name, "Low");
     }
 
+    #[test]
+    fn test_top_findings() {
+        let mut state = ResearchState::new("test");
+        state.findings = vec![
+            Finding::new("Low", "C", 0.3, ResearchPhase::Exploratory),
+            Finding::new("Tied older", "C", 0.8, ResearchPhase::Exploratory),
+            Finding::new("Best", "C", 0.95, ResearchPhase::Directed),
+            Finding::new("Tied newer", "C", 0.8, ResearchPhase::Directed),
+        ];
+
+        let titles: Vec<_> = state.top_findings(3).iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(titles, vec!["Best", "Tied newer", "Tied older"]);
+
+        assert_eq!(state.top_findings(10).len(), 4);
+        assert!(state.top_findings(0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detect_conflicts() {
+        let mut state = ResearchState::new("test");
+        state.sources = vec![
+            Source::new("https://a.com", "A", 0.9),
+            Source::new("https://b.com", "B", 0.8),
+        ];
+        state.findings = vec![
+            Finding::new("Rising", "Adoption is rising", 0.8, ResearchPhase::Directed)
+                .with_sources(vec![0])
+                .with_direction("Adoption"),
+            Finding::new("Falling", "Adoption is falling", 0.7, ResearchPhase::Directed)
+                .with_sources(vec![1])
+                .with_direction("Adoption"),
+            // Only finding in its topic: no LLM call needed
+            Finding::new("Cost", "Costs are stable", 0.6, ResearchPhase::Directed)
+                .with_direction("Cost"),
+        ];
+
+        let provider = MockProvider {
+            response: "```json\n[{\"topic\": \"adoption trend\", \"source_a\": \"https://a.com\", \"source_b\": \"https://b.com\"}]\n```".to_string(),
+            prompts: Mutex::new(Vec::new()),
+        };
+
+        let conflicts = state.detect_conflicts(&provider).await.unwrap();
+
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                topic: "adoption trend".to_string(),
+                source_a: "https://a.com".to_string(),
+                source_b: "https://b.com".to_string(),
+            }]
+        );
+
+        let prompts = provider.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("\"Adoption\""));
+        assert!(prompts[0].contains("Adoption is rising\n  Sources: https://a.com"));
+        assert!(!prompts[0].contains("Costs are stable"));
+
+        // Conflicts are stored alongside (not inside) the agreement analysis
+        let state = state.apply_update(ResearchUpdate::default().with_conflicts(conflicts.clone()));
+        assert_eq!(state.conflicts, conflicts);
+        assert_eq!(state.agreement, SourceAgreement::default());
+    }
+
+    #[tokio::test]
+    async fn test_detect_conflicts_rejects_invalid_response() {
+        let mut state = ResearchState::new("test");
+        state.findings = vec![
+            Finding::new("A", "Claim A", 0.8, ResearchPhase::Exploratory),
+            Finding::new("B", "Claim B", 0.8, ResearchPhase::Exploratory),
+        ];
+        let provider = MockProvider {
+            response: "No conflicts found.".to_string(),
+            prompts: Mutex::new(Vec::new()),
+        };
+
+        assert!(state.detect_conflicts(&provider).await.is_err());
+    }
+
     #[test]
     fn test_findings_by_direction() {
         let mut state = ResearchState::new("test");
@@ -748,4 +1426,66 @@ mod tests {
         assert!(formatted.contains("[1] Source A: https://a.com"));
         assert!(formatted.contains("[2] Source B: https://b.com"));
     }
-}
+
+    fn citation_state() -> ResearchState {
+        let mut state = ResearchState::new("Is Rust adoption growing?");
+        state.sources = vec![
+            Source::new("https://survey.example.com/2024", "Developer Survey 2024", 0.9)
+                .with_author("Stack Overflow")
+                .with_published("2024-07-24"),
+            Source::new("https://blog.example.com/rust", "Rust in Production", 0.7),
+            Source::new("https://acm.example.com/paper", "Memory Safety at Scale", 0.8)
+                .with_author("Anderson")
+                .with_published("2023"),
+        ];
+        state.findings = vec![
+            Finding::new("Popularity", "Rust is the most admired language.", 0.9, ResearchPhase::Exploratory)
+                .with_sources(vec![0]),
+            Finding::new("Industry use", "Large companies ship Rust in production.", 0.8, ResearchPhase::Directed)
+                .with_sources(vec![2, 1]),
+        ];
+        state
+    }
+
+    #[test]
+    fn test_render_report_numbered() {
+        let state = citation_state();
+
+        let report = state.render_report(CitationStyle::Numbered);
+
+        assert_eq!(
+            report,
+            "# Is Rust adoption growing?\n\n\
+             ## Popularity\n\nRust is the most admired language. [1]\n\n\
+             ## Industry use\n\nLarge companies ship Rust in production. [2][3]\n\n\
+             ## References\n\n\
+             [1] Developer Survey 2024: https://survey.example.com/2024\n\
+             [2] Memory Safety at Scale: https://acm.example.com/paper\n\
+             [3] Rust in Production: https://blog.example.com/rust\n"
+        );
+    }
+
+    #[test]
+    fn test_render_report_author_date_from_synthesis() {
+        let state = citation_state().apply_update(ResearchUpdate::default().with_synthesis(
+            "Adoption is growing [S3, S1]. Anecdotes agree [S2]. Unknown [S9] stays.",
+        ));
+
+        let report = state.render_report(CitationStyle::AuthorDate);
+
+        assert_eq!(
+            report,
+            "Adoption is growing (Anderson, 2023; Stack Overflow, 2024). \
+             Anecdotes agree (Rust in Production, n.d.). Unknown [S9] stays.\n\n\
+             ## References\n\n\
+             Anderson (2023). Memory Safety at Scale. https://acm.example.com/paper\n\
+             Rust in Production (n.d.). Rust in Production. https://blog.example.com/rust\n\
+             Stack Overflow (2024). Developer Survey 2024. https://survey.example.com/2024\n"
+        );
+
+        // Uncited sources are not listed
+        let state = citation_state()
+            .apply_update(ResearchUpdate::default().with_synthesis("No citations here."));
+        assert_eq!(state.render_report(CitationStyle::Numbered), "No citations here.\n");
+    }
+}
\ No newline at end of file
@@ -42,18 +42,25 @@
 //! let workflow = ResearchWorkflowBuilder::new()
 //!     .max_searches(6)
 //!     .max_directions(3)
+//!     // Specialize a phase without forking the workflow
+//!     .phase_prompt(
+//!         ResearchPhase::Exploratory,
+//!         PromptBuilder::new(LEGAL_EXPLORER_TEMPLATE).with("jurisdiction", "EU"),
+//!     )
 //!     .build()?;
 //!
 //! let initial_state = ResearchState::new("What is context engineering?");
 //! // Execute with PregelRuntime...
 //! ```
 
+use std::collections::HashMap;
+
 use crate::workflow::{
     AgentNodeConfig, Branch, BranchCondition, NodeKind, RouterNodeConfig, RoutingStrategy,
     StopCondition, WorkflowBuildError, WorkflowGraph, END,
 };
 
-use super::prompts::ResearchPrompts;
+use super::prompts::{PromptBuilder, ResearchPrompts};
 use super::state::{ResearchPhase, ResearchState, ResearchUpdate};
 
 /// Builder for constructing research workflows with configurable parameters.
@@ -76,6 +83,9 @@ pub struct ResearchWorkflowBuilder {
 
     /// Maximum iterations for the synthesizer agent
     max_synthesizer_iterations: usize,
+
+    /// Custom system prompts replacing the defaults for individual phases
+    phase_prompts: HashMap<ResearchPhase, PromptBuilder>,
 }
 
 impl Default for ResearchWorkflowBuilder {
@@ -87,6 +97,7 @@ impl Default for ResearchWorkflowBuilder {
             max_explorer_iterations: 5,
             max_directed_iterations: 8,
             max_synthesizer_iterations: 3,
+            phase_prompts: HashMap::new(),
         }
     }
 }
@@ -143,6 +154,24 @@ impl ResearchWorkflowBuilder {
         self
     }
 
+    /// Replace the default system prompt for a phase's agent.
+    ///
+    /// Phases without an override keep the built-in [`ResearchPrompts`]. The
+    /// search budget note is still appended to exploratory and directed
+    /// prompts. `ResearchPhase::Complete` has no agent, so its override is unused.
+    pub fn phase_prompt(mut self, phase: ResearchPhase, prompt: PromptBuilder) -> Self {
+        self.phase_prompts.insert(phase, prompt);
+        self
+    }
+
+    /// System prompt for a phase: the override if set, otherwise `default`
+    fn prompt_for(&self, phase: ResearchPhase, default: fn() -> String) -> String {
+        self.phase_prompts
+            .get(&phase)
+            .map(|prompt| prompt.clone().build())
+            .unwrap_or_else(default)
+    }
+
     /// Build the research workflow graph.
     pub fn build(self) -> Result<WorkflowGraph<ResearchState>, WorkflowBuildError> {
         // Create agent configurations
@@ -156,7 +185,7 @@ impl ResearchWorkflowBuilder {
         let explorer_config = AgentNodeConfig {
             system_prompt: format!(
                 "{}\n\n## Budget\nMax searches for this phase: 2",
-                ResearchPrompts::researcher()
+                self.prompt_for(ResearchPhase::Exploratory, ResearchPrompts::researcher)
             ),
             max_iterations: self.max_explorer_iterations,
             stop_conditions: vec![
@@ -171,7 +200,7 @@ impl ResearchWorkflowBuilder {
         let directed_config = AgentNodeConfig {
             system_prompt: format!(
                 "{}\n\n## Budget\nMax searches for this phase: {}",
-                ResearchPrompts::researcher(),
+                self.prompt_for(ResearchPhase::Directed, ResearchPrompts::researcher),
                 self.max_searches.saturating_sub(2) // Reserve 2 for exploratory
             ),
             max_iterations: self.max_directed_iterations,
@@ -185,7 +214,7 @@ impl ResearchWorkflowBuilder {
         };
 
         let synthesizer_config = AgentNodeConfig {
-            system_prompt: ResearchPrompts::synthesizer(),
+            system_prompt: self.prompt_for(ResearchPhase::Synthesis, ResearchPrompts::synthesizer),
             max_iterations: self.max_synthesizer_iterations,
             stop_conditions: vec![StopCondition::NoToolCalls],
             ..Default::default()
@@ -275,9 +304,18 @@ pub struct ResearchConfig {
     /// Maximum total searches across all phases
     pub max_searches: usize,
 
+    /// Optional cap on searches during the exploratory phase
+    pub exploratory_searches: Option<usize>,
+
+    /// Optional cap on searches during the directed phase
+    pub directed_searches: Option<usize>,
+
     /// Maximum research directions to explore in Phase 2
     pub max_directions: usize,
 
+    /// Maximum findings included in the synthesis prompt (most confident first)
+    pub max_synthesis_findings: usize,
+
     /// Whether to enable parallel direction exploration
     pub parallel_directions: bool,
 
@@ -289,7 +327,10 @@ impl Default for ResearchConfig {
     fn default() -> Self {
         Self {
             max_searches: 6,
+            exploratory_searches: None,
+            directed_searches: None,
             max_directions: 3,
+            max_synthesis_findings: 10,
             parallel_directions: false,
             timeout_secs: None,
         }
@@ -308,12 +349,30 @@ impl ResearchConfig {
         self
     }
 
+    /// Set the exploratory phase search budget.
+    pub fn with_exploratory_searches(mut self, max: usize) -> Self {
+        self.exploratory_searches = Some(max);
+        self
+    }
+
+    /// Set the directed phase search budget.
+    pub fn with_directed_searches(mut self, max: usize) -> Self {
+        self.directed_searches = Some(max);
+        self
+    }
+
     /// Set maximum directions.
     pub fn with_max_directions(mut self, max: usize) -> Self {
         self.max_directions = max;
         self
     }
 
+    /// Set the number of findings included in the synthesis prompt.
+    pub fn with_max_synthesis_findings(mut self, max: usize) -> Self {
+        self.max_synthesis_findings = max;
+        self
+    }
+
     /// Enable parallel direction exploration.
     pub fn with_parallel_directions(mut self, enabled: bool) -> Self {
         self.parallel_directions = enabled;
@@ -325,17 +384,39 @@ impl ResearchConfig {
         self.timeout_secs = Some(secs);
         self
     }
+
+    /// Build the synthesis prompt for `state` with the configured finding limit.
+    pub fn synthesis_prompt(&self, state: &ResearchState) -> String {
+        PromptBuilder::synthesis(state, self.max_synthesis_findings).build()
+    }
+
+    /// Create the initial research state with this configuration's budgets.
+    pub fn initial_state(&self, query: impl Into<String>) -> ResearchState {
+        let mut state = ResearchState::new(query).with_max_searches(self.max_searches);
+        if let Some(max) = self.exploratory_searches {
+            state = state.with_phase_budget(ResearchPhase::Exploratory, max);
+        }
+        if let Some(max) = self.directed_searches {
+            state = state.with_phase_budget(ResearchPhase::Directed, max);
+        }
+        state
+    }
 }
 
 /// Helper function to check if research can continue based on budget and phase.
 ///
 /// This delegates to the state's computed `can_continue` field for consistency.
-/// The field is automatically updated after each state update.
+/// The field is automatically updated after each state update, and is false
+/// once Synthesis → Directed loop-backs exceed `max_research_loops`.
 pub fn can_continue_research(state: &ResearchState) -> bool {
     state.can_continue
 }
 
 /// Determine the next phase based on current state.
+///
+/// Phases advance Exploratory → Directed → Synthesis → Complete, except that
+/// synthesis returns to Directed when an update asked for more research
+/// ([`ResearchUpdate::with_more_research`]) and a loop-back is still allowed.
 pub fn determine_next_phase(state: &ResearchState) -> ResearchPhase {
     match state.phase {
         ResearchPhase::Exploratory => {
@@ -355,7 +436,14 @@ pub fn determine_next_phase(state: &ResearchState) -> ResearchPhase {
                 ResearchPhase::Directed
             }
         }
-        ResearchPhase::Synthesis => ResearchPhase::Complete,
+        ResearchPhase::Synthesis => {
+            // Loop back when synthesis found gaps, up to `max_research_loops` times
+            if state.can_loop_back() {
+                ResearchPhase::Directed
+            } else {
+                ResearchPhase::Complete
+            }
+        }
         ResearchPhase::Complete => ResearchPhase::Complete,
     }
 }
@@ -401,12 +489,40 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_workflow_builder_phase_prompt_override() {
+        let graph = ResearchWorkflowBuilder::new()
+            .phase_prompt(
+                ResearchPhase::Exploratory,
+                PromptBuilder::new("You are a legal researcher. Survey {jurisdiction} case law first.")
+                    .with("jurisdiction", "EU"),
+            )
+            .build()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let system_prompt = |id: &str| match &graph.nodes[id] {
+            NodeKind::Agent(config) => config.system_prompt.clone(),
+            other => panic!("Expected agent node for {}, got {:?}", id, other),
+        };
+
+        let explorer = system_prompt("explorer");
+        assert!(explorer.starts_with("You are a legal researcher. Survey EU case law first."));
+        assert!(explorer.contains("Max searches for this phase: 2"));
+
+        // Phases without an override keep the defaults
+        assert!(system_prompt("directed").starts_with(&ResearchPrompts::researcher()));
+        assert_eq!(system_prompt("synthesizer"), ResearchPrompts::synthesizer());
+    }
+
     #[test]
     fn test_research_config_default() {
         let config = ResearchConfig::default();
 
         assert_eq!(config.max_searches, 6);
         assert_eq!(config.max_directions, 3);
+        assert_eq!(config.max_synthesis_findings, 10);
         assert!(!config.parallel_directions);
         assert!(config.timeout_secs.is_none());
     }
@@ -425,6 +541,52 @@ mod tests {
         assert_eq!(config.timeout_secs, Some(300));
     }
 
+    #[test]
+    fn test_research_config_synthesis_prompt_truncates_findings() {
+        use crate::research::Finding;
+
+        let config = ResearchConfig::new().with_max_synthesis_findings(1);
+        let mut state = ResearchState::new("test");
+        state.findings = vec![
+            Finding::new("Second", "C", 0.5, ResearchPhase::Directed),
+            Finding::new("First", "C", 0.9, ResearchPhase::Directed),
+        ];
+
+        let prompt = config.synthesis_prompt(&state);
+        assert!(prompt.contains("**First**"));
+        assert!(!prompt.contains("**Second**"));
+    }
+
+    #[test]
+    fn test_research_config_phase_budgets() {
+        let config = ResearchConfig::new()
+            .with_max_searches(6)
+            .with_exploratory_searches(1)
+            .with_directed_searches(4);
+
+        let mut state = config.initial_state("test");
+        state.directions.push(ResearchDirection::new("Dir", "Reason", 5));
+        assert_eq!(state.max_searches, 6);
+
+        // Exploratory budget exhausted, directed budget remains
+        let state = state.apply_update(ResearchUpdate::default().with_search("broad"));
+        assert!(!state.can_search());
+        assert!(can_continue_research(&state));
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Directed);
+
+        let mut state = state.apply_update(phase_transition_update(&state));
+        assert_eq!(state.phase, ResearchPhase::Directed);
+        assert_eq!(state.remaining_searches(), 4);
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Directed);
+
+        // Exhausting the directed budget moves on to synthesis
+        for i in 0..4 {
+            state = state.apply_update(ResearchUpdate::default().with_search(format!("deep {}", i)));
+        }
+        assert!(!can_continue_research(&state));
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Synthesis);
+    }
+
     #[test]
     fn test_can_continue_research_budget() {
         let mut state = ResearchState::new("test").with_max_searches(3);
@@ -517,6 +679,55 @@ mod tests {
         assert_eq!(determine_next_phase(&state), ResearchPhase::Complete);
     }
 
+    #[test]
+    fn test_synthesis_loops_back_to_directed_once() {
+        let mut state = ResearchState::new("test").with_max_searches(6);
+        state.phase = ResearchPhase::Synthesis;
+        state.search_count = 3;
+        state.refresh_can_continue();
+
+        // Synthesis found a gap: one loop-back is allowed by default
+        let state = state.apply_update(
+            ResearchUpdate::default()
+                .with_more_research()
+                .with_directions(vec![ResearchDirection::new("Gap", "Missing 2024 data", 5)]),
+        );
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Directed);
+
+        let state = state.apply_update(phase_transition_update(&state));
+        assert_eq!(state.phase, ResearchPhase::Directed);
+        assert_eq!(state.research_loops, 1);
+        assert!(!state.needs_more_research);
+        assert!(can_continue_research(&state));
+
+        // Back in synthesis, a second request hits the cap
+        let state = state
+            .apply_update(
+                ResearchUpdate::transition_to(ResearchPhase::Synthesis)
+                    .with_explored(vec!["Gap".to_string()]),
+            )
+            .apply_update(ResearchUpdate::default().with_more_research());
+        assert!(!state.can_loop_back());
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Complete);
+
+        // Forcing the transition anyway stops the research
+        let forced = state.apply_update(ResearchUpdate::transition_to(ResearchPhase::Directed));
+        assert_eq!(forced.research_loops, 2);
+        assert!(!can_continue_research(&forced));
+    }
+
+    #[test]
+    fn test_synthesis_loop_back_needs_budget() {
+        let mut state = ResearchState::new("test")
+            .with_max_searches(2)
+            .with_max_research_loops(3);
+        state.phase = ResearchPhase::Synthesis;
+        state.search_count = 2;
+
+        let state = state.apply_update(ResearchUpdate::default().with_more_research());
+        assert_eq!(determine_next_phase(&state), ResearchPhase::Complete);
+    }
+
     #[test]
     fn test_phase_transition_update() {
         let mut state = ResearchState::new("test");
@@ -6,8 +6,11 @@
 //! 도구 실행 시 필요한 컨텍스트를 제공합니다.
 
 use std::sync::Arc;
+use std::time::Duration;
 use crate::state::AgentState;
 use crate::backends::Backend;
+use crate::llm::LLMRetryConfig;
+use crate::middleware::PromptSection;
 
 /// 도구 실행 런타임
 /// Python: ToolRuntime
@@ -16,6 +19,7 @@ use crate::backends::Backend;
 /// - 현재 에이전트 상태
 /// - 백엔드 접근
 /// - 도구 호출 ID
+/// - 진행 상황 보고 콜백 (선택)
 pub struct ToolRuntime {
     /// 현재 에이전트 상태 (읽기 전용 스냅샷)
     state: AgentState,
@@ -25,6 +29,76 @@ pub struct ToolRuntime {
     tool_call_id: Option<String>,
     /// 추가 설정
     config: RuntimeConfig,
+    /// 진행 상황 보고 콜백
+    progress: Option<ToolProgressFn>,
+}
+
+/// 도구 실행 중 진행 상황을 보고하는 콜백
+///
+/// AgentExecutor는 이를 `ExecutorEvent::ToolProgress`로 전달합니다.
+pub type ToolProgressFn = Arc<dyn Fn(String) + Send + Sync>;
+
+/// 한 턴의 도구 호출 중 동시에 실행할 수 있는 기본 최대 개수
+pub const DEFAULT_MAX_CONCURRENT_TOOLS: usize = 4;
+
+/// 에이전트 루프의 기본 최대 반복 횟수 (모델 호출 기준)
+pub const DEFAULT_MAX_ITERATIONS: usize = 50;
+
+/// 시스템 프롬프트 섹션 배치 순서
+///
+/// 나열된 이름 순서대로 섹션을 배치하고, 나열되지 않은 섹션은 미들웨어 등록 순서대로
+/// 그 뒤에 붙입니다. [`SystemPromptLayout::BASE`]는 executor에 설정된 기본 시스템
+/// 프롬프트를 가리키며, 나열하지 않으면 맨 앞에 옵니다.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // 서브에이전트 안내를 맨 앞에, 기본 프롬프트를 맨 뒤에 배치
+/// let layout = SystemPromptLayout::new(["subagent", "skills", "filesystem", SystemPromptLayout::BASE]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemPromptLayout {
+    order: Vec<String>,
+}
+
+impl SystemPromptLayout {
+    /// 기본 시스템 프롬프트 섹션 이름
+    pub const BASE: &'static str = "base";
+
+    /// 섹션 이름 순서로 생성
+    pub fn new(order: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            order: order.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 설정된 섹션 이름 순서
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    /// 기본 프롬프트와 섹션들을 배치 순서대로 이어 붙임 (빈 섹션은 생략)
+    pub fn assemble(&self, base: &str, sections: Vec<PromptSection>) -> String {
+        let mut sections: Vec<PromptSection> = std::iter::once(PromptSection::new(Self::BASE, base))
+            .chain(sections)
+            .filter(|section| !section.content.trim().is_empty())
+            .collect();
+
+        // 안정 정렬: 같은 순위 안에서는 등록 순서 유지
+        sections.sort_by_key(|section| {
+            match self.order.iter().position(|name| *name == section.name) {
+                Some(index) => (1, index),
+                None if section.name == Self::BASE => (0, 0),
+                None => (2, 0),
+            }
+        });
+
+        sections
+            .iter()
+            .map(|section| section.content.trim())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
 }
 
 /// 런타임 설정
@@ -36,6 +110,16 @@ pub struct RuntimeConfig {
     pub max_recursion: usize,
     /// 현재 재귀 깊이
     pub current_recursion: usize,
+    /// 도구 실행 타임아웃 (None이면 제한 없음)
+    pub tool_timeout: Option<Duration>,
+    /// LLM 호출 재시도 설정
+    pub llm_retry: LLMRetryConfig,
+    /// 동시 실행 가능한 도구 호출 최대 개수 (1이면 순차 실행)
+    pub max_concurrent_tools: usize,
+    /// 에이전트 루프 최대 반복 횟수 (초과 시 `DeepAgentError::MaxIterationsExceeded`)
+    pub max_iterations: usize,
+    /// 시스템 프롬프트 섹션 배치 순서
+    pub prompt_layout: SystemPromptLayout,
 }
 
 impl RuntimeConfig {
@@ -44,6 +128,11 @@ impl RuntimeConfig {
             debug: false,
             max_recursion: 100,  // Python 기본값에 가깝게 조정
             current_recursion: 0,
+            tool_timeout: None,
+            llm_retry: LLMRetryConfig::default(),
+            max_concurrent_tools: DEFAULT_MAX_CONCURRENT_TOOLS,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            prompt_layout: SystemPromptLayout::default(),
         }
     }
 
@@ -53,8 +142,43 @@ impl RuntimeConfig {
             debug: false,
             max_recursion,
             current_recursion: 0,
+            tool_timeout: None,
+            llm_retry: LLMRetryConfig::default(),
+            max_concurrent_tools: DEFAULT_MAX_CONCURRENT_TOOLS,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            prompt_layout: SystemPromptLayout::default(),
         }
     }
+
+    /// 도구 실행 타임아웃 설정
+    pub fn with_tool_timeout(mut self, timeout: Duration) -> Self {
+        self.tool_timeout = Some(timeout);
+        self
+    }
+
+    /// 에이전트 루프 최대 반복 횟수 설정
+    pub fn with_max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = max;
+        self
+    }
+
+    /// 동시 실행 도구 호출 최대 개수 설정
+    pub fn with_max_concurrent_tools(mut self, max: usize) -> Self {
+        self.max_concurrent_tools = max;
+        self
+    }
+
+    /// LLM 호출 재시도 설정
+    pub fn with_llm_retry(mut self, llm_retry: LLMRetryConfig) -> Self {
+        self.llm_retry = llm_retry;
+        self
+    }
+
+    /// 시스템 프롬프트 섹션 배치 순서 설정
+    pub fn with_prompt_layout(mut self, layout: SystemPromptLayout) -> Self {
+        self.prompt_layout = layout;
+        self
+    }
 }
 
 impl ToolRuntime {
@@ -64,6 +188,7 @@ impl ToolRuntime {
             backend,
             tool_call_id: None,
             config: RuntimeConfig::new(),
+            progress: None,
         }
     }
 
@@ -77,6 +202,19 @@ impl ToolRuntime {
         self
     }
 
+    /// 진행 상황 보고 콜백 설정
+    pub fn with_progress(mut self, progress: ToolProgressFn) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 진행 상황 보고 (콜백이 없으면 무시)
+    pub fn report_progress(&self, message: impl Into<String>) {
+        if let Some(progress) = &self.progress {
+            progress(message.into());
+        }
+    }
+
     /// 현재 상태 참조
     pub fn state(&self) -> &AgentState {
         &self.state
@@ -107,6 +245,8 @@ impl ToolRuntime {
             backend: self.backend.clone(),
             tool_call_id: None,
             config: new_config,
+            // 하위 SubAgent의 진행 상황도 같은 도구 호출로 전달
+            progress: self.progress.clone(),
         }
     }
 
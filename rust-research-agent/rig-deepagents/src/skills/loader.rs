@@ -2,19 +2,22 @@
 //!
 //! Implements progressive disclosure pattern:
 //! - `list_skills()`: Returns only metadata (fast, for system prompt)
-//! - `load_skill()`: Returns full content on-demand (lazy)
+//! - `load_skill()`: Returns full content on-demand (lazy), including any
+//!   skills listed in its `requires` frontmatter field
 //!
 //! Skill files must be named SKILL.md and located in:
 //! - User skills: ~/.claude/skills/{skill-name}/SKILL.md
 //! - Project skills: {PROJECT_ROOT}/skills/{skill-name}/SKILL.md
+//!
+//! Malformed files are skipped and reported through [`SkillLoader::load_errors`].
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
-use super::types::{SkillContent, SkillMetadata, SkillSource};
+use super::types::{SkillContent, SkillLoadError, SkillMetadata, SkillSource};
 use crate::backends::Backend;
 use crate::error::MiddlewareError;
 
@@ -35,6 +38,7 @@ pub struct SkillLoader {
     storage: SkillStorage,
     metadata_cache: Arc<RwLock<HashMap<String, MetadataCacheEntry>>>,
     content_cache: Arc<RwLock<HashMap<String, SkillContent>>>,
+    load_errors: Arc<RwLock<Vec<SkillLoadError>>>,
 }
 
 impl SkillLoader {
@@ -43,6 +47,7 @@ impl SkillLoader {
             storage: SkillStorage::Filesystem { user_dir, project_dir },
             metadata_cache: Arc::new(RwLock::new(HashMap::new())),
             content_cache: Arc::new(RwLock::new(HashMap::new())),
+            load_errors: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -51,6 +56,7 @@ impl SkillLoader {
             storage: SkillStorage::Backend { backend, sources },
             metadata_cache: Arc::new(RwLock::new(HashMap::new())),
             content_cache: Arc::new(RwLock::new(HashMap::new())),
+            load_errors: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -74,42 +80,57 @@ impl SkillLoader {
     }
 
     /// Scan directories and populate metadata cache
+    ///
+    /// Files that fail to parse are skipped; the problems (including duplicate
+    /// names across sources) are available from [`load_errors`](Self::load_errors).
     pub async fn initialize(&self) -> Result<(), MiddlewareError> {
         let mut cache = self.metadata_cache.write().await;
         cache.clear();
+        let mut errors = Vec::new();
 
         match &self.storage {
             SkillStorage::Filesystem { user_dir, project_dir } => {
                 if let Some(user_dir) = user_dir {
                     if user_dir.exists() {
-                        self.scan_directory(user_dir, SkillSource::User, &mut cache)
+                        self.scan_directory(user_dir, SkillSource::User, &mut cache, &mut errors)
                             .await?;
                     }
                 }
 
                 if let Some(project_dir) = project_dir {
                     if project_dir.exists() {
-                        self.scan_directory(project_dir, SkillSource::Project, &mut cache)
+                        self.scan_directory(project_dir, SkillSource::Project, &mut cache, &mut errors)
                             .await?;
                     }
                 }
             }
             SkillStorage::Backend { backend, sources } => {
-                self.scan_backend_sources(backend, sources, &mut cache)
+                self.scan_backend_sources(backend, sources, &mut cache, &mut errors)
                     .await?;
             }
         }
 
-        debug!("Loaded {} skill metadata entries", cache.len());
+        debug!(
+            "Loaded {} skill metadata entries ({} load errors)",
+            cache.len(),
+            errors.len()
+        );
+        *self.load_errors.write().await = errors;
         Ok(())
     }
 
+    /// Problems found during the last [`initialize`](Self::initialize)
+    pub async fn load_errors(&self) -> Vec<SkillLoadError> {
+        self.load_errors.read().await.clone()
+    }
+
     /// Scan a directory for SKILL.md files
     async fn scan_directory(
         &self,
         dir: &Path,
         source: SkillSource,
         cache: &mut HashMap<String, (SkillMetadata, PathBuf, SkillSource)>,
+        errors: &mut Vec<SkillLoadError>,
     ) -> Result<(), MiddlewareError> {
         // Use tokio::fs for non-blocking directory reading
         let mut entries = match tokio::fs::read_dir(dir).await {
@@ -136,10 +157,11 @@ impl SkillLoader {
                                     skill_file,
                                     source.as_str()
                                 );
-                                cache.insert(skill_meta.name.clone(), (skill_meta, skill_file, source));
+                                insert_metadata(cache, errors, skill_meta, skill_file, source);
                             }
                             Err(e) => {
                                 warn!("Failed to parse skill {:?}: {}", skill_file, e);
+                                errors.push(e);
                             }
                         }
                     }
@@ -155,6 +177,7 @@ impl SkillLoader {
         backend: &Arc<dyn Backend>,
         sources: &[String],
         cache: &mut HashMap<String, (SkillMetadata, PathBuf, SkillSource)>,
+        errors: &mut Vec<SkillLoadError>,
     ) -> Result<(), MiddlewareError> {
         for source in sources {
             let entries = match backend.ls(source).await {
@@ -172,7 +195,7 @@ impl SkillLoader {
 
                 let skill_file = format!("{}/SKILL.md", entry.path.trim_end_matches('/'));
                 match backend.read_plain(&skill_file).await {
-                    Ok(content) => match parse_frontmatter(&content) {
+                    Ok(content) => match parse_frontmatter(&content, &skill_file) {
                         Ok(skill_meta) => {
                             debug!(
                                 "Loaded skill metadata: {} from {} ({})",
@@ -180,13 +203,17 @@ impl SkillLoader {
                                 skill_file,
                                 SkillSource::Backend.as_str()
                             );
-                            cache.insert(
-                                skill_meta.name.clone(),
-                                (skill_meta, PathBuf::from(&skill_file), SkillSource::Backend),
+                            insert_metadata(
+                                cache,
+                                errors,
+                                skill_meta,
+                                PathBuf::from(&skill_file),
+                                SkillSource::Backend,
                             );
                         }
                         Err(e) => {
                             warn!("Failed to parse skill {}: {}", skill_file, e);
+                            errors.push(e);
                         }
                     },
                     Err(e) => {
@@ -200,12 +227,16 @@ impl SkillLoader {
     }
 
     /// Parse only metadata from YAML frontmatter (fast)
-    async fn parse_metadata(&self, path: &Path) -> Result<SkillMetadata, MiddlewareError> {
+    async fn parse_metadata(&self, path: &Path) -> Result<SkillMetadata, SkillLoadError> {
+        let path_str = path.to_string_lossy();
         let content = tokio::fs::read_to_string(path)
             .await
-            .map_err(|e| MiddlewareError::ToolExecution(format!("Failed to read skill: {}", e)))?;
+            .map_err(|e| SkillLoadError::Unreadable {
+                path: path_str.to_string(),
+                message: e.to_string(),
+            })?;
 
-        parse_frontmatter(&content)
+        parse_frontmatter(&content, &path_str)
     }
 
     /// List all available skills (metadata only)
@@ -231,6 +262,10 @@ impl SkillLoader {
     }
 
     /// Load full skill content (lazy, cached)
+    ///
+    /// Skills named in `requires` are loaded transitively and appended to the
+    /// body in dependency order. Cyclic requirements fail with
+    /// [`SkillLoadError::CycleDetected`].
     pub async fn load_skill(&self, name: &str) -> Result<SkillContent, MiddlewareError> {
         // Check content cache first
         {
@@ -240,6 +275,29 @@ impl SkillLoader {
             }
         }
 
+        let order = {
+            let cache = self.metadata_cache.read().await;
+            resolve_requires(name, &cache)?
+        };
+
+        let mut content = self.read_skill(name).await?;
+        for required in order.iter().filter(|required| required.as_str() != name) {
+            let required = self.read_skill(required).await?;
+            content.body.push_str("\n\n---\n\n");
+            content.body.push_str(&required.full_content());
+        }
+
+        // Cache the content
+        {
+            let mut cache = self.content_cache.write().await;
+            cache.insert(name.to_string(), content.clone());
+        }
+
+        Ok(content)
+    }
+
+    /// Read a single skill file without resolving its requirements
+    async fn read_skill(&self, name: &str) -> Result<SkillContent, MiddlewareError> {
         // Get path from metadata cache
         let (metadata, path) = {
             let cache = self.metadata_cache.read().await;
@@ -268,15 +326,7 @@ impl SkillLoader {
         };
 
         let body = parse_body(&raw_content);
-        let content = SkillContent::new(metadata, body, path.to_string_lossy().to_string());
-
-        // Cache the content
-        {
-            let mut cache = self.content_cache.write().await;
-            cache.insert(name.to_string(), content.clone());
-        }
-
-        Ok(content)
+        Ok(SkillContent::new(metadata, body, path.to_string_lossy().to_string()))
     }
 
     /// Refresh skill cache (re-scan directories)
@@ -304,13 +354,15 @@ impl SkillLoader {
 /// ```
 ///
 /// The closing `---` must be on its own line (possibly with trailing whitespace).
-fn parse_frontmatter(content: &str) -> Result<SkillMetadata, MiddlewareError> {
+/// `path` is only used to label errors.
+fn parse_frontmatter(content: &str, path: &str) -> Result<SkillMetadata, SkillLoadError> {
     let content = content.trim();
 
     if !content.starts_with("---") {
-        return Err(MiddlewareError::ToolExecution(
-            "Skill file must start with YAML frontmatter (---)".to_string(),
-        ));
+        return Err(SkillLoadError::MissingFrontmatter {
+            path: path.to_string(),
+            reason: "Skill file must start with YAML frontmatter (---)".to_string(),
+        });
     }
 
     // Skip the opening --- and any trailing content on that line
@@ -320,14 +372,93 @@ fn parse_frontmatter(content: &str) -> Result<SkillMetadata, MiddlewareError> {
     // Find the closing --- on its own line
     // Look for \n--- followed by newline, whitespace+newline, or end of string
     let end_idx = find_closing_frontmatter(rest)
-        .ok_or_else(|| MiddlewareError::ToolExecution(
-            "Missing closing --- in frontmatter (must be on its own line)".to_string()
-        ))?;
+        .ok_or_else(|| SkillLoadError::MissingFrontmatter {
+            path: path.to_string(),
+            reason: "Missing closing --- in frontmatter (must be on its own line)".to_string(),
+        })?;
 
     let yaml_str = &rest[..end_idx];
+    let invalid_yaml = |e: serde_yaml::Error| SkillLoadError::InvalidYaml {
+        path: path.to_string(),
+        message: e.to_string(),
+    };
+
+    // Check `name` separately so the most common authoring mistake gets a clear error
+    let value: serde_yaml::Value = serde_yaml::from_str(yaml_str.trim()).map_err(invalid_yaml)?;
+    let has_name = value
+        .get("name")
+        .and_then(serde_yaml::Value::as_str)
+        .is_some_and(|name| !name.trim().is_empty());
+    if !has_name {
+        return Err(SkillLoadError::MissingName { path: path.to_string() });
+    }
+
+    serde_yaml::from_value(value).map_err(invalid_yaml)
+}
+
+/// Resolve `name` and its transitive requirements, dependencies first and `name` last
+fn resolve_requires(
+    name: &str,
+    cache: &HashMap<String, MetadataCacheEntry>,
+) -> Result<Vec<String>, MiddlewareError> {
+    fn visit(
+        name: &str,
+        cache: &HashMap<String, MetadataCacheEntry>,
+        stack: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), MiddlewareError> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(pos) = stack.iter().position(|visiting| visiting == name) {
+            let mut chain = stack[pos..].to_vec();
+            chain.push(name.to_string());
+            return Err(SkillLoadError::CycleDetected { chain }.into());
+        }
 
-    serde_yaml::from_str(yaml_str.trim())
-        .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid YAML frontmatter: {}", e)))
+        let (metadata, _, _) = cache.get(name).ok_or_else(|| match stack.last() {
+            Some(parent) => MiddlewareError::ToolExecution(format!(
+                "Skill '{}' requires unknown skill '{}'",
+                parent, name
+            )),
+            None => MiddlewareError::ToolExecution(format!("Skill not found: {}", name)),
+        })?;
+
+        stack.push(name.to_string());
+        for required in &metadata.requires {
+            visit(required, cache, stack, done, order)?;
+        }
+        stack.pop();
+
+        done.insert(name.to_string());
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    let mut order = Vec::new();
+    visit(name, cache, &mut Vec::new(), &mut HashSet::new(), &mut order)?;
+    Ok(order)
+}
+
+/// Insert skill metadata, recording a duplicate-name error when it overrides an earlier entry
+fn insert_metadata(
+    cache: &mut HashMap<String, MetadataCacheEntry>,
+    errors: &mut Vec<SkillLoadError>,
+    metadata: SkillMetadata,
+    path: PathBuf,
+    source: SkillSource,
+) {
+    let name = metadata.name.clone();
+    let kept = path.to_string_lossy().to_string();
+    if let Some((_, overridden, _)) = cache.insert(name.clone(), (metadata, path, source)) {
+        warn!("Skill '{}' at {:?} overrides {:?}", name, kept, overridden);
+        errors.push(SkillLoadError::DuplicateName {
+            name,
+            kept,
+            overridden: overridden.to_string_lossy().to_string(),
+        });
+    }
 }
 
 /// Find the position of the closing frontmatter delimiter
@@ -390,7 +521,7 @@ tags:
 This is the skill body.
 "#;
 
-        let metadata = parse_frontmatter(content).unwrap();
+        let metadata = parse_frontmatter(content, "SKILL.md").unwrap();
         assert_eq!(metadata.name, "test-skill");
         assert_eq!(metadata.description, "A test skill");
         assert_eq!(metadata.tags, vec!["testing"]);
@@ -405,7 +536,7 @@ description: Minimal skill
 Body here
 "#;
 
-        let metadata = parse_frontmatter(content).unwrap();
+        let metadata = parse_frontmatter(content, "SKILL.md").unwrap();
         assert_eq!(metadata.name, "minimal");
         assert!(metadata.tags.is_empty());
     }
@@ -413,7 +544,7 @@ Body here
     #[test]
     fn test_parse_frontmatter_missing_start() {
         let content = "No frontmatter here";
-        let result = parse_frontmatter(content);
+        let result = parse_frontmatter(content, "SKILL.md");
         assert!(result.is_err());
     }
 
@@ -423,7 +554,7 @@ Body here
 name: incomplete
 description: Missing closing
 "#;
-        let result = parse_frontmatter(content);
+        let result = parse_frontmatter(content, "SKILL.md");
         assert!(result.is_err());
     }
 
@@ -465,7 +596,7 @@ description: A skill with dashes---in the description
 Content here with --- in text.
 "#;
 
-        let metadata = parse_frontmatter(content).unwrap();
+        let metadata = parse_frontmatter(content, "SKILL.md").unwrap();
         assert_eq!(metadata.name, "complex-skill");
         assert_eq!(metadata.description, "A skill with dashes---in the description");
 
@@ -478,7 +609,7 @@ Content here with --- in text.
         // Edge case: closing --- with trailing whitespace
         let content = "---\nname: test\ndescription: Test\n---   \nBody";
 
-        let metadata = parse_frontmatter(content).unwrap();
+        let metadata = parse_frontmatter(content, "SKILL.md").unwrap();
         assert_eq!(metadata.name, "test");
 
         let body = parse_body(content);
@@ -604,4 +735,177 @@ Unique body.
         let unique = loader.get_metadata("unique").await.unwrap();
         assert_eq!(unique.description, "Unique description");
     }
+    fn write_skill(root: &Path, dir: &str, content: &str) {
+        let skill_dir = root.join(dir);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+    }
+
+    #[test]
+    fn test_parse_frontmatter_missing_name() {
+        let content = "---\ndescription: No name here\n---\nBody\n";
+        let result = parse_frontmatter(content, "nameless/SKILL.md");
+        assert_eq!(
+            result.unwrap_err(),
+            SkillLoadError::MissingName { path: "nameless/SKILL.md".to_string() }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skill_loader_collects_errors_and_keeps_valid_skills() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "valid",
+            "---\nname: valid\ndescription: Valid skill\n---\nBody\n",
+        );
+        write_skill(temp_dir.path(), "no-frontmatter", "# Just markdown\n");
+        write_skill(temp_dir.path(), "bad-yaml", "---\nname: [unclosed\n---\nBody\n");
+        write_skill(temp_dir.path(), "no-name", "---\ndescription: Nameless\n---\nBody\n");
+
+        let loader = SkillLoader::new(None, Some(temp_dir.path().to_path_buf()));
+        loader.initialize().await.unwrap();
+
+        let skills = loader.list_skills().await;
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].0.name, "valid");
+
+        let errors = loader.load_errors().await;
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            SkillLoadError::MissingFrontmatter { path, .. } if path.contains("no-frontmatter")
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            SkillLoadError::InvalidYaml { path, .. } if path.contains("bad-yaml")
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            SkillLoadError::MissingName { path } if path.contains("no-name")
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_skill_loader_duplicate_name_project_wins() {
+        let user_dir = tempfile::tempdir().unwrap();
+        let project_dir = tempfile::tempdir().unwrap();
+        write_skill(
+            user_dir.path(),
+            "shared",
+            "---\nname: shared\ndescription: User version\n---\nUser body\n",
+        );
+        write_skill(
+            project_dir.path(),
+            "shared",
+            "---\nname: shared\ndescription: Project version\n---\nProject body\n",
+        );
+
+        let loader = SkillLoader::new(
+            Some(user_dir.path().to_path_buf()),
+            Some(project_dir.path().to_path_buf()),
+        );
+        loader.initialize().await.unwrap();
+
+        let skills = loader.list_skills().await;
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].0.description, "Project version");
+        assert_eq!(skills[0].1, SkillSource::Project);
+
+        let errors = loader.load_errors().await;
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            SkillLoadError::DuplicateName { name, kept, overridden } => {
+                assert_eq!(name, "shared");
+                assert!(kept.starts_with(&*project_dir.path().to_string_lossy()));
+                assert!(overridden.starts_with(&*user_dir.path().to_string_lossy()));
+            }
+            other => panic!("expected DuplicateName, got {:?}", other),
+        }
+
+        // Re-initializing replaces rather than accumulates errors
+        loader.initialize().await.unwrap();
+        assert_eq!(loader.load_errors().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_skill_includes_transitive_requirements() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "report-writing",
+            "---\nname: report-writing\ndescription: Write reports\nrequires:\n  - data-synthesis\n---\nReport body\n",
+        );
+        write_skill(
+            temp_dir.path(),
+            "data-synthesis",
+            "---\nname: data-synthesis\ndescription: Synthesize data\nrequires: [source-gathering]\n---\nSynthesis body\n",
+        );
+        write_skill(
+            temp_dir.path(),
+            "source-gathering",
+            "---\nname: source-gathering\ndescription: Gather sources\n---\nGathering body\n",
+        );
+
+        let loader = SkillLoader::new(None, Some(temp_dir.path().to_path_buf()));
+        loader.initialize().await.unwrap();
+
+        let metadata = loader.get_metadata("report-writing").await.unwrap();
+        assert_eq!(metadata.requires, vec!["data-synthesis"]);
+
+        let content = loader.load_skill("report-writing").await.unwrap();
+        assert_eq!(content.name(), "report-writing");
+        let report = content.body.find("Report body").unwrap();
+        let gathering = content.body.find("# Skill: source-gathering").unwrap();
+        let synthesis = content.body.find("# Skill: data-synthesis").unwrap();
+        assert!(report < gathering && gathering < synthesis);
+        assert!(content.body.contains("Gathering body"));
+        assert!(content.body.contains("Synthesis body"));
+
+        // Leaf skills load on their own
+        let leaf = loader.load_skill("source-gathering").await.unwrap();
+        assert_eq!(leaf.body.trim(), "Gathering body");
+    }
+
+    #[tokio::test]
+    async fn test_load_skill_detects_cycles() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "alpha",
+            "---\nname: alpha\ndescription: A\nrequires: [beta]\n---\nAlpha\n",
+        );
+        write_skill(
+            temp_dir.path(),
+            "beta",
+            "---\nname: beta\ndescription: B\nrequires: [alpha]\n---\nBeta\n",
+        );
+
+        let loader = SkillLoader::new(None, Some(temp_dir.path().to_path_buf()));
+        loader.initialize().await.unwrap();
+
+        let err = loader.load_skill("alpha").await.unwrap_err();
+        match err {
+            MiddlewareError::SkillLoad(SkillLoadError::CycleDetected { chain }) => {
+                assert_eq!(chain, vec!["alpha", "beta", "alpha"]);
+            }
+            other => panic!("expected CycleDetected, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_skill_unknown_requirement() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "orphan",
+            "---\nname: orphan\ndescription: O\nrequires: [missing]\n---\nOrphan\n",
+        );
+
+        let loader = SkillLoader::new(None, Some(temp_dir.path().to_path_buf()));
+        loader.initialize().await.unwrap();
+
+        let err = loader.load_skill("orphan").await.unwrap_err();
+        assert!(err.to_string().contains("requires unknown skill 'missing'"));
+    }
 }
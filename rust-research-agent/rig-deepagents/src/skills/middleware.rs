@@ -1,7 +1,7 @@
 //! Skills middleware implementing AgentMiddleware trait
 //!
 //! Injects skill summaries into system prompt and provides
-//! the `use_skill` tool for on-demand skill loading.
+//! the `use_skill` tool ([`LoadSkillTool`]) for on-demand skill loading.
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -11,7 +11,9 @@ use tokio::sync::RwLock;
 use super::loader::SkillLoader;
 use super::types::{SkillMetadata, SkillSource};
 use crate::error::MiddlewareError;
-use crate::middleware::{AgentMiddleware, DynTool, Tool, ToolDefinition, ToolResult, StateUpdate};
+use crate::middleware::{
+    AgentMiddleware, DynTool, PromptSection, Tool, ToolDefinition, ToolResult, StateUpdate,
+};
 use crate::runtime::ToolRuntime;
 use crate::state::AgentState;
 
@@ -20,7 +22,7 @@ use crate::state::AgentState;
 /// Implements the progressive disclosure pattern:
 /// 1. On initialization: Loads skill metadata (fast)
 /// 2. In system prompt: Injects skill summaries
-/// 3. On tool call: Loads full skill content (lazy)
+/// 3. On tool call: Loads full skill content (lazy) via [`LoadSkillTool`]
 pub struct SkillsMiddleware {
     loader: Arc<SkillLoader>,
     /// Pre-computed skill summaries for sync access in modify_system_prompt
@@ -83,7 +85,8 @@ You have access to the following skills. Use the `use_skill` tool to load full i
 
 {}
 
-To use a skill, call: `use_skill({{"name": "skill-name"}})`
+Before attempting a skill, always call `use_skill({{"name": "skill-name"}})` to load its
+full instructions. The summaries above are not enough to apply a skill correctly.
 "#,
             skills
                 .iter()
@@ -115,9 +118,7 @@ impl AgentMiddleware for SkillsMiddleware {
     }
 
     fn tools(&self) -> Vec<DynTool> {
-        vec![Arc::new(UseSkillTool {
-            loader: Arc::clone(&self.loader),
-        })]
+        vec![Arc::new(LoadSkillTool::new(Arc::clone(&self.loader)))]
     }
 
     fn modify_system_prompt(&self, prompt: String) -> String {
@@ -128,6 +129,12 @@ impl AgentMiddleware for SkillsMiddleware {
         }
     }
 
+    fn system_prompt_sections(&self) -> Vec<PromptSection> {
+        self.get_cached_summaries_sync()
+            .map(|section| vec![PromptSection::new(self.name(), section.trim())])
+            .unwrap_or_default()
+    }
+
     async fn before_agent(
         &self,
         _state: &mut AgentState,
@@ -139,18 +146,29 @@ impl AgentMiddleware for SkillsMiddleware {
     }
 }
 
-/// Tool for loading skill content on-demand
-struct UseSkillTool {
+/// Tool for loading skill content on-demand (exposed to the model as `use_skill`)
+///
+/// Calls [`SkillLoader::load_skill`] and returns the skill's
+/// [`full_content`](super::types::SkillContent::full_content) as the tool result.
+/// Registered automatically by [`SkillsMiddleware::tools`].
+pub struct LoadSkillTool {
     loader: Arc<SkillLoader>,
 }
 
+impl LoadSkillTool {
+    /// Create a tool that loads skills from the given loader
+    pub fn new(loader: Arc<SkillLoader>) -> Self {
+        Self { loader }
+    }
+}
+
 #[derive(Debug, Deserialize)]
-struct UseSkillArgs {
+struct LoadSkillArgs {
     name: String,
 }
 
 #[async_trait]
-impl Tool for UseSkillTool {
+impl Tool for LoadSkillTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "use_skill".to_string(),
@@ -165,6 +183,7 @@ impl Tool for UseSkillTool {
                 },
                 "required": ["name"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -173,7 +192,7 @@ impl Tool for UseSkillTool {
         args: serde_json::Value,
         _runtime: &ToolRuntime,
     ) -> Result<ToolResult, MiddlewareError> {
-        let args: UseSkillArgs = serde_json::from_value(args)
+        let args: LoadSkillArgs = serde_json::from_value(args)
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
         let skill = self.loader.load_skill(&args.name).await?;
@@ -268,9 +287,7 @@ Different content here.
     #[tokio::test]
     async fn test_use_skill_tool() {
         let (loader, _temp_dir) = create_test_loader().await;
-        let tool = UseSkillTool {
-            loader: Arc::clone(&loader),
-        };
+        let tool = LoadSkillTool::new(Arc::clone(&loader));
 
         let backend = Arc::new(MemoryBackend::new());
         let state = AgentState::new();
@@ -286,12 +303,40 @@ Different content here.
         assert!(result.message.contains("1. Do this"));
     }
 
+    #[tokio::test]
+    async fn test_registered_tool_returns_skill_markdown_body() {
+        let (loader, _temp_dir) = create_test_loader().await;
+        let middleware = SkillsMiddleware::with_loader(Arc::clone(&loader)).await;
+
+        let tool = middleware.tools().remove(0);
+        let runtime = ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()));
+
+        let result = tool
+            .execute(serde_json::json!({"name": "another-skill"}), &runtime)
+            .await
+            .unwrap();
+
+        let expected = loader.load_skill("another-skill").await.unwrap().full_content();
+        assert_eq!(result.message, expected);
+        assert!(result.message.contains("# Another Skill\n\nDifferent content here."));
+    }
+
+    #[tokio::test]
+    async fn test_prompt_instructs_loading_before_use() {
+        let (loader, _temp_dir) = create_test_loader().await;
+        let middleware = SkillsMiddleware::with_loader(loader).await;
+
+        let modified = middleware.modify_system_prompt(String::new());
+
+        assert!(modified.contains("Before attempting a skill, always call `use_skill"));
+    }
+
     #[tokio::test]
     async fn test_use_skill_not_found() {
         let loader = Arc::new(SkillLoader::new(None, None));
         loader.initialize().await.unwrap();
 
-        let tool = UseSkillTool { loader };
+        let tool = LoadSkillTool::new(loader);
 
         let backend = Arc::new(MemoryBackend::new());
         let state = AgentState::new();
@@ -7,7 +7,8 @@
 //!
 //! 1. At session start: Only skill summaries (name + description) are
 //!    injected into the system prompt
-//! 2. When skill is invoked: Full skill content is loaded on-demand
+//! 2. When skill is invoked: Full skill content is loaded on-demand by the
+//!    model calling the `use_skill` tool ([`LoadSkillTool`])
 //!
 //! # Directory Structure
 //!
@@ -64,6 +65,6 @@ pub mod types;
 pub mod loader;
 pub mod middleware;
 
-pub use types::{SkillMetadata, SkillContent, SkillSource};
+pub use types::{SkillMetadata, SkillContent, SkillLoadError, SkillSource};
 pub use loader::SkillLoader;
-pub use middleware::SkillsMiddleware;
+pub use middleware::{LoadSkillTool, SkillsMiddleware};
@@ -14,6 +14,8 @@ use serde::{Deserialize, Serialize};
 /// ---
 /// name: academic-search
 /// description: Search arXiv papers with structured output
+/// requires:
+///   - data-synthesis
 /// ---
 /// [Full skill instructions...]
 /// ```
@@ -36,6 +38,10 @@ pub struct SkillMetadata {
     /// Optional author information
     #[serde(default)]
     pub author: Option<String>,
+
+    /// Names of skills this skill builds on; loaded together with it
+    #[serde(default)]
+    pub requires: Vec<String>,
 }
 
 /// Complete skill content including metadata and body
@@ -107,6 +113,41 @@ impl SkillSource {
     }
 }
 
+/// Problem found in a skill file during loading
+///
+/// Collected by `SkillLoader::initialize` so skill authors can see why a
+/// skill is missing or overridden; valid skills are still loaded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SkillLoadError {
+    /// The file does not start with a `---` delimited frontmatter block
+    #[error("{path}: {reason}")]
+    MissingFrontmatter { path: String, reason: String },
+
+    /// The frontmatter is not valid YAML or does not match the metadata schema
+    #[error("{path}: invalid YAML frontmatter: {message}")]
+    InvalidYaml { path: String, message: String },
+
+    /// The frontmatter has no (or an empty) `name` field
+    #[error("{path}: frontmatter is missing a non-empty `name`")]
+    MissingName { path: String },
+
+    /// Two skill files declare the same name; the later source takes precedence
+    #[error("duplicate skill '{name}': {overridden} is overridden by {kept}")]
+    DuplicateName {
+        name: String,
+        kept: String,
+        overridden: String,
+    },
+
+    /// The skill file could not be read
+    #[error("{path}: failed to read skill: {message}")]
+    Unreadable { path: String, message: String },
+
+    /// Skills require each other in a loop (`chain` starts and ends with the same skill)
+    #[error("cyclic skill dependency: {}", chain.join(" -> "))]
+    CycleDetected { chain: Vec<String> },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +191,7 @@ description: Minimal skill
             tags: vec![],
             version: None,
             author: None,
+            requires: vec![],
         };
         let content = SkillContent::new(
             metadata,
@@ -171,6 +213,7 @@ description: Minimal skill
             tags: vec![],
             version: None,
             author: None,
+            requires: vec![],
         };
         let content = SkillContent::new(
             metadata,
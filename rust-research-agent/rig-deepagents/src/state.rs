@@ -4,10 +4,15 @@
 //! Python Reference: langchain/agents/middleware/types.py의 AgentState
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::any::Any;
+use std::path::Path;
 use chrono::Utc;
 use tracing::warn;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use crate::error::{BackendError, MiddlewareError};
+use crate::llm::RunUsage;
+use crate::tokenization::TokenCounter;
 
 /// Todo 상태
 /// Python: Literal["pending", "in_progress", "completed"]
@@ -23,24 +28,59 @@ pub enum TodoStatus {
 /// Python: Todo(TypedDict)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Todo {
+    /// 의존성 참조용 식별자
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub content: String,
     pub status: TodoStatus,
+    /// 먼저 완료되어야 하는 Todo의 id 목록
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
 }
 
 impl Todo {
     pub fn new(content: &str) -> Self {
-        Self {
-            content: content.to_string(),
-            status: TodoStatus::Pending,
-        }
+        Self::with_status(content, TodoStatus::Pending)
     }
 
     pub fn with_status(content: &str, status: TodoStatus) -> Self {
         Self {
+            id: None,
             content: content.to_string(),
             status,
+            depends_on: Vec::new(),
         }
     }
+
+    /// 식별자 설정
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// 선행 Todo 설정
+    pub fn with_depends_on(mut self, depends_on: &[&str]) -> Self {
+        self.depends_on = depends_on.iter().map(|id| id.to_string()).collect();
+        self
+    }
+
+    /// 바로 시작할 수 있는 Todo 목록
+    ///
+    /// 아직 완료되지 않았고 모든 선행 Todo가 `Completed`인 항목을 반환합니다.
+    /// 목록에 없는 id에 대한 의존성은 충족되지 않은 것으로 봅니다.
+    pub fn ready(todos: &[Todo]) -> Vec<&Todo> {
+        let completed: HashSet<&str> = todos
+            .iter()
+            .filter(|t| t.status == TodoStatus::Completed)
+            .filter_map(|t| t.id.as_deref())
+            .collect();
+
+        todos
+            .iter()
+            .filter(|t| t.status != TodoStatus::Completed)
+            .filter(|t| t.depends_on.iter().all(|dep| completed.contains(dep.as_str())))
+            .collect()
+    }
 }
 
 /// 파일 데이터
@@ -52,6 +92,12 @@ pub struct FileData {
     pub content: Vec<String>,
     pub created_at: String,
     pub modified_at: String,
+    /// 바이너리 파일 여부
+    ///
+    /// `true`이면 `content`는 base64 인코딩된 단일 라인이며,
+    /// 라인 단위 편집/검색 대상에서 제외됩니다.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_binary: bool,
 }
 
 impl FileData {
@@ -61,6 +107,38 @@ impl FileData {
             content: content.lines().map(String::from).collect(),
             created_at: now.clone(),
             modified_at: now,
+            is_binary: false,
+        }
+    }
+
+    /// 바이너리 데이터로 생성 (base64로 저장)
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            content: vec![BASE64.encode(bytes)],
+            created_at: now.clone(),
+            modified_at: now,
+            is_binary: true,
+        }
+    }
+
+    /// 원본 바이트 반환
+    ///
+    /// 바이너리 파일은 base64를 디코딩하고, 텍스트 파일은 UTF-8 바이트를 반환합니다.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        if self.is_binary {
+            BASE64.decode(self.content.concat())
+        } else {
+            Ok(self.as_string().into_bytes())
+        }
+    }
+
+    /// 원본 데이터 크기 (바이트)
+    pub fn size(&self) -> usize {
+        if self.is_binary {
+            self.to_bytes().map(|b| b.len()).unwrap_or(0)
+        } else {
+            self.content.iter().map(|s| s.len()).sum()
         }
     }
 
@@ -71,6 +149,7 @@ impl FileData {
     pub fn update(&mut self, new_content: &str) {
         self.content = new_content.lines().map(String::from).collect();
         self.modified_at = Utc::now().to_rfc3339();
+        self.is_binary = false;
     }
 
     pub fn line_count(&self) -> usize {
@@ -79,7 +158,7 @@ impl FileData {
 }
 
 /// 메시지 역할
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Role {
     User,
@@ -96,17 +175,97 @@ pub struct ToolCall {
     pub arguments: serde_json::Value,
 }
 
+/// 메시지 콘텐츠 (텍스트, 이미지 또는 혼합)
+///
+/// 스크린샷 분석처럼 이미지를 모델에 전달해야 할 때 사용합니다.
+/// `url_or_b64`는 `http(s)://` URL, `data:` URL 또는 base64 데이터입니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageContent {
+    /// 텍스트
+    Text(String),
+    /// 이미지
+    Image { url_or_b64: String, mime: String },
+    /// 여러 조각의 조합 (예: 텍스트 + 이미지)
+    Parts(Vec<MessageContent>),
+}
+
+impl Default for MessageContent {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl From<&str> for MessageContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for MessageContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl MessageContent {
+    /// 이미지 콘텐츠 생성
+    pub fn image(url_or_b64: impl Into<String>, mime: impl Into<String>) -> Self {
+        Self::Image {
+            url_or_b64: url_or_b64.into(),
+            mime: mime.into(),
+        }
+    }
+
+    /// 텍스트 조각만 줄바꿈으로 이어붙인 문자열
+    pub fn text(&self) -> String {
+        let texts: Vec<&str> = self
+            .leaves()
+            .into_iter()
+            .filter_map(|part| match part {
+                Self::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        texts.join("\n")
+    }
+
+    /// 이미지 포함 여부
+    pub fn has_images(&self) -> bool {
+        self.leaves()
+            .into_iter()
+            .any(|part| matches!(part, Self::Image { .. }))
+    }
+
+    /// 중첩된 `Parts`를 펼친 텍스트/이미지 조각 목록
+    pub fn leaves(&self) -> Vec<&MessageContent> {
+        match self {
+            Self::Parts(parts) => parts.iter().flat_map(|part| part.leaves()).collect(),
+            other => vec![other],
+        }
+    }
+}
+
 /// 메시지
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: Role,
+    /// 텍스트 콘텐츠 (멀티모달 메시지는 텍스트 조각만 포함)
     pub content: String,
+    /// 이미지를 포함한 멀티모달 콘텐츠 (텍스트 전용 메시지는 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rich_content: Option<MessageContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
+    /// 출처 등 부가 정보 (어떤 SubAgent/단계가 생성했는지 등)
+    ///
+    /// 상태와 세션 저장에는 유지되지만 LLM으로 변환할 때는 전달되지 않습니다.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
 }
 
 impl Message {
@@ -117,6 +276,18 @@ impl Message {
             tool_call_id: None,
             tool_calls: None,
             status: None,
+            rich_content: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// 멀티모달 사용자 메시지 (텍스트 + 이미지)
+    pub fn user_with_content(content: MessageContent) -> Self {
+        let text = content.text();
+        let rich_content = content.has_images().then_some(content);
+        Self {
+            rich_content,
+            ..Self::user(&text)
         }
     }
 
@@ -127,6 +298,8 @@ impl Message {
             tool_call_id: None,
             tool_calls: None,
             status: None,
+            rich_content: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -137,6 +310,8 @@ impl Message {
             tool_call_id: None,
             tool_calls: Some(tool_calls),
             status: None,
+            rich_content: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -147,6 +322,8 @@ impl Message {
             tool_call_id: None,
             tool_calls: None,
             status: None,
+            rich_content: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -157,6 +334,8 @@ impl Message {
             tool_call_id: Some(tool_call_id.to_string()),
             tool_calls: None,
             status: None,
+            rich_content: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -167,9 +346,24 @@ impl Message {
             tool_call_id: Some(tool_call_id.to_string()),
             tool_calls: None,
             status: Some(status.to_string()),
+            rich_content: None,
+            metadata: HashMap::new(),
         }
     }
 
+    /// 메시지 콘텐츠 (텍스트 전용 메시지는 `MessageContent::Text`)
+    pub fn message_content(&self) -> MessageContent {
+        self.rich_content
+            .clone()
+            .unwrap_or_else(|| MessageContent::Text(self.content.clone()))
+    }
+
+    /// 메타데이터 항목 추가 (빌더 패턴)
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
     /// 이 메시지에 dangling tool call이 있는지 확인
     pub fn has_tool_calls(&self) -> bool {
         self.tool_calls.as_ref().is_some_and(|tc| !tc.is_empty())
@@ -180,7 +374,8 @@ impl Message {
 /// Python: AgentState(TypedDict) + FilesystemState + PlanningState
 ///
 /// Note: Clone은 extensions 필드 없이 수동 구현됨 (dyn Any는 Clone 불가)
-#[derive(Debug, Default)]
+/// 직렬화 시에도 extensions 필드는 제외됨 (`save_to`/`load_from`으로 세션 저장 가능)
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AgentState {
     /// 메시지 히스토리
     pub messages: Vec<Message>,
@@ -194,8 +389,12 @@ pub struct AgentState {
     /// 구조화된 응답
     pub structured_response: Option<serde_json::Value>,
 
+    /// 실행 중 누적된 LLM 토큰 사용량 (요약/SubAgent 호출 포함)
+    pub usage: RunUsage,
+
     /// 확장 데이터 (미들웨어별 커스텀 상태)
     /// Note: 이 필드는 Clone되지 않음 - 새 HashMap으로 초기화됨
+    #[serde(skip)]
     extensions: HashMap<String, Box<dyn Any + Send + Sync>>,
 }
 
@@ -215,6 +414,7 @@ impl Clone for AgentState {
             todos: self.todos.clone(),
             files: self.files.clone(),
             structured_response: self.structured_response.clone(),
+            usage: self.usage.clone(),
             // extensions는 Box<dyn Any>를 clone할 수 없어서 빈 상태로 시작
             // 향후 Arc<RwLock<_>> 패턴으로 개선 고려
             extensions: HashMap::new(),
@@ -264,6 +464,93 @@ impl AgentState {
     pub fn message_count(&self) -> usize {
         self.messages.len()
     }
+
+    /// 실행 전체의 누적 토큰 사용량
+    pub fn run_usage(&self) -> &RunUsage {
+        &self.usage
+    }
+
+    /// 세션 상태를 JSON 파일로 저장
+    ///
+    /// 임시 파일에 쓴 뒤 rename하므로 중간에 실패해도 기존 파일은 유지됩니다.
+    /// extensions는 저장되지 않습니다.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), MiddlewareError> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec_pretty(self)?;
+
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, json)
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| BackendError::Io(format!("{}: {}", path.display(), e)))?;
+
+        Ok(())
+    }
+
+    /// `save_to`로 저장한 세션 상태 복원
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, MiddlewareError> {
+        let path = path.as_ref();
+        let json = std::fs::read(path)
+            .map_err(|e| BackendError::Io(format!("{}: {}", path.display(), e)))?;
+
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// 토큰 예산을 넘지 않도록 가장 오래된 메시지부터 제거
+    ///
+    /// 선두의 시스템 메시지는 유지하며, 도구 호출을 보낸 어시스턴트 메시지와
+    /// 그 Tool 결과는 함께 제거되어 짝이 끊기지 않습니다. 요약 없이 히스토리
+    /// 크기를 제한하는 가벼운 안전장치입니다.
+    ///
+    /// 제거된 메시지를 원래 순서대로 반환합니다.
+    pub fn trim_to_tokens(&mut self, counter: &dyn TokenCounter, max_tokens: usize) -> Vec<Message> {
+        let system_len = self
+            .messages
+            .iter()
+            .take_while(|m| m.role == Role::System)
+            .count();
+        let body = &self.messages[system_len..];
+
+        let mut remaining = counter.count_messages(&self.messages);
+        if remaining <= max_tokens {
+            return Vec::new();
+        }
+
+        // 도구 호출을 보낸 어시스턴트 메시지의 위치
+        let call_sites: HashMap<&str, usize> = body
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| m.tool_calls.as_ref().map(|calls| (i, calls)))
+            .flat_map(|(i, calls)| calls.iter().map(move |c| (c.id.as_str(), i)))
+            .collect();
+
+        // 호출과 결과 사이에서 자르면 짝이 끊기므로 해당 위치는 제외
+        let mut safe = vec![true; body.len() + 1];
+        for (j, message) in body.iter().enumerate() {
+            if message.role == Role::Tool {
+                safe[j] = false;
+            }
+            let site = message
+                .tool_call_id
+                .as_deref()
+                .and_then(|id| call_sites.get(id).copied());
+            if let Some(site) = site.filter(|&site| site < j) {
+                safe[site + 1..=j].iter_mut().for_each(|s| *s = false);
+            }
+        }
+
+        let mut cutoff = body.len();
+        for (i, message) in body.iter().enumerate() {
+            remaining -= counter.count_message(message);
+            if remaining <= max_tokens && safe[i + 1] {
+                cutoff = i + 1;
+                break;
+            }
+        }
+
+        self.messages
+            .drain(system_len..system_len + cutoff)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +564,24 @@ mod tests {
         assert_eq!(json, "\"in_progress\"");
     }
 
+    #[test]
+    fn test_todo_ready_follows_dependencies() {
+        let todos = vec![
+            Todo::with_status("Search", TodoStatus::Completed).with_id("a"),
+            Todo::new("Read papers").with_id("b").with_depends_on(&["a"]),
+            Todo::new("Compare").with_id("c").with_depends_on(&["a", "b"]),
+            Todo::new("Draft outline"),
+        ];
+
+        let ready: Vec<&str> = Todo::ready(&todos).iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(ready, vec!["Read papers", "Draft outline"]);
+
+        // 의존성이 없으면 id/depends_on 필드 없이 직렬화됨
+        let json = serde_json::to_value(Todo::new("plain")).unwrap();
+        assert!(json.get("id").is_none());
+        assert!(json.get("depends_on").is_none());
+    }
+
     #[test]
     fn test_agent_state_default() {
         let state = AgentState::new();
@@ -293,6 +598,21 @@ mod tests {
         assert_eq!(file.line_count(), 2);
     }
 
+    #[test]
+    fn test_file_data_binary_roundtrip() {
+        let bytes = vec![0u8, 159, 146, 150, b'\n', 255];
+        let file = FileData::from_bytes(&bytes);
+        assert!(file.is_binary);
+        assert_eq!(file.size(), bytes.len());
+        assert_eq!(file.to_bytes().unwrap(), bytes);
+
+        // 텍스트 파일은 is_binary 필드 없이 직렬화됨 (기존 포맷 호환)
+        let json = serde_json::to_value(FileData::new("text")).unwrap();
+        assert!(json.get("is_binary").is_none());
+        let restored: FileData = serde_json::from_value(json).unwrap();
+        assert!(!restored.is_binary);
+    }
+
     #[test]
     fn test_message_with_tool_calls() {
         let tool_call = ToolCall {
@@ -304,6 +624,145 @@ mod tests {
         assert!(msg.has_tool_calls());
     }
 
+    #[test]
+    fn test_message_content_defaults_to_text() {
+        let message = Message::user("Hello");
+        assert!(message.rich_content.is_none());
+        assert_eq!(message.message_content(), MessageContent::Text("Hello".to_string()));
+
+        let mixed = MessageContent::Parts(vec![
+            MessageContent::Text("first".to_string()),
+            MessageContent::Parts(vec![
+                MessageContent::image("https://example.com/a.png", "image/png"),
+                MessageContent::Text("second".to_string()),
+            ]),
+        ]);
+        assert!(mixed.has_images());
+        assert_eq!(mixed.text(), "first\nsecond");
+        assert_eq!(mixed.leaves().len(), 3);
+
+        // 이미지가 없으면 텍스트 전용 메시지로 저장
+        let text_only = Message::user_with_content(MessageContent::Parts(vec!["only text".into()]));
+        assert!(text_only.rich_content.is_none());
+        assert_eq!(text_only.content, "only text");
+    }
+
+    #[test]
+    fn test_message_metadata_survives_state_roundtrip() {
+        let state = AgentState::with_messages(vec![
+            Message::user("Find papers on RAG"),
+            Message::assistant("Found three papers.")
+                .with_metadata("subagent", "researcher")
+                .with_metadata("phase", "Exploratory"),
+        ]);
+
+        let json = serde_json::to_value(&state).unwrap();
+        // 비어 있는 메타데이터는 직렬화하지 않음
+        assert!(json["messages"][0].get("metadata").is_none());
+
+        let restored: AgentState = serde_json::from_value(json).unwrap();
+        assert!(restored.messages[0].metadata.is_empty());
+        assert_eq!(restored.messages[1].metadata["subagent"], "researcher");
+        assert_eq!(restored.messages[1].metadata["phase"], "Exploratory");
+    }
+
+    #[test]
+    fn test_trim_to_tokens_preserves_system_and_pairs() {
+        use crate::tokenization::ApproxTokenCounter;
+
+        let call = |id: &str| ToolCall {
+            id: id.to_string(),
+            name: "search".to_string(),
+            arguments: serde_json::json!({"q": "rust"}),
+        };
+        let mut messages = vec![Message::system("You are a researcher.")];
+        for i in 0..10 {
+            let a = format!("call_{}a", i);
+            let b = format!("call_{}b", i);
+            messages.push(Message::user(&format!("Question {}", i)));
+            messages.push(Message::assistant_with_tool_calls("", vec![call(&a), call(&b)]));
+            messages.push(Message::tool("first result", &a));
+            messages.push(Message::tool("second result", &b));
+            messages.push(Message::assistant(&format!("Answer {}", i)));
+        }
+        let mut state = AgentState::with_messages(messages);
+        let counter = ApproxTokenCounter::default();
+        let before = state.message_count();
+
+        let budget = 100;
+        let removed = state.trim_to_tokens(&counter, budget);
+
+        assert!(!removed.is_empty());
+        assert_eq!(removed.len() + state.message_count(), before);
+        assert!(counter.count_messages(&state.messages) <= budget);
+        assert_eq!(state.messages[0].role, Role::System);
+        assert!(removed.iter().all(|m| m.role != Role::System));
+
+        // 남은 Tool 결과는 모두 남은 어시스턴트 호출과 짝을 이룸
+        let kept_calls: Vec<&str> = state
+            .messages
+            .iter()
+            .flat_map(|m| m.tool_calls.iter().flatten())
+            .map(|c| c.id.as_str())
+            .collect();
+        for message in state.messages.iter().filter(|m| m.role == Role::Tool) {
+            assert!(kept_calls.contains(&message.tool_call_id.as_deref().unwrap()));
+        }
+        assert_ne!(state.messages[1].role, Role::Tool);
+
+        // 예산 이내면 아무것도 제거하지 않음
+        assert!(state.trim_to_tokens(&counter, budget).is_empty());
+    }
+
+    #[test]
+    fn test_agent_state_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let mut state = AgentState::with_messages(vec![
+            Message::system("You are a researcher."),
+            Message::user("Find papers on RAG"),
+            Message::assistant_with_tool_calls(
+                "",
+                vec![ToolCall {
+                    id: "call_1".to_string(),
+                    name: "web_search".to_string(),
+                    arguments: serde_json::json!({"query": "RAG", "max_results": 5}),
+                }],
+            ),
+            Message::tool_with_status("3 results", "call_1", "success"),
+        ]);
+        state.todos = vec![
+            Todo::with_status("Search", TodoStatus::Completed),
+            Todo::new("Summarize"),
+        ];
+        state.files.insert("/notes.md".to_string(), FileData::new("# Notes\n- RAG"));
+        state.files.insert("/logo.png".to_string(), FileData::from_bytes(&[0, 1, 255]));
+        state.structured_response = Some(serde_json::json!({"done": false}));
+        state.usage.llm_calls = 2;
+
+        state.save_to(&path).unwrap();
+        let restored = AgentState::load_from(&path).unwrap();
+
+        assert_eq!(restored.message_count(), 4);
+        assert_eq!(restored.messages[0].role, Role::System);
+        let calls = restored.messages[2].tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].arguments, serde_json::json!({"query": "RAG", "max_results": 5}));
+        assert_eq!(restored.messages[3].tool_call_id.as_deref(), Some("call_1"));
+        assert_eq!(restored.messages[3].status.as_deref(), Some("success"));
+
+        assert_eq!(restored.todos.len(), 2);
+        assert_eq!(restored.todos[0].status, TodoStatus::Completed);
+        assert_eq!(restored.todos[1].content, "Summarize");
+
+        assert_eq!(restored.files["/notes.md"].as_string(), "# Notes\n- RAG");
+        assert_eq!(restored.files["/logo.png"].to_bytes().unwrap(), vec![0, 1, 255]);
+        assert_eq!(restored.structured_response, state.structured_response);
+        assert_eq!(restored.usage, state.usage);
+
+        assert!(AgentState::load_from(dir.path().join("missing.json")).is_err());
+    }
+
     #[test]
     fn test_agent_state_with_messages() {
         let state = AgentState::with_messages(vec![Message::user("Hello")]);
@@ -0,0 +1,261 @@
+//! Test support utilities
+//!
+//! Available to this crate's own tests and, through the `test-util` feature,
+//! to downstream crates that want to test agents without a real LLM.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! rig-deepagents = { version = "*", features = ["test-util"] }
+//! ```
+
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::error::DeepAgentError;
+use crate::llm::{FinishReason, LLMConfig, LLMProvider, LLMResponse, TokenUsage};
+use crate::middleware::{ModelRequest, ToolDefinition};
+use crate::state::{Message, ToolCall};
+
+/// One scripted reply
+enum ScriptedReply {
+    Response(LLMResponse),
+    Error(String),
+}
+
+/// Deterministic LLM provider that replays a scripted conversation
+///
+/// Replies are served in the order they were queued, one per `complete()`
+/// call (streaming falls back to `complete()`). Every request is recorded so
+/// tests can assert on the prompts, tools, and configuration the model saw.
+/// Once the script runs out, calls fail unless a fallback reply is set.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rig_deepagents::testing::MockLLMProvider;
+///
+/// let llm = Arc::new(
+///     MockLLMProvider::new()
+///         .with_tool_call("Let me check.", "read_file", json!({"file_path": "/notes.md"}))
+///         .with_usage(TokenUsage::new(120, 15))
+///         .with_response("The notes say hello."),
+/// );
+///
+/// let result = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), backend)
+///     .run(state)
+///     .await?;
+///
+/// assert_eq!(llm.request_count(), 2);
+/// assert!(llm.requests()[1].messages.iter().any(|m| m.role == Role::Tool));
+/// ```
+#[derive(Default)]
+pub struct MockLLMProvider {
+    script: Mutex<VecDeque<ScriptedReply>>,
+    fallback: Option<Message>,
+    requests: Mutex<Vec<ModelRequest>>,
+}
+
+impl MockLLMProvider {
+    /// Create a provider with an empty script
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a plain assistant text reply
+    pub fn with_response(self, content: impl Into<String>) -> Self {
+        self.with_message(Message::assistant(&content.into()))
+    }
+
+    /// Queue an arbitrary assistant message
+    pub fn with_message(self, message: Message) -> Self {
+        self.push(ScriptedReply::Response(LLMResponse::new(message)))
+    }
+
+    /// Queue several assistant messages in order
+    pub fn with_messages(self, messages: impl IntoIterator<Item = Message>) -> Self {
+        messages
+            .into_iter()
+            .fold(self, |provider, message| provider.with_message(message))
+    }
+
+    /// Queue an assistant reply that calls a single tool
+    ///
+    /// Call ids are numbered by script position (`call_0`, `call_1`, ...).
+    pub fn with_tool_call(
+        self,
+        content: impl Into<String>,
+        tool_name: impl Into<String>,
+        arguments: serde_json::Value,
+    ) -> Self {
+        let call = ToolCall {
+            id: format!("call_{}", self.script.lock().unwrap().len()),
+            name: tool_name.into(),
+            arguments,
+        };
+        self.with_message(Message::assistant_with_tool_calls(&content.into(), vec![call]))
+    }
+
+    /// Attach token usage to the most recently queued reply
+    pub fn with_usage(self, usage: TokenUsage) -> Self {
+        if let Some(ScriptedReply::Response(response)) = self.script.lock().unwrap().back_mut() {
+            response.usage = Some(usage);
+        }
+        self
+    }
+
+    /// Set the finish reason of the most recently queued reply
+    pub fn with_finish_reason(self, finish_reason: FinishReason) -> Self {
+        if let Some(ScriptedReply::Response(response)) = self.script.lock().unwrap().back_mut() {
+            response.finish_reason = finish_reason;
+        }
+        self
+    }
+
+    /// Queue a failed call (surfaced as [`DeepAgentError::LlmError`])
+    pub fn with_error(self, message: impl Into<String>) -> Self {
+        self.push(ScriptedReply::Error(message.into()))
+    }
+
+    /// Reply with this text whenever the script is exhausted
+    pub fn with_fallback(mut self, content: impl Into<String>) -> Self {
+        self.fallback = Some(Message::assistant(&content.into()));
+        self
+    }
+
+    /// All requests received so far, in call order
+    pub fn requests(&self) -> Vec<ModelRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The most recent request, if any
+    pub fn last_request(&self) -> Option<ModelRequest> {
+        self.requests.lock().unwrap().last().cloned()
+    }
+
+    /// Number of `complete()` calls received
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Number of scripted replies not yet served
+    pub fn remaining(&self) -> usize {
+        self.script.lock().unwrap().len()
+    }
+
+    fn push(self, reply: ScriptedReply) -> Self {
+        self.script.lock().unwrap().push_back(reply);
+        self
+    }
+}
+
+#[async_trait]
+impl LLMProvider for MockLLMProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        config: Option<&LLMConfig>,
+    ) -> Result<LLMResponse, DeepAgentError> {
+        self.requests.lock().unwrap().push(ModelRequest {
+            messages: messages.to_vec(),
+            tools: tools.to_vec(),
+            config: config.cloned(),
+        });
+
+        match self.script.lock().unwrap().pop_front() {
+            Some(ScriptedReply::Response(response)) => Ok(response),
+            Some(ScriptedReply::Error(message)) => Err(DeepAgentError::LlmError(message)),
+            None => self.fallback.clone().map(LLMResponse::new).ok_or_else(|| {
+                DeepAgentError::AgentExecution("No more mock responses".to_string())
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn default_model(&self) -> &str {
+        "mock-model"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+    use crate::executor::AgentExecutor;
+    use crate::middleware::MiddlewareStack;
+    use crate::state::{AgentState, Role};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_scripted_replies_in_order() {
+        let llm = MockLLMProvider::new()
+            .with_response("first")
+            .with_error("rate limited")
+            .with_response("third")
+            .with_usage(TokenUsage::new(7, 3));
+
+        let first = llm.complete(&[Message::user("a")], &[], None).await.unwrap();
+        assert_eq!(first.message.content, "first");
+        assert!(first.usage.is_none());
+
+        let second = llm.complete(&[Message::user("b")], &[], None).await;
+        assert!(matches!(second, Err(DeepAgentError::LlmError(msg)) if msg == "rate limited"));
+
+        let third = llm.complete(&[Message::user("c")], &[], None).await.unwrap();
+        assert_eq!(third.usage, Some(TokenUsage::new(7, 3)));
+
+        assert_eq!(llm.remaining(), 0);
+        assert!(llm.complete(&[Message::user("d")], &[], None).await.is_err());
+        assert_eq!(llm.request_count(), 4);
+        assert_eq!(llm.last_request().unwrap().messages[0].content, "d");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_after_script() {
+        let llm = MockLLMProvider::new().with_fallback("Default response");
+
+        let response = llm.complete(&[], &[], None).await.unwrap();
+        assert_eq!(response.message.content, "Default response");
+    }
+
+    #[tokio::test]
+    async fn test_multi_turn_tool_conversation() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/notes.md", "hello from notes").await.unwrap();
+
+        let llm = Arc::new(
+            MockLLMProvider::new()
+                .with_tool_call("", "read_file", serde_json::json!({"file_path": "/notes.md"}))
+                .with_usage(TokenUsage::new(100, 10))
+                .with_response("The notes say hello.")
+                .with_usage(TokenUsage::new(150, 20)),
+        );
+
+        let executor = AgentExecutor::new(llm.clone(), MiddlewareStack::new(), backend);
+        let result = executor
+            .run(AgentState::with_messages(vec![Message::user("What do the notes say?")]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.last_assistant_message().unwrap().content,
+            "The notes say hello."
+        );
+        assert_eq!(result.run_usage().tokens, TokenUsage::new(250, 30));
+
+        // The second turn sees the tool result for the scripted call
+        let requests = llm.requests();
+        assert_eq!(requests.len(), 2);
+        let tool_message = requests[1]
+            .messages
+            .iter()
+            .find(|m| m.role == Role::Tool)
+            .expect("tool result should be sent back to the model");
+        assert_eq!(tool_message.tool_call_id.as_deref(), Some("call_0"));
+        assert!(tool_message.content.contains("hello from notes"));
+    }
+}
@@ -1,9 +1,9 @@
 use crate::middleware::summarization::token_counter::{
     count_tokens_approximately, DEFAULT_CHARS_PER_TOKEN, DEFAULT_OVERHEAD_PER_MESSAGE,
 };
-use crate::state::Message;
-#[cfg(feature = "tokenizer-tiktoken")]
-use crate::state::Role;
+use std::collections::HashMap;
+
+use crate::state::{Message, Role};
 
 pub trait TokenCounter: Send + Sync {
     fn count_text(&self, text: &str) -> usize;
@@ -16,7 +16,12 @@ pub trait TokenCounter: Send + Sync {
 #[derive(Debug, Clone)]
 pub struct ApproxTokenCounter {
     pub chars_per_token: f32,
+    /// Overhead for roles without an entry in `role_overheads`
     pub overhead_per_message: usize,
+    /// Per-role overrides of `overhead_per_message`
+    pub role_overheads: HashMap<Role, usize>,
+    /// Extra overhead for messages that carry `tool_calls`
+    pub tool_call_overhead: usize,
 }
 
 impl ApproxTokenCounter {
@@ -24,16 +29,41 @@ impl ApproxTokenCounter {
         Self {
             chars_per_token,
             overhead_per_message,
+            role_overheads: HashMap::new(),
+            tool_call_overhead: 0,
+        }
+    }
+
+    /// Use a different structural overhead per role
+    pub fn with_role_overheads(mut self, role_overheads: HashMap<Role, usize>) -> Self {
+        self.role_overheads = role_overheads;
+        self
+    }
+
+    /// Add extra overhead to messages containing tool calls
+    pub fn with_tool_call_overhead(mut self, tool_call_overhead: usize) -> Self {
+        self.tool_call_overhead = tool_call_overhead;
+        self
+    }
+
+    fn overhead_for(&self, message: &Message) -> usize {
+        let role_overhead = self
+            .role_overheads
+            .get(&message.role)
+            .copied()
+            .unwrap_or(self.overhead_per_message);
+
+        if message.has_tool_calls() {
+            role_overhead + self.tool_call_overhead
+        } else {
+            role_overhead
         }
     }
 }
 
 impl Default for ApproxTokenCounter {
     fn default() -> Self {
-        Self {
-            chars_per_token: DEFAULT_CHARS_PER_TOKEN,
-            overhead_per_message: DEFAULT_OVERHEAD_PER_MESSAGE as usize,
-        }
+        Self::new(DEFAULT_CHARS_PER_TOKEN, DEFAULT_OVERHEAD_PER_MESSAGE as usize)
     }
 }
 
@@ -43,11 +73,55 @@ impl TokenCounter for ApproxTokenCounter {
     }
 
     fn count_message(&self, message: &Message) -> usize {
-        count_tokens_approximately(
-            std::slice::from_ref(message),
-            self.chars_per_token,
-            self.overhead_per_message as f32,
-        )
+        count_tokens_approximately(std::slice::from_ref(message), self.chars_per_token, 0.0)
+            + self.overhead_for(message)
+    }
+}
+
+/// BPE encodings used by OpenAI models
+#[cfg(feature = "tokenizer-tiktoken")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiktokenEncoding {
+    /// GPT-4, GPT-3.5 and the embedding models
+    Cl100kBase,
+    /// GPT-4o, GPT-4.1, GPT-5 and the o-series reasoning models
+    O200kBase,
+}
+
+#[cfg(feature = "tokenizer-tiktoken")]
+impl TiktokenEncoding {
+    /// Model-name prefixes that use `o200k_base`; everything else falls back to `cl100k_base`
+    const O200K_PREFIXES: &'static [&'static str] = &[
+        "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "chatgpt-4o", "o1", "o3", "o4",
+    ];
+
+    /// Pick the encoding for a model name such as `gpt-4o-mini` or `openai/gpt-4`
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+        let model = model.rsplit('/').next().unwrap_or(&model);
+
+        if Self::O200K_PREFIXES
+            .iter()
+            .any(|prefix| model.starts_with(prefix))
+        {
+            Self::O200kBase
+        } else {
+            Self::Cl100kBase
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cl100kBase => "cl100k_base",
+            Self::O200kBase => "o200k_base",
+        }
+    }
+
+    fn load(&self) -> Result<tiktoken_rs::CoreBPE, anyhow::Error> {
+        match self {
+            Self::Cl100kBase => tiktoken_rs::cl100k_base(),
+            Self::O200kBase => tiktoken_rs::o200k_base(),
+        }
     }
 }
 
@@ -55,19 +129,42 @@ impl TokenCounter for ApproxTokenCounter {
 #[derive(Debug, Clone)]
 pub struct TiktokenTokenCounter {
     encoder: tiktoken_rs::CoreBPE,
+    encoding: Option<TiktokenEncoding>,
 }
 
 #[cfg(feature = "tokenizer-tiktoken")]
 impl TiktokenTokenCounter {
     pub fn new(encoder: tiktoken_rs::CoreBPE) -> Self {
-        Self { encoder }
+        Self {
+            encoder,
+            encoding: None,
+        }
     }
 
-    pub fn cl100k_base() -> Result<Self, tiktoken_rs::Error> {
+    pub fn with_encoding(encoding: TiktokenEncoding) -> Result<Self, anyhow::Error> {
         Ok(Self {
-            encoder: tiktoken_rs::cl100k_base()?,
+            encoder: encoding.load()?,
+            encoding: Some(encoding),
         })
     }
+
+    pub fn cl100k_base() -> Result<Self, anyhow::Error> {
+        Self::with_encoding(TiktokenEncoding::Cl100kBase)
+    }
+
+    pub fn o200k_base() -> Result<Self, anyhow::Error> {
+        Self::with_encoding(TiktokenEncoding::O200kBase)
+    }
+
+    /// Counter using the encoding for `model`, falling back to `cl100k_base`
+    pub fn for_model(model: &str) -> Result<Self, anyhow::Error> {
+        Self::with_encoding(TiktokenEncoding::for_model(model))
+    }
+
+    /// Named encoding, or `None` when built from a custom encoder via [`new`](Self::new)
+    pub fn encoding(&self) -> Option<TiktokenEncoding> {
+        self.encoding
+    }
 }
 
 #[cfg(feature = "tokenizer-tiktoken")]
@@ -118,7 +215,7 @@ fn build_message_text(message: &Message) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::Message;
+    use crate::state::{Message, ToolCall};
 
     #[test]
     fn test_approx_counter_counts_non_zero() {
@@ -128,6 +225,40 @@ mod tests {
         assert!(counter.count_text("Hello there") > 0);
     }
 
+    #[test]
+    fn test_approx_counter_role_overheads() {
+        let plain = Message::user("Search for papers");
+        let tool_call = Message::assistant_with_tool_calls(
+            "",
+            vec![ToolCall {
+                id: "c1".to_string(),
+                name: "search".to_string(),
+                arguments: serde_json::json!({"q": "p"}),
+            }],
+        );
+
+        let uniform = ApproxTokenCounter::new(4.0, 3);
+        let tuned = ApproxTokenCounter::new(4.0, 3)
+            .with_role_overheads(HashMap::from([(Role::Assistant, 5)]))
+            .with_tool_call_overhead(20);
+
+        // Plain user messages fall back to the default overhead
+        assert_eq!(tuned.count_message(&plain), uniform.count_message(&plain));
+        // Tool-call messages get the role override plus the tool-call overhead
+        assert_eq!(
+            tuned.count_message(&tool_call),
+            uniform.count_message(&tool_call) - 3 + 5 + 20
+        );
+        assert_eq!(
+            tuned.count_messages(&[plain.clone(), tool_call.clone()]),
+            tuned.count_message(&plain) + tuned.count_message(&tool_call)
+        );
+
+        // Without tool calls an assistant message only gets the role override
+        let reply = Message::assistant("Done");
+        assert_eq!(tuned.count_message(&reply), uniform.count_message(&reply) + 2);
+    }
+
     #[cfg(feature = "tokenizer-tiktoken")]
     #[test]
     fn test_tiktoken_counter_counts_non_zero() {
@@ -136,4 +267,33 @@ mod tests {
         assert!(counter.count_messages(&messages) > 0);
         assert!(counter.count_text("Hello there") > 0);
     }
+
+    #[cfg(feature = "tokenizer-tiktoken")]
+    #[test]
+    fn test_tiktoken_encoding_for_model() {
+        let cases = [
+            ("gpt-4o", TiktokenEncoding::O200kBase),
+            ("gpt-4o-mini-2024-07-18", TiktokenEncoding::O200kBase),
+            ("GPT-4.1", TiktokenEncoding::O200kBase),
+            ("openai/o3-mini", TiktokenEncoding::O200kBase),
+            ("gpt-4", TiktokenEncoding::Cl100kBase),
+            ("gpt-4-turbo", TiktokenEncoding::Cl100kBase),
+            ("gpt-3.5-turbo", TiktokenEncoding::Cl100kBase),
+            ("claude-sonnet-4", TiktokenEncoding::Cl100kBase),
+        ];
+        for (model, expected) in cases {
+            assert_eq!(TiktokenEncoding::for_model(model), expected, "{}", model);
+        }
+    }
+
+    #[cfg(feature = "tokenizer-tiktoken")]
+    #[test]
+    fn test_tiktoken_counter_for_model() {
+        let counter = TiktokenTokenCounter::for_model("gpt-4o").unwrap();
+        assert_eq!(counter.encoding(), Some(TiktokenEncoding::O200kBase));
+        assert!(counter.count_text("Hello there") > 0);
+
+        let fallback = TiktokenTokenCounter::for_model("unknown-model").unwrap();
+        assert_eq!(fallback.encoding(), Some(TiktokenEncoding::Cl100kBase));
+    }
 }
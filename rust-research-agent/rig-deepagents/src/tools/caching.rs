@@ -0,0 +1,462 @@
+//! Caching Tool - Result cache for idempotent tools
+//!
+//! Agents frequently repeat an identical `tavily_search` or `fetch_url` call
+//! after forgetting they already made it. [`CachingTool`] wraps any tool and
+//! serves repeated calls from a bounded LRU cache keyed on
+//! `(tool_name, canonicalized_args_json)`.
+//!
+//! Only successful results without state updates are cached, and only for
+//! tools listed in [`CacheConfig::cacheable`] (web search and URL fetches by
+//! default). A result that writes files (e.g. `fetch_url` with `save_to`) is
+//! never replayed, since its update would overwrite later edits.
+//! Every other tool passes through to the inner tool: file reads, for example,
+//! must see writes made since the previous call.
+//!
+//! # Example
+//! ```ignore
+//! let tools = CachingTool::wrap_all(research_tools_with_tavily(key), CacheConfig::default());
+//! ```
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::error::MiddlewareError;
+use crate::middleware::{DynTool, Tool, ToolDefinition, ToolResult};
+use crate::runtime::ToolRuntime;
+
+/// Default maximum number of cached results
+const DEFAULT_MAX_ENTRIES: usize = 256;
+
+/// Default time-to-live for cached results
+const DEFAULT_TTL_SECS: u64 = 600;
+
+/// Idempotent network tools whose results are cached by default
+const DEFAULT_CACHEABLE: &[&str] = &["tavily_search", "web_search", "fetch_url"];
+
+/// Configuration for [`CachingTool`]
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Maximum number of cached results (least recently used are evicted first)
+    pub max_entries: usize,
+    /// How long a cached result stays valid
+    pub ttl: Duration,
+    /// Tool names whose results may be cached (all others bypass the cache)
+    pub cacheable: HashSet<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            cacheable: DEFAULT_CACHEABLE.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Set the maximum number of cached results
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the time-to-live for cached results
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Opt a tool into caching
+    pub fn with_cacheable(mut self, tool_name: impl Into<String>) -> Self {
+        self.cacheable.insert(tool_name.into());
+        self
+    }
+
+    /// Opt a tool out of caching
+    pub fn with_non_cacheable(mut self, tool_name: impl Into<String>) -> Self {
+        self.cacheable.remove(&tool_name.into());
+        self
+    }
+
+    /// Whether results of the given tool may be cached
+    pub fn is_cacheable(&self, tool_name: &str) -> bool {
+        self.max_entries > 0 && self.cacheable.contains(tool_name)
+    }
+}
+
+type CacheKey = (String, String);
+
+struct CacheEntry {
+    result: ToolResult,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+/// Bounded LRU cache with TTL, shared by the tools wrapped from one config
+struct ResultCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Monotonic access counter used for LRU ordering
+    clock: u64,
+}
+
+impl ResultCache {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey, ttl: Duration) -> Option<ToolResult> {
+        self.clock += 1;
+        let clock = self.clock;
+
+        let expired = match self.entries.get_mut(key) {
+            Some(entry) if entry.inserted_at.elapsed() < ttl => {
+                entry.last_used = clock;
+                return Some(entry.result.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            self.entries.remove(key);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: CacheKey, result: ToolResult, max_entries: usize) {
+        self.clock += 1;
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.entries.insert(
+            key,
+            CacheEntry {
+                result,
+                inserted_at: Instant::now(),
+                last_used: self.clock,
+            },
+        );
+    }
+}
+
+/// Serialize JSON with object keys sorted, so `{"a":1,"b":2}` and
+/// `{"b":2,"a":1}` map to the same cache key
+fn canonicalize(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|key| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::String(key.clone()),
+                        canonicalize(&map[key])
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonicalize).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Tool wrapper that caches successful results of idempotent tools
+pub struct CachingTool {
+    inner: DynTool,
+    config: Arc<CacheConfig>,
+    cache: Arc<Mutex<ResultCache>>,
+}
+
+impl CachingTool {
+    /// Wrap a single tool with its own cache
+    pub fn new(inner: DynTool, config: CacheConfig) -> Self {
+        Self {
+            inner,
+            config: Arc::new(config),
+            cache: Arc::new(Mutex::new(ResultCache::new())),
+        }
+    }
+
+    /// Wrap every cacheable tool, sharing one cache (and its `max_entries` bound)
+    ///
+    /// Non-cacheable tools are returned unwrapped.
+    pub fn wrap_all(tools: Vec<DynTool>, config: CacheConfig) -> Vec<DynTool> {
+        let config = Arc::new(config);
+        let cache = Arc::new(Mutex::new(ResultCache::new()));
+
+        tools
+            .into_iter()
+            .map(|tool| {
+                if config.is_cacheable(&tool.definition().name) {
+                    Arc::new(Self {
+                        inner: tool,
+                        config: config.clone(),
+                        cache: cache.clone(),
+                    }) as DynTool
+                } else {
+                    tool
+                }
+            })
+            .collect()
+    }
+
+    /// Get the wrapped tool
+    pub fn inner(&self) -> &DynTool {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl Tool for CachingTool {
+    fn definition(&self) -> ToolDefinition {
+        self.inner.definition()
+    }
+
+    fn is_concurrent_safe(&self) -> bool {
+        self.inner.is_concurrent_safe()
+    }
+
+    fn examples(&self) -> Vec<serde_json::Value> {
+        self.inner.examples()
+    }
+
+    fn validate_args(&self, args: &serde_json::Value) -> Result<(), MiddlewareError> {
+        self.inner.validate_args(args)
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        let tool_name = self.inner.definition().name;
+        if !self.config.is_cacheable(&tool_name) {
+            return self.inner.execute(args, runtime).await;
+        }
+
+        let key = (tool_name, canonicalize(&args));
+
+        let cached = self
+            .cache
+            .lock()
+            .expect("tool cache lock poisoned")
+            .get(&key, self.config.ttl);
+        if let Some(result) = cached {
+            debug!(tool = %key.0, "Tool result served from cache");
+            return Ok(result);
+        }
+
+        let result = self.inner.execute(args, runtime).await?;
+        if !result.updates.is_empty() {
+            return Ok(result);
+        }
+
+        self.cache
+            .lock()
+            .expect("tool cache lock poisoned")
+            .insert(key, result.clone(), self.config.max_entries);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+    use crate::state::AgentState;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingTool {
+        name: &'static str,
+        calls: AtomicUsize,
+    }
+
+    impl CountingTool {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl Tool for CountingTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: self.name.to_string(),
+                description: "Counts invocations.".to_string(),
+                parameters: json!({"type": "object", "properties": {}}),
+                examples: Vec::new(),
+            }
+        }
+
+        async fn execute(
+            &self,
+            args: serde_json::Value,
+            _runtime: &ToolRuntime,
+        ) -> Result<ToolResult, MiddlewareError> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(ToolResult::new(format!("call {} with {}", n, args)))
+        }
+    }
+
+    fn runtime() -> ToolRuntime {
+        ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()))
+    }
+
+    #[tokio::test]
+    async fn test_identical_call_hits_cache() {
+        let inner = CountingTool::new("tavily_search");
+        let tool = CachingTool::new(inner.clone(), CacheConfig::default());
+        let runtime = runtime();
+
+        let first = tool
+            .execute(json!({"query": "rust", "max_results": 5}), &runtime)
+            .await
+            .unwrap();
+        // Same arguments in a different key order
+        let second = tool
+            .execute(json!({"max_results": 5, "query": "rust"}), &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls(), 1);
+        assert_eq!(first.message, second.message);
+
+        tool.execute(json!({"query": "go"}), &runtime).await.unwrap();
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refreshed() {
+        let inner = CountingTool::new("fetch_url");
+        let tool = CachingTool::new(
+            inner.clone(),
+            CacheConfig::default().with_ttl(Duration::from_millis(20)),
+        );
+        let runtime = runtime();
+        let args = json!({"url": "https://example.com"});
+
+        tool.execute(args.clone(), &runtime).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        tool.execute(args, &runtime).await.unwrap();
+
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_entry_is_evicted() {
+        let inner = CountingTool::new("tavily_search");
+        let tool = CachingTool::new(inner.clone(), CacheConfig::default().with_max_entries(2));
+        let runtime = runtime();
+
+        tool.execute(json!({"query": "a"}), &runtime).await.unwrap();
+        tool.execute(json!({"query": "b"}), &runtime).await.unwrap();
+        // Touch "a" so "b" becomes the least recently used
+        tool.execute(json!({"query": "a"}), &runtime).await.unwrap();
+        tool.execute(json!({"query": "c"}), &runtime).await.unwrap();
+        assert_eq!(inner.calls(), 3);
+
+        tool.execute(json!({"query": "a"}), &runtime).await.unwrap();
+        assert_eq!(inner.calls(), 3);
+        tool.execute(json!({"query": "b"}), &runtime).await.unwrap();
+        assert_eq!(inner.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_non_cacheable_tools_pass_through() {
+        let writer = CountingTool::new("write_file");
+        let search = CountingTool::new("tavily_search");
+        let tools = CachingTool::wrap_all(
+            vec![writer.clone(), search.clone()],
+            CacheConfig::default().with_non_cacheable("tavily_search"),
+        );
+        let runtime = runtime();
+
+        for tool in &tools {
+            tool.execute(json!({"x": 1}), &runtime).await.unwrap();
+            tool.execute(json!({"x": 1}), &runtime).await.unwrap();
+        }
+
+        assert_eq!(writer.calls(), 2);
+        assert_eq!(search.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_results_with_state_updates_are_not_cached() {
+        use crate::backends::Backend;
+        use crate::tools::FetchUrlTool;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("original"))
+            .mount(&server)
+            .await;
+
+        let backend = Arc::new(MemoryBackend::new());
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+        let tool = CachingTool::new(Arc::new(FetchUrlTool::new()), CacheConfig::default());
+        let args = json!({"url": server.uri(), "save_to": "/sources/page.txt"});
+
+        let first = tool.execute(args.clone(), &runtime).await.unwrap();
+        assert_eq!(first.updates.len(), 1);
+        backend.edit("/sources/page.txt", "original", "edited", false).await.unwrap();
+
+        // The fetch runs again instead of replaying the stale file snapshot
+        // (and fails, since save_to refuses to overwrite the edited file)
+        assert!(tool.execute(args, &runtime).await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert_eq!(backend.read_plain("/sources/page.txt").await.unwrap(), "edited");
+    }
+
+    #[tokio::test]
+    async fn test_only_allowlisted_tools_are_cached() {
+        let reader = CountingTool::new("read_file");
+        let custom = CountingTool::new("lookup_docs");
+        let tools = CachingTool::wrap_all(
+            vec![reader.clone(), custom.clone()],
+            CacheConfig::default().with_cacheable("lookup_docs"),
+        );
+        let runtime = runtime();
+
+        for tool in &tools {
+            tool.execute(json!({"x": 1}), &runtime).await.unwrap();
+            tool.execute(json!({"x": 1}), &runtime).await.unwrap();
+        }
+
+        // Reads must observe intervening writes, so they are never cached by default
+        assert_eq!(reader.calls(), 2);
+        assert_eq!(custom.calls(), 1);
+    }
+}
@@ -4,10 +4,12 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::error::MiddlewareError;
+use crate::backends::Backend;
+use crate::error::{EditResult, MiddlewareError};
 use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
 use crate::state::FileData;
+use super::write_file::replace_content;
 
 /// edit_file 도구
 pub struct EditFileTool;
@@ -15,18 +17,175 @@ pub struct EditFileTool;
 #[derive(Debug, Deserialize)]
 struct EditFileArgs {
     file_path: String,
+    #[serde(default)]
+    old_string: Option<String>,
+    #[serde(default)]
+    new_string: Option<String>,
+    #[serde(default)]
+    replace_all: bool,
+    /// 다중 편집 (순서대로 적용)
+    #[serde(default)]
+    edits: Option<Vec<EditOperation>>,
+}
+
+/// 단일 편집 연산
+#[derive(Debug, Clone, Deserialize)]
+struct EditOperation {
     old_string: String,
     new_string: String,
     #[serde(default)]
     replace_all: bool,
 }
 
+/// 편집 목록을 순서대로 적용
+///
+/// 하나라도 실패하면 실패한 편집 번호(1-based)를 담은 `EditNotFound`/`AmbiguousEdit`를
+/// 반환하며, 이 경우 아무것도 반영되지 않습니다. `replace_all` 편집은 매치가 없어도 성공합니다.
+fn apply_edits(content: &str, edits: &[EditOperation]) -> Result<(String, Vec<usize>), MiddlewareError> {
+    let mut current = content.to_string();
+    let mut counts = Vec::with_capacity(edits.len());
+
+    for (index, edit) in edits.iter().enumerate() {
+        let occurrences = match edit.check_matches(&current) {
+            Ok(matches) => matches,
+            Err(MiddlewareError::EditNotFound { .. }) if edit.replace_all => 0,
+            Err(MiddlewareError::EditNotFound { old_string, .. }) => {
+                return Err(MiddlewareError::EditNotFound { old_string, edit: Some(index + 1) });
+            }
+            Err(MiddlewareError::AmbiguousEdit { matches, .. }) => {
+                return Err(MiddlewareError::AmbiguousEdit { matches, edit: Some(index + 1) });
+            }
+            Err(e) => return Err(e),
+        };
+
+        if occurrences > 0 {
+            current = edit.apply(&current);
+        }
+        counts.push(occurrences);
+    }
+
+    Ok((current, counts))
+}
+
+impl EditOperation {
+    /// 단일 편집의 매치 수 확인 (없으면 not found, replace_all 없이 여러 개면 모호함)
+    fn check_matches(&self, content: &str) -> Result<usize, MiddlewareError> {
+        let matches = content.matches(self.old_string.as_str()).count();
+        if matches == 0 {
+            return Err(MiddlewareError::EditNotFound { old_string: self.old_string.clone(), edit: None });
+        }
+        if matches > 1 && !self.replace_all {
+            return Err(MiddlewareError::AmbiguousEdit { matches, edit: None });
+        }
+        Ok(matches)
+    }
+
+    fn apply(&self, content: &str) -> String {
+        if self.replace_all {
+            content.replace(&self.old_string, &self.new_string)
+        } else {
+            content.replacen(&self.old_string, &self.new_string, 1)
+        }
+    }
+}
+
+/// 트랜잭션 안에서 원본 내용을 읽어 `apply`의 결과로 교체
+///
+/// 트랜잭션의 `read_plain`은 파일을 그대로 돌려주므로 CRLF와 마지막 개행이 보존됩니다.
+/// 백엔드가 트랜잭션을 지원하지 않으면 `None`을 반환합니다.
+async fn edit_in_transaction<T>(
+    backend: &dyn Backend,
+    file_path: &str,
+    apply: impl FnOnce(&str) -> Result<(String, T), MiddlewareError>,
+) -> Result<Option<(T, Option<HashMap<String, FileData>>)>, MiddlewareError> {
+    let Some(mut tx) = backend.transaction() else {
+        return Ok(None);
+    };
+
+    let original = tx.read_plain(file_path).await
+        .map_err(MiddlewareError::Backend)?;
+    let (updated, value) = apply(&original)?;
+    if updated == original {
+        return Ok(Some((value, None)));
+    }
+
+    tx.write(file_path, &updated).await
+        .map_err(MiddlewareError::Backend)?;
+    let files_update = tx.commit().await
+        .map_err(MiddlewareError::Backend)?;
+    Ok(Some((value, files_update)))
+}
+
+impl EditFileTool {
+    /// 단일 편집 실행
+    ///
+    /// 백엔드 구현과 무관하게 유일 매치를 보장합니다 (첫 매치만 조용히 바뀌는 것 방지).
+    /// 트랜잭션을 지원하면 한 번 읽은 원본 내용에서 매치를 세고 그대로 교체합니다.
+    async fn execute_single(
+        &self,
+        file_path: &str,
+        edit: &EditOperation,
+        runtime: &ToolRuntime,
+    ) -> Result<EditResult, MiddlewareError> {
+        let backend = runtime.backend();
+        let applied = edit_in_transaction(backend.as_ref(), file_path, |content| {
+            let matches = edit.check_matches(content)?;
+            Ok((edit.apply(content), matches))
+        }).await?;
+        if let Some((occurrences, files_update)) = applied {
+            let mut result = EditResult::success_external(file_path, occurrences);
+            result.files_update = files_update;
+            return Ok(result);
+        }
+
+        let content = backend.read_plain(file_path).await
+            .map_err(MiddlewareError::Backend)?;
+        edit.check_matches(&content)?;
+        backend.edit(file_path, &edit.old_string, &edit.new_string, edit.replace_all).await
+            .map_err(MiddlewareError::Backend)
+    }
+
+    /// 다중 편집 실행
+    ///
+    /// 원본을 읽어 모든 편집을 메모리에서 적용한 뒤 최종 내용을 한 번에 기록합니다.
+    /// 트랜잭션을 지원하지 않는 백엔드에서는 [`replace_content`]로 전체 내용을 교체하므로
+    /// 어느 쪽이든 모든 편집이 반영되거나 아무것도 반영되지 않습니다.
+    async fn execute_edits(
+        &self,
+        file_path: &str,
+        edits: &[EditOperation],
+        runtime: &ToolRuntime,
+    ) -> Result<EditResult, MiddlewareError> {
+        let backend = runtime.backend();
+        let applied = edit_in_transaction(backend.as_ref(), file_path, |content| {
+            apply_edits(content, edits)
+        }).await?;
+        if let Some((counts, files_update)) = applied {
+            let mut result = EditResult::success_external(file_path, 0).with_edit_occurrences(counts);
+            result.files_update = files_update;
+            return Ok(result);
+        }
+
+        let original = backend.read_plain(file_path).await
+            .map_err(MiddlewareError::Backend)?;
+        let (updated, counts) = apply_edits(&original, edits)?;
+        let files_update = replace_content(backend.as_ref(), file_path, &original, &updated).await
+            .map_err(MiddlewareError::Backend)?;
+
+        let mut result = EditResult::success_external(file_path, 0).with_edit_occurrences(counts);
+        result.files_update = files_update;
+        Ok(result)
+    }
+}
+
 #[async_trait]
 impl Tool for EditFileTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "edit_file".to_string(),
-            description: "Edit a file by replacing old_string with new_string.".to_string(),
+            description: "Edit a file by replacing old_string with new_string. \
+                To make several replacements in one call, pass an `edits` array instead; \
+                edits are applied in order and either all succeed or none are applied.".to_string(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -44,12 +203,26 @@ impl Tool for EditFileTool {
                     },
                     "replace_all": {
                         "type": "boolean",
-                        "description": "Replace all occurrences (default: false)",
+                        "description": "Replace all occurrences (default: false). Without it, old_string must match exactly once",
                         "default": false
+                    },
+                    "edits": {
+                        "type": "array",
+                        "description": "Multiple edits applied sequentially (alternative to old_string/new_string)",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "old_string": { "type": "string" },
+                                "new_string": { "type": "string" },
+                                "replace_all": { "type": "boolean", "default": false }
+                            },
+                            "required": ["old_string", "new_string"]
+                        }
                     }
                 },
-                "required": ["file_path", "old_string", "new_string"]
+                "required": ["file_path"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -61,18 +234,43 @@ impl Tool for EditFileTool {
         let args: EditFileArgs = serde_json::from_value(args)
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
-        let result = runtime.backend()
-            .edit(&args.file_path, &args.old_string, &args.new_string, args.replace_all)
-            .await
-            .map_err(MiddlewareError::Backend)?;
+        let result = match (args.edits, args.old_string, args.new_string) {
+            (Some(edits), None, None) => {
+                if edits.is_empty() {
+                    return Err(MiddlewareError::ToolExecution(
+                        "Invalid arguments: edits must not be empty".to_string()
+                    ));
+                }
+                self.execute_edits(&args.file_path, &edits, runtime).await?
+            }
+            (None, Some(old_string), Some(new_string)) => {
+                let edit = EditOperation { old_string, new_string, replace_all: args.replace_all };
+                self.execute_single(&args.file_path, &edit, runtime).await?
+            }
+            _ => {
+                return Err(MiddlewareError::ToolExecution(
+                    "Invalid arguments: provide either old_string and new_string, or edits".to_string()
+                ));
+            }
+        };
 
         if result.is_ok() {
             let occurrences = result.occurrences.unwrap_or(1);
-            let mut tool_result = ToolResult::new(format!(
-                "Replaced {} occurrence(s) in {}",
-                occurrences,
-                args.file_path
-            ));
+            let message = match &result.edit_occurrences {
+                Some(counts) => format!(
+                    "Applied {} edit(s) to {}: {} replacement(s) total ({})",
+                    counts.len(),
+                    args.file_path,
+                    occurrences,
+                    counts.iter()
+                        .enumerate()
+                        .map(|(i, n)| format!("#{}: {}", i + 1, n))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                None => format!("Replaced {} occurrence(s) in {}", occurrences, args.file_path),
+            };
+            let mut tool_result = ToolResult::new(message);
             if let Some(files_update) = result.files_update {
                 let updates: HashMap<String, Option<FileData>> = files_update
                     .into_iter()
@@ -123,4 +321,213 @@ mod tests {
             other => panic!("Unexpected update: {:?}", other),
         }
     }
-}
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/config.toml", "name = \"old\"\nversion = 1\nflag = x\nflag = x").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/config.toml",
+            "edits": [
+                {"old_string": "old", "new_string": "new"},
+                {"old_string": "version = 1", "new_string": "version = 2"},
+                {"old_string": "flag = x", "new_string": "flag = y", "replace_all": true}
+            ]
+        });
+
+        let result = EditFileTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("Applied 3 edit(s)"));
+        assert!(result.message.contains("4 replacement(s) total (#1: 1, #2: 1, #3: 2)"));
+        assert_eq!(
+            backend.read_plain("/config.toml").await.unwrap(),
+            "name = \"new\"\nversion = 2\nflag = y\nflag = y"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits_fail_atomically() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/notes.md", "alpha beta gamma").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/notes.md",
+            "edits": [
+                {"old_string": "alpha", "new_string": "ALPHA"},
+                {"old_string": "delta", "new_string": "DELTA"},
+                {"old_string": "gamma", "new_string": "GAMMA"}
+            ]
+        });
+
+        let err = EditFileTool.execute(args, &runtime).await.unwrap_err();
+        assert!(err.to_string().starts_with("Edit #2 failed"), "unexpected message: {}", err);
+        assert!(matches!(
+            err,
+            MiddlewareError::EditNotFound { ref old_string, edit: Some(2) } if old_string == "delta"
+        ));
+
+        // 첫 번째 편집도 반영되지 않아야 함
+        assert_eq!(backend.read_plain("/notes.md").await.unwrap(), "alpha beta gamma");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits_preserve_line_endings() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("notes.md"), "alpha\r\nbeta\r\n").unwrap();
+        let backend = Arc::new(crate::backends::FilesystemBackend::new(temp.path()));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = json!({
+            "file_path": "/notes.md",
+            "edits": [
+                {"old_string": "alpha", "new_string": "ALPHA"},
+                {"old_string": "beta", "new_string": "BETA"}
+            ]
+        });
+
+        EditFileTool.execute(args, &runtime).await.unwrap();
+        assert_eq!(std::fs::read(temp.path().join("notes.md")).unwrap(), b"ALPHA\r\nBETA\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits_without_transaction() {
+        let inner = Arc::new(MemoryBackend::new());
+        inner.write("/notes.md", "alpha beta gamma").await.unwrap();
+        let backend = Arc::new(crate::backends::QuotaBackend::new(inner.clone(), 1 << 20, None));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = json!({
+            "file_path": "/notes.md",
+            "edits": [
+                {"old_string": "alpha", "new_string": "ALPHA"},
+                {"old_string": "delta", "new_string": "DELTA", "replace_all": true},
+                {"old_string": "gamma", "new_string": "GAMMA"}
+            ]
+        });
+
+        let result = EditFileTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("(#1: 1, #2: 0, #3: 1)"));
+        assert_eq!(inner.read_plain("/notes.md").await.unwrap(), "ALPHA beta GAMMA");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits_without_transaction_fail_atomically() {
+        let inner = Arc::new(MemoryBackend::new());
+        inner.write("/notes.md", "alpha beta").await.unwrap();
+        // Room for the first edit's growth but not the second's
+        let backend = Arc::new(crate::backends::QuotaBackend::new(inner.clone(), 14, None));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = json!({
+            "file_path": "/notes.md",
+            "edits": [
+                {"old_string": "alpha", "new_string": "ALPHA!"},
+                {"old_string": "beta", "new_string": "BETA BETA"}
+            ]
+        });
+
+        assert!(EditFileTool.execute(args, &runtime).await.is_err());
+        assert_eq!(inner.read_plain("/notes.md").await.unwrap(), "alpha beta");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_edits_ambiguous_match_fails() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/a.txt", "x x").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/a.txt",
+            "edits": [{"old_string": "x", "new_string": "y"}]
+        });
+
+        let err = EditFileTool.execute(args, &runtime).await.unwrap_err();
+        assert!(matches!(err, MiddlewareError::AmbiguousEdit { matches: 2, edit: Some(1) }));
+        assert_eq!(backend.read_plain("/a.txt").await.unwrap(), "x x");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_rejects_binary_file() {
+        let backend = Arc::new(MemoryBackend::new());
+        let bytes: Vec<u8> = vec![0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0x0a, 0x80];
+        backend.write_bytes("/doc.pdf", &bytes).await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        // base64 텍스트("JVBERgD/CoA=")를 편집하려 해도 실패해야 함
+        let single = json!({"file_path": "/doc.pdf", "old_string": "JVBE", "new_string": "AAAA"});
+        assert!(EditFileTool.execute(single, &runtime).await.is_err());
+        let multi = json!({
+            "file_path": "/doc.pdf",
+            "edits": [{"old_string": "JVBE", "new_string": "AAAA"}]
+        });
+        assert!(EditFileTool.execute(multi, &runtime).await.is_err());
+
+        assert_eq!(backend.read_bytes("/doc.pdf").await.unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_unique_match_succeeds() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/lib.rs", "fn alpha() {}
+fn beta() {}").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/lib.rs",
+            "old_string": "fn beta",
+            "new_string": "fn gamma"
+        });
+
+        let result = EditFileTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("Replaced 1 occurrence(s)"));
+        assert_eq!(backend.read_plain("/lib.rs").await.unwrap(), "fn alpha() {}\nfn gamma() {}");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_zero_matches_fails() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/lib.rs", "fn alpha() {}").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/lib.rs",
+            "old_string": "fn missing",
+            "new_string": "fn found"
+        });
+
+        let err = EditFileTool.execute(args, &runtime).await.unwrap_err();
+        assert!(matches!(err, MiddlewareError::EditNotFound { ref old_string, edit: None } if old_string == "fn missing"));
+        assert_eq!(backend.read_plain("/lib.rs").await.unwrap(), "fn alpha() {}");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_multiple_matches_require_replace_all() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/a.txt", "x = 1\nx = 1\nx = 1").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({
+            "file_path": "/a.txt",
+            "old_string": "x = 1",
+            "new_string": "x = 2"
+        });
+
+        let err = EditFileTool.execute(args, &runtime).await.unwrap_err();
+        assert!(matches!(err, MiddlewareError::AmbiguousEdit { matches: 3, edit: None }));
+        assert_eq!(backend.read_plain("/a.txt").await.unwrap(), "x = 1\nx = 1\nx = 1");
+
+        // replace_all opts into editing every match
+        let args = json!({
+            "file_path": "/a.txt",
+            "old_string": "x = 1",
+            "new_string": "x = 2",
+            "replace_all": true
+        });
+
+        let result = EditFileTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("Replaced 3 occurrence(s)"));
+        assert_eq!(backend.read_plain("/a.txt").await.unwrap(), "x = 2\nx = 2\nx = 2");
+    }
+}
\ No newline at end of file
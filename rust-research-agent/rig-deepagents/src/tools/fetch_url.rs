@@ -0,0 +1,418 @@
+//! Fetch URL Tool - Retrieve web pages for research agents
+//!
+//! Complements `tavily_search`: once a search surfaces a promising URL,
+//! agents use `fetch_url` to read the full page.
+//!
+//! # Features
+//!
+//! - Configurable request timeout, maximum body size, and redirect cap
+//! - HTML-to-text conversion (requires the `html-to-text` feature);
+//!   without it, or with `raw: true`, the body is returned as-is
+//! - Optional persistence of the result to a backend path
+
+use async_trait::async_trait;
+use reqwest::{redirect, Client};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::error::MiddlewareError;
+use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
+use crate::runtime::ToolRuntime;
+use crate::state::FileData;
+
+/// Default timeout for fetch requests
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Default maximum body size (bytes)
+const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Default maximum number of redirects to follow
+const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+/// Line width used for HTML-to-text rendering
+#[cfg(feature = "html-to-text")]
+const TEXT_WIDTH: usize = 100;
+
+/// HTTP GET tool for reading web pages
+///
+/// # Example
+/// ```ignore
+/// let tool = FetchUrlTool::new()
+///     .with_timeout(Duration::from_secs(10))
+///     .with_max_body_bytes(512 * 1024);
+/// let result = tool.execute(json!({
+///     "url": "https://example.com/article",
+///     "save_to": "/sources/article.md"
+/// }), &runtime).await?;
+/// ```
+pub struct FetchUrlTool {
+    client: Client,
+    timeout: Duration,
+    max_body_bytes: usize,
+    max_redirects: usize,
+}
+
+impl FetchUrlTool {
+    /// Create a new FetchUrlTool with default limits
+    pub fn new() -> Self {
+        Self {
+            client: Self::build_client(DEFAULT_MAX_REDIRECTS),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+        }
+    }
+
+    /// Set custom timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set maximum body size; larger responses are truncated
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    /// Set maximum number of redirects to follow
+    pub fn with_max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self.client = Self::build_client(max_redirects);
+        self
+    }
+
+    fn build_client(max_redirects: usize) -> Client {
+        Client::builder()
+            .redirect(redirect::Policy::limited(max_redirects))
+            .build()
+            .unwrap_or_default()
+    }
+
+    /// Perform the GET request, returning (content type, body, truncated)
+    async fn fetch(&self, url: &str) -> Result<(Option<String>, Vec<u8>, bool), MiddlewareError> {
+        let mut response = self
+            .client
+            .get(url)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_redirect() {
+                    MiddlewareError::ToolExecution(format!(
+                        "Too many redirects fetching {} (max {})",
+                        url, self.max_redirects
+                    ))
+                } else if e.is_timeout() {
+                    MiddlewareError::ToolExecution(format!("Request timed out fetching {}", url))
+                } else {
+                    MiddlewareError::ToolExecution(format!("Failed to fetch {}: {}", url, e))
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(MiddlewareError::ToolExecution(format!(
+                "HTTP {} fetching {}",
+                status, url
+            )));
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        // Stream the body so oversized responses are never fully buffered
+        let mut body = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| MiddlewareError::ToolExecution(format!("Failed to read body of {}: {}", url, e)))?
+        {
+            let remaining = self.max_body_bytes - body.len();
+            if chunk.len() > remaining {
+                body.extend_from_slice(&chunk[..remaining]);
+                truncated = true;
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok((content_type, body, truncated))
+    }
+}
+
+impl Default for FetchUrlTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Arguments for the fetch_url tool
+#[derive(Debug, Deserialize)]
+struct FetchUrlArgs {
+    /// The URL to fetch
+    url: String,
+
+    /// Return the body as-is instead of converting HTML to text
+    #[serde(default)]
+    raw: bool,
+
+    /// Backend path to write the result to
+    #[serde(default)]
+    save_to: Option<String>,
+}
+
+fn is_html(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|ct| ct.contains("text/html") || ct.contains("application/xhtml"))
+}
+
+#[cfg(feature = "html-to-text")]
+fn html_to_text(html: &[u8]) -> Option<String> {
+    html2text::from_read(html, TEXT_WIDTH).ok()
+}
+
+#[cfg(not(feature = "html-to-text"))]
+fn html_to_text(_html: &[u8]) -> Option<String> {
+    None
+}
+
+#[async_trait]
+impl Tool for FetchUrlTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "fetch_url".to_string(),
+            description: "Fetch a web page by URL and return its readable text content. \
+                Optionally save the result to a file for later reference.".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "url": {
+                        "type": "string",
+                        "description": "The http(s) URL to fetch"
+                    },
+                    "raw": {
+                        "type": "boolean",
+                        "description": "Return the raw response body instead of readable text",
+                        "default": false
+                    },
+                    "save_to": {
+                        "type": "string",
+                        "description": "Optional file path to save the fetched content to"
+                    }
+                },
+                "required": ["url"],
+                "additionalProperties": false
+            }),
+            examples: Vec::new(),
+        }
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        if let Some(tool_call_id) = runtime.tool_call_id() {
+            debug!(tool_call_id, "Executing fetch_url");
+        }
+
+        let args: FetchUrlArgs = serde_json::from_value(args)
+            .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
+
+        if !(args.url.starts_with("http://") || args.url.starts_with("https://")) {
+            return Err(MiddlewareError::ToolExecution(format!(
+                "Unsupported URL (only http/https allowed): {}",
+                args.url
+            )));
+        }
+
+        let (content_type, body, truncated) = self.fetch(&args.url).await?;
+
+        let converted = if !args.raw && is_html(content_type.as_deref()) {
+            html_to_text(&body)
+        } else {
+            None
+        };
+        let mut content = converted.unwrap_or_else(|| String::from_utf8_lossy(&body).into_owned());
+
+        if truncated {
+            content.push_str(&format!(
+                "\n\n[truncated: response exceeded {} bytes]",
+                self.max_body_bytes
+            ));
+        }
+
+        let Some(path) = args.save_to else {
+            return Ok(ToolResult::new(content));
+        };
+
+        let result = runtime
+            .backend()
+            .write(&path, &content)
+            .await
+            .map_err(MiddlewareError::Backend)?;
+
+        if let Some(error) = result.error {
+            return Err(MiddlewareError::ToolExecution(error));
+        }
+
+        let mut tool_result = ToolResult::new(format!(
+            "Fetched {} ({} bytes{}) and saved to {}",
+            args.url,
+            body.len(),
+            if truncated { ", truncated" } else { "" },
+            path
+        ));
+        if let Some(files_update) = result.files_update {
+            let updates: HashMap<String, Option<FileData>> = files_update
+                .into_iter()
+                .map(|(path, data)| (path, Some(data)))
+                .collect();
+            tool_result = tool_result.with_update(StateUpdate::UpdateFiles(updates));
+        }
+        Ok(tool_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{Backend, MemoryBackend};
+    use crate::state::AgentState;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_runtime() -> (ToolRuntime, Arc<MemoryBackend>) {
+        let backend = Arc::new(MemoryBackend::new());
+        (ToolRuntime::new(AgentState::new(), backend.clone()), backend)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_returns_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/doc.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("plain text body"))
+            .mount(&server)
+            .await;
+
+        let (runtime, _) = create_runtime();
+        let result = FetchUrlTool::new()
+            .execute(serde_json::json!({"url": format!("{}/doc.txt", server.uri())}), &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(result.message, "plain text body");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_non_2xx_is_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let (runtime, _) = create_runtime();
+        let err = FetchUrlTool::new()
+            .execute(serde_json::json!({"url": format!("{}/missing", server.uri())}), &runtime)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, MiddlewareError::ToolExecution(ref msg) if msg.contains("404")));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_redirect_cap() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/loop"))
+            .respond_with(
+                ResponseTemplate::new(302).insert_header("Location", format!("{}/loop", server.uri())),
+            )
+            .mount(&server)
+            .await;
+
+        let (runtime, _) = create_runtime();
+        let err = FetchUrlTool::new()
+            .with_max_redirects(2)
+            .execute(serde_json::json!({"url": format!("{}/loop", server.uri())}), &runtime)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, MiddlewareError::ToolExecution(ref msg) if msg.contains("Too many redirects")));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_truncates_and_saves() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(100)))
+            .mount(&server)
+            .await;
+
+        let (runtime, backend) = create_runtime();
+        let result = FetchUrlTool::new()
+            .with_max_body_bytes(10)
+            .execute(
+                serde_json::json!({"url": server.uri(), "save_to": "/sources/page.txt"}),
+                &runtime,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.message.contains("truncated"));
+        assert!(result.message.contains("/sources/page.txt"));
+        assert_eq!(result.updates.len(), 1);
+
+        let saved = backend.read_plain("/sources/page.txt").await.unwrap();
+        assert!(saved.starts_with("xxxxxxxxxx\n"));
+        assert!(saved.contains("[truncated: response exceeded 10 bytes]"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_rejects_non_http_scheme() {
+        let (runtime, _) = create_runtime();
+        let err = FetchUrlTool::new()
+            .execute(serde_json::json!({"url": "file:///etc/passwd"}), &runtime)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, MiddlewareError::ToolExecution(ref msg) if msg.contains("Unsupported URL")));
+    }
+
+    #[cfg(feature = "html-to-text")]
+    #[tokio::test]
+    async fn test_fetch_url_converts_html() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(
+                    "<html><body><h1>Title</h1><p>Hello <b>world</b></p></body></html>",
+                    "text/html; charset=utf-8",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let (runtime, _) = create_runtime();
+        let tool = FetchUrlTool::new();
+
+        let text = tool.execute(serde_json::json!({"url": server.uri()}), &runtime).await.unwrap();
+        assert!(text.message.contains("Hello"));
+        assert!(!text.message.contains("<p>"));
+
+        let raw = tool
+            .execute(serde_json::json!({"url": server.uri(), "raw": true}), &runtime)
+            .await
+            .unwrap();
+        assert!(raw.message.contains("<p>Hello <b>world</b></p>"));
+    }
+}
@@ -32,7 +32,7 @@ impl Tool for GlobTool {
                 "properties": {
                     "pattern": {
                         "type": "string",
-                        "description": "Glob pattern (e.g., '**/*.rs', '*.txt')"
+                        "description": "Glob pattern supporting *, **, ?, [abc] and {a,b} alternation (e.g., '**/*.rs', 'src/**/*.{rs,toml}')"
                     },
                     "base_path": {
                         "type": "string",
@@ -42,6 +42,7 @@ impl Tool for GlobTool {
                 },
                 "required": ["pattern"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -70,4 +71,8 @@ impl Tool for GlobTool {
             )))
         }
     }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
+    }
 }
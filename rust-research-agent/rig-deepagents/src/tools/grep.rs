@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 
+use crate::backends::{GrepOptions, GREP_GLOB_MAX_MATCHES};
 use crate::error::MiddlewareError;
 use crate::middleware::{Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
@@ -17,6 +18,16 @@ struct GrepArgs {
     path: Option<String>,
     #[serde(default)]
     glob_filter: Option<String>,
+    #[serde(default)]
+    files: Option<String>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    multiline: bool,
+    #[serde(default)]
+    before: usize,
+    #[serde(default)]
+    after: usize,
 }
 
 #[async_trait]
@@ -39,10 +50,37 @@ impl Tool for GrepTool {
                     "glob_filter": {
                         "type": "string",
                         "description": "Glob pattern to filter files (e.g., '**/*.rs')"
+                    },
+                    "files": {
+                        "type": "string",
+                        "description": format!(
+                            "Glob selecting the files to scan across the whole tree, e.g. '**/*.rs' \
+                             (replaces path/glob_filter; results sorted by path and capped at {} matches)",
+                            GREP_GLOB_MAX_MATCHES
+                        )
+                    },
+                    "case_insensitive": {
+                        "type": "boolean",
+                        "description": "Ignore case when matching (default: false)"
+                    },
+                    "multiline": {
+                        "type": "boolean",
+                        "description": "Allow the pattern to span multiple lines, e.g. containing '\\n' (default: false)"
+                    },
+                    "before": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Lines of context to show before each match, like grep -B (default: 0)"
+                    },
+                    "after": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Lines of context to show after each match, like grep -A (default: 0)"
                     }
                 },
                 "required": ["pattern"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -54,22 +92,124 @@ impl Tool for GrepTool {
         let args: GrepArgs = serde_json::from_value(args)
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
-        let matches = runtime.backend()
-            .grep(&args.pattern, args.path.as_deref(), args.glob_filter.as_deref())
-            .await
-            .map_err(MiddlewareError::Backend)?;
+        let options = GrepOptions::new()
+            .with_case_insensitive(args.case_insensitive)
+            .with_multiline(args.multiline)
+            .with_context(args.before, args.after);
+
+        let backend = runtime.backend();
+        let matches = match args.files.as_deref() {
+            Some(files) => backend.grep_glob(&args.pattern, files, options).await,
+            None => {
+                backend
+                    .grep(&args.pattern, args.path.as_deref(), args.glob_filter.as_deref(), options)
+                    .await
+            }
+        }
+        .map_err(MiddlewareError::Backend)?;
+        let truncated = args.files.is_some() && matches.len() >= GREP_GLOB_MAX_MATCHES;
 
         if matches.is_empty() {
             Ok(ToolResult::new("No matches found."))
         } else {
+            // grep -C 형식: 매칭은 `path:N:`, 컨텍스트는 `path-N-`
             let output: Vec<String> = matches.iter()
-                .map(|m| format!("{}:{}: {}", m.path, m.line, m.text))
+                .map(|m| {
+                    let first = m.line - m.context_before.len();
+                    let after_start = m.line + m.text.lines().count().max(1);
+                    let before = m.context_before.iter().enumerate()
+                        .map(|(i, text)| format!("{}-{}- {}\n", m.path, first + i, text));
+                    let after = m.context_after.iter().enumerate()
+                        .map(|(i, text)| format!("\n{}-{}- {}", m.path, after_start + i, text));
+                    format!(
+                        "{}{}:{}: {}{}",
+                        before.collect::<String>(),
+                        m.path,
+                        m.line,
+                        m.text,
+                        after.collect::<String>()
+                    )
+                })
                 .collect();
-            Ok(ToolResult::new(format!(
-                "Found {} matches:\n{}",
-                matches.len(),
-                output.join("\n")
-            )))
+            let mut message = format!("Found {} matches:\n{}", matches.len(), output.join("\n"));
+            if truncated {
+                message.push_str(&format!(
+                    "\n(results truncated at {} matches; use a narrower glob or pattern)",
+                    GREP_GLOB_MAX_MATCHES
+                ));
+            }
+            Ok(ToolResult::new(message))
         }
     }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{Backend, MemoryBackend};
+    use crate::state::AgentState;
+    use std::sync::Arc;
+
+    async fn runtime_with_file(path: &str, content: &str) -> ToolRuntime {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write(path, content).await.unwrap();
+        ToolRuntime::new(AgentState::new(), backend)
+    }
+
+    #[tokio::test]
+    async fn test_grep_tool_case_insensitive() {
+        let runtime = runtime_with_file("/notes.md", "TODO: fix parser\ndone").await;
+
+        // 기본값은 대소문자 구분
+        let result = GrepTool.execute(serde_json::json!({"pattern": "todo"}), &runtime).await.unwrap();
+        assert_eq!(result.message, "No matches found.");
+
+        let result = GrepTool
+            .execute(serde_json::json!({"pattern": "todo", "case_insensitive": true}), &runtime)
+            .await
+            .unwrap();
+        assert!(result.message.contains("/notes.md:1: TODO: fix parser"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_tool_multiline() {
+        let runtime = runtime_with_file("/main.rs", "fn main() {\n    run();\n}").await;
+
+        let args = serde_json::json!({"pattern": "{\n    run", "multiline": true});
+        let result = GrepTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("Found 1 matches"));
+        assert!(result.message.contains("/main.rs:1: fn main() {\n    run();"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_tool_context_lines() {
+        let runtime = runtime_with_file("/log.txt", "alpha\nbeta\nTARGET one\ngamma\nTARGET two").await;
+
+        let args = serde_json::json!({"pattern": "TARGET", "before": 1, "after": 2});
+        let result = GrepTool.execute(args, &runtime).await.unwrap();
+        assert!(result.message.contains("/log.txt-2- beta\n/log.txt:3: TARGET one\n/log.txt-4- gamma"));
+        // 파일 끝에서 after 컨텍스트가 잘림
+        assert!(result.message.ends_with("/log.txt-4- gamma\n/log.txt:5: TARGET two"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_tool_files_glob() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/src/lib.rs", "// TODO: split
+fn lib() {}").await.unwrap();
+        backend.write("/src/bin/cli.rs", "fn main() {} // TODO: flags").await.unwrap();
+        backend.write("/README.md", "TODO: docs").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = serde_json::json!({"pattern": "TODO", "files": "**/*.rs"});
+        let result = GrepTool.execute(args, &runtime).await.unwrap();
+        assert_eq!(
+            result.message,
+            "Found 2 matches:\n/src/bin/cli.rs:1: fn main() {} // TODO: flags\n/src/lib.rs:1: // TODO: split"
+        );
+    }
 }
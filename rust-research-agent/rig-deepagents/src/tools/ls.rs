@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 
+use crate::backends::{FileInfo, LS_RECURSIVE_MAX_ENTRIES};
 use crate::error::MiddlewareError;
 use crate::middleware::{Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
@@ -10,10 +11,21 @@ use crate::runtime::ToolRuntime;
 /// ls 도구
 pub struct LsTool;
 
+fn format_entry(f: &FileInfo) -> String {
+    if f.is_dir {
+        format!("{}/ (dir)", f.path.trim_end_matches('/'))
+    } else {
+        format!("{} ({} bytes)", f.path, f.size.unwrap_or(0))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct LsArgs {
     #[serde(default = "default_path")]
     path: String,
+    /// 나열 깊이 (None이면 한 단계)
+    #[serde(default)]
+    depth: Option<usize>,
 }
 
 fn default_path() -> String {
@@ -25,7 +37,8 @@ impl Tool for LsTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "ls".to_string(),
-            description: "List files and directories at the given path.".to_string(),
+            description: "List files and directories at the given path. \
+                Set `depth` to also list subdirectories as a tree (paths relative to `path`).".to_string(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -33,9 +46,15 @@ impl Tool for LsTool {
                         "type": "string",
                         "description": "The directory path to list",
                         "default": "/"
+                    },
+                    "depth": {
+                        "type": "integer",
+                        "description": "How many directory levels to list (default: 1, the given directory only)",
+                        "minimum": 1
                     }
                 }
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -47,25 +66,85 @@ impl Tool for LsTool {
         let args: LsArgs = serde_json::from_value(args)
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
-        let files = runtime.backend()
-            .ls(&args.path)
-            .await
-            .map_err(MiddlewareError::Backend)?;
-
-        let output: Vec<String> = files.iter()
-            .map(|f| {
-                if f.is_dir {
-                    format!("{}/ (dir)", f.path)
-                } else {
-                    format!("{} ({} bytes)", f.path, f.size.unwrap_or(0))
-                }
-            })
-            .collect();
-
-        if output.is_empty() {
-            Ok(ToolResult::new("Directory is empty."))
+        let depth = args.depth.unwrap_or(1).max(1);
+        let files = if depth == 1 {
+            runtime.backend().ls(&args.path).await
         } else {
-            Ok(ToolResult::new(output.join("\n")))
+            runtime.backend().ls_recursive(&args.path, depth).await
         }
+        .map_err(MiddlewareError::Backend)?;
+
+        if files.is_empty() {
+            return Ok(ToolResult::new("Directory is empty."));
+        }
+
+        let mut output: Vec<String> = files.iter().map(format_entry).collect();
+        if depth > 1 && files.len() >= LS_RECURSIVE_MAX_ENTRIES {
+            output.push(format!(
+                "(listing truncated at {} entries; use a narrower path or smaller depth)",
+                LS_RECURSIVE_MAX_ENTRIES
+            ));
+        }
+
+        Ok(ToolResult::new(output.join("\n")))
+    }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{Backend, MemoryBackend};
+    use crate::state::AgentState;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_ls_depth_two_lists_nested_tree() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/project/README.md", "readme").await.unwrap();
+        backend.write("/project/src/lib.rs", "lib").await.unwrap();
+        backend.write("/project/src/nested/deep.rs", "deep").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let result = LsTool
+            .execute(json!({"path": "/project", "depth": 2}), &runtime)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.message,
+            "README.md (6 bytes)\nsrc/ (dir)\nsrc/lib.rs (3 bytes)\nsrc/nested/ (dir)"
+        );
+
+        // Without depth only the top level is listed
+        let result = LsTool.execute(json!({"path": "/project"}), &runtime).await.unwrap();
+        assert!(!result.message.contains("lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_ls_recursive_is_capped() {
+        let backend = Arc::new(MemoryBackend::new());
+        for i in 0..LS_RECURSIVE_MAX_ENTRIES + 20 {
+            backend.write(&format!("/data/batch/{:04}.json", i), "{}").await.unwrap();
+        }
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let entries = backend.ls_recursive("/data", 3).await.unwrap();
+        assert_eq!(entries.len(), LS_RECURSIVE_MAX_ENTRIES);
+        assert_eq!(entries[0].path, "batch");
+        assert!(entries[0].is_dir);
+
+        let result = LsTool
+            .execute(json!({"path": "/data", "depth": 3}), &runtime)
+            .await
+            .unwrap();
+        assert!(result.message.ends_with(&format!(
+            "(listing truncated at {} entries; use a narrower path or smaller depth)",
+            LS_RECURSIVE_MAX_ENTRIES
+        )));
+    }
+}
\ No newline at end of file
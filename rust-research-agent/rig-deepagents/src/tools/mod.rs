@@ -8,8 +8,12 @@
 //! - Delegation: task (SubAgent)
 //!
 //! ## Domain Tools (optional, require configuration)
-//! - Research: tavily_search (requires TAVILY_API_KEY)
+//! - Research: tavily_search (requires TAVILY_API_KEY), fetch_url
 //! - Reflection: think (explicit reasoning tool)
+//! - Coding: shell (allowlisted command execution)
+//!
+//! ## Wrappers
+//! - CachingTool: LRU/TTL result cache for idempotent tools
 
 mod read_file;
 mod write_file;
@@ -23,7 +27,13 @@ mod task;
 
 // Domain tools
 mod tavily;
+mod web_search;
+mod fetch_url;
 mod think;
+mod shell;
+
+// Wrappers
+mod caching;
 
 pub use read_file::ReadFileTool;
 pub use write_file::WriteFileTool;
@@ -37,7 +47,13 @@ pub use task::TaskTool;
 
 // Domain tool exports
 pub use tavily::{TavilySearchTool, TavilyError, SearchDepth, Topic};
-pub use think::ThinkTool;
+pub use web_search::{
+    SearchOptions, SearchProvider, SearchResponse, SearchResult, WebSearchTool,
+};
+pub use fetch_url::FetchUrlTool;
+pub use think::{LoggingThinkTool, ThinkTool};
+pub use shell::ShellTool;
+pub use caching::{CacheConfig, CachingTool};
 
 use crate::middleware::DynTool;
 use std::sync::Arc;
@@ -28,7 +28,9 @@ impl Tool for ReadFileTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "read_file".to_string(),
-            description: "Read content from a file with optional line offset and limit.".to_string(),
+            description: "Read content from a file with optional line offset and limit. \
+                Lines are returned prefixed with 1-based line numbers (like `cat -n`); \
+                use offset/limit to read a slice of a large file.".to_string(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -49,6 +51,7 @@ impl Tool for ReadFileTool {
                 },
                 "required": ["file_path"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -61,12 +64,16 @@ impl Tool for ReadFileTool {
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
         let content = runtime.backend()
-            .read(&args.file_path, args.offset, args.limit)
+            .read_file_range(&args.file_path, args.offset, args.limit)
             .await
             .map_err(MiddlewareError::Backend)?;
 
         Ok(ToolResult::new(content))
     }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +101,25 @@ mod tests {
         assert!(result.message.contains("line1"));
         assert!(result.message.contains("line2"));
     }
+
+    #[tokio::test]
+    async fn test_read_file_tool_line_range() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let content: Vec<String> = (1..=500).map(|i| format!("line {}", i)).collect();
+        std::fs::write(temp.path().join("big.txt"), content.join("\n")).unwrap();
+
+        let backend = Arc::new(crate::backends::FilesystemBackend::new(temp.path()));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        // 1-based 100~110행 = offset 99, limit 11
+        let result = ReadFileTool.execute(
+            serde_json::json!({"file_path": "/big.txt", "offset": 99, "limit": 11}),
+            &runtime,
+        ).await.unwrap();
+
+        let lines: Vec<&str> = result.message.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "100\tline 100");
+        assert_eq!(lines[10], "110\tline 110");
+    }
 }
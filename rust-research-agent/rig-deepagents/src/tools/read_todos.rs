@@ -16,6 +16,7 @@ impl Tool for ReadTodosTool {
                 "type": "object",
                 "properties": {},
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -29,6 +30,10 @@ impl Tool for ReadTodosTool {
             .map_err(|e| MiddlewareError::ToolExecution(format!("Failed to serialize todos: {e}")))?;
         Ok(ToolResult::new(json))
     }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
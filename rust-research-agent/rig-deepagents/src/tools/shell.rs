@@ -0,0 +1,274 @@
+//! Shell Tool - Sandboxed command execution for coding agents
+//!
+//! Runs a single program (e.g. `cargo test`) via `tokio::process::Command`.
+//! Commands are never passed through a shell, so pipes, redirects and
+//! substitutions are not interpreted.
+//!
+//! # Sandboxing
+//!
+//! - Only programs on the allowlist given to [`ShellTool::new`] can run
+//! - The working directory is the backend's root directory
+//!   ([`Backend::root_dir`](crate::backends::Backend::root_dir)); backends
+//!   without one (e.g. `MemoryBackend`) are rejected
+//! - Processes are killed when the timeout elapses
+//! - stdout/stderr are truncated to keep results within the token budget
+//!
+//! # Example
+//! ```ignore
+//! let tool = ShellTool::new(["cargo", "git"]).with_timeout(Duration::from_secs(300));
+//! ```
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::debug;
+
+use crate::error::MiddlewareError;
+use crate::middleware::{Tool, ToolDefinition, ToolResult};
+use crate::runtime::ToolRuntime;
+
+/// Default command timeout
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Default maximum bytes kept per output stream
+const DEFAULT_MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Shell command tool restricted to an allowlist of programs
+pub struct ShellTool {
+    allowed_programs: HashSet<String>,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+/// Arguments for the shell tool
+#[derive(Debug, Deserialize)]
+struct ShellArgs {
+    /// Program to run (must be on the allowlist)
+    command: String,
+    /// Program arguments
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+impl ShellTool {
+    /// Create a shell tool that may only run the given programs
+    ///
+    /// Programs are matched exactly against the requested command name,
+    /// so `"cargo"` does not allow `"/usr/bin/cargo"` or `"./cargo"`.
+    pub fn new<I, S>(allowed_programs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed_programs: allowed_programs.into_iter().map(Into::into).collect(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set command timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set maximum bytes kept per output stream
+    pub fn with_max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = max_output_bytes;
+        self
+    }
+
+    /// Whether a program is on the allowlist
+    pub fn is_allowed(&self, program: &str) -> bool {
+        self.allowed_programs.contains(program)
+    }
+
+    /// Decode an output stream, truncating it at a char boundary
+    fn format_output(&self, bytes: &[u8]) -> String {
+        let text = String::from_utf8_lossy(bytes);
+        if text.len() <= self.max_output_bytes {
+            return text.into_owned();
+        }
+
+        let end = (0..=self.max_output_bytes)
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        format!(
+            "{}\n[truncated: {} of {} bytes shown]",
+            &text[..end],
+            end,
+            text.len()
+        )
+    }
+}
+
+#[async_trait]
+impl Tool for ShellTool {
+    fn definition(&self) -> ToolDefinition {
+        let mut allowed: Vec<&str> = self.allowed_programs.iter().map(String::as_str).collect();
+        allowed.sort_unstable();
+
+        ToolDefinition {
+            name: "shell".to_string(),
+            description: format!(
+                "Run a program in the workspace root and return its exit code, stdout and stderr. \
+                 The command is not run through a shell (no pipes or redirects). \
+                 Allowed programs: {}.",
+                allowed.join(", ")
+            ),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "command": {
+                        "type": "string",
+                        "description": "Program to run (e.g. 'cargo')",
+                        "enum": allowed
+                    },
+                    "args": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Arguments passed to the program (e.g. ['test', '--lib'])"
+                    }
+                },
+                "required": ["command"]
+            }),
+            examples: Vec::new(),
+        }
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        let args: ShellArgs = serde_json::from_value(args)
+            .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
+
+        if !self.is_allowed(&args.command) {
+            return Err(MiddlewareError::ToolExecution(format!(
+                "Command '{}' is not allowed",
+                args.command
+            )));
+        }
+
+        let root = runtime.backend().root_dir().ok_or_else(|| {
+            MiddlewareError::ToolExecution(
+                "Shell commands require a backend with a local root directory".to_string(),
+            )
+        })?;
+
+        debug!(command = %args.command, args = ?args.args, cwd = %root.display(), "Running shell command");
+
+        let child = Command::new(&args.command)
+            .args(&args.args)
+            .current_dir(root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                MiddlewareError::ToolExecution(format!("Failed to start '{}': {}", args.command, e))
+            })?;
+
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| MiddlewareError::ToolTimeout {
+                tool_name: "shell".to_string(),
+                timeout: self.timeout,
+            })?
+            .map_err(|e| {
+                MiddlewareError::ToolExecution(format!("Failed to run '{}': {}", args.command, e))
+            })?;
+
+        let exit_code = output
+            .status
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "terminated by signal".to_string());
+
+        Ok(ToolResult::new(format!(
+            "Exit code: {}\n\nSTDOUT:\n{}\n\nSTDERR:\n{}",
+            exit_code,
+            self.format_output(&output.stdout),
+            self.format_output(&output.stderr)
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{FilesystemBackend, MemoryBackend};
+    use crate::state::AgentState;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn runtime_in(dir: &std::path::Path) -> ToolRuntime {
+        ToolRuntime::new(AgentState::new(), Arc::new(FilesystemBackend::new(dir)))
+    }
+
+    #[tokio::test]
+    async fn test_shell_runs_allowed_command_in_backend_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("marker.txt"), "x").unwrap();
+        let tool = ShellTool::new(["ls", "sh"]);
+        let runtime = runtime_in(temp.path());
+
+        let result = tool.execute(json!({"command": "ls"}), &runtime).await.unwrap();
+        assert!(result.message.starts_with("Exit code: 0"));
+        assert!(result.message.contains("marker.txt"));
+
+        let result = tool
+            .execute(json!({"command": "sh", "args": ["-c", "echo oops >&2; exit 3"]}), &runtime)
+            .await
+            .unwrap();
+        assert!(result.message.starts_with("Exit code: 3"));
+        assert!(result.message.contains("STDERR:\noops"));
+    }
+
+    #[tokio::test]
+    async fn test_shell_rejects_unlisted_program() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tool = ShellTool::new(["ls"]);
+
+        let err = tool
+            .execute(json!({"command": "/bin/ls"}), &runtime_in(temp.path()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MiddlewareError::ToolExecution(msg) if msg.contains("not allowed")));
+    }
+
+    #[tokio::test]
+    async fn test_shell_requires_local_root() {
+        let tool = ShellTool::new(["ls"]);
+        let runtime = ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()));
+
+        let err = tool.execute(json!({"command": "ls"}), &runtime).await.unwrap_err();
+        assert!(err.to_string().contains("local root directory"));
+    }
+
+    #[tokio::test]
+    async fn test_shell_timeout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tool = ShellTool::new(["sleep"]).with_timeout(Duration::from_millis(100));
+
+        let err = tool
+            .execute(json!({"command": "sleep", "args": ["5"]}), &runtime_in(temp.path()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MiddlewareError::ToolTimeout { .. }));
+    }
+
+    #[test]
+    fn test_output_truncation() {
+        let tool = ShellTool::new(Vec::<String>::new()).with_max_output_bytes(4);
+        let output = tool.format_output("héllo".as_bytes());
+        assert!(output.starts_with("hél\n[truncated: 4 of 6 bytes shown]"));
+    }
+}
@@ -42,6 +42,7 @@ impl Tool for TaskTool {
                 },
                 "required": ["subagent_type", "prompt"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -9,6 +9,7 @@
 //! - HTTP timeout and retry with exponential backoff
 //! - Typed error handling for rate limits and timeouts
 //! - Complete JSON schema for LLM function calling
+//! - Implements [`SearchProvider`], so it can also back a generic `WebSearchTool`
 
 use async_trait::async_trait;
 use reqwest::Client;
@@ -20,6 +21,14 @@ use crate::error::MiddlewareError;
 use crate::middleware::{Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
 
+use super::web_search::{
+    run_search, search_parameters_schema, SearchOptions, SearchProvider, SearchResponse,
+    SearchResult,
+};
+
+/// Default Tavily API endpoint
+const DEFAULT_BASE_URL: &str = "https://api.tavily.com";
+
 /// Default timeout for Tavily API requests
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
@@ -82,6 +91,7 @@ impl Topic {
 pub struct TavilySearchTool {
     api_key: String,
     client: Client,
+    base_url: String,
     timeout: Duration,
     max_retries: u32,
 }
@@ -92,6 +102,7 @@ impl TavilySearchTool {
         Self {
             api_key: api_key.into(),
             client: Client::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
             timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
             max_retries: MAX_RETRIES,
         }
@@ -113,6 +124,12 @@ impl TavilySearchTool {
         self
     }
 
+    /// Set custom API base URL (e.g. for a proxy)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     /// Set custom max retries
     pub fn with_max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = max_retries;
@@ -156,7 +173,7 @@ impl TavilySearchTool {
     ) -> Result<TavilyResponse, TavilyError> {
         let response = self
             .client
-            .post("https://api.tavily.com/search")
+            .post(format!("{}/search", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .timeout(self.timeout)
@@ -249,37 +266,6 @@ impl From<TavilyError> for MiddlewareError {
     }
 }
 
-/// Arguments for the tavily_search tool
-#[derive(Debug, Deserialize)]
-struct TavilySearchArgs {
-    /// The search query
-    query: String,
-
-    /// Maximum number of results (default: 5)
-    #[serde(default = "default_max_results")]
-    max_results: u32,
-
-    /// Search depth (default: basic)
-    #[serde(default)]
-    search_depth: SearchDepth,
-
-    /// Topic filter (default: general)
-    #[serde(default)]
-    topic: Topic,
-
-    /// Include AI-generated answer in response
-    #[serde(default)]
-    include_answer: bool,
-
-    /// Include raw HTML content in results
-    #[serde(default)]
-    include_raw_content: bool,
-}
-
-fn default_max_results() -> u32 {
-    5
-}
-
 /// Request body for Tavily API
 #[derive(Debug, Serialize)]
 struct TavilyRequest {
@@ -289,6 +275,10 @@ struct TavilyRequest {
     topic: String,
     include_answer: bool,
     include_raw_content: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    include_domains: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exclude_domains: Vec<String>,
 }
 
 /// Response from Tavily API
@@ -320,30 +310,48 @@ struct TavilyResult {
     raw_content: Option<String>,
 }
 
-impl TavilyResult {
-    /// Format as markdown for LLM consumption
-    fn to_markdown(&self, include_raw: bool) -> String {
-        let mut output = format!(
-            "### [{}]({})\n**Relevance:** {:.0}%\n\n{}\n",
-            self.title,
-            self.url,
-            self.score * 100.0,
-            self.content
-        );
-
-        if include_raw {
-            if let Some(ref raw) = self.raw_content {
-                // Truncate raw content to avoid token explosion
-                let truncated = if raw.len() > 2000 {
-                    format!("{}...[truncated]", &raw[..2000])
-                } else {
-                    raw.clone()
-                };
-                output.push_str(&format!("\n<details>\n<summary>Raw Content</summary>\n\n```html\n{}\n```\n</details>\n", truncated));
-            }
+impl From<TavilyResult> for SearchResult {
+    fn from(result: TavilyResult) -> Self {
+        Self {
+            title: result.title,
+            url: result.url,
+            content: result.content,
+            score: result.score,
+            raw_content: result.raw_content,
         }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for TavilySearchTool {
+    fn name(&self) -> &str {
+        "tavily"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResponse, MiddlewareError> {
+        // Build request with type-safe enums
+        let request = TavilyRequest {
+            query: query.to_string(),
+            max_results: options.max_results,
+            search_depth: options.search_depth.as_str().to_string(),
+            topic: options.topic.as_str().to_string(),
+            include_answer: options.include_answer,
+            include_raw_content: options.include_raw_content,
+            include_domains: options.include_domains.clone(),
+            exclude_domains: options.exclude_domains.clone(),
+        };
+
+        // Execute with retry
+        let response = self.execute_with_retry(&request).await?;
 
-        output
+        Ok(SearchResponse {
+            answer: response.answer,
+            results: response.results.into_iter().map(SearchResult::from).collect(),
+        })
     }
 }
 
@@ -353,47 +361,8 @@ impl Tool for TavilySearchTool {
         ToolDefinition {
             name: "tavily_search".to_string(),
             description: "Search the web using Tavily Search API. Returns relevant web pages with titles, URLs, and content snippets.".to_string(),
-            parameters: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "query": {
-                        "type": "string",
-                        "description": "The search query to execute",
-                        "maxLength": 400
-                    },
-                    "max_results": {
-                        "type": "integer",
-                        "description": "Maximum number of results to return (default: 5, max: 20)",
-                        "default": 5,
-                        "minimum": 1,
-                        "maximum": 20
-                    },
-                    "search_depth": {
-                        "type": "string",
-                        "enum": ["basic", "advanced"],
-                        "description": "Search depth - 'basic' for fast results, 'advanced' for more thorough search",
-                        "default": "basic"
-                    },
-                    "topic": {
-                        "type": "string",
-                        "enum": ["general", "news"],
-                        "description": "Topic filter - 'general' for all content, 'news' for recent news",
-                        "default": "general"
-                    },
-                    "include_answer": {
-                        "type": "boolean",
-                        "description": "Include an AI-generated answer summarizing the results",
-                        "default": false
-                    },
-                    "include_raw_content": {
-                        "type": "boolean",
-                        "description": "Include raw HTML content in results (increases response size)",
-                        "default": false
-                    }
-                },
-                "required": ["query"],
-                "additionalProperties": false
-            }),
+            parameters: search_parameters_schema(),
+            examples: Vec::new(),
         }
     }
 
@@ -407,63 +376,18 @@ impl Tool for TavilySearchTool {
             debug!(tool_call_id, "Executing tavily_search");
         }
 
-        let args: TavilySearchArgs = serde_json::from_value(args)
-            .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
-
-        // Validate query length
-        if args.query.len() > 400 {
-            return Err(MiddlewareError::ToolExecution(
-                "Query too long (max 400 characters)".to_string(),
-            ));
-        }
-
-        // Validate and clamp max_results
-        let max_results = args.max_results.clamp(1, 20);
-
-        // Build request with type-safe enums
-        let request = TavilyRequest {
-            query: args.query.clone(),
-            max_results,
-            search_depth: args.search_depth.as_str().to_string(),
-            topic: args.topic.as_str().to_string(),
-            include_answer: args.include_answer,
-            include_raw_content: args.include_raw_content,
-        };
-
-        // Execute with retry
-        let tavily_response = self.execute_with_retry(&request).await?;
-
-        // Format results as markdown
-        let mut output = format!("## Search Results for: \"{}\"\n\n", args.query);
-
-        // Include AI answer if present
-        if let Some(answer) = tavily_response.answer {
-            output.push_str("### AI Summary\n");
-            output.push_str(&answer);
-            output.push_str("\n\n---\n\n");
-        }
-
-        // Add results
-        if tavily_response.results.is_empty() {
-            output.push_str("No results found.\n");
-        } else {
-            output.push_str(&format!(
-                "Found {} results:\n\n",
-                tavily_response.results.len()
-            ));
-            for result in &tavily_response.results {
-                output.push_str(&result.to_markdown(args.include_raw_content));
-                output.push('\n');
-            }
-        }
+        run_search(self, args).await
+    }
 
-        Ok(ToolResult::new(output))
+    fn is_concurrent_safe(&self) -> bool {
+        true
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::web_search::SearchArgs as TavilySearchArgs;
 
     // ==================== Unit Tests ====================
 
@@ -567,7 +491,7 @@ mod tests {
             raw_content: None,
         };
 
-        let md = result.to_markdown(false);
+        let md = SearchResult::from(result).to_markdown(false);
         assert!(md.contains("### [Test Title](https://example.com)"));
         assert!(md.contains("**Relevance:** 95%"));
         assert!(md.contains("This is test content."));
@@ -584,7 +508,7 @@ mod tests {
             raw_content: Some("<html><body>Raw HTML</body></html>".to_string()),
         };
 
-        let md = result.to_markdown(true);
+        let md = SearchResult::from(result).to_markdown(true);
         assert!(md.contains("<details>"));
         assert!(md.contains("Raw HTML"));
     }
@@ -600,7 +524,7 @@ mod tests {
             raw_content: Some(long_html),
         };
 
-        let md = result.to_markdown(true);
+        let md = SearchResult::from(result).to_markdown(true);
         assert!(md.contains("...[truncated]"));
         assert!(md.len() < 3500); // Should be truncated
     }
@@ -647,7 +571,7 @@ mod tests {
 #[cfg(test)]
 mod http_tests {
     use super::*;
-    use wiremock::matchers::{header, method, path};
+    use wiremock::matchers::{body_partial_json, header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     /// Create a TavilySearchTool that uses a custom base URL (for mocking)
@@ -704,7 +628,7 @@ mod http_tests {
         async fn execute_single(&self, request: &TavilyRequest) -> Result<TavilyResponse, TavilyError> {
             let response = self
                 .client
-                .post(&format!("{}/search", self.base_url))
+                .post(format!("{}/search", self.base_url))
                 .header("Authorization", format!("Bearer {}", self.api_key))
                 .header("Content-Type", "application/json")
                 .timeout(self.timeout)
@@ -782,6 +706,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: true,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -793,6 +719,44 @@ mod http_tests {
         assert_eq!(response.results[0].title, "Rust Programming Language");
     }
 
+    #[tokio::test]
+    async fn test_http_request_carries_domain_filters() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(body_partial_json(serde_json::json!({
+                "include_domains": ["arxiv.org", "mit.edu"],
+                "exclude_domains": ["pinterest.com"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(sample_success_response()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let tool = TavilySearchTool::new("test-api-key")
+            .with_base_url(mock_server.uri())
+            .with_max_retries(0);
+        let runtime = ToolRuntime::new(
+            crate::state::AgentState::new(),
+            std::sync::Arc::new(crate::backends::MemoryBackend::new()),
+        );
+
+        let result = tool
+            .execute(
+                serde_json::json!({
+                    "query": "transformers",
+                    "include_domains": ["arxiv.org", "mit.edu"],
+                    "exclude_domains": ["pinterest.com"]
+                }),
+                &runtime,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.message.contains("Rust Programming Language"));
+    }
+
     #[tokio::test]
     async fn test_http_unauthorized_error() {
         let mock_server = MockServer::start().await;
@@ -811,6 +775,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -836,6 +802,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -861,6 +829,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -886,6 +856,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -921,6 +893,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -951,6 +925,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -979,6 +955,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
@@ -1007,6 +985,8 @@ mod http_tests {
             topic: "general".to_string(),
             include_answer: false,
             include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
         };
 
         let result = tool.execute_request(&request).await;
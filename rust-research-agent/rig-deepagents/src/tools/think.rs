@@ -1,8 +1,10 @@
 //! Think Tool - Explicit reflection for agent reasoning
 //!
 //! Provides a structured way for agents to record their thinking process.
-//! This tool has no side effects - it simply echoes the reflection back,
-//! making the agent's reasoning explicit and traceable.
+//! [`ThinkTool`] has no side effects - it simply acknowledges the reflection,
+//! making the agent's reasoning explicit and traceable. [`LoggingThinkTool`]
+//! additionally appends each reflection to a log file in the backend so a
+//! later synthesis step (or a human) can review the reasoning trail.
 //!
 //! # Production Considerations
 //!
@@ -11,12 +13,20 @@
 //! - Integrates with ToolRuntime for tracing
 
 use async_trait::async_trait;
+use chrono::Utc;
 use serde::Deserialize;
-use tracing::debug;
+use std::collections::HashMap;
+use tracing::{debug, warn};
 
-use crate::error::MiddlewareError;
-use crate::middleware::{Tool, ToolDefinition, ToolResult};
+use crate::backends::Backend;
+use crate::error::{BackendError, MiddlewareError};
+use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
+use crate::state::FileData;
+use super::write_file::rewrite_file;
+
+/// Header written when the thought log is created
+const LOG_HEADER: &str = "# Thoughts\n";
 
 /// Think Tool for explicit agent reflection
 ///
@@ -35,32 +45,143 @@ use crate::runtime::ToolRuntime;
 /// ```
 pub struct ThinkTool;
 
+/// Think tool that also appends each reflection to a backend file
+///
+/// Entries go under a timestamp heading (with the phase, when given), so the
+/// log reads as a reasoning trail.
+///
+/// # Example
+/// ```ignore
+/// let tool = LoggingThinkTool::new(LoggingThinkTool::DEFAULT_LOG_PATH);
+/// let result = tool.execute(json!({
+///     "reflection": "Two sources disagree on the release date.",
+///     "phase": "exploration"
+/// }), &runtime).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoggingThinkTool {
+    /// Backend file that reflections are appended to
+    log_path: String,
+}
+
+impl LoggingThinkTool {
+    /// Conventional location for the thought log
+    pub const DEFAULT_LOG_PATH: &'static str = "/thoughts.md";
+
+    /// Create a think tool that appends reflections to `log_path`
+    pub fn new(log_path: impl Into<String>) -> Self {
+        Self { log_path: log_path.into() }
+    }
+
+    /// The backend file reflections are logged to
+    pub fn log_path(&self) -> &str {
+        &self.log_path
+    }
+
+    /// Format one log entry
+    fn log_entry(reflection: &str, phase: Option<&str>) -> String {
+        let timestamp = Utc::now().to_rfc3339();
+        match phase {
+            Some(phase) => format!("\n## {} [{}]\n\n{}\n", timestamp, phase, reflection),
+            None => format!("\n## {}\n\n{}\n", timestamp, reflection),
+        }
+    }
+
+    /// Append an entry to the log file, creating it if needed
+    ///
+    /// Returns the files update for checkpointing backends.
+    async fn append_to_log(
+        backend: &dyn Backend,
+        path: &str,
+        entry: &str,
+    ) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+        rewrite_file(backend, path, |existing| {
+            format!("{}{}", existing.unwrap_or_else(|| LOG_HEADER.to_string()), entry)
+        })
+        .await
+    }
+}
+
 /// Arguments for the think tool
 #[derive(Debug, Deserialize)]
 struct ThinkArgs {
     /// The reflection or thought to record
     reflection: String,
+    /// Research phase the reflection belongs to (e.g. "exploration")
+    #[serde(default)]
+    phase: Option<String>,
+}
+
+/// Tool definition shared by both think tools
+fn think_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "think".to_string(),
+        description: "Record your thinking process explicitly. Use this tool to pause and reflect on your reasoning, analyze information, or plan your next steps. The reflection is recorded and returned as confirmation.".to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "reflection": {
+                    "type": "string",
+                    "description": "Your thought process, analysis, or reasoning to record",
+                    "minLength": 1
+                },
+                "phase": {
+                    "type": "string",
+                    "description": "Optional phase of the work this reflection belongs to (e.g. exploration, directed research, synthesis)"
+                }
+            },
+            "required": ["reflection"],
+            "additionalProperties": false
+        }),
+        examples: Vec::new(),
+    }
+}
+
+/// Parse the arguments and acknowledge the reflection
+fn acknowledge(
+    args: serde_json::Value,
+    runtime: &ToolRuntime,
+) -> Result<(ThinkArgs, ToolResult), MiddlewareError> {
+    let args: ThinkArgs = serde_json::from_value(args)
+        .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
+
+    // Log for tracing
+    if let Some(tool_call_id) = runtime.tool_call_id() {
+        debug!(
+            tool_call_id,
+            reflection_len = args.reflection.len(),
+            "Think tool executed"
+        );
+    }
+
+    // Minimal output to avoid prompt pollution
+    // The reflection itself is the valuable content - we just acknowledge it
+    let result = ToolResult::new(format!(
+        "[Reflection recorded: {} chars]",
+        args.reflection.len()
+    ));
+    Ok((args, result))
 }
 
 #[async_trait]
 impl Tool for ThinkTool {
     fn definition(&self) -> ToolDefinition {
-        ToolDefinition {
-            name: "think".to_string(),
-            description: "Record your thinking process explicitly. Use this tool to pause and reflect on your reasoning, analyze information, or plan your next steps. The reflection is recorded and returned as confirmation.".to_string(),
-            parameters: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "reflection": {
-                        "type": "string",
-                        "description": "Your thought process, analysis, or reasoning to record",
-                        "minLength": 1
-                    }
-                },
-                "required": ["reflection"],
-                "additionalProperties": false
-            }),
-        }
+        think_definition()
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        acknowledge(args, runtime).map(|(_, result)| result)
+    }
+}
+
+#[async_trait]
+impl Tool for LoggingThinkTool {
+    fn definition(&self) -> ToolDefinition {
+        think_definition()
     }
 
     async fn execute(
@@ -68,24 +189,29 @@ impl Tool for ThinkTool {
         args: serde_json::Value,
         runtime: &ToolRuntime,
     ) -> Result<ToolResult, MiddlewareError> {
-        let args: ThinkArgs = serde_json::from_value(args)
-            .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
-
-        // Log for tracing
-        if let Some(tool_call_id) = runtime.tool_call_id() {
-            debug!(
-                tool_call_id,
-                reflection_len = args.reflection.len(),
-                "Think tool executed"
-            );
+        let (args, mut result) = acknowledge(args, runtime)?;
+
+        // A failed log write shouldn't cost the agent its turn; the reflection
+        // is still in the message history
+        let entry = Self::log_entry(&args.reflection, args.phase.as_deref());
+        match Self::append_to_log(runtime.backend().as_ref(), &self.log_path, &entry).await {
+            Ok(Some(files_update)) => {
+                let updates = files_update
+                    .into_iter()
+                    .map(|(path, data)| (path, Some(data)))
+                    .collect();
+                result = result.with_update(StateUpdate::UpdateFiles(updates));
+            }
+            Ok(None) => {}
+            Err(e) => warn!(path = %self.log_path, error = %e, "Failed to append to thought log"),
         }
 
-        // Minimal output to avoid prompt pollution
-        // The reflection itself is the valuable content - we just acknowledge it
-        Ok(ToolResult::new(format!(
-            "[Reflection recorded: {} chars]",
-            args.reflection.len()
-        )))
+        Ok(result)
+    }
+
+    fn is_concurrent_safe(&self) -> bool {
+        // Appends to the log are read-modify-write
+        false
     }
 }
 
@@ -203,4 +329,77 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_think_tool_appends_to_log() {
+        let tool = LoggingThinkTool::new(LoggingThinkTool::DEFAULT_LOG_PATH);
+        let runtime = create_test_runtime();
+
+        let result = tool
+            .execute(
+                serde_json::json!({
+                    "reflection": "Two sources disagree on the release date.",
+                    "phase": "exploration"
+                }),
+                &runtime,
+            )
+            .await
+            .unwrap();
+        tool.execute(serde_json::json!({"reflection": "Use the vendor's changelog."}), &runtime)
+            .await
+            .unwrap();
+
+        let log = runtime.backend().read_plain("/thoughts.md").await.unwrap();
+        assert!(log.starts_with("# Thoughts"));
+
+        let lines: Vec<&str> = log.lines().collect();
+        let first = lines
+            .iter()
+            .position(|l| *l == "Two sources disagree on the release date.")
+            .unwrap();
+        assert!(lines[first - 2].starts_with("## "));
+        assert!(lines[first - 2].ends_with("[exploration]"));
+        assert!(lines.contains(&"Use the vendor's changelog."));
+
+        // The log write is surfaced as a files update
+        assert!(matches!(
+            &result.updates[0],
+            StateUpdate::UpdateFiles(files) if files.contains_key("/thoughts.md")
+        ));
+        assert!(!tool.is_concurrent_safe());
+    }
+
+    #[tokio::test]
+    async fn test_think_tool_without_log_writes_nothing() {
+        let tool = ThinkTool;
+        let runtime = create_test_runtime();
+
+        let result = tool
+            .execute(serde_json::json!({"reflection": "Just thinking"}), &runtime)
+            .await
+            .unwrap();
+
+        assert!(result.updates.is_empty());
+        assert!(!runtime.backend().exists("/thoughts.md").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_logging_think_tool_appends_without_transaction() {
+        let inner = Arc::new(MemoryBackend::new());
+        let backend = Arc::new(crate::backends::QuotaBackend::new(inner.clone(), 1 << 20, None));
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+        let tool = LoggingThinkTool::new("/notes/thoughts.md");
+
+        for reflection in ["First thought", "Second thought"] {
+            tool.execute(serde_json::json!({"reflection": reflection}), &runtime)
+                .await
+                .unwrap();
+        }
+
+        let log = inner.read_plain("/notes/thoughts.md").await.unwrap();
+        assert!(log.starts_with("# Thoughts"));
+        let first = log.find("First thought").unwrap();
+        let second = log.find("Second thought").unwrap();
+        assert!(first < second);
+    }
 }
@@ -0,0 +1,457 @@
+//! Web Search Tool - Provider-agnostic web search for research agents
+//!
+//! Defines the [`SearchProvider`] trait so search backends (Tavily, Brave, SearXNG, ...)
+//! can be swapped without changing agent prompts. [`WebSearchTool`] exposes any provider
+//! with the same JSON schema and markdown output as `tavily_search`.
+//!
+//! # Example
+//! ```ignore
+//! // Same tool name and output format as before, backed by any provider
+//! let tool = WebSearchTool::new(TavilySearchTool::new(api_key)).with_name("tavily_search");
+//! ```
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::error::MiddlewareError;
+use crate::middleware::{Tool, ToolDefinition, ToolResult};
+use crate::runtime::ToolRuntime;
+
+use super::tavily::{SearchDepth, Topic};
+
+/// Maximum query length accepted by the search tools
+const MAX_QUERY_LENGTH: usize = 400;
+
+/// Maximum number of results a single call may request
+const MAX_RESULTS: u32 = 20;
+
+/// Maximum number of entries in a domain include/exclude list
+const MAX_DOMAIN_FILTERS: usize = 50;
+
+/// Maximum characters of raw content included per result
+const RAW_CONTENT_PREVIEW_CHARS: usize = 2000;
+
+/// Options passed to a [`SearchProvider`]
+///
+/// Providers ignore options they don't support (e.g. `topic` for SearXNG).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    /// Maximum number of results (already clamped to 1..=20)
+    pub max_results: u32,
+    pub search_depth: SearchDepth,
+    pub topic: Topic,
+    /// Ask the provider for a generated answer, if supported
+    pub include_answer: bool,
+    /// Ask the provider for raw page content, if supported
+    pub include_raw_content: bool,
+    /// Only return results from these domains (e.g. `arxiv.org`)
+    pub include_domains: Vec<String>,
+    /// Never return results from these domains
+    pub exclude_domains: Vec<String>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            max_results: default_max_results(),
+            search_depth: SearchDepth::default(),
+            topic: Topic::default(),
+            include_answer: false,
+            include_raw_content: false,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
+        }
+    }
+}
+
+/// A single search result
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// Page title
+    pub title: String,
+    /// Page URL
+    pub url: String,
+    /// Extracted content/snippet
+    pub content: String,
+    /// Relevance score (0-1)
+    pub score: f64,
+    /// Raw page content (if requested and supported)
+    pub raw_content: Option<String>,
+}
+
+impl SearchResult {
+    /// Format as markdown for LLM consumption
+    pub fn to_markdown(&self, include_raw: bool) -> String {
+        let mut output = format!(
+            "### [{}]({})\n**Relevance:** {:.0}%\n\n{}\n",
+            self.title,
+            self.url,
+            self.score * 100.0,
+            self.content
+        );
+
+        if include_raw {
+            if let Some(ref raw) = self.raw_content {
+                // Truncate raw content to avoid token explosion
+                let truncated = if raw.len() > RAW_CONTENT_PREVIEW_CHARS {
+                    let end = (0..=RAW_CONTENT_PREVIEW_CHARS)
+                        .rev()
+                        .find(|&i| raw.is_char_boundary(i))
+                        .unwrap_or(0);
+                    format!("{}...[truncated]", &raw[..end])
+                } else {
+                    raw.clone()
+                };
+                output.push_str(&format!("\n<details>\n<summary>Raw Content</summary>\n\n```html\n{}\n```\n</details>\n", truncated));
+            }
+        }
+
+        output
+    }
+}
+
+/// Results of a search call
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResponse {
+    /// Provider-generated answer (if requested and supported)
+    pub answer: Option<String>,
+    pub results: Vec<SearchResult>,
+}
+
+impl SearchResponse {
+    /// Format the response as markdown (shared by all search tools)
+    pub fn to_markdown(&self, query: &str, include_raw: bool) -> String {
+        let mut output = format!("## Search Results for: \"{}\"\n\n", query);
+
+        // Include AI answer if present
+        if let Some(ref answer) = self.answer {
+            output.push_str("### AI Summary\n");
+            output.push_str(answer);
+            output.push_str("\n\n---\n\n");
+        }
+
+        if self.results.is_empty() {
+            output.push_str("No results found.\n");
+        } else {
+            output.push_str(&format!("Found {} results:\n\n", self.results.len()));
+            for result in &self.results {
+                output.push_str(&result.to_markdown(include_raw));
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+/// Web search backend
+#[async_trait]
+pub trait SearchProvider: Send + Sync {
+    /// Provider name (for logging)
+    fn name(&self) -> &str;
+
+    /// Run a search query
+    async fn search(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResponse, MiddlewareError>;
+}
+
+/// Arguments shared by all web search tools
+#[derive(Debug, Deserialize)]
+pub(crate) struct SearchArgs {
+    /// The search query
+    pub(crate) query: String,
+
+    /// Maximum number of results (default: 5)
+    #[serde(default = "default_max_results")]
+    pub(crate) max_results: u32,
+
+    /// Search depth (default: basic)
+    #[serde(default)]
+    pub(crate) search_depth: SearchDepth,
+
+    /// Topic filter (default: general)
+    #[serde(default)]
+    pub(crate) topic: Topic,
+
+    /// Include AI-generated answer in response
+    #[serde(default)]
+    pub(crate) include_answer: bool,
+
+    /// Include raw HTML content in results
+    #[serde(default)]
+    pub(crate) include_raw_content: bool,
+
+    /// Restrict results to these domains
+    #[serde(default)]
+    pub(crate) include_domains: Vec<String>,
+
+    /// Exclude results from these domains
+    #[serde(default)]
+    pub(crate) exclude_domains: Vec<String>,
+}
+
+fn default_max_results() -> u32 {
+    5
+}
+
+impl SearchArgs {
+    /// Validate the arguments and convert them to provider options
+    pub(crate) fn to_options(&self) -> Result<SearchOptions, MiddlewareError> {
+        if self.query.len() > MAX_QUERY_LENGTH {
+            return Err(MiddlewareError::ToolExecution(format!(
+                "Query too long (max {} characters)",
+                MAX_QUERY_LENGTH
+            )));
+        }
+
+        for (field, domains) in [
+            ("include_domains", &self.include_domains),
+            ("exclude_domains", &self.exclude_domains),
+        ] {
+            if domains.len() > MAX_DOMAIN_FILTERS {
+                return Err(MiddlewareError::ToolExecution(format!(
+                    "Too many entries in {} ({}, max {})",
+                    field,
+                    domains.len(),
+                    MAX_DOMAIN_FILTERS
+                )));
+            }
+        }
+
+        Ok(SearchOptions {
+            max_results: self.max_results.clamp(1, MAX_RESULTS),
+            search_depth: self.search_depth,
+            topic: self.topic,
+            include_answer: self.include_answer,
+            include_raw_content: self.include_raw_content,
+            include_domains: self.include_domains.clone(),
+            exclude_domains: self.exclude_domains.clone(),
+        })
+    }
+}
+
+/// JSON schema shared by all web search tools
+pub(crate) fn search_parameters_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "query": {
+                "type": "string",
+                "description": "The search query to execute",
+                "maxLength": MAX_QUERY_LENGTH
+            },
+            "max_results": {
+                "type": "integer",
+                "description": "Maximum number of results to return (default: 5, max: 20)",
+                "default": 5,
+                "minimum": 1,
+                "maximum": MAX_RESULTS
+            },
+            "search_depth": {
+                "type": "string",
+                "enum": ["basic", "advanced"],
+                "description": "Search depth - 'basic' for fast results, 'advanced' for more thorough search",
+                "default": "basic"
+            },
+            "topic": {
+                "type": "string",
+                "enum": ["general", "news"],
+                "description": "Topic filter - 'general' for all content, 'news' for recent news",
+                "default": "general"
+            },
+            "include_answer": {
+                "type": "boolean",
+                "description": "Include an AI-generated answer summarizing the results",
+                "default": false
+            },
+            "include_raw_content": {
+                "type": "boolean",
+                "description": "Include raw HTML content in results (increases response size)",
+                "default": false
+            },
+            "include_domains": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": MAX_DOMAIN_FILTERS,
+                "description": "Only return results from these domains (e.g. ['arxiv.org', 'mit.edu'])"
+            },
+            "exclude_domains": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": MAX_DOMAIN_FILTERS,
+                "description": "Exclude results from these domains"
+            }
+        },
+        "required": ["query"],
+        "additionalProperties": false
+    })
+}
+
+/// Parse arguments, run the provider, and format the results
+pub(crate) async fn run_search<P: SearchProvider + ?Sized>(
+    provider: &P,
+    args: serde_json::Value,
+) -> Result<ToolResult, MiddlewareError> {
+    let args: SearchArgs = serde_json::from_value(args)
+        .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
+    let options = args.to_options()?;
+
+    let response = provider.search(&args.query, &options).await?;
+    Ok(ToolResult::new(response.to_markdown(&args.query, options.include_raw_content)))
+}
+
+/// Generic web search tool backed by any [`SearchProvider`]
+pub struct WebSearchTool<P: SearchProvider> {
+    provider: P,
+    name: String,
+}
+
+impl<P: SearchProvider> WebSearchTool<P> {
+    /// Create a `web_search` tool for the given provider
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            name: "web_search".to_string(),
+        }
+    }
+
+    /// Override the tool name (e.g. `tavily_search` to keep existing prompts)
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Access the underlying provider
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+}
+
+#[async_trait]
+impl<P: SearchProvider + 'static> Tool for WebSearchTool<P> {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: self.name.clone(),
+            description: "Search the web. Returns relevant web pages with titles, URLs, and content snippets.".to_string(),
+            parameters: search_parameters_schema(),
+            examples: Vec::new(),
+        }
+    }
+
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        runtime: &ToolRuntime,
+    ) -> Result<ToolResult, MiddlewareError> {
+        if let Some(tool_call_id) = runtime.tool_call_id() {
+            tracing::debug!(tool_call_id, provider = self.provider.name(), "Executing {}", self.name);
+        }
+
+        run_search(&self.provider, args).await
+    }
+
+    fn is_concurrent_safe(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+    use crate::state::AgentState;
+    use std::sync::{Arc, Mutex};
+
+    /// Provider that records the options it was called with
+    struct StaticProvider {
+        calls: Mutex<Vec<(String, SearchOptions)>>,
+    }
+
+    #[async_trait]
+    impl SearchProvider for StaticProvider {
+        fn name(&self) -> &str {
+            "static"
+        }
+
+        async fn search(
+            &self,
+            query: &str,
+            options: &SearchOptions,
+        ) -> Result<SearchResponse, MiddlewareError> {
+            self.calls.lock().unwrap().push((query.to_string(), options.clone()));
+            Ok(SearchResponse {
+                answer: Some("Rust is a systems language.".to_string()),
+                results: vec![SearchResult {
+                    title: "Rust".to_string(),
+                    url: "https://www.rust-lang.org".to_string(),
+                    content: "A language empowering everyone.".to_string(),
+                    score: 0.9,
+                    raw_content: None,
+                }],
+            })
+        }
+    }
+
+    fn runtime() -> ToolRuntime {
+        ToolRuntime::new(AgentState::new(), Arc::new(MemoryBackend::new()))
+    }
+
+    #[tokio::test]
+    async fn test_web_search_tool_formats_results() {
+        let tool = WebSearchTool::new(StaticProvider { calls: Mutex::new(Vec::new()) });
+
+        let result = tool
+            .execute(serde_json::json!({"query": "rust", "max_results": 50}), &runtime())
+            .await
+            .unwrap();
+
+        assert!(result.message.starts_with("## Search Results for: \"rust\""));
+        assert!(result.message.contains("### AI Summary\nRust is a systems language."));
+        assert!(result.message.contains("Found 1 results:"));
+        assert!(result.message.contains("### [Rust](https://www.rust-lang.org)"));
+        assert!(result.message.contains("**Relevance:** 90%"));
+
+        // max_results is clamped before reaching the provider
+        let calls = tool.provider().calls.lock().unwrap();
+        assert_eq!(calls[0].1.max_results, MAX_RESULTS);
+    }
+
+    #[test]
+    fn test_web_search_tool_definition_matches_tavily_schema() {
+        let tool = WebSearchTool::new(StaticProvider { calls: Mutex::new(Vec::new()) })
+            .with_name("tavily_search");
+        let def = tool.definition();
+
+        assert_eq!(def.name, "tavily_search");
+        assert_eq!(
+            def.parameters,
+            crate::tools::TavilySearchTool::new("key").definition().parameters
+        );
+    }
+
+    #[tokio::test]
+    async fn test_web_search_rejects_too_many_domains() {
+        let tool = WebSearchTool::new(StaticProvider { calls: Mutex::new(Vec::new()) });
+        let domains: Vec<String> = (0..=MAX_DOMAIN_FILTERS).map(|i| format!("site{}.com", i)).collect();
+
+        let err = tool
+            .execute(serde_json::json!({"query": "rust", "exclude_domains": domains}), &runtime())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exclude_domains"));
+        assert!(tool.provider().calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_web_search_rejects_long_query() {
+        let tool = WebSearchTool::new(StaticProvider { calls: Mutex::new(Vec::new()) });
+        let query = "q".repeat(MAX_QUERY_LENGTH + 1);
+
+        let result = tool.execute(serde_json::json!({"query": query}), &runtime()).await;
+        assert!(result.is_err());
+        assert!(tool.provider().calls.lock().unwrap().is_empty());
+    }
+}
@@ -4,7 +4,8 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::error::MiddlewareError;
+use crate::backends::Backend;
+use crate::error::{BackendError, MiddlewareError};
 use crate::middleware::{StateUpdate, Tool, ToolDefinition, ToolResult};
 use crate::runtime::ToolRuntime;
 use crate::state::FileData;
@@ -16,6 +17,90 @@ pub struct WriteFileTool;
 struct WriteFileArgs {
     file_path: String,
     content: String,
+    /// 기존 파일 덮어쓰기 허용
+    #[serde(default)]
+    overwrite: bool,
+    /// 기존 파일 끝에 추가 (없으면 생성)
+    #[serde(default)]
+    append: bool,
+}
+
+/// 파일 내용을 현재 내용 기반으로 다시 씀 (덮어쓰기/추가용)
+///
+/// `Backend::write`는 기존 파일을 거부하므로 기존 파일은 트랜잭션으로 교체합니다.
+/// 트랜잭션을 지원하지 않는 백엔드에서는 [`replace_content`]로 전체 내용을 한 번에
+/// 교체합니다. 바이너리 파일은 텍스트로 읽을 수 없으므로 거부됩니다.
+/// `rewrite`는 현재 내용(파일이 없으면 `None`)을 받아 새 내용을 반환합니다.
+///
+/// Returns: 체크포인트 백엔드는 `{path: FileData}` 상태 업데이트
+pub(crate) async fn rewrite_file(
+    backend: &dyn Backend,
+    path: &str,
+    rewrite: impl FnOnce(Option<String>) -> String + Send,
+) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+    if let Some(mut tx) = backend.transaction() {
+        let existing = match tx.read_plain(path).await {
+            Ok(content) => Some(content),
+            Err(BackendError::FileNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        tx.write(path, &rewrite(existing)).await?;
+        return tx.commit().await;
+    }
+
+    if backend.exists(path).await? {
+        let existing = backend.read_plain(path).await?;
+        let updated = rewrite(Some(existing.clone()));
+        return replace_content(backend, path, &existing, &updated).await;
+    }
+
+    let result = backend.write(path, &rewrite(None)).await?;
+    match result.error {
+        Some(error) => Err(BackendError::Io(error)),
+        None => Ok(result.files_update),
+    }
+}
+
+/// 기존 파일의 전체 내용을 한 번의 `edit`로 교체 (트랜잭션이 없는 백엔드용)
+///
+/// 파일을 삭제했다가 다시 쓰지 않으므로 실패해도 기존 내용이 그대로 남습니다.
+/// `existing`은 `read_plain`으로 읽은 현재 내용이어야 하며, 바이너리 파일은
+/// 백엔드의 `edit`가 거부합니다.
+pub(crate) async fn replace_content(
+    backend: &dyn Backend,
+    path: &str,
+    existing: &str,
+    updated: &str,
+) -> Result<Option<HashMap<String, FileData>>, BackendError> {
+    if existing == updated {
+        return Ok(None);
+    }
+    // 빈 old_string으로는 전체 내용을 가리킬 수 없음
+    if existing.is_empty() {
+        return Err(BackendError::Unsupported(format!(
+            "rewriting empty file {} requires a backend with transaction support",
+            path
+        )));
+    }
+
+    let result = backend.edit(path, existing, updated, false).await?;
+    match result.error {
+        Some(error) => Err(BackendError::Io(error)),
+        None => Ok(result.files_update),
+    }
+}
+
+/// 기존 내용 끝에 새 내용을 추가
+///
+/// 줄 단위로 저장하는 백엔드(`MemoryBackend`)는 마지막 줄바꿈을 돌려주지 않으므로,
+/// 기존 내용이 줄바꿈으로 끝나지 않으면 줄바꿈을 넣어 줄이 붙지 않게 합니다.
+fn append_content(existing: Option<String>, content: &str) -> String {
+    match existing {
+        Some(existing) if !existing.is_empty() && !existing.ends_with('\n') => {
+            format!("{}\n{}", existing, content)
+        }
+        existing => format!("{}{}", existing.unwrap_or_default(), content),
+    }
 }
 
 #[async_trait]
@@ -23,7 +108,8 @@ impl Tool for WriteFileTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "write_file".to_string(),
-            description: "Write content to a file, creating it if it doesn't exist.".to_string(),
+            description: "Write content to a new file. Writing to an existing file fails unless \
+                `overwrite` (replace its content) or `append` (add to the end) is set.".to_string(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -34,10 +120,21 @@ impl Tool for WriteFileTool {
                     "content": {
                         "type": "string",
                         "description": "The content to write to the file"
+                    },
+                    "overwrite": {
+                        "type": "boolean",
+                        "description": "Replace the file if it already exists (default: false)",
+                        "default": false
+                    },
+                    "append": {
+                        "type": "boolean",
+                        "description": "Append to the end of the file on a new line, creating it if needed (default: false)",
+                        "default": false
                     }
                 },
                 "required": ["file_path", "content"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -49,27 +146,52 @@ impl Tool for WriteFileTool {
         let args: WriteFileArgs = serde_json::from_value(args)
             .map_err(|e| MiddlewareError::ToolExecution(format!("Invalid arguments: {}", e)))?;
 
-        let result = runtime.backend()
-            .write(&args.file_path, &args.content)
-            .await
+        if args.overwrite && args.append {
+            return Err(MiddlewareError::ToolExecution(
+                "Invalid arguments: overwrite and append cannot both be set".to_string()
+            ));
+        }
+
+        let backend = runtime.backend();
+        let exists = backend.exists(&args.file_path).await
             .map_err(MiddlewareError::Backend)?;
 
-        if result.is_ok() {
-            let mut tool_result =
-                ToolResult::new(format!("Successfully wrote to {}", args.file_path));
-            if let Some(files_update) = result.files_update {
-                let updates: HashMap<String, Option<FileData>> = files_update
-                    .into_iter()
-                    .map(|(path, data)| (path, Some(data)))
-                    .collect();
-                tool_result = tool_result.with_update(StateUpdate::UpdateFiles(updates));
+        let (files_update, message) = if !exists {
+            let result = backend.write(&args.file_path, &args.content).await
+                .map_err(MiddlewareError::Backend)?;
+            if !result.is_ok() {
+                return Err(MiddlewareError::ToolExecution(
+                    result.error.unwrap_or_else(|| "Unknown error".to_string())
+                ));
             }
-            Ok(tool_result)
+            (result.files_update, format!("Successfully wrote to {}", args.file_path))
+        } else if args.append {
+            let content = args.content.as_str();
+            let update = rewrite_file(backend.as_ref(), &args.file_path, |existing| {
+                append_content(existing, content)
+            })
+            .await
+            .map_err(MiddlewareError::Backend)?;
+            (update, format!("Successfully appended to {}", args.file_path))
+        } else if args.overwrite {
+            let content = args.content.clone();
+            let update = rewrite_file(backend.as_ref(), &args.file_path, |_| content)
+                .await
+                .map_err(MiddlewareError::Backend)?;
+            (update, format!("Successfully overwrote {}", args.file_path))
         } else {
-            Err(MiddlewareError::ToolExecution(
-                result.error.unwrap_or_else(|| "Unknown error".to_string())
-            ))
+            return Err(MiddlewareError::FileExists(args.file_path));
+        };
+
+        let mut tool_result = ToolResult::new(message);
+        if let Some(files_update) = files_update {
+            let updates: HashMap<String, Option<FileData>> = files_update
+                .into_iter()
+                .map(|(path, data)| (path, Some(data)))
+                .collect();
+            tool_result = tool_result.with_update(StateUpdate::UpdateFiles(updates));
         }
+        Ok(tool_result)
     }
 }
 
@@ -103,4 +225,113 @@ mod tests {
             other => panic!("Unexpected update: {:?}", other),
         }
     }
+
+    #[tokio::test]
+    async fn test_write_file_refuses_existing_file() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/notes.md", "original").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let err = WriteFileTool
+            .execute(json!({"file_path": "/notes.md", "content": "clobbered"}), &runtime)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, MiddlewareError::FileExists(ref path) if path == "/notes.md"));
+        assert_eq!(backend.read_plain("/notes.md").await.unwrap(), "original");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_overwrite() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/notes.md", "original").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({"file_path": "/notes.md", "content": "replacement", "overwrite": true});
+        let result = WriteFileTool.execute(args, &runtime).await.unwrap();
+
+        assert!(result.message.contains("overwrote"));
+        assert_eq!(backend.read_plain("/notes.md").await.unwrap(), "replacement");
+        match &result.updates[0] {
+            StateUpdate::UpdateFiles(files) => {
+                let file = files.get("/notes.md").and_then(|v| v.as_ref()).unwrap();
+                assert_eq!(file.as_string(), "replacement");
+            }
+            other => panic!("Unexpected update: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_file_append() {
+        let backend = Arc::new(MemoryBackend::new());
+        backend.write("/log.md", "- first\n").await.unwrap();
+        let runtime = ToolRuntime::new(AgentState::new(), backend.clone());
+
+        let args = json!({"file_path": "/log.md", "content": "- second\n", "append": true});
+        WriteFileTool.execute(args, &runtime).await.unwrap();
+        let args = json!({"file_path": "/log.md", "content": "- third", "append": true});
+        WriteFileTool.execute(args, &runtime).await.unwrap();
+        // MemoryBackend stores lines, so the final newline is not returned
+        assert_eq!(backend.read_plain("/log.md").await.unwrap(), "- first\n- second\n- third");
+
+        // Appending to a missing file creates it
+        let args = json!({"file_path": "/new.md", "content": "fresh", "append": true});
+        WriteFileTool.execute(args, &runtime).await.unwrap();
+        assert_eq!(backend.read_plain("/new.md").await.unwrap(), "fresh");
+
+        let args = json!({"file_path": "/log.md", "content": "x", "append": true, "overwrite": true});
+        assert!(WriteFileTool.execute(args, &runtime).await.is_err());
+    }
+
+    #[test]
+    fn test_append_content_keeps_lines_apart() {
+        assert_eq!(append_content(None, "a\n"), "a\n");
+        assert_eq!(append_content(Some(String::new()), "a"), "a");
+        assert_eq!(append_content(Some("a\n".to_string()), "b"), "a\nb");
+        assert_eq!(append_content(Some("a".to_string()), "b"), "a\nb");
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_without_transaction_edits_in_place() {
+        // QuotaBackend does not support transactions
+        let inner = Arc::new(MemoryBackend::new());
+        let backend = crate::backends::QuotaBackend::new(inner.clone(), 1 << 20, None);
+        backend.write("/notes.md", "keep me").await.unwrap();
+
+        rewrite_file(&backend, "/notes.md", |existing| append_content(existing, "and me"))
+            .await
+            .unwrap();
+        assert_eq!(inner.read_plain("/notes.md").await.unwrap(), "keep me\nand me");
+
+        rewrite_file(&backend, "/notes.md", |_| "replacement".to_string())
+            .await
+            .unwrap();
+        assert_eq!(inner.read_plain("/notes.md").await.unwrap(), "replacement");
+
+        // New files need no rewrite and are still written
+        rewrite_file(&backend, "/new.md", |existing| append_content(existing, "fresh"))
+            .await
+            .unwrap();
+        assert_eq!(backend.read_plain("/new.md").await.unwrap(), "fresh");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_rejects_binary_target() {
+        let bytes: Vec<u8> = vec![0x89, 0x50, 0x4e, 0x47, 0x00, 0xff];
+        let memory = Arc::new(MemoryBackend::new());
+        memory.write_bytes("/image.png", &bytes).await.unwrap();
+        let quota = Arc::new(crate::backends::QuotaBackend::new(memory.clone(), 1 << 20, None));
+
+        // With (MemoryBackend) and without (QuotaBackend) transaction support
+        let backends: [Arc<dyn Backend>; 2] = [memory.clone(), quota];
+        for backend in backends {
+            let runtime = ToolRuntime::new(AgentState::new(), backend);
+            for mode in ["append", "overwrite"] {
+                let args = json!({"file_path": "/image.png", "content": "text", mode: true});
+                assert!(WriteFileTool.execute(args, &runtime).await.is_err(), "{} succeeded", mode);
+            }
+        }
+
+        assert_eq!(memory.read_bytes("/image.png").await.unwrap(), bytes);
+    }
 }
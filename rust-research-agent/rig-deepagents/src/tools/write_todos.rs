@@ -1,5 +1,7 @@
 //! write_todos 도구 구현
 
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
@@ -13,9 +15,13 @@ pub struct WriteTodosTool;
 
 #[derive(Debug, Deserialize)]
 struct TodoItem {
+    #[serde(default)]
+    id: Option<String>,
     content: String,
     #[serde(default)]
     status: String,
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +43,10 @@ impl Tool for WriteTodosTool {
                         "items": {
                             "type": "object",
                             "properties": {
+                                "id": {
+                                    "type": "string",
+                                    "description": "Identifier other items can depend on"
+                                },
                                 "content": {
                                     "type": "string",
                                     "description": "The todo item content"
@@ -45,6 +55,11 @@ impl Tool for WriteTodosTool {
                                     "type": "string",
                                     "enum": ["pending", "in_progress", "completed"],
                                     "default": "pending"
+                                },
+                                "depends_on": {
+                                    "type": "array",
+                                    "items": {"type": "string"},
+                                    "description": "Ids of items that must be completed first"
                                 }
                             },
                             "required": ["content"]
@@ -54,6 +69,7 @@ impl Tool for WriteTodosTool {
                 },
                 "required": ["todos"]
             }),
+            examples: Vec::new(),
         }
     }
 
@@ -72,10 +88,16 @@ impl Tool for WriteTodosTool {
                     "completed" => TodoStatus::Completed,
                     _ => TodoStatus::Pending,
                 };
-                Todo::with_status(&t.content, status)
+                Todo {
+                    id: t.id.clone(),
+                    depends_on: t.depends_on.clone(),
+                    ..Todo::with_status(&t.content, status)
+                }
             })
             .collect();
 
+        validate_dependencies(&todos)?;
+
         Ok(
             ToolResult::new(format!("Updated {} todo items", todos.len()))
                 .with_update(StateUpdate::SetTodos(todos)),
@@ -83,6 +105,65 @@ impl Tool for WriteTodosTool {
     }
 }
 
+/// 의존성 검증: 중복/미정의 id와 순환 의존성을 거부
+fn validate_dependencies(todos: &[Todo]) -> Result<(), MiddlewareError> {
+    let mut ids = HashSet::new();
+    for id in todos.iter().filter_map(|t| t.id.as_deref()) {
+        if !ids.insert(id) {
+            return Err(MiddlewareError::ToolExecution(format!("Duplicate todo id '{}'", id)));
+        }
+    }
+
+    let mut dependencies: HashMap<&str, &[String]> = HashMap::new();
+    for todo in todos {
+        if let Some(missing) = todo.depends_on.iter().find(|dep| !ids.contains(dep.as_str())) {
+            return Err(MiddlewareError::ToolExecution(format!(
+                "Todo '{}' depends on unknown id '{}'",
+                todo.content, missing
+            )));
+        }
+        if let Some(id) = todo.id.as_deref() {
+            dependencies.insert(id, &todo.depends_on);
+        }
+    }
+
+    // 진입 차수가 0인 노드부터 제거 (Kahn) - 남는 노드가 있으면 순환
+    let mut pending: HashMap<&str, usize> = dependencies
+        .iter()
+        .map(|(&id, deps)| (id, deps.len()))
+        .collect();
+    let mut queue: Vec<&str> = pending
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(&id, _)| id)
+        .collect();
+
+    while let Some(done) = queue.pop() {
+        pending.remove(done);
+        for (&id, deps) in &dependencies {
+            if deps.iter().any(|dep| dep == done) {
+                if let Some(count) = pending.get_mut(id) {
+                    *count -= 1;
+                    if *count == 0 {
+                        queue.push(id);
+                    }
+                }
+            }
+        }
+    }
+
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        let mut cycle: Vec<&str> = pending.into_keys().collect();
+        cycle.sort();
+        Err(MiddlewareError::ToolExecution(format!(
+            "Cyclic todo dependencies among: {}",
+            cycle.join(", ")
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,4 +199,53 @@ mod tests {
             other => panic!("Unexpected update: {:?}", other),
         }
     }
+
+    #[tokio::test]
+    async fn test_write_todos_accepts_dependency_dag() {
+        let tool = WriteTodosTool;
+        let backend = Arc::new(MemoryBackend::new());
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = json!({
+            "todos": [
+                {"id": "search", "content": "Search", "status": "completed"},
+                {"id": "read", "content": "Read", "depends_on": ["search"]},
+                {"id": "compare", "content": "Compare", "depends_on": ["search", "read"]},
+                {"id": "write", "content": "Write", "depends_on": ["compare"]}
+            ]
+        });
+
+        let result = tool.execute(args, &runtime).await.unwrap();
+        match &result.updates[0] {
+            StateUpdate::SetTodos(todos) => {
+                assert_eq!(todos[2].depends_on, vec!["search", "read"]);
+                let ready: Vec<&str> = Todo::ready(todos).iter().map(|t| t.content.as_str()).collect();
+                assert_eq!(ready, vec!["Read"]);
+            }
+            other => panic!("Unexpected update: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_todos_rejects_cycle() {
+        let tool = WriteTodosTool;
+        let backend = Arc::new(MemoryBackend::new());
+        let runtime = ToolRuntime::new(AgentState::new(), backend);
+
+        let args = json!({
+            "todos": [
+                {"id": "a", "content": "A", "depends_on": ["c"]},
+                {"id": "b", "content": "B", "depends_on": ["a"]},
+                {"id": "c", "content": "C", "depends_on": ["b"]},
+                {"id": "d", "content": "D"}
+            ]
+        });
+
+        let err = tool.execute(args, &runtime).await.unwrap_err();
+        assert!(matches!(err, MiddlewareError::ToolExecution(_)));
+        assert!(err.to_string().contains("a, b, c"));
+
+        let self_loop = json!({"todos": [{"id": "a", "content": "A", "depends_on": ["a"]}]});
+        assert!(tool.execute(self_loop, &runtime).await.is_err());
+    }
 }
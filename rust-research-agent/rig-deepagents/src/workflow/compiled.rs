@@ -50,7 +50,8 @@ use crate::middleware::subagent::{SubAgentExecutorFactory, SubAgentRegistry};
 use crate::workflow::graph::{BuiltWorkflowGraph, END};
 use crate::workflow::node::NodeKind;
 use crate::workflow::vertices::{
-    AgentVertex, FanInVertex, FanOutVertex, RouterVertex, SubAgentVertex, ToolVertex,
+    AgentVertex, FanInVertex, FanOutVertex, RouterVertex, SubAgentVertex, SubWorkflowRunner,
+    SubWorkflowVertex, ToolVertex,
 };
 use crate::runtime::ToolRuntime;
 use crate::state::AgentState;
@@ -464,6 +465,7 @@ where
         executor_factory: Option<Arc<dyn SubAgentExecutorFactory>>,
         backend: Option<Arc<dyn Backend>>,
     ) -> Result<Self, WorkflowCompileError> {
+        let seed = config.seed;
         let mut runtime = PregelRuntime::with_config(config);
         let mut node_kinds = HashMap::new();
 
@@ -478,6 +480,8 @@ where
                 subagent_registry.as_ref(),
                 executor_factory.as_ref(),
                 backend.as_ref(),
+                graph.sub_workflows.get(node_id),
+                seed,
             )?;
             runtime.add_vertex(vertex);
             node_kinds.insert(VertexId::new(node_id), kind.clone());
@@ -491,6 +495,7 @@ where
                 }
             }
         }
+        Self::add_conditional_edges(&mut runtime, &graph);
 
         // Set entry point
         runtime.set_entry(graph.entry_point.as_str());
@@ -520,6 +525,7 @@ where
         workflow_id: impl Into<String>,
     ) -> Result<Self, WorkflowCompileError> {
         let workflow_id = workflow_id.into();
+        let seed = config.seed;
         let mut runtime = PregelRuntime::with_config(config);
         let mut node_kinds = HashMap::new();
 
@@ -537,6 +543,8 @@ where
                 subagent_registry.as_ref(),
                 executor_factory.as_ref(),
                 backend.as_ref(),
+                graph.sub_workflows.get(node_id),
+                seed,
             )?;
             runtime.add_vertex(vertex);
             node_kinds.insert(VertexId::new(node_id), kind.clone());
@@ -550,6 +558,7 @@ where
                 }
             }
         }
+        Self::add_conditional_edges(&mut runtime, &graph);
 
         // Set entry point
         runtime.set_entry(graph.entry_point.as_str());
@@ -564,6 +573,22 @@ where
         })
     }
 
+    /// Add predicate-routed edges (END targets activate nothing)
+    fn add_conditional_edges(
+        runtime: &mut PregelRuntime<S, WorkflowMessage>,
+        graph: &BuiltWorkflowGraph<S>,
+    ) {
+        let target = |to: &str| (to != END).then(|| VertexId::new(to));
+        for edge in &graph.conditional_edges {
+            runtime.add_conditional_edge(
+                edge.from.as_str(),
+                Arc::clone(&edge.predicate),
+                target(&edge.to_if_true),
+                target(&edge.to_if_false),
+            );
+        }
+    }
+
     /// Create a vertex from a NodeKind
     #[allow(clippy::too_many_arguments)]
     fn create_vertex(
@@ -575,6 +600,8 @@ where
         subagent_registry: Option<&Arc<SubAgentRegistry>>,
         executor_factory: Option<&Arc<dyn SubAgentExecutorFactory>>,
         backend: Option<&Arc<dyn Backend>>,
+        sub_workflow: Option<&Arc<dyn SubWorkflowRunner<S>>>,
+        seed: Option<u64>,
     ) -> Result<BoxedVertex<S, WorkflowMessage>, WorkflowCompileError> {
         match kind {
             NodeKind::Agent(config) => {
@@ -647,7 +674,10 @@ where
             }
             NodeKind::Router(config) => {
                 // RouterVertex can work with or without LLM (for StateField strategy)
-                Ok(Arc::new(RouterVertex::<S>::new(node_id, config, llm)))
+                Ok(Arc::new(match seed {
+                    Some(seed) => RouterVertex::<S>::with_seed(node_id, config, llm, seed),
+                    None => RouterVertex::<S>::new(node_id, config, llm),
+                }))
             }
             NodeKind::SubAgent(config) => {
                 // Create SubAgentVertex if all required resources are available
@@ -670,6 +700,20 @@ where
                     }
                 }
             }
+            NodeKind::SubWorkflow(config) => match sub_workflow {
+                Some(runner) => Ok(Arc::new(SubWorkflowVertex::<S>::new(
+                    node_id,
+                    config,
+                    Arc::clone(runner),
+                ))),
+                None => {
+                    tracing::warn!(
+                        node_id = node_id,
+                        "SubWorkflow node has no registered workflow - using passthrough"
+                    );
+                    Ok(Arc::new(PassthroughVertex::new(node_id)))
+                }
+            },
             NodeKind::FanOut(config) => Ok(Arc::new(FanOutVertex::<S>::new(node_id, config))),
             NodeKind::FanIn(config) => Ok(Arc::new(FanInVertex::<S>::new(node_id, config))),
             NodeKind::Passthrough => Ok(Arc::new(PassthroughVertex::new(node_id))),
@@ -985,6 +1029,157 @@ mod tests {
         assert!(result.supersteps >= 1);
     }
 
+    #[tokio::test]
+    async fn test_run_conditional_edge_workflow() {
+        let build = |approved: bool| {
+            WorkflowGraph::<UnitState>::new()
+                .name("review")
+                .node("review", NodeKind::Passthrough)
+                .node("publish", NodeKind::Passthrough)
+                .node("notify", NodeKind::Passthrough)
+                .entry("review")
+                .conditional_edge("review", Arc::new(move |_: &UnitState| approved), "publish", END)
+                .edge("publish", "notify")
+                .edge("notify", END)
+                .build()
+                .unwrap()
+        };
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+
+        // review -> publish -> notify
+        let mut approved = CompiledWorkflow::compile(build(true), config.clone()).unwrap();
+        let result = approved.run(UnitState).await.unwrap();
+        assert!(result.completed);
+        assert_eq!(result.supersteps, 3);
+
+        // review -> END
+        let mut rejected = CompiledWorkflow::compile(build(false), config).unwrap();
+        let result = rejected.run(UnitState).await.unwrap();
+        assert!(result.completed);
+        assert_eq!(result.supersteps, 1);
+        assert!(rejected.to_mermaid().contains("true"));
+    }
+
+    #[tokio::test]
+    async fn test_run_nested_subworkflow() {
+        use crate::pregel::state::UnitUpdate;
+        use crate::workflow::node::SubWorkflowConfig;
+        use crate::workflow::vertices::SubWorkflow;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+        struct ParentState {
+            query: u32,
+            answer: Option<u32>,
+        }
+
+        #[derive(Clone, Debug)]
+        struct ParentUpdate(Option<u32>);
+
+        impl StateUpdate for ParentUpdate {
+            fn empty() -> Self {
+                ParentUpdate(None)
+            }
+
+            fn is_empty(&self) -> bool {
+                self.0.is_none()
+            }
+        }
+
+        impl WorkflowState for ParentState {
+            type Update = ParentUpdate;
+
+            fn apply_update(&self, update: Self::Update) -> Self {
+                ParentState {
+                    query: self.query,
+                    answer: update.0.or(self.answer),
+                }
+            }
+
+            fn merge_updates(updates: Vec<Self::Update>) -> Self::Update {
+                ParentUpdate(updates.into_iter().find_map(|u| u.0))
+            }
+        }
+
+        #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+        struct InnerState {
+            value: u32,
+        }
+
+        impl WorkflowState for InnerState {
+            type Update = UnitUpdate;
+
+            fn apply_update(&self, _update: Self::Update) -> Self {
+                self.clone()
+            }
+
+            fn merge_updates(_updates: Vec<Self::Update>) -> Self::Update {
+                UnitUpdate
+            }
+        }
+
+        // Inner two-node workflow; the predicate records that it actually ran
+        let inner_checks = Arc::new(AtomicUsize::new(0));
+        let checks = Arc::clone(&inner_checks);
+        let inner = WorkflowGraph::<InnerState>::new()
+            .name("inner")
+            .node("fetch", NodeKind::Passthrough)
+            .node("rank", NodeKind::Passthrough)
+            .entry("fetch")
+            .conditional_edge(
+                "fetch",
+                Arc::new(move |s: &InnerState| {
+                    checks.fetch_add(1, Ordering::SeqCst);
+                    s.value > 0
+                }),
+                "rank",
+                END,
+            )
+            .edge("rank", END)
+            .build()
+            .unwrap();
+        let config = PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven);
+
+        let nested = SubWorkflow::new(
+            inner,
+            config.clone(),
+            |parent: &ParentState| InnerState { value: parent.query },
+            |_parent: &ParentState, inner: InnerState| ParentUpdate(Some(inner.value * 2)),
+        );
+
+        let graph = WorkflowGraph::<ParentState>::new()
+            .name("outer")
+            .node("plan", NodeKind::Passthrough)
+            .sub_workflow(
+                "nested",
+                SubWorkflowConfig {
+                    workflow_name: "inner".into(),
+                    ..Default::default()
+                },
+                Arc::new(nested),
+            )
+            .node("report", NodeKind::Passthrough)
+            .entry("plan")
+            .edge("plan", "nested")
+            .edge("nested", "report")
+            .edge("report", END)
+            .build()
+            .unwrap();
+
+        let mut workflow = CompiledWorkflow::compile(graph, config).unwrap();
+        assert!(workflow.to_mermaid().contains("nested{{nested}}"));
+
+        let result = workflow
+            .run(ParentState { query: 21, answer: None })
+            .await
+            .unwrap();
+
+        assert!(result.completed);
+        assert_eq!(result.supersteps, 3);
+        assert_eq!(result.state.answer, Some(42));
+        assert_eq!(inner_checks.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_run_single_node_workflow() {
         let graph = WorkflowGraph::<UnitState>::new()
@@ -3,13 +3,16 @@
 //! Provides a fluent API for defining nodes, edges, and entry points,
 //! then validates and compiles the graph into a built representation.
 
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use thiserror::Error;
 
-use crate::pregel::WorkflowState;
-use crate::workflow::node::NodeKind;
+use crate::pregel::{EdgePredicate, WorkflowState};
+use crate::workflow::node::{NodeKind, RoutingStrategy, SubWorkflowConfig};
+use crate::workflow::vertices::SubWorkflowRunner;
 
 /// Sentinel target for terminal edges.
 pub const END: &str = "END";
@@ -29,6 +32,27 @@ pub struct GraphEdge {
     pub condition: Option<String>,
 }
 
+/// Predicate-routed two-way edge for a workflow graph.
+///
+/// Either target may be [`END`], which ends that branch without activating a node.
+#[derive(Clone)]
+pub struct GraphConditionalEdge<S> {
+    pub from: String,
+    pub predicate: EdgePredicate<S>,
+    pub to_if_true: String,
+    pub to_if_false: String,
+}
+
+impl<S> std::fmt::Debug for GraphConditionalEdge<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphConditionalEdge")
+            .field("from", &self.from)
+            .field("to_if_true", &self.to_if_true)
+            .field("to_if_false", &self.to_if_false)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Errors that can occur while building a workflow graph.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum WorkflowBuildError {
@@ -36,6 +60,22 @@ pub enum WorkflowBuildError {
     NoEntryPoint,
     #[error("unknown node id: {0}")]
     UnknownNode(String),
+    #[error("edge from '{from}' targets undeclared node '{to}'")]
+    UnknownEdgeTarget { from: String, to: String },
+    #[error("nodes unreachable from the entry point: {}", .0.join(", "))]
+    UnreachableNodes(Vec<String>),
+    #[error("duplicate node id: {0}")]
+    DuplicateNode(String),
+}
+
+/// Namespaced ids of a graph spliced in with [`WorkflowGraph::embed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedGraph {
+    /// The embedded graph's entry point
+    pub entry: String,
+    /// Nodes that ended the embedded graph (edge to [`END`] or no outgoing
+    /// edges), sorted
+    pub exits: Vec<String>,
 }
 
 /// Builder for constructing workflow graphs with fluent API.
@@ -44,6 +84,8 @@ pub struct WorkflowGraph<S: WorkflowState> {
     name: String,
     nodes: HashMap<String, NodeKind>,
     edges: Vec<GraphEdge>,
+    conditional_edges: Vec<GraphConditionalEdge<S>>,
+    sub_workflows: HashMap<String, Arc<dyn SubWorkflowRunner<S>>>,
     entry_point: Option<String>,
     _state: PhantomData<S>,
 }
@@ -54,6 +96,8 @@ impl<S: WorkflowState> Default for WorkflowGraph<S> {
             name: String::new(),
             nodes: HashMap::new(),
             edges: Vec::new(),
+            conditional_edges: Vec::new(),
+            sub_workflows: HashMap::new(),
             entry_point: None,
             _state: PhantomData,
         }
@@ -78,6 +122,32 @@ impl<S: WorkflowState> WorkflowGraph<S> {
         self
     }
 
+    /// Add a node that runs a nested workflow to completion.
+    ///
+    /// Adds a `NodeKind::SubWorkflow` node and registers the runner that
+    /// compiles and executes the inner workflow when the node is activated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let graph = WorkflowGraph::<ParentState>::new()
+    ///     .sub_workflow("search", SubWorkflowConfig::default(), Arc::new(search))
+    ///     .entry("search")
+    ///     .edge("search", END)
+    ///     .build()?;
+    /// ```
+    pub fn sub_workflow(
+        mut self,
+        id: impl Into<String>,
+        config: SubWorkflowConfig,
+        runner: Arc<dyn SubWorkflowRunner<S>>,
+    ) -> Self {
+        let id = id.into();
+        self.nodes.insert(id.clone(), NodeKind::SubWorkflow(config));
+        self.sub_workflows.insert(id, runner);
+        self
+    }
+
     /// Set the entry point node.
     pub fn entry(mut self, id: impl Into<String>) -> Self {
         self.entry_point = Some(id.into());
@@ -107,6 +177,180 @@ impl<S: WorkflowState> WorkflowGraph<S> {
         self
     }
 
+    /// Add a two-way edge chosen by a predicate on the workflow state.
+    ///
+    /// When `from` halts, `predicate` is evaluated against the workflow state
+    /// (after that superstep's updates) and only the chosen target is
+    /// activated. Use [`END`] as a target to stop that branch; the workflow
+    /// terminates once no other node is active. Like plain edges, conditional
+    /// edges are routed in `ExecutionMode::EdgeDriven`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let graph = WorkflowGraph::<ReviewState>::new()
+    ///     .node("review", NodeKind::Passthrough)
+    ///     .node("publish", NodeKind::Passthrough)
+    ///     .entry("review")
+    ///     .conditional_edge("review", Arc::new(|s: &ReviewState| s.approved), "publish", END)
+    ///     .build()?;
+    /// ```
+    pub fn conditional_edge(
+        mut self,
+        from: impl Into<String>,
+        predicate: EdgePredicate<S>,
+        to_if_true: impl Into<String>,
+        to_if_false: impl Into<String>,
+    ) -> Self {
+        self.conditional_edges.push(GraphConditionalEdge {
+            from: from.into(),
+            predicate,
+            to_if_true: to_if_true.into(),
+            to_if_false: to_if_false.into(),
+        });
+        self
+    }
+
+    /// Splice another graph into this one under a namespace.
+    ///
+    /// Every node of `other` is added as `{prefix}/{id}`, together with its
+    /// edges, conditional edges, sub-workflow runners, and the targets its
+    /// routers, fan-outs, and fan-ins refer to. No [`END`] target survives the
+    /// embedding, since finishing the subgraph must not finish this graph:
+    ///
+    /// - unconditional edges to [`END`] are dropped and their source nodes
+    ///   become exits, as do nodes with no outgoing edges;
+    /// - every other way of reaching [`END`] (router branches and defaults,
+    ///   fan-out targets, conditional edges) is redirected to a passthrough
+    ///   node `{prefix}/END`, which is reported as an exit too.
+    ///
+    /// Exits are sorted. `other`'s entry point and name are not carried over.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut graph = WorkflowGraph::<ResearchState>::new().node("start", NodeKind::Passthrough);
+    /// let first = graph.embed("first", research_trio())?;
+    /// let second = graph.embed("second", research_trio())?;
+    ///
+    /// let graph = graph
+    ///     .entry("start")
+    ///     .edge("start", &first.entry)
+    ///     .edge(&first.exits[0], &second.entry)
+    ///     .edge(&second.exits[0], END)
+    ///     .build()?;
+    /// ```
+    pub fn embed(
+        &mut self,
+        prefix: &str,
+        other: WorkflowGraph<S>,
+    ) -> Result<EmbeddedGraph, WorkflowBuildError> {
+        let end_exit = format!("{}/{}", prefix, END);
+        let routes_to_end = Cell::new(false);
+        let rename = |id: &str| {
+            if id == END {
+                routes_to_end.set(true);
+                end_exit.clone()
+            } else {
+                format!("{}/{}", prefix, id)
+            }
+        };
+
+        let entry = other.entry_point.ok_or(WorkflowBuildError::NoEntryPoint)?;
+        if !other.nodes.contains_key(&entry) {
+            return Err(WorkflowBuildError::UnknownNode(entry));
+        }
+        if let Some(id) = other
+            .nodes
+            .keys()
+            .map(|id| rename(id))
+            .chain(std::iter::once(end_exit.clone()))
+            .filter(|id| self.nodes.contains_key(id))
+            .min()
+        {
+            return Err(WorkflowBuildError::DuplicateNode(id));
+        }
+
+        let mut exits: Vec<String> = other
+            .nodes
+            .iter()
+            .filter(|(id, kind)| {
+                let mut plain = other.edges.iter().filter(|e| &e.from == *id).peekable();
+                let is_sink = plain.peek().is_none()
+                    && !matches!(kind, NodeKind::Router(_) | NodeKind::FanOut(_))
+                    && !other.conditional_edges.iter().any(|e| &e.from == *id);
+                is_sink || plain.any(|e| e.to == END && e.condition.is_none())
+            })
+            .map(|(id, _)| rename(id))
+            .collect();
+
+        for (id, mut kind) in other.nodes {
+            match &mut kind {
+                NodeKind::Router(config) => {
+                    for branch in &mut config.branches {
+                        branch.target = rename(&branch.target);
+                    }
+                    if let Some(default) = &mut config.default {
+                        *default = rename(default);
+                    }
+                    if let RoutingStrategy::Weighted { weights } = &mut config.strategy {
+                        for (target, _) in weights {
+                            target.0 = rename(&target.0);
+                        }
+                    }
+                }
+                NodeKind::FanOut(config) => {
+                    for target in &mut config.targets {
+                        *target = rename(target);
+                    }
+                }
+                NodeKind::FanIn(config) => {
+                    for source in &mut config.sources {
+                        *source = rename(source);
+                    }
+                }
+                _ => {}
+            }
+            self.nodes.insert(rename(&id), kind);
+        }
+
+        self.edges.extend(
+            other
+                .edges
+                .into_iter()
+                .filter(|e| e.to != END || e.condition.is_some())
+                .map(|e| GraphEdge {
+                    from: rename(&e.from),
+                    to: rename(&e.to),
+                    condition: e.condition,
+                }),
+        );
+        self.conditional_edges
+            .extend(other.conditional_edges.into_iter().map(|e| GraphConditionalEdge {
+                from: rename(&e.from),
+                predicate: e.predicate,
+                to_if_true: rename(&e.to_if_true),
+                to_if_false: rename(&e.to_if_false),
+            }));
+        self.sub_workflows.extend(
+            other
+                .sub_workflows
+                .into_iter()
+                .map(|(id, runner)| (rename(&id), runner)),
+        );
+
+        if routes_to_end.get() {
+            self.nodes.insert(end_exit.clone(), NodeKind::Passthrough);
+            exits.push(end_exit);
+        }
+        exits.sort();
+
+        Ok(EmbeddedGraph {
+            entry: rename(&entry),
+            exits,
+        })
+    }
+
     /// Validate and build the workflow graph.
     pub fn build(self) -> Result<BuiltWorkflowGraph<S>, WorkflowBuildError> {
         let entry_point = self.entry_point.ok_or(WorkflowBuildError::NoEntryPoint)?;
@@ -121,14 +365,68 @@ impl<S: WorkflowState> WorkflowGraph<S> {
                 return Err(WorkflowBuildError::UnknownNode(edge.from));
             }
             if edge.to != END && !self.nodes.contains_key(&edge.to) {
-                return Err(WorkflowBuildError::UnknownNode(edge.to));
+                return Err(WorkflowBuildError::UnknownEdgeTarget {
+                    from: edge.from,
+                    to: edge.to,
+                });
             }
             edges.entry(edge.from).or_default().push(edge.to);
         }
 
+        for edge in &self.conditional_edges {
+            if !self.nodes.contains_key(&edge.from) {
+                return Err(WorkflowBuildError::UnknownNode(edge.from.clone()));
+            }
+            for to in [&edge.to_if_true, &edge.to_if_false] {
+                if to != END && !self.nodes.contains_key(to) {
+                    return Err(WorkflowBuildError::UnknownEdgeTarget {
+                        from: edge.from.clone(),
+                        to: to.clone(),
+                    });
+                }
+            }
+        }
+
+        // Router branches and fan-out targets are edges declared inside the node
+        for (from, kind) in &self.nodes {
+            let targets: Vec<&String> = match kind {
+                NodeKind::Router(config) => {
+                    let mut targets: Vec<&String> = config
+                        .branches
+                        .iter()
+                        .map(|b| &b.target)
+                        .chain(config.default.as_ref())
+                        .collect();
+                    if let RoutingStrategy::Weighted { weights } = &config.strategy {
+                        targets.extend(weights.iter().map(|(target, _)| &target.0));
+                    }
+                    targets
+                }
+                NodeKind::FanOut(config) => config.targets.iter().collect(),
+                _ => continue,
+            };
+            if let Some(to) = targets
+                .into_iter()
+                .find(|to| *to != END && !self.nodes.contains_key(*to))
+            {
+                return Err(WorkflowBuildError::UnknownEdgeTarget {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        let unreachable =
+            unreachable_nodes(&self.nodes, &edges, &self.conditional_edges, &entry_point);
+        if !unreachable.is_empty() {
+            return Err(WorkflowBuildError::UnreachableNodes(unreachable));
+        }
+
         Ok(BuiltWorkflowGraph {
             nodes: self.nodes,
             edges,
+            conditional_edges: self.conditional_edges,
+            sub_workflows: self.sub_workflows,
             entry_point,
             name: self.name,
             _state: PhantomData,
@@ -136,11 +434,66 @@ impl<S: WorkflowState> WorkflowGraph<S> {
     }
 }
 
+/// Nodes that can never be activated starting from the entry point.
+///
+/// Follows plain and conditional edges plus the targets a node routes to
+/// itself (router branches and defaults, fan-out targets). Returned sorted.
+fn unreachable_nodes<S: WorkflowState>(
+    nodes: &HashMap<String, NodeKind>,
+    edges: &HashMap<String, Vec<String>>,
+    conditional_edges: &[GraphConditionalEdge<S>],
+    entry_point: &str,
+) -> Vec<String> {
+    let mut reached: HashSet<&str> = HashSet::from([entry_point]);
+    let mut stack = vec![entry_point];
+
+    while let Some(id) = stack.pop() {
+        let mut successors: Vec<&str> = edges
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        for edge in conditional_edges.iter().filter(|e| e.from == id) {
+            successors.extend([edge.to_if_true.as_str(), edge.to_if_false.as_str()]);
+        }
+        match nodes.get(id) {
+            Some(NodeKind::Router(config)) => {
+                successors.extend(config.branches.iter().map(|b| b.target.as_str()));
+                successors.extend(config.default.as_deref());
+                if let RoutingStrategy::Weighted { weights } = &config.strategy {
+                    successors.extend(weights.iter().map(|(target, _)| target.as_str()));
+                }
+            }
+            Some(NodeKind::FanOut(config)) => {
+                successors.extend(config.targets.iter().map(String::as_str));
+            }
+            _ => {}
+        }
+
+        for next in successors {
+            if next != END && reached.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    let mut unreachable: Vec<String> = nodes
+        .keys()
+        .filter(|id| !reached.contains(id.as_str()))
+        .cloned()
+        .collect();
+    unreachable.sort();
+    unreachable
+}
+
 /// Built workflow graph representation.
 #[derive(Debug, Clone)]
 pub struct BuiltWorkflowGraph<S: WorkflowState> {
     pub nodes: HashMap<String, NodeKind>,
     pub edges: HashMap<String, Vec<String>>,
+    pub conditional_edges: Vec<GraphConditionalEdge<S>>,
+    pub sub_workflows: HashMap<String, Arc<dyn SubWorkflowRunner<S>>>,
     pub entry_point: String,
     pub name: String,
     _state: PhantomData<S>,
@@ -188,9 +541,64 @@ mod tests {
             .edge("start", "missing")
             .build();
 
+        let err = result.unwrap_err();
+        assert_eq!(
+            err,
+            WorkflowBuildError::UnknownEdgeTarget {
+                from: "start".to_string(),
+                to: "missing".to_string(),
+            }
+        );
+        assert_eq!(err.to_string(), "edge from 'start' targets undeclared node 'missing'");
+    }
+
+    #[test]
+    fn test_workflow_builder_invalid_router_target() {
+        use crate::workflow::node::RouterNodeConfig;
+
+        let router = RouterNodeConfig {
+            default: Some("typo".to_string()),
+            ..Default::default()
+        };
+        let result = WorkflowGraph::<UnitState>::new()
+            .node("route", NodeKind::Router(router))
+            .entry("route")
+            .build();
+
         assert_eq!(
             result.unwrap_err(),
-            WorkflowBuildError::UnknownNode("missing".to_string())
+            WorkflowBuildError::UnknownEdgeTarget {
+                from: "route".to_string(),
+                to: "typo".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_workflow_conditional_edge_validation() {
+        let predicate: EdgePredicate<UnitState> = std::sync::Arc::new(|_: &UnitState| true);
+
+        let workflow = WorkflowGraph::<UnitState>::new()
+            .node("review", NodeKind::Passthrough)
+            .node("publish", NodeKind::Passthrough)
+            .entry("review")
+            .conditional_edge("review", predicate.clone(), "publish", END)
+            .build()
+            .unwrap();
+        assert_eq!(workflow.conditional_edges.len(), 1);
+        assert_eq!(workflow.conditional_edges[0].to_if_false, END);
+
+        let result = WorkflowGraph::<UnitState>::new()
+            .node("review", NodeKind::Passthrough)
+            .entry("review")
+            .conditional_edge("review", predicate, END, "missing")
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            WorkflowBuildError::UnknownEdgeTarget {
+                from: "review".to_string(),
+                to: "missing".to_string(),
+            }
         );
     }
 
@@ -211,6 +619,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_workflow_reachable_through_routers_and_fan_out() {
+        use crate::workflow::node::{Branch, BranchCondition, FanOutNodeConfig, RouterNodeConfig};
+
+        let router = RouterNodeConfig {
+            branches: vec![Branch {
+                target: "fan".to_string(),
+                condition: BranchCondition::IsTruthy,
+            }],
+            default: Some("fallback".to_string()),
+            ..Default::default()
+        };
+        let fan_out = FanOutNodeConfig {
+            targets: vec!["a".to_string(), "b".to_string()],
+            ..Default::default()
+        };
+
+        let result = WorkflowGraph::<UnitState>::new()
+            .node("start", NodeKind::Passthrough)
+            .node("route", NodeKind::Router(router))
+            .node("fan", NodeKind::FanOut(fan_out))
+            .node("a", NodeKind::Passthrough)
+            .node("b", NodeKind::Passthrough)
+            .node("fallback", NodeKind::Passthrough)
+            .node("review", NodeKind::Passthrough)
+            .entry("start")
+            .edge("start", "route")
+            .conditional_edge("fallback", Arc::new(|_: &UnitState| true), "review", END)
+            .build();
+
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_workflow_island_node_is_unreachable() {
+        let err = WorkflowGraph::<UnitState>::new()
+            .node("start", NodeKind::Passthrough)
+            .node("next", NodeKind::Passthrough)
+            .node("island", NodeKind::Passthrough)
+            .node("orphan", NodeKind::Passthrough)
+            .entry("start")
+            .edge("start", "next")
+            .edge("next", END)
+            .edge("island", "orphan")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            WorkflowBuildError::UnreachableNodes(vec!["island".to_string(), "orphan".to_string()])
+        );
+        assert_eq!(err.to_string(), "nodes unreachable from the entry point: island, orphan");
+    }
+
+    /// plan → search → synthesize, ending the graph
+    fn research_trio() -> WorkflowGraph<UnitState> {
+        WorkflowGraph::new()
+            .node("plan", NodeKind::Passthrough)
+            .node("search", NodeKind::Passthrough)
+            .node("synthesize", NodeKind::Passthrough)
+            .entry("plan")
+            .edge("plan", "search")
+            .edge("search", "synthesize")
+            .edge("synthesize", END)
+    }
+
+    #[test]
+    fn test_workflow_embed_twice_without_collisions() {
+        let mut graph = WorkflowGraph::<UnitState>::new().node("start", NodeKind::Passthrough);
+        let first = graph.embed("first", research_trio()).unwrap();
+        let second = graph.embed("second", research_trio()).unwrap();
+
+        assert_eq!(first.entry, "first/plan");
+        assert_eq!(first.exits, vec!["first/synthesize".to_string()]);
+        assert_eq!(second.entry, "second/plan");
+
+        let workflow = graph
+            .entry("start")
+            .edge("start", &first.entry)
+            .edge(&first.exits[0], &second.entry)
+            .edge(&second.exits[0], END)
+            .build()
+            .unwrap();
+
+        assert_eq!(workflow.nodes.len(), 7);
+        assert_eq!(
+            workflow.edges.get("first/plan"),
+            Some(&vec!["first/search".to_string()])
+        );
+        assert_eq!(
+            workflow.edges.get("first/synthesize"),
+            Some(&vec!["second/plan".to_string()])
+        );
+        assert_eq!(
+            workflow.edges.get("second/synthesize"),
+            Some(&vec![END.to_string()])
+        );
+    }
+
+    #[test]
+    fn test_workflow_embed_renames_node_targets() {
+        use crate::workflow::node::{Branch, BranchCondition, RouterNodeConfig};
+
+        let router = RouterNodeConfig {
+            branches: vec![Branch {
+                target: "deep".to_string(),
+                condition: BranchCondition::IsTruthy,
+            }],
+            default: Some(END.to_string()),
+            ..Default::default()
+        };
+        let inner = WorkflowGraph::<UnitState>::new()
+            .node("route", NodeKind::Router(router))
+            .node("deep", NodeKind::Passthrough)
+            .entry("route");
+
+        let mut graph = WorkflowGraph::<UnitState>::new();
+        let embedded = graph.embed("sub", inner).unwrap();
+        assert_eq!(embedded.exits, vec!["sub/END".to_string(), "sub/deep".to_string()]);
+
+        let workflow = graph.entry(&embedded.entry).build().unwrap();
+        let Some(NodeKind::Router(config)) = workflow.nodes.get("sub/route") else {
+            panic!("router not embedded");
+        };
+        assert_eq!(config.branches[0].target, "sub/deep");
+        assert_eq!(config.default.as_deref(), Some("sub/END"));
+        assert!(matches!(workflow.nodes.get("sub/END"), Some(NodeKind::Passthrough)));
+    }
+
+    #[test]
+    fn test_workflow_embed_redirects_conditional_end_edges() {
+        let inner = WorkflowGraph::<UnitState>::new()
+            .node("check", NodeKind::Passthrough)
+            .node("retry", NodeKind::Passthrough)
+            .entry("check")
+            .conditional_edge("check", Arc::new(|_: &UnitState| true), END, "retry")
+            .edge("retry", END);
+
+        let mut graph = WorkflowGraph::<UnitState>::new();
+        let embedded = graph.embed("sub", inner).unwrap();
+        assert_eq!(embedded.exits, vec!["sub/END".to_string(), "sub/retry".to_string()]);
+        assert!(graph.conditional_edges.iter().all(|e| e.to_if_true == "sub/END"));
+    }
+
+    #[test]
+    fn test_workflow_embed_rejects_duplicate_prefix() {
+        let mut graph = WorkflowGraph::<UnitState>::new();
+        graph.embed("trio", research_trio()).unwrap();
+
+        assert_eq!(
+            graph.embed("trio", research_trio()).unwrap_err(),
+            WorkflowBuildError::DuplicateNode("trio/plan".to_string())
+        );
+    }
+
     #[test]
     fn test_workflow_end_sentinel() {
         let workflow = WorkflowGraph::<UnitState>::new()
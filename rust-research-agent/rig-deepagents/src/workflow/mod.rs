@@ -53,9 +53,12 @@ pub mod vertices;
 pub use node::{
     AgentNodeConfig, Branch, BranchCondition, FanInNodeConfig, FanOutNodeConfig, MergeStrategy,
     NodeKind, RouterNodeConfig, RoutingStrategy, SplitStrategy, StopCondition, SubAgentNodeConfig,
-    ToolNodeConfig,
+    SubWorkflowConfig, ToolNodeConfig,
+};
+pub use graph::{
+    BuiltWorkflowGraph, EmbeddedGraph, GraphConditionalEdge, GraphEdge, GraphNode, WorkflowBuildError,
+    WorkflowGraph, END,
 };
-pub use graph::{BuiltWorkflowGraph, GraphEdge, GraphNode, WorkflowBuildError, WorkflowGraph, END};
 pub use compiled::{CompiledWorkflow, PassthroughVertex, WorkflowCompileError};
 
 pub use vertices::agent::AgentVertex;
@@ -9,6 +9,7 @@
 //! - **Tool**: Single tool execution with static or dynamic arguments
 //! - **Router**: Conditional branching based on state or LLM decisions
 //! - **SubAgent**: Delegation to nested workflows with recursion protection
+//! - **SubWorkflow**: A compiled workflow run to completion as a single node
 //! - **FanOut**: Parallel dispatch to multiple targets
 //! - **FanIn**: Synchronization point waiting for multiple sources
 //! - **Passthrough**: Simple data forwarding (identity transformation)
@@ -17,6 +18,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
+use crate::pregel::vertex::VertexId;
+
 /// The kind of node in a workflow graph.
 ///
 /// Each variant represents a different computation pattern.
@@ -35,6 +38,9 @@ pub enum NodeKind {
     /// Delegation to a sub-workflow
     SubAgent(SubAgentNodeConfig),
 
+    /// A nested compiled workflow run to completion
+    SubWorkflow(SubWorkflowConfig),
+
     /// Parallel dispatch to multiple targets
     FanOut(FanOutNodeConfig),
 
@@ -184,6 +190,16 @@ pub enum RoutingStrategy {
         /// Model to use (optional, uses default if not specified)
         model: Option<String>,
     },
+
+    /// Pick a target at random, proportionally to its weight
+    ///
+    /// Selection is reproducible when `PregelConfig::seed` is set.
+    /// Non-positive weights are never chosen; `default` is used if no
+    /// weight is positive.
+    Weighted {
+        /// Candidate targets and their relative weights
+        weights: Vec<(VertexId, f64)>,
+    },
 }
 
 /// A branch in a routing decision.
@@ -260,6 +276,21 @@ fn default_max_recursion() -> usize {
     5
 }
 
+/// Configuration for a SubWorkflow node.
+///
+/// The inner workflow itself and the functions mapping parent state in and
+/// out are generic over the state types, so they are registered on the graph
+/// with `WorkflowGraph::sub_workflow` rather than stored here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubWorkflowConfig {
+    /// Name of the nested workflow (for logging)
+    pub workflow_name: String,
+
+    /// Timeout for running the nested workflow to completion
+    #[serde(default, with = "humantime_serde")]
+    pub timeout: Option<Duration>,
+}
+
 /// Configuration for a FanOut node.
 ///
 /// Broadcasts messages to multiple targets in parallel.
@@ -300,6 +331,15 @@ pub enum SplitStrategy {
 
     /// Round-robin distribution
     RoundRobin,
+
+    /// Send one message per element of a collection in the workflow state
+    ///
+    /// `field` is a dot-separated path to an array. Elements are dealt to
+    /// `targets` in order (wrapping around), each as a `FanOutPayload`.
+    FromStateField {
+        /// Path to the array in the workflow state
+        field: String,
+    },
 }
 
 /// Configuration for a FanIn node.
@@ -321,6 +361,13 @@ pub struct FanInNodeConfig {
     /// Timeout for waiting for all sources
     #[serde(default, with = "humantime_serde")]
     pub timeout: Option<Duration>,
+
+    /// Number of source messages that triggers the merge (None = all sources)
+    ///
+    /// Once the quorum fires, the stragglers of that round are dropped: the
+    /// next `sources.len() - quorum` messages are discarded without output.
+    #[serde(default)]
+    pub quorum: Option<usize>,
 }
 
 impl Default for FanInNodeConfig {
@@ -330,6 +377,7 @@ impl Default for FanInNodeConfig {
             merge_strategy: MergeStrategy::Collect,
             result_path: None,
             timeout: None,
+            quorum: None,
         }
     }
 }
@@ -469,6 +517,7 @@ mod tests {
             merge_strategy: MergeStrategy::Collect,
             result_path: Some("results".into()),
             timeout: Some(Duration::from_secs(60)),
+            quorum: None,
         };
 
         assert_eq!(fanout.targets, fanin.sources);
@@ -100,6 +100,8 @@ impl<S: WorkflowState> AgentVertex<S> {
                     vertex_id: self.id.clone(),
                     message: format!("Tool '{}' execution failed: {}", tool_name, e),
                     source: None,
+                    superstep: None,
+                    inbox: None,
                 })
         } else {
             // Tool not in registry - return error message as result
@@ -229,6 +231,8 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
             tool_calls: None,
             tool_call_id: None,
             status: None,
+            rich_content: None,
+            metadata: Default::default(),
         }];
 
         // Add any incoming workflow messages as user messages
@@ -240,6 +244,8 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
                     tool_calls: None,
                     tool_call_id: None,
             status: None,
+            rich_content: None,
+            metadata: Default::default(),
                 });
             }
         }
@@ -252,6 +258,8 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
                 tool_calls: None,
                 tool_call_id: None,
             status: None,
+            rich_content: None,
+            metadata: Default::default(),
             });
         }
 
@@ -272,6 +280,8 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
                     vertex_id: self.id.clone(),
                     message: e.to_string(),
                     source: Some(Box::new(e)),
+                    superstep: None,
+                    inbox: None,
                 })?;
 
             let assistant_message = response.message.clone();
@@ -280,13 +290,10 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
             // Check stop conditions (with state for StateMatch)
             if self.check_stop_conditions(&assistant_message, iteration, state_json.as_ref()) {
                 // Send final response as output message
-                ctx.send_message(
-                    "output",
-                    WorkflowMessage::Data {
-                        key: "response".to_string(),
-                        value: serde_json::Value::String(assistant_message.content),
-                    },
-                );
+                ctx.send_output(WorkflowMessage::Data {
+                    key: "response".to_string(),
+                    value: serde_json::Value::String(assistant_message.content),
+                });
                 return Ok(ComputeResult::halt(S::Update::empty()));
             }
 
@@ -310,13 +317,10 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
                 }
             } else {
                 // No tool calls and no stop condition matched, halt anyway
-                ctx.send_message(
-                    "output",
-                    WorkflowMessage::Data {
-                        key: "response".to_string(),
-                        value: serde_json::Value::String(assistant_message.content),
-                    },
-                );
+                ctx.send_output(WorkflowMessage::Data {
+                    key: "response".to_string(),
+                    value: serde_json::Value::String(assistant_message.content),
+                });
                 return Ok(ComputeResult::halt(S::Update::empty()));
             }
         }
@@ -332,80 +336,9 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for AgentVe
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::DeepAgentError;
-    use crate::llm::LLMResponse;
     use crate::pregel::state::UnitState;
     use crate::pregel::vertex::VertexState;
-    use crate::state::ToolCall;
-    use std::sync::Mutex;
-
-    // Mock LLM provider for testing
-    struct MockLLMProvider {
-        responses: Arc<Mutex<Vec<Message>>>,
-    }
-
-    impl MockLLMProvider {
-        fn new() -> Self {
-            Self {
-                responses: Arc::new(Mutex::new(Vec::new())),
-            }
-        }
-
-        fn with_response(self, content: impl Into<String>) -> Self {
-            let message = Message {
-                role: Role::Assistant,
-                content: content.into(),
-                tool_calls: None,
-                tool_call_id: None,
-            status: None,
-            };
-            self.responses.lock().unwrap().push(message);
-            self
-        }
-
-        fn with_tool_call(self, content: impl Into<String>, tool_name: impl Into<String>) -> Self {
-            let message = Message {
-                role: Role::Assistant,
-                content: content.into(),
-                tool_calls: Some(vec![ToolCall {
-                    id: "test_call_1".to_string(),
-                    name: tool_name.into(),
-                    arguments: serde_json::json!({}),
-                }]),
-                tool_call_id: None,
-            status: None,
-            };
-            self.responses.lock().unwrap().push(message);
-            self
-        }
-    }
-
-    #[async_trait]
-    impl LLMProvider for MockLLMProvider {
-        async fn complete(
-            &self,
-            _messages: &[Message],
-            _tools: &[ToolDefinition],
-            _config: Option<&LLMConfig>,
-        ) -> Result<LLMResponse, DeepAgentError> {
-            let mut responses = self.responses.lock().unwrap();
-            if responses.is_empty() {
-                return Err(DeepAgentError::AgentExecution(
-                    "No more mock responses".to_string(),
-                ));
-            }
-            let message = responses.remove(0);
-            Ok(LLMResponse::new(message))
-        }
-
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        fn default_model(&self) -> &str {
-            "mock-model"
-        }
-    }
+    use crate::testing::MockLLMProvider;
 
     #[tokio::test]
     async fn test_agent_vertex_single_response() {
@@ -433,7 +366,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_agent_vertex_stop_on_tool() {
-        let mock_llm = MockLLMProvider::new().with_tool_call("Let me search for that", "search");
+        let mock_llm = MockLLMProvider::new().with_tool_call("Let me search for that", "search", serde_json::json!({}));
 
         let vertex = AgentVertex::<UnitState>::new(
             "agent",
@@ -461,7 +394,7 @@ mod tests {
         // Mock LLM that always returns tool calls (would loop forever without limit)
         let mut mock_llm = MockLLMProvider::new();
         for _ in 0..15 {
-            mock_llm = mock_llm.with_tool_call("Still thinking...", "think");
+            mock_llm = mock_llm.with_tool_call("Still thinking...", "think", serde_json::json!({}));
         }
 
         let vertex = AgentVertex::<UnitState>::new(
@@ -569,6 +502,8 @@ mod tests {
             tool_calls: Some(vec![]),
             tool_call_id: None,
             status: None,
+            rich_content: None,
+            metadata: Default::default(),
         };
 
         // State with non-matching phase
@@ -8,6 +8,7 @@
 //! - [`tool::ToolVertex`]: Single tool execution with static/dynamic args
 //! - [`router::RouterVertex`]: Conditional routing based on state or LLM decisions
 //! - [`subagent::SubAgentVertex`]: Delegates to sub-agents from registry
+//! - [`subworkflow::SubWorkflowVertex`]: Runs a nested compiled workflow
 //! - [`parallel::FanOutVertex`]: Broadcasts messages to multiple targets
 //! - [`parallel::FanInVertex`]: Synchronizes messages from multiple sources
 
@@ -15,11 +16,13 @@ pub mod agent;
 pub mod parallel;
 pub mod router;
 pub mod subagent;
+pub mod subworkflow;
 pub mod tool;
 
 // Re-export main vertex types
 pub use agent::AgentVertex;
-pub use parallel::{FanInVertex, FanOutVertex};
+pub use parallel::{FanInVertex, FanOutPayload, FanOutVertex, FAN_OUT_ITEM_KEY};
 pub use router::RouterVertex;
 pub use subagent::SubAgentVertex;
+pub use subworkflow::{SubWorkflow, SubWorkflowRunner, SubWorkflowVertex};
 pub use tool::ToolVertex;
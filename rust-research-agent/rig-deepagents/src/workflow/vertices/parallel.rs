@@ -3,12 +3,13 @@
 //! Implements vertices for parallelizing workflow execution and synchronizing results.
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 use crate::pregel::error::PregelError;
-use crate::pregel::message::WorkflowMessage;
+use crate::pregel::message::{MessageOrigin, WorkflowMessage};
 use crate::pregel::state::WorkflowState;
 use crate::pregel::vertex::{ComputeContext, ComputeResult, StateUpdate, Vertex, VertexId, VertexState};
 use crate::workflow::node::{FanInNodeConfig, FanOutNodeConfig, MergeStrategy, SplitStrategy};
@@ -16,6 +17,34 @@ use crate::workflow::node::{FanInNodeConfig, FanOutNodeConfig, MergeStrategy, Sp
 /// Type alias for FanIn's message buffer (source_id, message)
 type ReceivedMessages = Arc<Mutex<Vec<(Option<String>, WorkflowMessage)>>>;
 
+/// Message key used for elements sent by `SplitStrategy::FromStateField`
+pub const FAN_OUT_ITEM_KEY: &str = "fan_out_item";
+
+/// Payload of one element dispatched by `SplitStrategy::FromStateField`
+///
+/// Sent as the value of a `WorkflowMessage::Data` keyed [`FAN_OUT_ITEM_KEY`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FanOutPayload {
+    /// Position of the element in the state collection
+    pub index: usize,
+    /// Number of elements dispatched in this fan-out
+    pub total: usize,
+    /// The element itself
+    pub item: Value,
+}
+
+impl FanOutPayload {
+    /// Read a payload back out of a received message
+    pub fn from_message(msg: &WorkflowMessage) -> Option<Self> {
+        match msg {
+            WorkflowMessage::Data { key, value } if key == FAN_OUT_ITEM_KEY => {
+                serde_json::from_value(value.clone()).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
 /// FanOut Vertex: Dispatches messages to multiple targets
 pub struct FanOutVertex<S: WorkflowState> {
     id: VertexId,
@@ -44,10 +73,45 @@ impl<S: WorkflowState> FanOutVertex<S> {
         *counter += 1;
         Some(VertexId::new(&self.config.targets[idx]))
     }
+
+    /// Dispatch one `FanOutPayload` per element of the state array at `field`
+    fn dispatch_from_state(
+        &self,
+        ctx: &mut ComputeContext<'_, S, WorkflowMessage>,
+        field: &str,
+    ) -> Result<(), PregelError>
+    where
+        S: Serialize,
+    {
+        let state = serde_json::to_value(ctx.state)
+            .map_err(|e| PregelError::vertex_error(self.id.clone(), e.to_string()))?;
+        let items = field
+            .split('.')
+            .try_fold(&state, |value, part| value.get(part))
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                PregelError::vertex_error(
+                    self.id.clone(),
+                    format!("state field '{}' is not an array", field),
+                )
+            })?;
+
+        let total = items.len();
+        for (index, item) in items.iter().enumerate() {
+            let target = &self.config.targets[index % self.config.targets.len()];
+            let payload = FanOutPayload {
+                index,
+                total,
+                item: item.clone(),
+            };
+            ctx.send_message(target.as_str(), WorkflowMessage::data(FAN_OUT_ITEM_KEY, payload));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
-impl<S: WorkflowState> Vertex<S, WorkflowMessage> for FanOutVertex<S> {
+impl<S: WorkflowState + Serialize> Vertex<S, WorkflowMessage> for FanOutVertex<S> {
     fn id(&self) -> &VertexId {
         &self.id
     }
@@ -60,6 +124,12 @@ impl<S: WorkflowState> Vertex<S, WorkflowMessage> for FanOutVertex<S> {
             return Ok(ComputeResult::halt(S::Update::empty()));
         }
 
+        // State-driven fan-out ignores message payloads: one dispatch per activation
+        if let SplitStrategy::FromStateField { field } = &self.config.split_strategy {
+            self.dispatch_from_state(ctx, field)?;
+            return Ok(ComputeResult::halt(S::Update::empty()));
+        }
+
         for msg in ctx.messages {
             match self.config.split_strategy {
                 SplitStrategy::Broadcast => {
@@ -108,6 +178,8 @@ impl<S: WorkflowState> Vertex<S, WorkflowMessage> for FanOutVertex<S> {
                         ctx.broadcast(self.config.targets.iter().map(|t| t.as_str()), msg.clone());
                     }
                 }
+                // Dispatched from state above
+                SplitStrategy::FromStateField { .. } => {}
             }
         }
 
@@ -120,9 +192,12 @@ pub struct FanInVertex<S: WorkflowState> {
     id: VertexId,
     config: FanInNodeConfig,
     /// Store received messages.
-    /// Since we can't easily identify sender of Data messages, we track count and payload.
-    /// Vector stores (source_id_opt, message)
+    /// Vector stores (source_id_opt, message); the source is the sending vertex,
+    /// or `None` for runtime-injected messages (e.g. restored from a checkpoint)
     received: ReceivedMessages,
+    /// Sources that had not reported when the last quorum merge closed its round;
+    /// their late message is dropped instead of starting a new round
+    stragglers: Mutex<HashSet<String>>,
     _phantom: std::marker::PhantomData<S>,
 }
 
@@ -132,16 +207,23 @@ impl<S: WorkflowState> FanInVertex<S> {
             id: id.into(),
             config,
             received: Arc::new(Mutex::new(Vec::new())),
+            stragglers: Mutex::new(HashSet::new()),
             _phantom: std::marker::PhantomData,
         }
     }
 
-    /// Check if we have received input from all expected sources
+    /// Number of messages needed before merging (quorum, capped at all sources)
+    fn required_count(&self) -> usize {
+        let all = self.config.sources.len();
+        self.config.quorum.map_or(all, |q| q.min(all))
+    }
+
+    /// Check if we have received input from enough sources
     fn check_completion(&self, received: &[(Option<String>, WorkflowMessage)]) -> bool {
         // If we know sources, check if we have messages from all of them
         // For Data messages without source ID, we fall back to counting
         
-        let expected_count = self.config.sources.len();
+        let expected_count = self.required_count();
         if received.len() >= expected_count {
             return true;
         }
@@ -223,19 +305,36 @@ impl<S: WorkflowState> Vertex<S, WorkflowMessage> for FanInVertex<S> {
         ctx: &mut ComputeContext<'_, S, WorkflowMessage>,
     ) -> Result<ComputeResult<S::Update>, PregelError> {
         let mut received_lock = self.received.lock().unwrap();
-
-        // Process incoming messages
-        for msg in ctx.messages {
-            let source = match msg {
-                WorkflowMessage::Completed { source, .. } => Some(source.as_str().to_string()),
-                _ => None,
+        let mut stragglers = self.stragglers.lock().unwrap();
+
+        // Process incoming messages, dropping the late arrivals of a quorum round
+        for (origin, msg) in ctx.messages_with_source() {
+            let source = match (msg, origin) {
+                (WorkflowMessage::Completed { source, .. }, _) => Some(source.as_str().to_string()),
+                (_, MessageOrigin::Vertex(sender)) => Some(sender.as_str().to_string()),
+                (_, MessageOrigin::Runtime) => None,
             };
+            if source.as_ref().is_some_and(|src| stragglers.remove(src)) {
+                tracing::debug!(vertex_id = %self.id, source = ?source, "FanIn dropping late message after quorum");
+                continue;
+            }
             received_lock.push((source, msg.clone()));
         }
 
         // Check if ready to merge
         if self.check_completion(&received_lock) {
             let received_data = std::mem::take(&mut *received_lock);
+            let reported: HashSet<&str> = received_data
+                .iter()
+                .filter_map(|(src, _)| src.as_deref())
+                .collect();
+            *stragglers = self
+                .config
+                .sources
+                .iter()
+                .filter(|src| !reported.contains(src.as_str()))
+                .cloned()
+                .collect();
             let result = self.merge_results(received_data);
 
             // Send merged result
@@ -415,4 +514,143 @@ mod tests {
         assert!(res.state.is_active());
         assert!(ctx.into_outbox().is_empty());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_fanin_quorum_two_of_three() {
+        let config = FanInNodeConfig {
+            sources: vec!["a".into(), "b".into(), "c".into()],
+            quorum: Some(2),
+            ..Default::default()
+        };
+        let vertex = FanInVertex::<UnitState>::new("fanin", config);
+
+        let first = [WorkflowMessage::data("a", 1)];
+        let from_a = [MessageOrigin::Vertex(VertexId::new("a"))];
+        let mut ctx = create_ctx("fanin", &first, &UnitState).with_origins(&from_a);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_active());
+        assert!(ctx.into_outbox().is_empty());
+
+        // Second source reaches the quorum
+        let second = [WorkflowMessage::data("b", 2)];
+        let from_b = [MessageOrigin::Vertex(VertexId::new("b"))];
+        let mut ctx = create_ctx("fanin", &second, &UnitState).with_origins(&from_b);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_halted());
+        let outbox = ctx.into_outbox();
+        match &outbox.get(&VertexId::new("output")).unwrap()[0] {
+            WorkflowMessage::Data { value, .. } => assert_eq!(value, &json!([1, 2])),
+            other => panic!("Expected Data message, got {:?}", other),
+        }
+
+        // The straggler is dropped instead of starting a new round
+        let late = [WorkflowMessage::data("c", 3)];
+        let from_c = [MessageOrigin::Vertex(VertexId::new("c"))];
+        let mut ctx = create_ctx("fanin", &late, &UnitState).with_origins(&from_c);
+        vertex.compute(&mut ctx).await.unwrap();
+        assert!(ctx.into_outbox().is_empty());
+        assert!(vertex.received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fanin_quorum_keeps_next_round_when_source_never_reports() {
+        let config = FanInNodeConfig {
+            sources: vec!["a".into(), "b".into(), "c".into()],
+            quorum: Some(2),
+            ..Default::default()
+        };
+        let vertex = FanInVertex::<UnitState>::new("fanin", config);
+
+        let round_one = [WorkflowMessage::data("a", 1), WorkflowMessage::data("b", 2)];
+        let origins = [
+            MessageOrigin::Vertex(VertexId::new("a")),
+            MessageOrigin::Vertex(VertexId::new("b")),
+        ];
+        let mut ctx = create_ctx("fanin", &round_one, &UnitState).with_origins(&origins);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_halted());
+
+        // "c" failed and never sends; the next round's first message is kept
+        let round_two = [WorkflowMessage::data("a", 10)];
+        let from_a = [MessageOrigin::Vertex(VertexId::new("a"))];
+        let mut ctx = create_ctx("fanin", &round_two, &UnitState).with_origins(&from_a);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_active());
+        assert_eq!(vertex.received.lock().unwrap().len(), 1);
+
+        let from_b = [MessageOrigin::Vertex(VertexId::new("b"))];
+        let round_two = [WorkflowMessage::data("b", 20)];
+        let mut ctx = create_ctx("fanin", &round_two, &UnitState).with_origins(&from_b);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_halted());
+        match &ctx.into_outbox().get(&VertexId::new("output")).unwrap()[0] {
+            WorkflowMessage::Data { value, .. } => assert_eq!(value, &json!([10, 20])),
+            other => panic!("Expected Data message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fanout_from_state_field() {
+        #[derive(Clone, serde::Serialize)]
+        struct PlanState {
+            plan: Plan,
+        }
+
+        #[derive(Clone, serde::Serialize)]
+        struct Plan {
+            directions: Vec<String>,
+        }
+
+        impl WorkflowState for PlanState {
+            type Update = crate::pregel::state::UnitUpdate;
+
+            fn apply_update(&self, _update: Self::Update) -> Self {
+                self.clone()
+            }
+
+            fn merge_updates(_updates: Vec<Self::Update>) -> Self::Update {
+                crate::pregel::state::UnitUpdate
+            }
+
+            fn is_terminal(&self) -> bool {
+                false
+            }
+        }
+
+        let config = FanOutNodeConfig {
+            targets: vec!["worker_a".into(), "worker_b".into(), "worker_c".into()],
+            split_strategy: SplitStrategy::FromStateField {
+                field: "plan.directions".into(),
+            },
+            ..Default::default()
+        };
+        let vertex = FanOutVertex::<PlanState>::new("fanout", config);
+        let state = PlanState {
+            plan: Plan {
+                directions: vec!["history".into(), "economics".into(), "policy".into()],
+            },
+        };
+
+        let messages = [WorkflowMessage::Activate];
+        let mut ctx = ComputeContext::new(VertexId::new("fanout"), &messages, 0, &state);
+        assert!(vertex.compute(&mut ctx).await.unwrap().state.is_halted());
+
+        let outbox = ctx.into_outbox();
+        assert_eq!(outbox.len(), 3);
+        for (index, (worker, direction)) in [
+            ("worker_a", "history"),
+            ("worker_b", "economics"),
+            ("worker_c", "policy"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let msgs = &outbox[&VertexId::new(worker)];
+            assert_eq!(msgs.len(), 1);
+            let payload = FanOutPayload::from_message(&msgs[0]).unwrap();
+            assert_eq!(
+                payload,
+                FanOutPayload {
+                    index,
+                    total: 3,
+                    item: json!(direction),
+                }
+            );
+        }
+    }
+}
@@ -1,12 +1,14 @@
 //! RouterVertex: Conditional routing based on state or LLM decisions
 //!
 //! Implements the Vertex trait for router nodes that route messages
-//! based on state field inspection or LLM-based classification.
+//! based on state field inspection, LLM-based classification, or
+//! weighted random selection.
 
 use async_trait::async_trait;
 use serde::Serialize;
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::llm::LLMProvider;
 use crate::pregel::error::PregelError;
@@ -20,9 +22,29 @@ pub struct RouterVertex<S: WorkflowState> {
     id: VertexId,
     config: RouterNodeConfig,
     llm: Option<Arc<dyn LLMProvider>>,
+    rng: Mutex<SplitMix64>,
     _phantom: std::marker::PhantomData<S>,
 }
 
+/// Small deterministic PRNG used for weighted routing
+#[derive(Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
 impl<S: WorkflowState + Serialize> RouterVertex<S> {
     /// Create a new router vertex
     pub fn new(
@@ -30,10 +52,34 @@ impl<S: WorkflowState + Serialize> RouterVertex<S> {
         config: RouterNodeConfig,
         llm: Option<Arc<dyn LLMProvider>>,
     ) -> Self {
+        let entropy = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::with_seed(id, config, llm, entropy)
+    }
+
+    /// Create a router vertex whose weighted choices are reproducible
+    ///
+    /// The seed is mixed with the vertex id so that several weighted routers
+    /// sharing one workflow seed don't make correlated choices.
+    pub fn with_seed(
+        id: impl Into<VertexId>,
+        config: RouterNodeConfig,
+        llm: Option<Arc<dyn LLMProvider>>,
+        seed: u64,
+    ) -> Self {
+        let id = id.into();
+        // FNV-1a keeps the mixing stable across Rust versions
+        let id_hash = id
+            .as_str()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
         Self {
-            id: id.into(),
+            id,
             config,
             llm,
+            rng: Mutex::new(SplitMix64(seed ^ id_hash)),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -114,6 +160,27 @@ impl<S: WorkflowState + Serialize> RouterVertex<S> {
         default.map(|s| s.to_string())
     }
 
+    /// Route by weighted random choice among positively weighted targets
+    fn route_by_weight(&self, weights: &[(VertexId, f64)], default: Option<&str>) -> Option<String> {
+        let usable = |w: f64| w.is_finite() && w > 0.0;
+        let total: f64 = weights.iter().map(|(_, w)| *w).filter(|w| usable(*w)).sum();
+        if total <= 0.0 {
+            return default.map(|s| s.to_string());
+        }
+
+        let mut pick = self.rng.lock().unwrap().next_f64() * total;
+        let mut chosen = None;
+        for (target, weight) in weights.iter().filter(|(_, w)| usable(*w)) {
+            chosen = Some(target);
+            if pick < *weight {
+                break;
+            }
+            pick -= weight;
+        }
+        // Float rounding can leave `pick` past the last bucket; that falls to the last target
+        chosen.map(|t| t.as_str().to_string())
+    }
+
     /// Route based on LLM decision
     async fn route_by_llm_decision(&self, state: &S, branches: &[Branch]) -> Result<Option<String>, PregelError> {
         let llm = self.llm.as_ref().ok_or_else(|| {
@@ -201,6 +268,18 @@ impl<S: WorkflowState + Serialize> Vertex<S, WorkflowMessage> for RouterVertex<S
         &self.id
     }
 
+    fn rng_state(&self) -> Option<u64> {
+        // Only weighted routing draws random numbers
+        match self.config.strategy {
+            RoutingStrategy::Weighted { .. } => Some(self.rng.lock().unwrap().0),
+            _ => None,
+        }
+    }
+
+    fn restore_rng_state(&self, state: u64) {
+        self.rng.lock().unwrap().0 = state;
+    }
+
     async fn compute(
         &self,
         ctx: &mut ComputeContext<'_, S, WorkflowMessage>,
@@ -213,6 +292,9 @@ impl<S: WorkflowState + Serialize> Vertex<S, WorkflowMessage> for RouterVertex<S
             RoutingStrategy::LLMDecision { .. } => {
                 self.route_by_llm_decision(ctx.state, &self.config.branches).await?
             }
+            RoutingStrategy::Weighted { weights } => {
+                self.route_by_weight(weights, self.config.default.as_deref())
+            }
         };
 
         // Send the message to the selected target or default
@@ -234,13 +316,10 @@ impl<S: WorkflowState + Serialize> Vertex<S, WorkflowMessage> for RouterVertex<S
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::DeepAgentError;
-    use crate::llm::{LLMConfig, LLMResponse};
     use crate::pregel::state::UnitUpdate;
     use crate::pregel::vertex::VertexState;
-    use crate::state::Message as StateMessage;
+    use crate::testing::MockLLMProvider;
     use serde_json::json;
-    use std::sync::Mutex;
 
     // Mock state for testing
     #[derive(Clone, Debug, Default, serde::Serialize)]
@@ -278,51 +357,6 @@ mod tests {
         }
     }
 
-    // Mock LLM provider for testing
-    struct MockLLMProvider {
-        responses: Arc<Mutex<Vec<String>>>,
-    }
-
-    impl MockLLMProvider {
-        fn new() -> Self {
-            Self {
-                responses: Arc::new(Mutex::new(Vec::new())),
-            }
-        }
-
-        fn with_response(self, content: impl Into<String>) -> Self {
-            self.responses.lock().unwrap().push(content.into());
-            self
-        }
-    }
-
-    #[async_trait]
-    impl LLMProvider for MockLLMProvider {
-        async fn complete(
-            &self,
-            _messages: &[StateMessage],
-            _tools: &[crate::middleware::ToolDefinition],
-            _config: Option<&LLMConfig>,
-        ) -> Result<LLMResponse, DeepAgentError> {
-            let mut responses = self.responses.lock().unwrap();
-            if responses.is_empty() {
-                return Err(DeepAgentError::AgentExecution(
-                    "No more mock responses".to_string(),
-                ));
-            }
-            let content = responses.remove(0);
-            Ok(LLMResponse::new(StateMessage::assistant(&content)))
-        }
-
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        fn default_model(&self) -> &str {
-            "mock-model"
-        }
-    }
-
     #[tokio::test]
     async fn test_router_state_field_equals() {
         let config = RouterNodeConfig {
@@ -544,4 +578,67 @@ mod tests {
         let outbox = ctx.into_outbox();
         assert!(outbox.contains_key(&VertexId::new("exploration")));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_router_weighted_distribution() {
+        let config = RouterNodeConfig {
+            strategy: RoutingStrategy::Weighted {
+                weights: vec![
+                    (VertexId::new("prompt_a"), 6.0),
+                    (VertexId::new("prompt_b"), 3.0),
+                    (VertexId::new("prompt_c"), 1.0),
+                    (VertexId::new("disabled"), 0.0),
+                ],
+            },
+            branches: vec![],
+            default: None,
+        };
+
+        async fn run(config: RouterNodeConfig, seed: u64) -> Vec<VertexId> {
+            let vertex = RouterVertex::<TestState>::with_seed("router", config, None, seed);
+            let test_state = TestState::default();
+            let messages = vec![WorkflowMessage::data("input", "test")];
+            let mut picks = Vec::new();
+            for _ in 0..10_000 {
+                let mut ctx =
+                    ComputeContext::new(VertexId::new("router"), &messages, 0, &test_state);
+                let _: ComputeResult<UnitUpdate> = vertex.compute(&mut ctx).await.unwrap();
+                let outbox = ctx.into_outbox();
+                assert_eq!(outbox.len(), 1);
+                picks.extend(outbox.into_keys());
+            }
+            picks
+        }
+
+        let picks = run(config.clone(), 42).await;
+        let share = |target: &str| {
+            picks.iter().filter(|id| id.as_str() == target).count() as f64 / picks.len() as f64
+        };
+        assert!((share("prompt_a") - 0.6).abs() < 0.02, "prompt_a: {}", share("prompt_a"));
+        assert!((share("prompt_b") - 0.3).abs() < 0.02, "prompt_b: {}", share("prompt_b"));
+        assert!((share("prompt_c") - 0.1).abs() < 0.02, "prompt_c: {}", share("prompt_c"));
+        assert_eq!(share("disabled"), 0.0);
+
+        // Same seed, same sequence of choices
+        assert_eq!(run(config, 42).await, picks);
+    }
+
+    #[tokio::test]
+    async fn test_router_weighted_falls_back_to_default() {
+        let config = RouterNodeConfig {
+            strategy: RoutingStrategy::Weighted {
+                weights: vec![(VertexId::new("never"), 0.0)],
+            },
+            branches: vec![],
+            default: Some("fallback".to_string()),
+        };
+        let vertex = RouterVertex::<TestState>::with_seed("router", config, None, 7);
+
+        let test_state = TestState::default();
+        let messages = vec![WorkflowMessage::data("input", "test")];
+        let mut ctx = ComputeContext::new(VertexId::new("router"), &messages, 0, &test_state);
+        let _: ComputeResult<UnitUpdate> = vertex.compute(&mut ctx).await.unwrap();
+
+        assert!(ctx.into_outbox().contains_key(&VertexId::new("fallback")));
+    }
+}
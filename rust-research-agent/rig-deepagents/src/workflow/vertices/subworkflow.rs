@@ -0,0 +1,239 @@
+//! SubWorkflowVertex: runs a nested compiled workflow as a single node
+//!
+//! A sub-workflow lets a complex pipeline be composed from smaller workflows.
+//! Each activation maps a slice of the parent state into the inner workflow's
+//! state, compiles a fresh inner workflow, runs it to completion, and maps
+//! the final inner state back into a parent update.
+//!
+//! # Usage in Workflows
+//!
+//! ```rust,ignore
+//! let inner = WorkflowGraph::<SearchState>::new()
+//!     .node("search", NodeKind::Passthrough)
+//!     .node("rank", NodeKind::Passthrough)
+//!     .entry("search")
+//!     .edge("search", "rank")
+//!     .edge("rank", END)
+//!     .build()?;
+//!
+//! let search = SubWorkflow::new(
+//!     inner,
+//!     PregelConfig::default().with_execution_mode(ExecutionMode::EdgeDriven),
+//!     |parent: &ResearchState| SearchState::for_query(&parent.query),
+//!     |_parent: &ResearchState, inner: SearchState| ResearchUpdate::findings(inner.results),
+//! );
+//!
+//! let graph = WorkflowGraph::<ResearchState>::new()
+//!     .sub_workflow("search", SubWorkflowConfig::default(), Arc::new(search))
+//!     .entry("search")
+//!     .edge("search", END)
+//!     .build()?;
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::pregel::error::PregelError;
+use crate::pregel::message::WorkflowMessage;
+use crate::pregel::state::WorkflowState;
+use crate::pregel::vertex::{ComputeContext, ComputeResult, Vertex, VertexId};
+use crate::pregel::PregelConfig;
+use crate::workflow::compiled::{CompiledWorkflow, WorkflowCompileError};
+use crate::workflow::graph::BuiltWorkflowGraph;
+use crate::workflow::node::SubWorkflowConfig;
+
+/// Runs a nested workflow against a parent state and returns the parent update.
+///
+/// Implemented by [`SubWorkflow`]; the trait erases the inner state type so
+/// graphs over `S` can hold sub-workflows of any inner state.
+#[async_trait]
+pub trait SubWorkflowRunner<S: WorkflowState>: Send + Sync {
+    /// Run the nested workflow to completion and produce an update for the parent
+    async fn run(&self, parent: &S) -> Result<S::Update, PregelError>;
+}
+
+impl<S: WorkflowState> std::fmt::Debug for dyn SubWorkflowRunner<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SubWorkflowRunner")
+    }
+}
+
+/// Builds a fresh inner workflow for each run
+pub type SubWorkflowBuilder<I> =
+    Arc<dyn Fn() -> Result<CompiledWorkflow<I>, WorkflowCompileError> + Send + Sync>;
+
+/// Maps the parent state into the inner workflow's initial state
+pub type SubWorkflowInput<S, I> = Arc<dyn Fn(&S) -> I + Send + Sync>;
+
+/// Maps the inner workflow's final state into a parent update
+pub type SubWorkflowOutput<S, I> =
+    Arc<dyn Fn(&S, I) -> <S as WorkflowState>::Update + Send + Sync>;
+
+/// A nested workflow over inner state `I`, embedded in a parent over `S`
+///
+/// A new inner workflow is compiled for every run so that concurrent or
+/// repeated activations never share runtime state.
+pub struct SubWorkflow<S, I>
+where
+    S: WorkflowState,
+    I: WorkflowState + Serialize + for<'de> Deserialize<'de>,
+{
+    build: SubWorkflowBuilder<I>,
+    map_in: SubWorkflowInput<S, I>,
+    map_out: SubWorkflowOutput<S, I>,
+}
+
+impl<S, I> SubWorkflow<S, I>
+where
+    S: WorkflowState,
+    I: WorkflowState + Serialize + for<'de> Deserialize<'de>,
+{
+    /// Create a sub-workflow from a built graph compiled with `CompiledWorkflow::compile`
+    ///
+    /// Use [`SubWorkflow::from_builder`] when the inner workflow needs LLM
+    /// providers, tools, or other compile-time resources.
+    pub fn new(
+        graph: BuiltWorkflowGraph<I>,
+        config: PregelConfig,
+        map_in: impl Fn(&S) -> I + Send + Sync + 'static,
+        map_out: impl Fn(&S, I) -> S::Update + Send + Sync + 'static,
+    ) -> Self {
+        Self::from_builder(
+            move || CompiledWorkflow::compile(graph.clone(), config.clone()),
+            map_in,
+            map_out,
+        )
+    }
+
+    /// Create a sub-workflow from a closure that compiles the inner workflow
+    pub fn from_builder(
+        build: impl Fn() -> Result<CompiledWorkflow<I>, WorkflowCompileError> + Send + Sync + 'static,
+        map_in: impl Fn(&S) -> I + Send + Sync + 'static,
+        map_out: impl Fn(&S, I) -> S::Update + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            build: Arc::new(build),
+            map_in: Arc::new(map_in),
+            map_out: Arc::new(map_out),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, I> SubWorkflowRunner<S> for SubWorkflow<S, I>
+where
+    S: WorkflowState,
+    I: WorkflowState + Serialize + for<'de> Deserialize<'de>,
+{
+    async fn run(&self, parent: &S) -> Result<S::Update, PregelError> {
+        let mut workflow = (self.build)().map_err(|e| PregelError::config_error(e.to_string()))?;
+        let result = workflow.run((self.map_in)(parent)).await?;
+        Ok((self.map_out)(parent, result.state))
+    }
+}
+
+/// A Pregel vertex that runs a nested workflow to completion
+pub struct SubWorkflowVertex<S: WorkflowState> {
+    /// Vertex identifier
+    id: VertexId,
+
+    /// SubWorkflow configuration
+    config: SubWorkflowConfig,
+
+    /// The nested workflow with its state mappings
+    runner: Arc<dyn SubWorkflowRunner<S>>,
+}
+
+impl<S: WorkflowState> SubWorkflowVertex<S> {
+    /// Create a new SubWorkflowVertex
+    pub fn new(
+        id: impl Into<VertexId>,
+        config: SubWorkflowConfig,
+        runner: Arc<dyn SubWorkflowRunner<S>>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            config,
+            runner,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: WorkflowState> Vertex<S, WorkflowMessage> for SubWorkflowVertex<S> {
+    fn id(&self) -> &VertexId {
+        &self.id
+    }
+
+    async fn compute(
+        &self,
+        ctx: &mut ComputeContext<'_, S, WorkflowMessage>,
+    ) -> Result<ComputeResult<S::Update>, PregelError> {
+        tracing::info!(
+            vertex_id = %self.id,
+            workflow_name = %self.config.workflow_name,
+            superstep = ctx.superstep,
+            "SubWorkflowVertex compute starting"
+        );
+
+        let run = self.runner.run(ctx.state);
+        let result = match self.config.timeout {
+            Some(limit) => tokio::time::timeout(limit, run)
+                .await
+                .map_err(|_| PregelError::VertexTimeout(self.id.clone()))?,
+            None => run.await,
+        };
+
+        let update = result.map_err(|e| {
+            PregelError::vertex_error_with_source(
+                self.id.clone(),
+                format!("SubWorkflow '{}' failed", self.config.workflow_name),
+                e,
+            )
+        })?;
+
+        Ok(ComputeResult::halt(update))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregel::state::{UnitState, UnitUpdate};
+
+    struct FailingRunner;
+
+    #[async_trait]
+    impl SubWorkflowRunner<UnitState> for FailingRunner {
+        async fn run(&self, _parent: &UnitState) -> Result<UnitUpdate, PregelError> {
+            Err(PregelError::MaxSuperstepsExceeded(3))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subworkflow_vertex_wraps_inner_error() {
+        let vertex = SubWorkflowVertex::<UnitState>::new(
+            "nested",
+            SubWorkflowConfig {
+                workflow_name: "inner".into(),
+                timeout: None,
+            },
+            Arc::new(FailingRunner),
+        );
+
+        let state = UnitState;
+        let mut ctx = ComputeContext::new(VertexId::new("nested"), &[], 0, &state);
+        let err = vertex.compute(&mut ctx).await.unwrap_err();
+
+        match err {
+            PregelError::VertexError { vertex_id, message, source, .. } => {
+                assert_eq!(vertex_id, VertexId::new("nested"));
+                assert!(message.contains("inner"));
+                assert!(source.unwrap().to_string().contains("Max supersteps"));
+            }
+            other => panic!("Expected VertexError, got {:?}", other),
+        }
+    }
+}
@@ -188,7 +188,7 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for ToolVer
 
         // Try to parse result as JSON, fallback to string
         let result_value = serde_json::from_str(&result.message)
-            .unwrap_or_else(|_| serde_json::Value::String(result.message));
+            .unwrap_or(serde_json::Value::String(result.message));
 
         // Build output key based on result_path or default
         let output_key = self
@@ -198,13 +198,10 @@ impl<S: WorkflowState + serde::Serialize> Vertex<S, WorkflowMessage> for ToolVer
             .unwrap_or_else(|| format!("{}_result", self.config.tool_name));
 
         // Send result as output message
-        ctx.send_message(
-            "output",
-            WorkflowMessage::Data {
-                key: output_key,
-                value: result_value,
-            },
-        );
+        ctx.send_output(WorkflowMessage::Data {
+            key: output_key,
+            value: result_value,
+        });
 
         // Tool vertices complete after single execution
         Ok(ComputeResult::halt(S::Update::empty()))
@@ -247,6 +244,7 @@ mod tests {
                     "type": "object",
                     "properties": {}
                 }),
+                examples: Vec::new(),
             }
         }
 
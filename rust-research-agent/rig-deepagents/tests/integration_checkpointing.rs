@@ -5,6 +5,7 @@
 //! - Resume from checkpoint after simulated failure
 //! - Pending message preservation
 //! - Retry count preservation
+//! - Deterministic weighted routing after resume
 //! - Different checkpointer backends
 //! - Error handling for non-checkpointed workflows
 
@@ -18,7 +19,7 @@ use rig_deepagents::pregel::vertex::{VertexId, VertexState};
 use rig_deepagents::pregel::PregelConfig;
 use rig_deepagents::pregel::state::UnitState;
 use rig_deepagents::workflow::graph::WorkflowGraph;
-use rig_deepagents::workflow::node::NodeKind;
+use rig_deepagents::workflow::node::{NodeKind, RouterNodeConfig, RoutingStrategy};
 use rig_deepagents::workflow::{CompiledWorkflow, END};
 
 // =============================================================================
@@ -244,6 +245,81 @@ async fn test_resume_preserves_retry_counts() {
     assert!(result.completed);
 }
 
+/// Build a workflow whose router picks one of eight equally weighted branches
+fn weighted_routing_graph() -> rig_deepagents::workflow::graph::BuiltWorkflowGraph<UnitState> {
+    let branches: Vec<String> = (0..8).map(|i| format!("branch_{}", i)).collect();
+    let router = RouterNodeConfig {
+        strategy: RoutingStrategy::Weighted {
+            weights: branches.iter().map(|b| (VertexId::new(b.as_str()), 1.0)).collect(),
+        },
+        branches: vec![],
+        default: None,
+    };
+
+    let mut graph = WorkflowGraph::<UnitState>::new()
+        .name("weighted_resume")
+        .node("start", NodeKind::Passthrough)
+        .node("router", NodeKind::Router(router))
+        .entry("start")
+        .edge("start", "router");
+    for branch in &branches {
+        graph = graph.node(branch.as_str(), NodeKind::Passthrough).edge(branch.as_str(), END);
+    }
+    graph.build().expect("Failed to build graph")
+}
+
+/// Branch the router sent its message to, as recorded in a checkpoint
+fn routed_branch(checkpoint: &Checkpoint<UnitState>) -> Vec<VertexId> {
+    checkpoint
+        .pending_messages
+        .iter()
+        .filter(|(vid, msgs)| vid.as_str().starts_with("branch_") && !msgs.is_empty())
+        .map(|(vid, _)| vid.clone())
+        .collect()
+}
+
+/// Test that resuming before a weighted router reproduces its original choice
+#[tokio::test]
+async fn test_resume_reproduces_weighted_routing() {
+    let config = PregelConfig::default()
+        .with_execution_mode(ExecutionMode::EdgeDriven)
+        .with_checkpoint_interval(1);
+
+    // Original run: checkpoint 1 is taken before the router computes, checkpoint 2 after
+    let original = Arc::new(MemoryCheckpointer::<UnitState>::new());
+    let mut workflow = CompiledWorkflow::compile_with_checkpointer(
+        weighted_routing_graph(),
+        config.clone().with_seed(42),
+        original.clone(),
+        "weighted-resume",
+    )
+    .expect("Failed to compile workflow");
+    workflow.run(UnitState).await.expect("Workflow failed");
+
+    let before_routing = original.load(1).await.unwrap().expect("Missing checkpoint 1");
+    assert!(before_routing.rng_states.contains_key(&VertexId::new("router")));
+    let expected = routed_branch(&original.load(2).await.unwrap().expect("Missing checkpoint 2"));
+    assert_eq!(expected.len(), 1);
+
+    // Resumed run uses a different seed; the checkpointed RNG state must win
+    let resumed = Arc::new(MemoryCheckpointer::<UnitState>::new());
+    let mut workflow = CompiledWorkflow::compile_with_checkpointer(
+        weighted_routing_graph(),
+        config.with_seed(7),
+        resumed.clone(),
+        "weighted-resume",
+    )
+    .expect("Failed to compile workflow");
+    let result = workflow
+        .run_from_checkpoint(before_routing)
+        .await
+        .expect("Resume failed");
+    assert!(result.completed);
+
+    let actual = routed_branch(&resumed.load(2).await.unwrap().expect("Missing checkpoint 2"));
+    assert_eq!(actual, expected);
+}
+
 // =============================================================================
 // Backend Integration Tests
 // =============================================================================